@@ -31,12 +31,25 @@
 //! Furtif offers a generic implementation in Rust of functionalities for manipulating belief functions and merging them. 
 //! This crate includes:
 //! * Traits defining the notion of lattice and its variants
-//!   * Two types of lattices are implemented
+//!   * Three types of lattices are implemented
 //!     * Powerset
 //!     * Taxonomy
+//!     * Interval lattice (unions of cells of a discretised real axis)
 //! * Tools for transforming between different forms of belief functions
+//! * Uncertainty measures of belief functions (Deng entropy, aggregate uncertainty, confusion, dissonance, nonspecificity, ambiguity)
+//! * Possibility theory: possibility distributions over leaves, possibility and necessity measures, min/product/max fusion, conversion to and from consonant assignments
+//! * Imprecise assignments: interval-valued masses with admissibility checking and normalization, combination on interval bounds (imprecise DSmT), lower and upper credibility and plausibility
+//! * Qualitative assignments: masses given by (refined) linguistic labels, label arithmetic, qualitative conjunctive, PCR5 and PCR6 fusion
+//! * Fusion trace: opt-in provenance of the fused elements (contributing conditions, shares redistributed by the referee, accounting of the conflict), exportable by means of serde
+//! * Conflict analysis: pairwise conflicts between sources, sources responsible for the conflict, detection and discounting or exclusion of outlier sources
+//! * Distance between assignments (Jousselme distance)
+//! * Classical and contextual discounting, and estimation of source reliabilities from labelled histories
+//! * Evidential k-nearest neighbours classifier on nalgebra feature vectors (feature `use_nalgebra`)
+//! * Evidential c-means clustering, producing credal partitions over a powerset of clusters (feature `use_nalgebra`)
+//! * Valuation-based systems: valuations on product frames, combination, marginalization and Shenoy-Shafer message passing on join trees
 //! * The notion of referee functions is implemented, thus enabling:
 //!   * the ability to generically define fusion rules
+//!   * the ability to define global rules by pre-processing the assignments or post-processing the fused assignment (averaging, Murphy and Yager rules)
 //!   * the design of generic engines for computing fused assignments
 //!     * Presently, an exact computation method with pruning is proposed  
 //! 
@@ -49,6 +62,9 @@
 //!   * Features `silx-types`, `serde` and `rkyv` are enabled
 //! * `serde` : implements serde serialization/deserialization for some types
 //! * `rkyv` : implements rkyv serialization/zero-copy deserialization for some types
+//! * `use_nalgebra` : implements learning tools based on nalgebra feature vectors (evidential k-nearest neighbours and evidential c-means); this feature is not enabled by default
+//! * `parallel` : implements `DiscountedFusion::par_fuse`, which dispatches the product of the assignments over worker threads
+//! * `testing` : implements the `testing` module, with proptest strategies and law checkers for lattices, transforms and rules
 //! * `silx-types` : builds implementations with silx types
 //!   * Silx types `f64slx`, `u128slx`, `u32slx` are used instead of native types `f64`, `u128`, `u32`, in order to implement lattices, elements and assignments  
 //! 
@@ -157,6 +173,10 @@ pub mod traits;
 /// Struct and Enum definitions
 pub mod structs;
 
+#[cfg(feature = "testing")]
+/// Property-based testing tools: strategies and law checkers
+pub mod testing;
+
 #[doc(hidden)]
 /// Probes for testing features activation
 pub mod probes;
\ No newline at end of file
@@ -231,11 +231,12 @@ impl<X> AssignmentBuilder<X> where X: Clone + Eq + Ord + Hash, {
     }
 
     /// Compute the cumulative weight of the assignment
+    /// * Weights are summed by increasing order, so that the result does not depend on the hashing of the elements
     /// * Output: the cumulative weight or an error if some weights are non finite or negative
     pub fn cumul_weight(&self) -> Result<f64slx, String> {
         let mut cumul = 0.0;
-        let elements = &self.elements.elements;
-        for (_,rw) in elements.iter() {
+        let elements = &self.elements.ord_elements;
+        for hidden::OrdData((_,rw)) in elements.iter() {
             let w = (*rw).unslx();
             if w.is_finite() && w >= 0.0 {
                 cumul += w;
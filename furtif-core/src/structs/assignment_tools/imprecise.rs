@@ -0,0 +1,146 @@
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the Lesser GNU General Public License as published
+// by the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// Lesser GNU General Public License for more details.
+
+// You should have received a copy of the Lesser GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+// Copyright 2024 Frederic Dambreville, Jean Dezert Developers.
+
+
+use std::{ collections::HashMap, fmt::{ Debug, Display, }, hash::Hash, };
+
+use hashed_type_def::HashedTypeDef;
+use crate::types::{ u128slx, f64slx, SlxInto, IntoSlx, };
+
+#[cfg(feature = "serde")] use serde::{ Serialize as SerdeSerialize, Deserialize as SerdeDeserialize, };
+#[cfg(feature = "rkyv")] use rkyv::{ Archive, Serialize as RkyvSerialize, Deserialize as RkyvDeserialize, };
+
+use super::{ Assignment, ASSIGNMENT_EPSILON, };
+
+#[derive(Clone,HashedTypeDef,)]
+#[cfg_attr(feature = "rkyv", derive(Archive,RkyvSerialize,RkyvDeserialize))]
+/// Imprecise mass assignment: the mass of each focal element is an interval `[lower, upper]` within `[0, 1]`
+/// * Imprecise assignment contains a lattice hash and a sequence of encoded elements from this lattice, weighted by intervals
+/// * Imprecise assignment is admissible if a (precise) mass assignment can be chosen within the intervals
+/// * Imprecise assignments should be built by means of `ImpreciseBelief::imprecise_assignment` or converted from precise assignments
+/// * `X` : type of lattice element encoding
+pub struct ImpreciseAssignment<X> where X: Eq + Hash {
+    pub elements: HashMap<X,(f64slx,f64slx)>,
+    pub lattice_hash: u128slx,
+}
+
+impl<X> From<Assignment<X>> for ImpreciseAssignment<X> where X: Eq + Hash {
+    fn from(value: Assignment<X>) -> Self {
+        let Assignment { elements, lattice_hash, } = value;
+        let elements = elements.into_iter().map(|(x,w)| (x,(w,w))).collect();
+        Self { elements, lattice_hash, }
+    }
+}
+
+impl<X> ImpreciseAssignment<X> where X: Clone + Eq + Hash {
+    /// Sums of the lower bounds and of the upper bounds
+    /// * Output: pair of sums
+    pub fn bounds_sums(&self) -> (f64,f64) {
+        self.elements.values().fold((0.0,0.0), |(l,u),(lo,hi)| (l + (*lo).unslx(), u + (*hi).unslx()))
+    }
+
+    /// Test if the imprecise assignment is admissible, i.e. the lower bound of each element does not exceed its upper bound, 
+    /// the sum of the lower bounds does not exceed `1` and the sum of the upper bounds is not less than `1`
+    /// * Output: boolean
+    pub fn is_admissible(&self) -> bool {
+        if self.elements.values().any(|(lo,hi)| (*lo).unslx() > (*hi).unslx() + ASSIGNMENT_EPSILON) { return false; }
+        let (sum_lower, sum_upper) = self.bounds_sums();
+        sum_lower <= 1.0 + ASSIGNMENT_EPSILON && sum_upper >= 1.0 - ASSIGNMENT_EPSILON
+    }
+
+    /// Normalize the imprecise assignment, i.e. tighten the intervals to the masses actually reachable by admissible assignments
+    /// * The lower bound of `X` becomes `max(lower(X), 1 - ∑_{Y≠X} upper(Y))` and its upper bound becomes `min(upper(X), 1 - ∑_{Y≠X} lower(Y))`
+    /// * Elements with null upper bound are removed
+    /// * Output: nothing or an error if the assignment is not admissible
+    pub fn normalize(&mut self) -> Result<(),String> {
+        if !self.is_admissible() { return Err("Imprecise assignment is not admissible".to_string()); }
+        let (sum_lower, sum_upper) = self.bounds_sums();
+        for (lo,hi) in self.elements.values_mut() {
+            let (l, h) = ((*lo).unslx(), (*hi).unslx());
+            let (l, h) = (l.max(1.0 - (sum_upper - h)), h.min(1.0 - (sum_lower - l)));
+            *lo = l.clamp(0.0, 1.0).slx(); *hi = h.clamp(0.0, 1.0).slx();
+        }
+        self.elements.retain(|_,(_,hi)| (*hi).unslx() > ASSIGNMENT_EPSILON);
+        Ok(())
+    }
+
+    /// Assignment of the lower bounds; elements with null lower bound are discarded
+    /// * The result is generally not normalized
+    /// * Output: lower bounds assignment
+    pub fn lower(&self) -> Assignment<X> {
+        let elements = self.elements.iter().filter(|(_,(lo,_))| (*lo).unslx() > ASSIGNMENT_EPSILON)
+            .map(|(x,(lo,_))| (x.clone(),*lo)).collect();
+        Assignment { elements, lattice_hash: self.lattice_hash, }
+    }
+
+    /// Assignment of the upper bounds; elements with null upper bound are discarded
+    /// * The result is generally not normalized
+    /// * Output: upper bounds assignment
+    pub fn upper(&self) -> Assignment<X> {
+        let elements = self.elements.iter().filter(|(_,(_,hi))| (*hi).unslx() > ASSIGNMENT_EPSILON)
+            .map(|(x,(_,hi))| (x.clone(),*hi)).collect();
+        Assignment { elements, lattice_hash: self.lattice_hash, }
+    }
+}
+
+// implementation of Serde serialization
+#[cfg(feature = "serde")] 
+mod serding {
+    use std::collections::BTreeMap;
+    use super::{ ImpreciseAssignment as SerdingImpreciseAssignment, SerdeSerialize, SerdeDeserialize, Hash, };
+    use crate::types::{ SlxInto, IntoSlx, };
+    #[derive(SerdeSerialize,SerdeDeserialize)]
+    pub struct ImpreciseAssignment<X> where X: Eq + Ord, {
+        elements: BTreeMap<X,(f64,f64)>,
+        lattice_hash: u128,
+    }
+
+    impl<'de, X> SerdeDeserialize<'de> for SerdingImpreciseAssignment<X>  
+                                        where X: Clone + Eq + Ord + Hash + SerdeDeserialize<'de>, {
+        fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where D: serde::Deserializer<'de> {
+            let ImpreciseAssignment { elements, lattice_hash, } = ImpreciseAssignment::<X>::deserialize(deserializer)?;
+            let elements = elements.into_iter().map(|(x,(lo,hi))| (x,(lo.slx(),hi.slx()))).collect(); 
+            Ok(Self { elements, lattice_hash: lattice_hash.slx(), })
+        }
+    }
+    
+    impl<X> SerdeSerialize for SerdingImpreciseAssignment<X>  where X: Clone + Eq + Ord + Hash + SerdeSerialize, {
+        fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where S: serde::Serializer {
+            let SerdingImpreciseAssignment { elements, lattice_hash, } = self;
+            let elements = elements.iter().map(|(x,(lo,hi))| (x.clone(), ((*lo).unslx(), (*hi).unslx()))).collect();
+            let assignment = ImpreciseAssignment { elements, lattice_hash: (*lattice_hash).unslx(), };
+            assignment.serialize(serializer)
+        }
+    }
+}
+
+impl<X> Debug for ImpreciseAssignment<X> where X: Eq + Hash + Debug, {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let value = self.elements.iter()
+            .fold(" ".to_string(),|acc,(u,(lo,hi))| format!("{acc}{u:?} -> [{lo}, {hi}], "));
+        f.debug_struct("ImpreciseAssignment").field("elements", &value).field("lattice_hash", &self.lattice_hash).finish()
+    }
+}
+
+impl<X> Display for ImpreciseAssignment<X> where X: Eq + Hash + Display, {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let value = self.elements.iter()
+            .fold("[ ".to_string(),|acc,(u,(lo,hi))| format!("{acc}{u} -> [{lo:.4}, {hi:.4}], "));
+        f.write_str(&value)?;
+        f.write_str("]")
+    }
+}
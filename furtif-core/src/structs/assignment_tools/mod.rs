@@ -20,3 +20,7 @@ mod safe; pub use self::safe::{ SafeElement, SafeArray, };
 /// Definition of assignment and related tools
 mod assignment; pub use self::assignment::{ Assignment, AssignmentBuilder, exp_hidden, ASSIGNMENT_EPSILON, }; 
 pub (crate) use self::assignment::{ hidden, one_f64slx, zero_f64slx, };
+/// Definition of imprecise (interval-valued) assignments
+mod imprecise; pub use self::imprecise::ImpreciseAssignment;
+/// Definition of qualitative (linguistic labels) assignments
+mod qualitative; pub use self::qualitative::{ Label, LinguisticScale, QualitativeAssignment, };
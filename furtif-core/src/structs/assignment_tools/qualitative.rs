@@ -0,0 +1,301 @@
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the Lesser GNU General Public License as published
+// by the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// Lesser GNU General Public License for more details.
+
+// You should have received a copy of the Lesser GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+// Copyright 2024 Frederic Dambreville, Jean Dezert Developers.
+
+
+use std::{ collections::HashMap, fmt::{ Debug, Display, }, hash::Hash, ops::{ Add, Sub, Mul, Div, }, };
+
+use hashed_type_def::HashedTypeDef;
+use crate::types::{ u128slx, u32slx, f64slx, SlxInto, IntoSlx, };
+
+#[cfg(feature = "serde")] use serde::{ Serialize as SerdeSerialize, Deserialize as SerdeDeserialize, };
+#[cfg(feature = "rkyv")] use rkyv::{ Archive, Serialize as RkyvSerialize, Deserialize as RkyvDeserialize, };
+
+use super::{ Assignment, ASSIGNMENT_EPSILON, };
+
+#[derive(Clone, Copy, Debug, PartialEq, PartialOrd, HashedTypeDef)]
+#[cfg_attr(feature = "rkyv", derive(Archive,RkyvSerialize,RkyvDeserialize))]
+#[cfg_attr(feature = "serde", derive(SerdeSerialize, SerdeDeserialize))]
+/// Refined linguistic label `L_index` of a scale `L_0 < L_1 < ... < L_max_index`
+/// * Refined labels have real indices, so that the label arithmetic of DSmT is closed:
+///   * `L_i + L_j = L_(i+j)`, `L_i - L_j = L_(i-j)`, `α L_i = L_(α i)`
+///   * `L_i × L_j = L_(i j / max_index)`, `L_i ÷ L_j = L_(max_index i / j)`
+/// * Label arithmetic is isomorphic to the arithmetic of the numbers `index / max_index`
+/// * Operators panic when the labels are not defined on the same scale, and when dividing by the minimal label `L_0` 
+///   (see `checked_div` for a fallible division)
+pub struct Label {
+    pub index: f64,
+    pub max_index: u32,
+}
+
+impl Label {
+    /// Constructor of a refined label
+    /// * `index: f64` : index of the label
+    /// * `max_index: u32` : index of the maximal label of the scale
+    /// * Output: the label or an error
+    pub fn new(index: f64, max_index: u32) -> Result<Self,String> {
+        if max_index == 0 { return Err("Maximal label index should be positive".to_string()); }
+        if !index.is_finite() { return Err(format!("Label index {index} is not finite")); }
+        Ok(Self { index, max_index, })
+    }
+
+    /// Minimal label `L_0` of the scale
+    /// * `max_index: u32` : index of the maximal label of the scale
+    pub fn min_label(max_index: u32) -> Self { Self { index: 0.0, max_index, } }
+
+    /// Maximal label `L_max_index` of the scale
+    /// * `max_index: u32` : index of the maximal label of the scale
+    pub fn max_label(max_index: u32) -> Self { Self { index: max_index as f64, max_index, } }
+
+    /// Test if the label is refined, i.e. its index is not an integer
+    pub fn is_refined(&self) -> bool { (self.index - self.index.round()).abs() > ASSIGNMENT_EPSILON }
+
+    /// Numerical value `index / max_index` of the label
+    pub fn to_numeric(&self) -> f64 { self.index / self.max_index as f64 }
+
+    /// Refined label of a numerical value
+    /// * `value: f64` : numerical value
+    /// * `max_index: u32` : index of the maximal label of the scale
+    pub fn from_numeric(value: f64, max_index: u32) -> Self { Self { index: value * max_index as f64, max_index, } }
+
+    /// Division of labels, `L_i ÷ L_j = L_(max_index i / j)`
+    /// * `rhs: Self` : divisor label
+    /// * Output: the quotient or an error if the scales mismatch or if the divisor is the minimal label `L_0`
+    pub fn checked_div(self, rhs: Self) -> Result<Self,String> {
+        if self.max_index != rhs.max_index { return Err("Mismatching label scales".to_string()); }
+        if rhs.index == 0.0 { return Err("Division by the minimal label".to_string()); }
+        Ok(Self { index: self.max_index as f64 * self.index / rhs.index, ..self })
+    }
+
+    /// For intern use: check the scales of two labels
+    fn check_scale(&self, other: &Self) {
+        assert_eq!(self.max_index, other.max_index, "Mismatching label scales");
+    }
+}
+
+impl Add for Label {
+    type Output = Label;
+    fn add(self, rhs: Self) -> Self::Output { self.check_scale(&rhs); Self { index: self.index + rhs.index, ..self } }
+}
+
+impl Sub for Label {
+    type Output = Label;
+    fn sub(self, rhs: Self) -> Self::Output { self.check_scale(&rhs); Self { index: self.index - rhs.index, ..self } }
+}
+
+impl Mul for Label {
+    type Output = Label;
+    fn mul(self, rhs: Self) -> Self::Output { 
+        self.check_scale(&rhs); Self { index: self.index * rhs.index / self.max_index as f64, ..self } 
+    }
+}
+
+impl Div for Label {
+    type Output = Label;
+    fn div(self, rhs: Self) -> Self::Output { 
+        match self.checked_div(rhs) { Ok(label) => label, Err(e) => panic!("{e}"), }
+    }
+}
+
+impl Mul<f64> for Label {
+    type Output = Label;
+    fn mul(self, rhs: f64) -> Self::Output { Self { index: self.index * rhs, ..self } }
+}
+
+impl Display for Label {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.is_refined() { write!(f, "L{:.2}", self.index) } else { write!(f, "L{}", self.index.round()) }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, HashedTypeDef)]
+#[cfg_attr(feature = "rkyv", derive(Archive,RkyvSerialize,RkyvDeserialize))]
+#[cfg_attr(feature = "serde", derive(SerdeSerialize, SerdeDeserialize))]
+/// Ordered scale of linguistic labels `L_0 < L_1 < ... < L_max`, e.g. `none < low < medium < high < certain`
+/// * The first label means the absence of mass, and the last label means the whole mass
+pub struct LinguisticScale {
+    labels: Vec<String>,
+}
+
+impl LinguisticScale {
+    /// Constructor of a linguistic scale
+    /// * `labels: &[&str]` : ordered sequence of at least 2 distinct label names
+    /// * Output: the scale or an error
+    pub fn new(labels: &[&str]) -> Result<Self,String> {
+        if labels.len() < 2 { return Err("Linguistic scale should contain at least 2 labels".to_string()); }
+        for (u,name) in labels.iter().enumerate() {
+            if labels[..u].contains(name) { return Err(format!("Label {name} is duplicated")); }
+        }
+        Ok(Self { labels: labels.iter().map(|name| name.to_string()).collect(), })
+    }
+
+    /// Index of the maximal label
+    pub fn max_index(&self) -> u32 { (self.labels.len() - 1) as u32 }
+
+    /// Names of the labels
+    pub fn labels(&self) -> &[String] { &self.labels }
+
+    /// Label of a given name
+    /// * `name: &str` : name of the label
+    /// * Output: the label or an error
+    pub fn label(&self, name: &str) -> Result<Label,String> {
+        match self.labels.iter().position(|l| l == name) {
+            Some(u) => Ok(Label { index: u as f64, max_index: self.max_index(), }),
+            None => Err(format!("Label {name} is unknown")),
+        }
+    }
+
+    /// Name of a label
+    /// * Refined labels are named by means of their index, e.g. `L2.50`
+    /// * `label: &Label` : label
+    /// * Output: the name or an error if the label is not defined on the scale
+    pub fn name(&self, label: &Label) -> Result<String,String> {
+        if label.max_index != self.max_index() { return Err("Mismatching label scales".to_string()); }
+        let rounded = label.index.round();
+        if label.is_refined() || rounded < 0.0 || rounded > self.max_index() as f64 { Ok(format!("{label}")) } 
+        else { Ok(self.labels[rounded as usize].clone()) }
+    }
+}
+
+#[derive(Clone,HashedTypeDef,)]
+#[cfg_attr(feature = "rkyv", derive(Archive,RkyvSerialize,RkyvDeserialize))]
+/// Qualitative mass assignment: the masses are refined linguistic labels of a scale
+/// * Qualitative assignment contains a lattice hash, the maximal index of the label scale and a sequence of encoded elements 
+///   from the lattice, weighted by label indices
+/// * Qualitative assignment is normalized when the sum of its labels is the maximal label
+/// * Qualitative assignments should be built by means of `QualitativeBelief::qualitative_assignment`
+/// * `X` : type of lattice element encoding
+pub struct QualitativeAssignment<X> where X: Eq + Hash {
+    pub elements: HashMap<X,f64slx>,
+    pub max_index: u32slx,
+    pub lattice_hash: u128slx,
+}
+
+impl<X> QualitativeAssignment<X> where X: Clone + Eq + Hash {
+    /// Label of an element; the minimal label if the element is not focal
+    /// * `element: &X` : encoded element
+    /// * Output: the label
+    pub fn label(&self, element: &X) -> Label {
+        let index = self.elements.get(element).map(|i| (*i).unslx()).unwrap_or(0.0);
+        Label { index, max_index: self.max_index.unslx(), }
+    }
+
+    /// Sum of the labels
+    pub fn sum(&self) -> Label {
+        let index = self.elements.values().map(|i| (*i).unslx()).sum::<f64>();
+        Label { index, max_index: self.max_index.unslx(), }
+    }
+
+    /// Test if the sum of the labels is the maximal label
+    pub fn is_normalized(&self) -> bool {
+        let max_index = self.max_index.unslx() as f64;
+        (self.sum().index - max_index).abs() <= ASSIGNMENT_EPSILON * max_index
+    }
+
+    /// Qualitative normalization: each label is divided by the sum of the labels
+    /// * Output: nothing or an error if the sum is the minimal label
+    pub fn normalize(&mut self) -> Result<(),String> {
+        let sum = self.sum();
+        if sum.index <= ASSIGNMENT_EPSILON { return Err("Sum of labels is minimal, cannot be normalized".to_string()); }
+        let max_index = self.max_index.unslx();
+        for index in self.elements.values_mut() { *index = (Label { index: (*index).unslx(), max_index, } / sum).index.slx(); }
+        Ok(())
+    }
+
+    /// Approximate the refined labels by labels of the scale
+    /// * Indices are rounded so that their sum is kept when it is an integer (largest remainder method)
+    /// * Elements approximated by the minimal label are removed
+    /// * Output: the approximated assignment
+    pub fn approximate(&self) -> Self {
+        let mut indices = self.elements.iter().map(|(x,i)| (x.clone(),(*i).unslx())).collect::<Vec<_>>();
+        let total = indices.iter().map(|(_,i)| *i).sum::<f64>().round();
+        indices.sort_by(|(_,i),(_,j)| (j - j.floor()).total_cmp(&(i - i.floor())));
+        let nb_up = (total - indices.iter().map(|(_,i)| i.floor()).sum::<f64>()).max(0.0) as usize;
+        let elements = indices.into_iter().enumerate().map(|(u,(x,i))| {
+            (x, if u < nb_up { i.floor() + 1.0 } else { i.floor() })
+        }).filter(|(_,i)| *i > 0.0).map(|(x,i)| (x, i.slx())).collect();
+        Self { elements, max_index: self.max_index, lattice_hash: self.lattice_hash, }
+    }
+
+    /// Numerical assignment isomorphic to the qualitative assignment, i.e. with weights `index / max_index`
+    /// * The result is not normalized when the qualitative assignment is not normalized
+    pub fn to_numeric(&self) -> Assignment<X> {
+        let max_index = self.max_index.unslx() as f64;
+        let elements = self.elements.iter().filter(|(_,i)| (**i).unslx() > ASSIGNMENT_EPSILON)
+            .map(|(x,i)| (x.clone(), ((*i).unslx() / max_index).slx())).collect();
+        Assignment { elements, lattice_hash: self.lattice_hash, }
+    }
+
+    /// Qualitative assignment isomorphic to a numerical assignment, i.e. with label indices `weight × max_index`
+    /// * `mass: &Assignment<X>` : numerical assignment
+    /// * `max_index: u32` : index of the maximal label of the scale
+    pub fn from_numeric(mass: &Assignment<X>, max_index: u32) -> Self {
+        let elements = mass.elements.iter().map(|(x,w)| (x.clone(), ((*w).unslx() * max_index as f64).slx())).collect();
+        Self { elements, max_index: max_index.slx(), lattice_hash: mass.lattice_hash, }
+    }
+}
+
+// implementation of Serde serialization
+#[cfg(feature = "serde")] 
+mod serding {
+    use std::collections::BTreeMap;
+    use super::{ QualitativeAssignment as SerdingQualitativeAssignment, SerdeSerialize, SerdeDeserialize, Hash, };
+    use crate::types::{ SlxInto, IntoSlx, };
+    #[derive(SerdeSerialize,SerdeDeserialize)]
+    pub struct QualitativeAssignment<X> where X: Eq + Ord, {
+        elements: BTreeMap<X,f64>,
+        max_index: u32,
+        lattice_hash: u128,
+    }
+
+    impl<'de, X> SerdeDeserialize<'de> for SerdingQualitativeAssignment<X>  
+                                        where X: Clone + Eq + Ord + Hash + SerdeDeserialize<'de>, {
+        fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where D: serde::Deserializer<'de> {
+            let QualitativeAssignment { elements, max_index, lattice_hash, } = QualitativeAssignment::<X>::deserialize(deserializer)?;
+            let elements = elements.into_iter().map(|(x,i)| (x,i.slx())).collect(); 
+            Ok(Self { elements, max_index: max_index.slx(), lattice_hash: lattice_hash.slx(), })
+        }
+    }
+    
+    impl<X> SerdeSerialize for SerdingQualitativeAssignment<X>  where X: Clone + Eq + Ord + Hash + SerdeSerialize, {
+        fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where S: serde::Serializer {
+            let SerdingQualitativeAssignment { elements, max_index, lattice_hash, } = self;
+            let elements = elements.iter().map(|(x,i)| (x.clone(), (*i).unslx())).collect();
+            let assignment = QualitativeAssignment { 
+                elements, max_index: (*max_index).unslx(), lattice_hash: (*lattice_hash).unslx(), 
+            };
+            assignment.serialize(serializer)
+        }
+    }
+}
+
+impl<X> Debug for QualitativeAssignment<X> where X: Eq + Hash + Debug, {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let value = self.elements.iter()
+            .fold(" ".to_string(),|acc,(u,i)| format!("{acc}{u:?} -> L{i}, "));
+        f.debug_struct("QualitativeAssignment").field("elements", &value).field("max_index", &self.max_index)
+            .field("lattice_hash", &self.lattice_hash).finish()
+    }
+}
+
+impl<X> Display for QualitativeAssignment<X> where X: Clone + Eq + Hash + Display, {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let value = self.elements.keys()
+            .fold("[ ".to_string(),|acc,u| format!("{acc}{u} -> {}, ", self.label(u)));
+        f.write_str(&value)?;
+        f.write_str("]")
+    }
+}
@@ -0,0 +1,238 @@
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the Lesser GNU General Public License as published
+// by the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// Lesser GNU General Public License for more details.
+
+// You should have received a copy of the Lesser GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+// Copyright 2024 Frederic Dambreville, Jean Dezert Developers.
+
+
+use std::{ collections::HashSet, hash::Hash, };
+
+use hashed_type_def::HashedTypeDef;
+use crate::{
+    types::{ f64slx, SlxInto, IntoSlx, },
+    structs::Assignment,
+    traits::{ LatticeWithLeaves, BeliefMetric, Discounting, },
+};
+
+#[cfg(feature = "serde")] use serde::{ Serialize as SerdeSerialize, Deserialize as SerdeDeserialize, };
+#[cfg(feature = "rkyv")] use rkyv::{ Archive, Serialize as RkyvSerialize, Deserialize as RkyvDeserialize, };
+
+#[derive(HashedTypeDef, Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "rkyv", derive(Archive,RkyvSerialize,RkyvDeserialize))]
+#[cfg_attr(feature = "serde", derive(SerdeSerialize, SerdeDeserialize))]
+/// Measure of the conflict between two sources, within `[0, 1]`
+pub enum ConflictMeasure {
+    /// Mass of bottom within the conjunctive combination of the sources
+    Conjunctive,
+    /// Jousselme distance between the sources
+    Jousselme,
+    /// Maximal difference between the pignistic probabilities of the sources
+    Pignistic,
+}
+
+#[derive(Clone, Debug)]
+/// Result of a conflict analysis
+pub struct ConflictReport {
+    /// Pairwise conflicts between the sources; symmetric matrix with null diagonal
+    pub pairwise: Vec<Vec<f64slx>>,
+    /// Conflict of each source, i.e. its mean conflict with the other sources
+    pub source_conflicts: Vec<f64slx>,
+    /// Global conflict, i.e. the mean of the pairwise conflicts
+    pub global_conflict: f64slx,
+    /// Indices of the sources responsible for most of the conflict (see `ConflictAnalyzer::with_responsibility`), 
+    /// sorted by decreasing responsibility
+    pub responsible: Vec<usize>,
+    /// Indices of the sources whose conflict exceeds the outlier threshold
+    pub outliers: Vec<usize>,
+}
+
+#[derive(Copy, Clone, Debug)]
+/// Analyzer of the conflict between sources, with detection of outlier sources
+/// * The conflict of a source is its mean pairwise conflict with the other sources; a source is flagged as outlier 
+///   when its conflict exceeds a threshold
+/// * The sources responsible for most of the conflict are selected greedily: the source whose removal cancels 
+///   the greatest part of the remaining pairwise conflicts is selected first, until the cancelled part reaches the responsibility ratio
+/// * Outliers can then be discounted or excluded before the fusion
+pub struct ConflictAnalyzer {
+    measure: ConflictMeasure,
+    outlier_threshold: f64,
+    responsibility: f64,
+}
+
+impl ConflictAnalyzer {
+    /// Constructor of conflict analyzer; the responsibility ratio is `0.5` by default
+    /// * `measure: ConflictMeasure` : measure of the pairwise conflicts
+    /// * `outlier_threshold: f64slx` : threshold of the conflict of a source above which it is flagged as outlier, within `[0, 1]`
+    /// * Output: the conflict analyzer or an error
+    pub fn new(measure: ConflictMeasure, outlier_threshold: f64slx) -> Result<Self,String> {
+        let outlier_threshold = outlier_threshold.unslx();
+        if !(0.0..=1.0).contains(&outlier_threshold) { return Err(format!("Outlier threshold {outlier_threshold} is not within [0, 1]")); }
+        Ok(Self { measure, outlier_threshold, responsibility: 0.5, })
+    }
+
+    /// Set the responsibility ratio, i.e. the part of the pairwise conflicts which should be explained by the responsible sources
+    /// * `responsibility: f64slx` : responsibility ratio, within `]0, 1]`
+    /// * Output: the updated conflict analyzer or an error
+    pub fn with_responsibility(self, responsibility: f64slx) -> Result<Self,String> {
+        let responsibility = responsibility.unslx();
+        if !(responsibility > 0.0 && responsibility <= 1.0) { return Err(format!("Responsibility ratio {responsibility} is not within ]0, 1]")); }
+        Ok(Self { responsibility, ..self })
+    }
+
+    /// Conflict between two sources
+    /// * `lattice: &L` : lattice of definition of the assignments
+    /// * `left: &Assignment<L::Item>` : left mass assignment
+    /// * `right: &Assignment<L::Item>` : right mass assignment
+    /// * `L` : type of lattice
+    /// * Output: conflict or error
+    pub fn conflict<L>(&self, lattice: &L, left: &Assignment<L::Item>, right: &Assignment<L::Item>) -> Result<f64slx,String> 
+                                                                    where L: LatticeWithLeaves, L::Item: Ord + Hash, {
+        if &left.lattice_hash != lattice.ref_lattice_hash() || &right.lattice_hash != lattice.ref_lattice_hash() { 
+            return Err("Mismatching lattice hash".to_string()); 
+        }
+        match self.measure {
+            ConflictMeasure::Conjunctive => {
+                let mut conflict = 0.0;
+                for (x,v) in &left.elements { for (y,w) in &right.elements {
+                    if unsafe { lattice.unsafe_is_bottom(&lattice.unsafe_meet(x, y)) } { conflict += (*v * *w).unslx(); }
+                } }
+                Ok(conflict.slx())
+            },
+            ConflictMeasure::Jousselme => lattice.jousselme_distance(left, right),
+            ConflictMeasure::Pignistic => {
+                let (left, right) = (lattice.mass_to_pignistic(left)?, lattice.mass_to_pignistic(right)?);
+                let leaves = left.elements.keys().chain(right.elements.keys()).collect::<HashSet<_>>();
+                let difference = leaves.into_iter().map(|l| {
+                    let (p, q) = (left.elements.get(l).map(|p| (*p).unslx()), right.elements.get(l).map(|q| (*q).unslx()));
+                    (p.unwrap_or(0.0) - q.unwrap_or(0.0)).abs()
+                }).sum::<f64>();
+                Ok((0.5 * difference).slx())
+            },
+        }
+    }
+
+    /// Pairwise conflicts between sources
+    /// * `lattice: &L` : lattice of definition of the assignments
+    /// * `bbas: &[&Assignment<L::Item>]` : sequence of assignments
+    /// * `L` : type of lattice
+    /// * Output: symmetric matrix of the pairwise conflicts or error
+    pub fn pairwise<L>(&self, lattice: &L, bbas: &[&Assignment<L::Item>]) -> Result<Vec<Vec<f64slx>>,String> 
+                                                                    where L: LatticeWithLeaves, L::Item: Ord + Hash, {
+        let mut pairwise = vec![vec![0.0.slx(); bbas.len()]; bbas.len()];
+        for i in 0..bbas.len() { for j in (i+1)..bbas.len() {
+            let conflict = self.conflict(lattice, bbas[i], bbas[j])?;
+            pairwise[i][j] = conflict; pairwise[j][i] = conflict;
+        } }
+        Ok(pairwise)
+    }
+
+    /// Analyze the conflict between sources
+    /// * `lattice: &L` : lattice of definition of the assignments
+    /// * `bbas: &[&Assignment<L::Item>]` : sequence of at least 2 assignments
+    /// * `L` : type of lattice
+    /// * Output: conflict report or error
+    pub fn analyze<L>(&self, lattice: &L, bbas: &[&Assignment<L::Item>]) -> Result<ConflictReport,String> 
+                                                                    where L: LatticeWithLeaves, L::Item: Ord + Hash, {
+        let len = bbas.len();
+        if len < 2 { return Err("Conflict analysis needs at least 2 sources".to_string()); }
+        let pairwise = self.pairwise(lattice, bbas)?;
+        let conflicts = pairwise.iter().map(|row| row.iter().map(|c| (*c).unslx()).collect::<Vec<_>>()).collect::<Vec<_>>();
+        let source_conflicts = conflicts.iter().map(|row| row.iter().sum::<f64>() / (len - 1) as f64).collect::<Vec<_>>();
+        let total = conflicts.iter().flatten().sum::<f64>() / 2.0;
+        let global_conflict = total / (len * (len - 1) / 2) as f64;
+        // greedy selection of the responsible sources
+        let mut responsible = Vec::new();
+        let mut remaining = (0..len).collect::<Vec<_>>();
+        let mut cancelled = 0.0;
+        while total > 0.0 && cancelled < self.responsibility * total && !remaining.is_empty() {
+            let (rank, gain) = remaining.iter().enumerate().map(|(rank,i)| {
+                (rank, remaining.iter().map(|j| conflicts[*i][*j]).sum::<f64>())
+            }).fold((0, f64::NEG_INFINITY), |best,candidate| if candidate.1 > best.1 { candidate } else { best });
+            cancelled += gain;
+            responsible.push(remaining.remove(rank));
+        }
+        let outliers = (0..len).filter(|i| source_conflicts[*i] > self.outlier_threshold).collect();
+        Ok(ConflictReport {
+            pairwise, source_conflicts: source_conflicts.into_iter().map(|c| c.slx()).collect(), 
+            global_conflict: global_conflict.slx(), responsible, outliers,
+        })
+    }
+
+    /// Discount the outlier sources; the reliability of an outlier is `1 - conflict` of the source
+    /// * `lattice: &L` : lattice of definition of the assignments
+    /// * `bbas: &[&Assignment<L::Item>]` : sequence of assignments
+    /// * `report: &ConflictReport` : conflict report of the assignments
+    /// * `L` : type of lattice
+    /// * Output: sequence of assignments, where the outliers are discounted, or error
+    pub fn discount_outliers<L>(&self, lattice: &L, bbas: &[&Assignment<L::Item>], report: &ConflictReport) 
+                                -> Result<Vec<Assignment<L::Item>>,String> where L: LatticeWithLeaves, L::Item: Ord + Hash, {
+        if report.source_conflicts.len() != bbas.len() { return Err("Report does not match the assignments".to_string()); }
+        bbas.iter().enumerate().map(|(i,bba)| {
+            let reliability = if report.outliers.contains(&i) { 1.0 - report.source_conflicts[i].unslx() } else { 1.0 };
+            lattice.discount(bba, reliability.slx())
+        }).collect()
+    }
+
+    /// Exclude the outlier sources
+    /// * `bbas: &[&Assignment<X>]` : sequence of assignments
+    /// * `report: &ConflictReport` : conflict report of the assignments
+    /// * `X` : type of lattice element encoding
+    /// * Output: sequence of the assignments which are not outliers, or error
+    pub fn exclude_outliers<'a,X>(&self, bbas: &[&'a Assignment<X>], report: &ConflictReport) 
+                                                    -> Result<Vec<&'a Assignment<X>>,String> where X: Eq + Hash, {
+        if report.source_conflicts.len() != bbas.len() { return Err("Report does not match the assignments".to_string()); }
+        Ok(bbas.iter().enumerate().filter(|(i,_)| !report.outliers.contains(i)).map(|(_,bba)| *bba).collect())
+    }
+}
+
+pub mod experiment {
+    use crate::{
+        types::IntoSlx,
+        structs::{ Powerset, DiscountedFuser, EnumRule, ConflictAnalyzer, ConflictMeasure, },
+        traits::{ Lattice, DiscountedFusion, },
+    };
+
+    /// Experimentation with conflict analysis
+    pub fn exp_conflict() -> Result<(),String> {
+        println!("===================== conflict =====");
+        let lattice = Powerset::new_with_label(&["A".to_string(), "B".to_string(), "C".to_string()], 1024)?;
+        let (a, b, c) = (lattice.from_str("A")?, lattice.from_str("B")?, lattice.from_str("C")?);
+        let ab = lattice.from_str("A | B")?;
+        let sensors = [
+            lattice.assignment() + (a, 0.7) + (ab, 0.2) + (lattice.top(), 0.1) + (),
+            lattice.assignment() + (a, 0.6) + (b, 0.1) + (lattice.top(), 0.3) + (),
+            lattice.assignment() + (a, 0.8) + (lattice.top(), 0.2) + (),
+            lattice.assignment() + (c, 0.9) + (b, 0.1) + (), // faulty sensor
+        ];
+        let bbas = sensors.iter().collect::<Vec<_>>();
+        let engine = DiscountedFuser::new(512..=1024);
+        for measure in [ConflictMeasure::Conjunctive, ConflictMeasure::Jousselme, ConflictMeasure::Pignistic] {
+            println!("-------------------- {measure:?} -----");
+            let analyzer = ConflictAnalyzer::new(measure, 0.5.slx())?;
+            let report = analyzer.analyze(&lattice, &bbas)?;
+            for row in &report.pairwise { println!("{:?}", row.iter().map(|c| format!("{c:.3}")).collect::<Vec<_>>()); }
+            println!("source conflicts -> {:?}", report.source_conflicts.iter().map(|c| format!("{c:.3}")).collect::<Vec<_>>());
+            println!("global conflict -> {:.4}", report.global_conflict);
+            println!("responsible -> {:?} ; outliers -> {:?}", report.responsible, report.outliers);
+            let discounted = analyzer.discount_outliers(&lattice, &bbas, &report)?;
+            let (m, z) = engine.fuse(&lattice, &EnumRule::DempsterShafer, &discounted.iter().collect::<Vec<_>>())?;
+            println!("fused with discounted outliers -> {m} ; conflict -> {z:.4}");
+            let (m, z) = engine.fuse(&lattice, &EnumRule::DempsterShafer, &analyzer.exclude_outliers(&bbas, &report)?)?;
+            println!("fused without outliers -> {m} ; conflict -> {z:.4}");
+        }
+        let (m, z) = engine.fuse(&lattice, &EnumRule::DempsterShafer, &bbas)?;
+        println!("-------------------- reference -----");
+        println!("fused with all sources -> {m} ; conflict -> {z:.4}");
+        println!();
+        Ok(())
+    }
+}
@@ -18,7 +18,7 @@ use std::ops::RangeInclusive;
 
 use hashed_type_def::HashedTypeDef;
 #[cfg(feature = "rkyv")] use rkyv::{Archive, Serialize as RkyvSerialize, Deserialize as RkyvDeserialize, };
-use crate::types::{ u32slx, SlxInto, IntoSlx, };
+use crate::types::{ u32slx, f64slx, SlxInto, IntoSlx, };
 // #[cfg(not(feature = "silx-types"))] use crate::fake_slx::{ u32slx, FakeSlx };
 // #[cfg(feature = "silx-types")] use silx_types::{u32slx, IntoSlx, SlxInto};
 
@@ -29,8 +29,9 @@ use crate::traits::DiscountedFusion;
 #[cfg_attr(feature = "rkyv", derive(Archive,RkyvSerialize,RkyvDeserialize))]
 /// Generic fusion engine based on exact computation, but with mass discounting when above a given range
 /// * Mass discounting is performed by iteratively putting the mass of the weakest assigments on their disjunction 
+/// * Optionally, the conditions of the product with weight below a threshold are pruned before fusion
 pub struct DiscountedFuser {
-    range_min: u32slx, range_max: u32slx,
+    range_min: u32slx, range_max: u32slx, threshold: f64slx,
 }
 
 // implementation of Serde serialization
@@ -43,25 +44,27 @@ pub struct DiscountedFuser {
     };
     #[derive(SerdeSerialize,SerdeDeserialize)]
     pub struct DiscountedFuser {
-        range_min: u32, range_max: u32,
+        range_min: u32, range_max: u32, #[serde(default)] threshold: f64,
     }
 
     impl<'de> SerdeDeserialize<'de> for SerdingDiscountedFuser {
         fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
         where D: serde::Deserializer<'de> {
-            let DiscountedFuser { range_min, range_max } = DiscountedFuser::deserialize(deserializer)?;
+            let DiscountedFuser { range_min, range_max, threshold } = DiscountedFuser::deserialize(deserializer)?;
             let range_min = range_min.slx();
             let range_max = range_max.slx();
-            Ok(Self { range_min, range_max })
+            let threshold = threshold.slx();
+            Ok(Self { range_min, range_max, threshold })
         }
     }
     impl SerdeSerialize for SerdingDiscountedFuser {
         fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
         where S: serde::Serializer {
-            let Self { range_min, range_max  } = *self;
+            let Self { range_min, range_max, threshold } = *self;
             let range_min = range_min.unslx();
             let range_max = range_max.unslx();
-            let discounted_fuser = DiscountedFuser { range_min, range_max };
+            let threshold = threshold.unslx();
+            let discounted_fuser = DiscountedFuser { range_min, range_max, threshold };
             discounted_fuser.serialize(serializer)
         }
     }
@@ -76,15 +79,23 @@ impl DiscountedFuser {
         let range_max = (*range.end()) as u32;
         let range_min = range_min.slx();
         let range_max = range_max.slx();
-        Self { range_min, range_max }
+        let threshold = 0.0.slx();
+        Self { range_min, range_max, threshold }
     }
+
+    /// Set the threshold for the early pruning of the product of the assignments
+    /// * `threshold: f64slx` : conditions of the product with weight below the threshold are discarded; zero means no pruning
+    /// * Output: fusion engine
+    pub fn with_threshold(self, threshold: f64slx) -> Self { Self { threshold, ..self } }
 }
 
 impl DiscountedFusion for DiscountedFuser {
     fn size_range(&self) -> RangeInclusive<usize> {
-        let Self { range_min, range_max } = *self;
+        let Self { range_min, range_max, .. } = *self;
         let range_min = range_min.unslx() as usize;
         let range_max = range_max.unslx() as usize;
         range_min..=range_max
     }
+
+    fn product_threshold(&self) -> f64slx { self.threshold }
 }
\ No newline at end of file
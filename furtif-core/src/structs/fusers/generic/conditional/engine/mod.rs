@@ -16,5 +16,10 @@
 
 /// Discounted implementation of fusion engine
 mod discounted; pub use self::discounted::DiscountedFuser;
+/// Lazy product of assignments
+mod product; pub use self::product::{ BbaProduct, experiment::exp_bba_product, };
+/// Trace of the fusion
+mod trace; pub use self::trace::{ FusionTrace, TracedElement, TracedContribution, experiment::exp_fusion_trace, };
+pub (crate) use self::trace::FusionTraceBuilder;
 /// Sampled implementation of fusion engine
 mod sampled; // unimplemented for this version
\ No newline at end of file
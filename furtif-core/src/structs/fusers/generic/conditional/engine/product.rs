@@ -0,0 +1,200 @@
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the Lesser GNU General Public License as published
+// by the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// Lesser GNU General Public License for more details.
+
+// You should have received a copy of the Lesser GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+// Copyright 2024 Frederic Dambreville, Jean Dezert Developers.
+
+
+use std::hash::Hash;
+
+use crate::{
+    types::{ u128slx, f64slx, IntoSlx, },
+    structs::{ Assignment, SafeArray, },
+    traits::Lattice,
+};
+
+#[derive(Clone, Debug)]
+/// Lazy iterator over the weighted conditions of the tensor product of assignments
+/// * The conditions are produced one by one, so that the memory footprint is `O(∑|bba_i|)` instead of `O(∏|bba_i|)`
+/// * The elements of each assignment are sorted by decreasing weight, and the last assignment is iterated first
+/// * Early pruning: a partial product whose weight falls below the threshold is discarded together with all its completions
+///   * since the weights of normalized assignments do not exceed `1`, this is the same as discarding the conditions with weight below the threshold
+///   * the cumulated weight of the discarded conditions is available by means of `pruned_weight`
+/// * A null threshold disables the pruning
+pub struct BbaProduct<'a,X> {
+    lattice_hash: u128slx,
+    factors: Vec<Vec<(&'a X,f64slx)>>,
+    tails: Vec<f64slx>,
+    fixed: usize,
+    digits: Vec<usize>,
+    prefix: Vec<f64slx>,
+    threshold: f64slx,
+    pruned: f64slx,
+    started: bool,
+    done: bool,
+}
+
+impl<'a,X> BbaProduct<'a,X> where X: Eq + Ord + Hash, {
+    /// Constructor of the product iterator
+    /// * `lattice: &L` : lattice of definition of the assignments
+    /// * `bbas: &'a [&'a Assignment<X>]` : collection of assignments
+    /// * `threshold: f64slx` : pruning threshold of the products weights
+    /// * `L` : type of lattice
+    /// * Output: the product iterator or an error
+    pub fn new<L>(lattice: &L, bbas: &'a [&'a Assignment<X>], threshold: f64slx) -> Result<Self,String> where L: Lattice<Item = X>, {
+        let lattice_hash = lattice.lattice_hash();
+        // Compatibility tests:
+        for (u,bba) in bbas.iter().enumerate() {
+            if bba.lattice_hash != lattice_hash { return Err(format!("bbas of index {u} is not defined over lattice")); } 
+        }
+        let factors = bbas.iter().map(|bba| {
+            let mut factor = bba.elements.iter().map(|(x,w)| (x,*w)).collect::<Vec<_>>();
+            factor.sort_by(|(x,v),(y,w)| w.partial_cmp(v).unwrap_or(core::cmp::Ordering::Equal).then_with(|| x.cmp(y)));
+            factor
+        }).collect::<Vec<_>>();
+        let mut tails = vec![1.0.slx(); factors.len() + 1];
+        for u in (0..factors.len()).rev() {
+            tails[u] = tails[u+1] * factors[u].iter().map(|(_,w)| *w).sum::<f64slx>();
+        }
+        let len = factors.len();
+        Ok(Self { 
+            lattice_hash, factors, tails, fixed: 0, digits: vec![0; len], prefix: vec![1.0.slx(); len + 1], 
+            threshold, pruned: 0.0.slx(), started: false, done: false,
+        })
+    }
+
+    /// Number of conditions of the product, including the pruned conditions, or `None` if this number overflows
+    pub fn nb_conditions(&self) -> Option<usize> { Self::nb_conditions_within(&self.factors) }
+
+    /// For intern use: number of conditions of a product
+    fn nb_conditions_within(factors: &[Vec<(&'a X,f64slx)>]) -> Option<usize> {
+        factors.iter().try_fold(1usize, |acc,f| acc.checked_mul(f.len()))
+    }
+
+    /// Smallest depth such that fixing the conditions of the first `depth` assignments produces sub-products of at most `len` conditions
+    /// * `len: usize` : maximal number of conditions of the sub-products
+    /// * Output: the depth and the number of sub-products, or `None` if the number of sub-products overflows
+    pub fn chunk_depth(&self, len: usize) -> Option<(usize,usize)> {
+        let depth = (0..=self.factors.len()).find(|d| {
+            Self::nb_conditions_within(&self.factors[*d..]).map(|n| n <= len).unwrap_or(false)
+        })?;
+        Some((depth, Self::nb_conditions_within(&self.factors[..depth])?))
+    }
+
+    /// Sub-product obtained by fixing the conditions of the first `depth` assignments
+    /// * The sub-products of a given depth form a partition of the product; they are ranked in the iteration order of the product
+    /// * `depth: usize` : number of fixed assignments
+    /// * `rank: usize` : rank of the sub-product
+    /// * Output: the sub-product iterator or an error
+    pub fn chunk(&self, depth: usize, mut rank: usize) -> Result<Self,String> where X: Clone, {
+        if depth > self.factors.len() { return Err(format!("depth {depth} exceeds the number of assignments")); }
+        let mut chunk = Self { 
+            fixed: depth, digits: vec![0; self.factors.len()], prefix: vec![1.0.slx(); self.factors.len() + 1], 
+            pruned: 0.0.slx(), started: false, done: false, ..self.clone() 
+        };
+        for u in (0..depth).rev() {
+            let len = self.factors[u].len();
+            if len == 0 { chunk.done = true; return Ok(chunk); }
+            chunk.digits[u] = rank % len; rank /= len;
+        }
+        if rank > 0 { return Err("rank of sub-product is out of range".to_string()); }
+        for u in 0..depth { chunk.prefix[u+1] = chunk.prefix[u] * self.factors[u][chunk.digits[u]].1; }
+        if chunk.prefix[depth] < chunk.threshold {
+            chunk.pruned = chunk.prefix[depth] * chunk.tails[depth];
+            chunk.done = true;
+        }
+        Ok(chunk)
+    }
+
+    /// Cumulated weight of the pruned conditions, among the conditions already iterated
+    pub fn pruned_weight(&self) -> f64slx { self.pruned }
+
+    /// For intern use: complete the current partial product from `level`, backtracking until a non pruned condition is found
+    /// * Output: `true` if a condition is found, `false` if the product is exhausted
+    fn settle(&mut self, mut level: usize) -> bool {
+        let len = self.factors.len();
+        loop {
+            if level == len { return true; }
+            let factor = &self.factors[level];
+            let digit = self.digits[level];
+            if digit < factor.len() {
+                let weight = self.prefix[level] * factor[digit].1;
+                if weight >= self.threshold {
+                    self.prefix[level+1] = weight;
+                    level += 1;
+                    if level < len { self.digits[level] = 0; }
+                    continue;
+                }
+                // the remaining elements of this factor have smaller weights and are pruned
+                let remaining = factor[digit..].iter().map(|(_,w)| *w).sum::<f64slx>();
+                self.pruned += self.prefix[level] * remaining * self.tails[level+1];
+                self.digits[level] = factor.len();
+            }
+            if level == self.fixed { return false; }
+            level -= 1;
+            self.digits[level] += 1;
+        }
+    }
+}
+
+impl<'a,X> Iterator for BbaProduct<'a,X> where X: Eq + Ord + Hash, {
+    type Item = (SafeArray<'a,X>,f64slx);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done { return None; }
+        let len = self.factors.len();
+        let found = if self.started {
+            if len == self.fixed { false } else { self.digits[len-1] += 1; self.settle(len-1) }
+        } else {
+            self.started = true;
+            if self.fixed < len { self.digits[self.fixed] = 0; }
+            self.settle(self.fixed)
+        };
+        if !found { self.done = true; return None; }
+        let product = self.digits.iter().zip(&self.factors).map(|(d,f)| f[*d].0).collect();
+        Some((SafeArray { lattice_hash: self.lattice_hash, product, }, self.prefix[len]))
+    }
+}
+
+pub mod experiment {
+    use rand::prelude::*;
+    use crate::{
+        types::{ u128slx, IntoSlx, SlxInto, },
+        structs::{ Powerset, Assignment, BbaProduct, DiscountedFuser, EnumRule, },
+        traits::{ Lattice, DiscountedFusion, },
+    };
+
+    /// Experimentation with the lazy product of assignments
+    pub fn exp_bba_product() -> Result<(),String> {
+        println!("========================== bba product =====");
+        let lattice = Powerset::new(5, 1024)?;
+        let mut rng = StdRng::seed_from_u64(0);
+        let bbas = (0..4).map(|_| {
+            let mut bba = lattice.assignment();
+            for _ in 0..10 { bba.push(lattice.rand_element(&mut rng), rng.gen_range(0.0f64..1.0).powi(4).slx())?; }
+            bba.normalize()?;
+            Ok(bba.into())
+        }).collect::<Result<Vec<Assignment<u128slx>>,String>>()?;
+        let bbas = bbas.iter().collect::<Vec<_>>();
+        for threshold in [0.0, 1e-5, 1e-3] {
+            let mut products = BbaProduct::new(&lattice, &bbas, threshold.slx())?;
+            let (count, weight) = products.by_ref().fold((0usize, 0.0), |(c,s),(_,w)| (c + 1, s + w.unslx()));
+            println!("threshold {threshold:e} -> {count} / {:?} conditions, weight -> {weight:.6}, pruned weight -> {:.6}", 
+                products.nb_conditions(), products.pruned_weight());
+            let engine = DiscountedFuser::new(512..=1024).with_threshold(threshold.slx());
+            let (fused, z) = engine.fuse(&lattice, &EnumRule::DempsterShafer, &bbas)?;
+            println!("  fused size -> {}, z -> {z:.6}", fused.elements.len());
+        }
+        println!();
+        Ok(())
+    }
+}
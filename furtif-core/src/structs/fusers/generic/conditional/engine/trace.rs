@@ -0,0 +1,195 @@
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the Lesser GNU General Public License as published
+// by the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// Lesser GNU General Public License for more details.
+
+// You should have received a copy of the Lesser GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+// Copyright 2024 Frederic Dambreville, Jean Dezert Developers.
+
+
+use std::{ collections::BTreeMap, hash::Hash, };
+
+use hashed_type_def::HashedTypeDef;
+use crate::{ types::SlxInto, structs::Assignment, };
+
+#[cfg(feature = "serde")] use serde::{ Serialize as SerdeSerialize, Deserialize as SerdeDeserialize, };
+#[cfg(feature = "rkyv")] use rkyv::{ Archive, Serialize as RkyvSerialize, Deserialize as RkyvDeserialize, };
+
+#[derive(Clone, Debug, HashedTypeDef)]
+#[cfg_attr(feature = "rkyv", derive(Archive,RkyvSerialize,RkyvDeserialize))]
+#[cfg_attr(feature = "serde", derive(SerdeSerialize, SerdeDeserialize))]
+/// Contribution of a condition of the product of the assignments to a fused element
+/// * Weights are unnormalized, i.e. computed before the normalization of the fused assignment
+/// * `X` : type of lattice element encoding
+pub struct TracedContribution<X> {
+    /// Condition tuple: one focal element per source
+    pub conditions: Vec<X>,
+    /// Weight of the condition, i.e. product of the weights of its elements
+    pub condition_weight: f64,
+    /// Share of the condition given to the element by the referee
+    pub share: f64,
+    /// Contributed weight, i.e. `condition_weight × share`
+    pub weight: f64,
+    /// Is the element different from the meet of the condition, i.e. is the contribution redistributed by the referee?
+    pub redistributed: bool,
+}
+
+#[derive(Clone, Debug, HashedTypeDef)]
+#[cfg_attr(feature = "rkyv", derive(Archive,RkyvSerialize,RkyvDeserialize))]
+#[cfg_attr(feature = "serde", derive(SerdeSerialize, SerdeDeserialize))]
+/// Provenance of a fused element
+/// * `X` : type of lattice element encoding
+pub struct TracedElement<X> {
+    /// Fused element
+    pub element: X,
+    /// Contributions of the conditions to the element
+    pub contributions: Vec<TracedContribution<X>>,
+    /// Unnormalized weight coming from conditions whose meet is the element
+    pub direct_weight: f64,
+    /// Unnormalized weight redistributed to the element by the referee
+    pub redistributed_weight: f64,
+    /// Normalized weight of the element within the fused assignment, including weights merged by the reduction; 
+    /// zero if the element has been merged by the reduction
+    pub fused_weight: f64,
+}
+
+#[derive(Clone, Debug, HashedTypeDef)]
+#[cfg_attr(feature = "rkyv", derive(Archive,RkyvSerialize,RkyvDeserialize))]
+#[cfg_attr(feature = "serde", derive(SerdeSerialize, SerdeDeserialize))]
+/// Trace of a fusion, produced by `DiscountedFusion::fuse_traced`
+/// * The trace records, for each fused element, the contributing conditions and the weight redistributed by the referee
+/// * The trace records where the weight of the product went: conflicting conditions, weight discarded by the referee,
+///   weight pruned from the product, weight merged by the reduction of the fused assignment
+/// * Weights are stored with native types, so that the trace is easily exported for audit
+/// * The conditions are those of the bbas pre-processed by the referee (see `Referee::pre_process`); the changes made by
+///   `Referee::post_process` are not traced, but appear within the fused weights
+/// * `X` : type of lattice element encoding
+pub struct FusionTrace<X> {
+    /// Hash of the lattice
+    pub lattice_hash: u128,
+    /// Provenance of the elements, sorted by element
+    pub elements: Vec<TracedElement<X>>,
+    /// Weight of the conditions whose meet is bottom
+    pub conflicting_weight: f64,
+    /// Total weight redistributed by the referee
+    pub redistributed_weight: f64,
+    /// Weight of the conditions not assigned by the referee, which is removed by the normalization (e.g. Dempster-Shafer rule)
+    pub discarded_weight: f64,
+    /// Weight of the conditions pruned from the product (see `DiscountedFusion::product_threshold`)
+    pub pruned_weight: f64,
+    /// Weight of the traced elements merged by the reduction of the fused assignment (see `DiscountedFusion::size_range`)
+    pub reduced_weight: f64,
+    /// Cumulative weight of the fused assignment before normalization
+    pub normalization: f64,
+    /// Conflict returned by the fusion
+    pub conflict: f64,
+}
+
+impl<X> FusionTrace<X> where X: Ord, {
+    /// Provenance of an element
+    /// * `element: &X` : encoded element
+    /// * Output: provenance of the element, or `None` if the element has not been produced by the referee
+    pub fn element(&self, element: &X) -> Option<&TracedElement<X>> {
+        self.elements.binary_search_by(|traced| traced.element.cmp(element)).ok().map(|u| &self.elements[u])
+    }
+}
+
+/// For intern use: builder of fusion traces
+pub (crate) struct FusionTraceBuilder<X> {
+    elements: BTreeMap<X,TracedElement<X>>,
+    conflicting_weight: f64,
+    discarded_weight: f64,
+}
+
+impl<X> FusionTraceBuilder<X> where X: Clone + Eq + Ord + Hash, {
+    pub (crate) fn new() -> Self { Self { elements: BTreeMap::new(), conflicting_weight: 0.0, discarded_weight: 0.0, } }
+
+    /// Record the outputs of the referee for a condition
+    /// * `conditions: Vec<X>` : condition tuple
+    /// * `meet: &X` : meet of the condition
+    /// * `is_conflicting: bool` : is the meet bottom?
+    /// * `condition_weight: f64` : weight of the condition
+    /// * `outputs: Vec<(X,f64)>` : shares given by the referee
+    pub (crate) fn record(&mut self, conditions: Vec<X>, meet: &X, is_conflicting: bool, condition_weight: f64, outputs: Vec<(X,f64)>) {
+        if is_conflicting { self.conflicting_weight += condition_weight; }
+        let total_share = outputs.iter().map(|(_,share)| *share).sum::<f64>();
+        self.discarded_weight += condition_weight * (1.0 - total_share);
+        for (element, share) in outputs {
+            let weight = condition_weight * share;
+            let redistributed = &element != meet;
+            let traced = self.elements.entry(element.clone()).or_insert_with(|| TracedElement {
+                element, contributions: Vec::new(), direct_weight: 0.0, redistributed_weight: 0.0, fused_weight: 0.0,
+            });
+            if redistributed { traced.redistributed_weight += weight; } else { traced.direct_weight += weight; }
+            traced.contributions.push(TracedContribution { conditions: conditions.clone(), condition_weight, share, weight, redistributed, });
+        }
+    }
+
+    /// Build the trace
+    /// * `fused: &Assignment<X>` : fused assignment
+    /// * `pruned_weight: f64` : weight pruned from the product
+    /// * `normalization: f64` : cumulative weight before normalization
+    /// * `conflict: f64` : conflict returned by the fusion
+    pub (crate) fn build(self, fused: &Assignment<X>, pruned_weight: f64, normalization: f64, conflict: f64) -> FusionTrace<X> {
+        let Self { elements, conflicting_weight, discarded_weight, } = self;
+        let (mut redistributed_weight, mut reduced_weight) = (0.0, 0.0);
+        let elements = elements.into_values().map(|mut traced| {
+            redistributed_weight += traced.redistributed_weight;
+            match fused.elements.get(&traced.element) {
+                Some(w) => traced.fused_weight = (*w).unslx(),
+                None => reduced_weight += traced.direct_weight + traced.redistributed_weight,
+            }
+            traced
+        }).collect();
+        FusionTrace { 
+            lattice_hash: fused.lattice_hash.unslx(), elements, conflicting_weight, redistributed_weight, discarded_weight, 
+            pruned_weight, reduced_weight, normalization, conflict, 
+        }
+    }
+}
+
+pub mod experiment {
+    use crate::{
+        structs::{ Powerset, DiscountedFuser, EnumRule, },
+        traits::{ Lattice, DiscountedFusion, },
+    };
+
+    /// Experimentation with the trace of the fusion
+    pub fn exp_fusion_trace() -> Result<(),String> {
+        println!("===================== fusion trace =====");
+        let lattice = Powerset::new_with_label(&["A".to_string(), "B".to_string(), "C".to_string()], 1024)?;
+        let (a, b, c) = (lattice.from_str("A")?, lattice.from_str("B")?, lattice.from_str("C")?);
+        let m1 = lattice.assignment() + (a, 0.6) + (b, 0.3) + (lattice.top(), 0.1) + ();
+        let m2 = lattice.assignment() + (b, 0.5) + (c, 0.4) + (lattice.top(), 0.1) + ();
+        let engine = DiscountedFuser::new(512..=1024);
+        for rule in [EnumRule::Pcr6, EnumRule::DempsterShafer] {
+            println!("-------------------- {rule:?} -----");
+            let (fused, z, trace) = engine.fuse_traced(&lattice, &rule, &[&m1, &m2])?;
+            println!("fused -> {fused} ; conflict -> {z:.4}");
+            for traced in &trace.elements {
+                println!("{} -> {:.4} = direct {:.4} + redistributed {:.4} (before normalization)", 
+                    lattice.to_string(&lattice.check_safe(traced.element)?)?, traced.fused_weight, 
+                    traced.direct_weight, traced.redistributed_weight
+                );
+                for contribution in traced.contributions.iter().filter(|c| c.redistributed) {
+                    let conditions = contribution.conditions.iter()
+                        .map(|x| lattice.to_string(&lattice.check_safe(*x)?))
+                        .collect::<Result<Vec<_>,_>>()?;
+                    println!("    <- {conditions:?} : {:.4} × {:.4}", contribution.condition_weight, contribution.share);
+                }
+            }
+            println!("conflicting -> {:.4}, redistributed -> {:.4}, discarded -> {:.4}, pruned -> {:.4}, reduced -> {:.4}", 
+                trace.conflicting_weight, trace.redistributed_weight, trace.discarded_weight, trace.pruned_weight, trace.reduced_weight
+            );
+        }
+        println!();
+        Ok(())
+    }
+}
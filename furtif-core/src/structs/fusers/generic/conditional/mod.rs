@@ -15,9 +15,13 @@
 
 
 /// Definition of fusion engines
-mod engine; pub use self::engine::DiscountedFuser;
+mod engine; pub use self::engine::{ 
+    DiscountedFuser, BbaProduct, FusionTrace, TracedElement, TracedContribution, exp_bba_product, exp_fusion_trace, 
+};
+pub (crate) use self::engine::FusionTraceBuilder;
 /// Definition of rules
 mod rules; pub use self::rules::{ 
-    Pcr6, PcrSharp, DuboisPrade2D, Disjunctive, Conjunctive, DempsterShafer, EnumRule,
-    exp_pcr6, exp_conjunctive, exp_dempster_shafer, exp_disjunctive, exp_pcr_sharp, exp_dubois_prade_2d,
+    Pcr5, Pcr6, PcrSharp, DuboisPrade2D, Disjunctive, Conjunctive, DempsterShafer, Averaging, Murphy, Yager, EnumRule,
+    exp_pcr5, exp_pcr6, exp_conjunctive, exp_dempster_shafer, exp_disjunctive, exp_pcr_sharp, exp_dubois_prade_2d,
+    exp_averaging, exp_murphy, exp_yager,
 };
\ No newline at end of file
@@ -0,0 +1,102 @@
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the Lesser GNU General Public License as published
+// by the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// Lesser GNU General Public License for more details.
+
+// You should have received a copy of the Lesser GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+// Copyright 2024 Frederic Dambreville, Jean Dezert Developers.
+
+
+use std::{ iter::once, hash::Hash, };
+
+use crate::{
+    types::IntoSlx,
+    traits::{ Referee, Lattice, },
+    structs::{Assignment, SafeArray, one_f64slx, },
+};
+
+use hashed_type_def::HashedTypeDef;
+#[cfg(feature = "rkyv")] use rkyv::{Archive, Serialize as RkyvSerialize, Deserialize as RkyvDeserialize, };
+#[cfg(feature = "serde")] use serde::{Serialize as SerdeSerialize, Deserialize as SerdeDeserialize};
+
+/// For intern use: average of bbas
+/// * `lattice: &L` : reference lattice
+/// * `bbas: &[&Assignment<L::Item>]` : sequence of bbas
+/// * Output: averaged bba or error
+pub (crate) fn average_bbas<L>(lattice: &L, bbas: &[&Assignment<L::Item>]) -> Result<Assignment<L::Item>,String> 
+                                                                    where L: Lattice, L::Item: Eq + Ord + Hash, {
+    if bbas.is_empty() { return Err("No bba to average".to_string()); }
+    let scale = (bbas.len() as f64).recip().slx();
+    let mut average = lattice.assignment();
+    for bba in bbas {
+        for (x,w) in &bba.elements { unsafe { average.unsafe_push(x.clone(), *w * scale)?; } }
+    }
+    Ok(average.into())
+}
+
+#[derive(HashedTypeDef, Copy, Clone, Debug)]
+#[cfg_attr(feature = "rkyv", derive(Archive,RkyvSerialize,RkyvDeserialize))]
+#[cfg_attr(feature = "serde", derive(SerdeSerialize, SerdeDeserialize))]
+/// Averaging referee function
+/// * The fused assignment is the mean of the bbas: the bbas are replaced by their mean (see `Referee::pre_process`),
+///   so that the product is reduced to the conditions of the mean
+pub struct Averaging;
+
+impl Referee for Averaging {
+    fn is_allowed<L>(&self, _lattice: &L, _bbas: &[&Assignment<L::Item>]) -> bool 
+                                                where L: Lattice, L::Item: Eq + Ord + Hash, {
+        true // always defined
+    }
+
+    unsafe fn unsafe_from_conditions<L>(&self, 
+        lattice: &L, _bbas: &[&Assignment<L::Item>], conditions: SafeArray<L::Item>
+    ) -> Result<Assignment<L::Item>,String> where L: Lattice, L::Item: Eq + Ord + Hash, {
+        let SafeArray { product, lattice_hash, } = conditions;
+        let top = lattice.top().code;
+        let meet = product.iter()
+            .copied().fold(top, 
+                |acc,e| unsafe { lattice.unsafe_meet(&acc,e) }
+            );
+        let elements = once((meet,*one_f64slx())).collect();
+        Ok(Assignment { elements, lattice_hash, })
+    }
+
+    fn pre_process<L>(&self, lattice: &L, bbas: &[&Assignment<L::Item>]) -> Result<Option<Vec<Assignment<L::Item>>>,String> 
+                                                                            where L: Lattice, L::Item: Eq + Ord + Hash, {
+        Ok(Some(vec![average_bbas(lattice, bbas)?]))
+    }
+}
+
+pub mod experiment {
+    use crate::{
+        types::IntoSlx,
+        structs::{ Powerset, DiscountedFuser, Averaging, }, 
+        traits::{ Lattice, DiscountedFusion, },
+    };
+
+    /// Experimentation with the averaging rule
+    pub fn exp_averaging() -> Result<(),String> {
+        println!("================= Averaging ======");
+        let lattice = Powerset::new_with_label(&["A".to_string(), "B".to_string(), "C".to_string()], 1024)?;
+        let (a, b, bc) = (lattice.from_str("A")?, lattice.from_str("B")?, lattice.from_str("B | C")?);
+        let m1 = lattice.assignment() + (a, 0.6) + (bc, 0.4) + ();
+        let m2 = lattice.assignment() + (b, 0.8) + (bc, 0.2) + ();
+        let m3 = lattice.assignment() + (a, 0.5) + (b, 0.5) + ();
+        let engine = DiscountedFuser::new(512..=1024);
+        let (fused,z) = engine.fuse(&lattice, &Averaging, &[&m1,&m2,&m3])?;
+        let fused_theoretic = lattice.assignment() + (a, (0.6 + 0.5) / 3.0) + (b, (0.8 + 0.5) / 3.0) + (bc, (0.4 + 0.2) / 3.0) + ();
+        println!("fused: {fused}");
+        println!("z -> {z}");
+        println!("fused_theoretic: {fused_theoretic}");
+        println!("z_theoric -> {}", 0.0.slx());
+        println!();
+        Ok(())
+    }
+}
@@ -19,8 +19,8 @@ use std::hash::Hash;
 use crate::{
     traits::{ Referee, Lattice, },
     structs::{
-        Assignment, SafeArray, Conjunctive, DempsterShafer,
-        Disjunctive, DuboisPrade2D, Pcr6, PcrSharp
+        Assignment, AssignmentBuilder, SafeArray, Conjunctive, DempsterShafer,
+        Disjunctive, DuboisPrade2D, Pcr5, Pcr6, PcrSharp, Averaging, Murphy, Yager,
     },
 };
 
@@ -33,6 +33,7 @@ use hashed_type_def::HashedTypeDef;
 #[cfg_attr(feature = "serde", derive(SerdeSerialize, SerdeDeserialize))]
 /// Enumeration of referee functions implemented by default
 /// * This is useful for defining the choice of a rule within a single type
+/// * Rules working on the conditions of the product (e.g. `Pcr6`) and rules needing global information (e.g. `Murphy`) are both enumerated
 pub enum EnumRule {
     /// Conjunctive rule
     Conjunctive,
@@ -42,10 +43,18 @@ pub enum EnumRule {
     Disjunctive,
     /// Dubois & Prade rule (2 assignments)
     DuboisPrade2D,
+    /// PCR5 rule
+    Pcr5,
     /// PCR6 rule
     Pcr6,
     /// PCR# rule
     PcrSharp(PcrSharp),
+    /// Averaging rule
+    Averaging,
+    /// Murphy rule
+    Murphy,
+    /// Yager rule
+    Yager,
 }
 
 impl Referee for EnumRule {
@@ -55,8 +64,12 @@ impl Referee for EnumRule {
             EnumRule::DempsterShafer => DempsterShafer.is_allowed(lattice, bbas),
             EnumRule::Disjunctive => Disjunctive.is_allowed(lattice, bbas),
             EnumRule::DuboisPrade2D => DuboisPrade2D.is_allowed(lattice, bbas),
+            EnumRule::Pcr5 => Pcr5.is_allowed(lattice, bbas),
             EnumRule::Pcr6 => Pcr6.is_allowed(lattice, bbas),
             EnumRule::PcrSharp(rule) => rule.is_allowed(lattice, bbas),
+            EnumRule::Averaging => Averaging.is_allowed(lattice, bbas),
+            EnumRule::Murphy => Murphy.is_allowed(lattice, bbas),
+            EnumRule::Yager => Yager.is_allowed(lattice, bbas),
         }
     }
 
@@ -67,8 +80,44 @@ impl Referee for EnumRule {
             EnumRule::DempsterShafer => DempsterShafer.unsafe_from_conditions(lattice, bbas, conditions),
             EnumRule::Disjunctive => Disjunctive.unsafe_from_conditions(lattice, bbas, conditions),
             EnumRule::DuboisPrade2D => DuboisPrade2D.unsafe_from_conditions(lattice, bbas, conditions),
+            EnumRule::Pcr5 => Pcr5.unsafe_from_conditions(lattice, bbas, conditions),
             EnumRule::Pcr6 => Pcr6.unsafe_from_conditions(lattice, bbas, conditions),
             EnumRule::PcrSharp(rule) => rule.unsafe_from_conditions(lattice, bbas, conditions),
+            EnumRule::Averaging => Averaging.unsafe_from_conditions(lattice, bbas, conditions),
+            EnumRule::Murphy => Murphy.unsafe_from_conditions(lattice, bbas, conditions),
+            EnumRule::Yager => Yager.unsafe_from_conditions(lattice, bbas, conditions),
+        }
+    }
+
+    fn pre_process<L>(&self, lattice: &L, bbas: &[&Assignment<L::Item>]) -> Result<Option<Vec<Assignment<L::Item>>>,String> 
+                                                                            where L: Lattice, L::Item: Eq + Ord + Hash, {
+        match self  {
+            EnumRule::Conjunctive => Conjunctive.pre_process(lattice, bbas),
+            EnumRule::DempsterShafer => DempsterShafer.pre_process(lattice, bbas),
+            EnumRule::Disjunctive => Disjunctive.pre_process(lattice, bbas),
+            EnumRule::DuboisPrade2D => DuboisPrade2D.pre_process(lattice, bbas),
+            EnumRule::Pcr5 => Pcr5.pre_process(lattice, bbas),
+            EnumRule::Pcr6 => Pcr6.pre_process(lattice, bbas),
+            EnumRule::PcrSharp(rule) => rule.pre_process(lattice, bbas),
+            EnumRule::Averaging => Averaging.pre_process(lattice, bbas),
+            EnumRule::Murphy => Murphy.pre_process(lattice, bbas),
+            EnumRule::Yager => Yager.pre_process(lattice, bbas),
+        }
+    }
+
+    fn post_process<L>(&self, lattice: &L, bbas: &[&Assignment<L::Item>], accumulated: &mut AssignmentBuilder<L::Item>) 
+                                                            -> Result<(),String> where L: Lattice, L::Item: Eq + Ord + Hash, {
+        match self  {
+            EnumRule::Conjunctive => Conjunctive.post_process(lattice, bbas, accumulated),
+            EnumRule::DempsterShafer => DempsterShafer.post_process(lattice, bbas, accumulated),
+            EnumRule::Disjunctive => Disjunctive.post_process(lattice, bbas, accumulated),
+            EnumRule::DuboisPrade2D => DuboisPrade2D.post_process(lattice, bbas, accumulated),
+            EnumRule::Pcr5 => Pcr5.post_process(lattice, bbas, accumulated),
+            EnumRule::Pcr6 => Pcr6.post_process(lattice, bbas, accumulated),
+            EnumRule::PcrSharp(rule) => rule.post_process(lattice, bbas, accumulated),
+            EnumRule::Averaging => Averaging.post_process(lattice, bbas, accumulated),
+            EnumRule::Murphy => Murphy.post_process(lattice, bbas, accumulated),
+            EnumRule::Yager => Yager.post_process(lattice, bbas, accumulated),
         }
     }
 }
\ No newline at end of file
@@ -22,9 +22,17 @@ mod disjunctive; pub use self::disjunctive::{ Disjunctive, experiment::exp_disju
 mod conjunctive; pub use self::conjunctive::{ Conjunctive, experiment::exp_conjunctive, };
 /// Definition of Dubois & Prade rule
 mod dubois_prade; pub use self::dubois_prade::{ DuboisPrade2D, experiment::exp_dubois_prade_2d, };
+/// Definition of PCR5 rule
+mod pcr5; pub use self::pcr5::{ Pcr5, experiment::exp_pcr5, };
 /// Definition of PCR6 rule
 mod pcr6; pub use self::pcr6::{ Pcr6, experiment::exp_pcr6, };
 /// Definition of PCR# rule
 mod pcr_sharp; pub use self::pcr_sharp::{ PcrSharp, experiment::exp_pcr_sharp, };
+/// Definition of averaging rule
+mod averaging; pub use self::averaging::{ Averaging, experiment::exp_averaging, };
+/// Definition of Murphy rule
+mod murphy; pub use self::murphy::{ Murphy, experiment::exp_murphy, };
+/// Definition of Yager rule
+mod yager; pub use self::yager::{ Yager, experiment::exp_yager, };
 /// Enumeration of different rules
 mod enum_rule; pub use self::enum_rule::EnumRule;
\ No newline at end of file
@@ -0,0 +1,84 @@
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the Lesser GNU General Public License as published
+// by the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// Lesser GNU General Public License for more details.
+
+// You should have received a copy of the Lesser GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+// Copyright 2024 Frederic Dambreville, Jean Dezert Developers.
+
+
+use std::hash::Hash;
+
+use crate::{
+    traits::{ Referee, Lattice, },
+    structs::{ Assignment, SafeArray, DempsterShafer, },
+};
+
+use hashed_type_def::HashedTypeDef;
+#[cfg(feature = "rkyv")] use rkyv::{Archive, Serialize as RkyvSerialize, Deserialize as RkyvDeserialize, };
+#[cfg(feature = "serde")] use serde::{Serialize as SerdeSerialize, Deserialize as SerdeDeserialize};
+
+use super::averaging::average_bbas;
+
+#[derive(HashedTypeDef, Copy, Clone, Debug)]
+#[cfg_attr(feature = "rkyv", derive(Archive,RkyvSerialize,RkyvDeserialize))]
+#[cfg_attr(feature = "serde", derive(SerdeSerialize, SerdeDeserialize))]
+/// Murphy referee function
+/// * The `n` bbas are replaced by `n` copies of their mean (see `Referee::pre_process`), 
+///   which are then combined by means of the Dempster-Shafer rule
+pub struct Murphy;
+
+impl Referee for Murphy {
+    fn is_allowed<L>(&self, _lattice: &L, _bbas: &[&Assignment<L::Item>]) -> bool 
+                                                where L: Lattice, L::Item: Eq + Ord + Hash, {
+        true // always defined
+    }
+
+    unsafe fn unsafe_from_conditions<L>(&self, 
+        lattice: &L, bbas: &[&Assignment<L::Item>], conditions: SafeArray<L::Item>
+    ) -> Result<Assignment<L::Item>,String> where L: Lattice, L::Item: Eq + Ord + Hash, {
+        DempsterShafer.unsafe_from_conditions(lattice, bbas, conditions)
+    }
+
+    fn pre_process<L>(&self, lattice: &L, bbas: &[&Assignment<L::Item>]) -> Result<Option<Vec<Assignment<L::Item>>>,String> 
+                                                                            where L: Lattice, L::Item: Eq + Ord + Hash, {
+        let average = average_bbas(lattice, bbas)?;
+        Ok(Some(vec![average; bbas.len()]))
+    }
+}
+
+pub mod experiment {
+    use crate::{
+        structs::{ Powerset, DiscountedFuser, Murphy, }, 
+        traits::{ Lattice, DiscountedFusion, },
+    };
+
+    /// Experimentation with the Murphy rule
+    pub fn exp_murphy() -> Result<(),String> {
+        println!("================= Murphy ======");
+        let lattice = Powerset::new_with_label(&["A".to_string(), "B".to_string(), "C".to_string()], 1024)?;
+        let (a, b, c) = (lattice.from_str("A")?, lattice.from_str("B")?, lattice.from_str("C")?);
+        // Zadeh's example
+        let m1 = lattice.assignment() + (a, 0.99) + (b, 0.01) + ();
+        let m2 = lattice.assignment() + (c, 0.99) + (b, 0.01) + ();
+        let engine = DiscountedFuser::new(512..=1024);
+        let (fused,z) = engine.fuse(&lattice, &Murphy, &[&m1,&m2])?;
+        // mean is (0.495 A, 0.01 B, 0.495 C), combined with itself
+        let (wa, wb, wc) = (0.495f64 * 0.495, 0.01f64 * 0.01, 0.495f64 * 0.495);
+        let co_z_theoric = wa + wb + wc;
+        let fused_theoretic = lattice.assignment() + (a, wa) + (b, wb) + (c, wc) + ();
+        println!("fused: {fused}");
+        println!("z -> {z}");
+        println!("fused_theoretic: {fused_theoretic}");
+        println!("z_theoric -> {}", 1.0 - co_z_theoric);
+        println!();
+        Ok(())
+    }
+}
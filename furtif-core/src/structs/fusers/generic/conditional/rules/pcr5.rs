@@ -0,0 +1,119 @@
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the Lesser GNU General Public License as published
+// by the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// Lesser GNU General Public License for more details.
+
+// You should have received a copy of the Lesser GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+// Copyright 2024 Frederic Dambreville, Jean Dezert Developers.
+
+
+use std::{ collections::BTreeMap, hash::Hash, iter::once, };
+
+use crate::{
+    types::f64slx,
+    traits::{ Referee, Lattice, },
+    structs::{Assignment, SafeArray, hidden::OrdMap, one_f64slx},
+};
+
+use hashed_type_def::HashedTypeDef;
+#[cfg(feature = "rkyv")] use rkyv::{Archive, Serialize as RkyvSerialize, Deserialize as RkyvDeserialize, };
+#[cfg(feature = "serde")] use serde::{Serialize as SerdeSerialize, Deserialize as SerdeDeserialize};
+
+#[derive(HashedTypeDef, Copy, Clone, Debug)]
+#[cfg_attr(feature = "rkyv", derive(Archive,RkyvSerialize,RkyvDeserialize))]
+#[cfg_attr(feature = "serde", derive(SerdeSerialize, SerdeDeserialize))]
+/// Pcr5 referee function
+/// * A conflicting condition is redistributed to its elements proportionally to the product of the masses of the sources committed to each element
+/// * PCR5 and PCR6 coincide for two sources; for more sources, PCR6 is proportional to the sum of these masses
+pub struct Pcr5;
+
+impl Referee for Pcr5 {
+    fn is_allowed<L>(&self, _lattice: &L, _bbas: &[&Assignment<L::Item>]) -> bool 
+                                                where L: Lattice, L::Item: Eq + Ord + Hash, {
+        true // always defined
+    }
+
+    unsafe fn unsafe_from_conditions<L>(&self, 
+        lattice: &L, bbas: &[&Assignment<L::Item>], conditions: SafeArray<L::Item>
+    ) -> Result<Assignment<L::Item>,String> where L: Lattice, L::Item: Eq + Ord + Hash, {
+        let SafeArray { product, lattice_hash, } = conditions;
+        let top = lattice.top().code;
+        let meet = product.iter().copied()
+            .fold(top, |acc,e| unsafe { lattice.unsafe_meet(&acc,e) });
+        if !unsafe { lattice.unsafe_is_bottom(&meet) } {
+            let elements = once((meet.clone(),*one_f64slx())).collect();
+            Ok(Assignment { elements, lattice_hash, })
+        } else {
+            // product of the masses of the sources committed to each element
+            let mut unnormed = BTreeMap::<&L::Item,f64slx>::new();
+            for (x,m) in product.iter().zip(bbas) {
+                let w = m.elements[*x];
+                unnormed.entry(*x).and_modify(|v| *v *= w).or_insert(w);
+            }
+            let norm = unnormed.values().copied().sum::<f64slx>();
+            let mut elements = OrdMap::new();
+            for (x,w) in unnormed { elements.push(x.clone(), w / norm); }
+            Ok(Assignment { elements: elements.elements, lattice_hash, })
+        }
+    }
+}
+
+pub mod experiment {
+    use crate::{
+        types::IntoSlx,
+        structs::{Powerset, DiscountedFuser, Pcr5, Pcr6, Assignment, }, 
+        traits::{Lattice, DiscountedFusion, LatticeWithLeaves, }
+    };
+
+    /// Experimentation with the PCR5 rule
+    pub fn exp_pcr5() -> Result<(),String> {
+        println!("================= PCR5 ================");
+        let lattice = Powerset::new(3,1024)
+                .expect("unexpected powwerset initialisation failure");
+        let (prop_a, prop_b, top) = (lattice.leaf(0)?, lattice.leaf(1)?, lattice.top());
+        let (m_a, m_b2, m_b3) = (0.6.slx(), 0.7.slx(), 0.8.slx());
+        let (m_t1, m_t2, m_t3) = (0.4.slx(), 0.3.slx(), 0.2.slx());
+        let m1 = lattice.assignment() + (prop_a, m_a) + (top, m_t1) + ();
+        let m2 = lattice.assignment() + (prop_b, m_b2) + (top, m_t2) + ();
+        let m3 = lattice.assignment() + (prop_b, m_b3) + (top, m_t3) + ();
+        let engine = DiscountedFuser::new(512..=1024);
+        let (fused,z) = engine.fuse(&lattice, &Pcr5, &[&m1,&m2,&m3]).expect("unexpected fusion failure");
+        let (fused_pcr6,_) = engine.fuse(&lattice, &Pcr6, &[&m1,&m2,&m3]).expect("unexpected fusion failure");
+        let mut fused_theoretic = lattice.assignment_with_capacity(3);
+        // A,B,B -> redistributed to A and B proportionally to m1(A) and m2(B)·m3(B)
+        let weight = m_a * m_b2 * m_b3 / (m_a + m_b2 * m_b3);
+        fused_theoretic.push(prop_a,m_a * weight)?;
+        fused_theoretic.push(prop_b,m_b2 * m_b3 * weight)?;
+        // A,B,Θ -> redistributed
+        let weight = m_a * m_b2 * m_t3 / (m_a + m_b2 + m_t3);
+        fused_theoretic.push(prop_a,m_a * weight)?;
+        fused_theoretic.push(prop_b,m_b2 * weight)?;
+        fused_theoretic.push(top,m_t3 * weight)?;
+        // A,Θ,B -> redistributed
+        let weight = m_a * m_t2 * m_b3 / (m_a + m_t2 + m_b3);
+        fused_theoretic.push(prop_a,m_a * weight)?;
+        fused_theoretic.push(top,m_t2 * weight)?;
+        fused_theoretic.push(prop_b,m_b3 * weight)?;
+        // A,Θ,Θ -> conjunctive
+        fused_theoretic.push(prop_a,m_a * m_t2 * m_t3)?;
+        // Θ,B,B / Θ,B,Θ / Θ,Θ,B -> conjunctive
+        fused_theoretic.push(prop_b,m_t1 * (m_b2 * m_b3 + m_b2 * m_t3 + m_t2 * m_b3))?;
+        // Θ,Θ,Θ -> conjunctive
+        fused_theoretic.push(top,m_t1 * m_t2 * m_t3)?;
+        let fused_theoretic: Assignment<_> = fused_theoretic.into();
+        println!("ms: {:?}",[&m1,&m2,&m3]);
+        println!("fused: {:?}",fused);
+        println!("z -> {z}");
+        println!("fused_theoretic: {:?}",fused_theoretic);
+        println!("fused by pcr6: {:?}",fused_pcr6);
+        println!();
+        Ok(())
+    }
+}
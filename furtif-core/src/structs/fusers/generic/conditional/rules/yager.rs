@@ -0,0 +1,81 @@
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the Lesser GNU General Public License as published
+// by the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// Lesser GNU General Public License for more details.
+
+// You should have received a copy of the Lesser GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+// Copyright 2024 Frederic Dambreville, Jean Dezert Developers.
+
+
+use std::hash::Hash;
+
+use crate::{
+    traits::{ Referee, Lattice, },
+    structs::{ Assignment, AssignmentBuilder, SafeArray, Conjunctive, },
+};
+
+use hashed_type_def::HashedTypeDef;
+#[cfg(feature = "rkyv")] use rkyv::{Archive, Serialize as RkyvSerialize, Deserialize as RkyvDeserialize, };
+#[cfg(feature = "serde")] use serde::{Serialize as SerdeSerialize, Deserialize as SerdeDeserialize};
+
+#[derive(HashedTypeDef, Copy, Clone, Debug)]
+#[cfg_attr(feature = "rkyv", derive(Archive,RkyvSerialize,RkyvDeserialize))]
+#[cfg_attr(feature = "serde", derive(SerdeSerialize, SerdeDeserialize))]
+/// Yager referee function
+/// * The bbas are combined conjunctively, and the global conflict (mass of bottom) is then assigned to top (see `Referee::post_process`)
+/// * The conflict returned by the fusion engines is thus zero
+pub struct Yager;
+
+impl Referee for Yager {
+    fn is_allowed<L>(&self, _lattice: &L, _bbas: &[&Assignment<L::Item>]) -> bool 
+                                                where L: Lattice, L::Item: Eq + Ord + Hash, {
+        true // always defined
+    }
+
+    unsafe fn unsafe_from_conditions<L>(&self, 
+        lattice: &L, bbas: &[&Assignment<L::Item>], conditions: SafeArray<L::Item>
+    ) -> Result<Assignment<L::Item>,String> where L: Lattice, L::Item: Eq + Ord + Hash, {
+        Conjunctive.unsafe_from_conditions(lattice, bbas, conditions)
+    }
+
+    fn post_process<L>(&self, lattice: &L, _bbas: &[&Assignment<L::Item>], accumulated: &mut AssignmentBuilder<L::Item>) 
+                                                            -> Result<(),String> where L: Lattice, L::Item: Eq + Ord + Hash, {
+        if let Some((_,conflict)) = accumulated.remove(&lattice.bottom())? {
+            accumulated.push(lattice.top(), conflict)?;
+        }
+        Ok(())
+    }
+}
+
+pub mod experiment {
+    use crate::{
+        structs::{ Powerset, DiscountedFuser, Yager, }, 
+        traits::{ Lattice, DiscountedFusion, },
+    };
+
+    /// Experimentation with the Yager rule
+    pub fn exp_yager() -> Result<(),String> {
+        println!("================= Yager ======");
+        let lattice = Powerset::new_with_label(&["A".to_string(), "B".to_string(), "C".to_string()], 1024)?;
+        let (a, b, c) = (lattice.from_str("A")?, lattice.from_str("B")?, lattice.from_str("C")?);
+        // Zadeh's example
+        let m1 = lattice.assignment() + (a, 0.99) + (b, 0.01) + ();
+        let m2 = lattice.assignment() + (c, 0.99) + (b, 0.01) + ();
+        let engine = DiscountedFuser::new(512..=1024);
+        let (fused,z) = engine.fuse(&lattice, &Yager, &[&m1,&m2])?;
+        let fused_theoretic = lattice.assignment() + (b, 0.0001) + (lattice.top(), 0.9999) + ();
+        println!("fused: {fused}");
+        println!("z -> {z}");
+        println!("fused_theoretic: {fused_theoretic}");
+        println!("z_theoric -> 0");
+        println!();
+        Ok(())
+    }
+}
@@ -16,6 +16,9 @@
 
 /// Fusion rules and fusion engines from a conditional view
 mod conditional; pub use self::conditional::{ 
-    Conjunctive, Disjunctive, DiscountedFuser, DuboisPrade2D, Pcr6, PcrSharp, DempsterShafer, EnumRule,
-    exp_pcr6, exp_conjunctive, exp_dempster_shafer, exp_disjunctive, exp_pcr_sharp, exp_dubois_prade_2d,
-};
\ No newline at end of file
+    Conjunctive, Disjunctive, DiscountedFuser, BbaProduct, DuboisPrade2D, Pcr5, Pcr6, PcrSharp, DempsterShafer, Averaging, Murphy, Yager, EnumRule,
+    FusionTrace, TracedElement, TracedContribution,
+    exp_pcr5, exp_pcr6, exp_bba_product, exp_conjunctive, exp_dempster_shafer, exp_disjunctive, exp_pcr_sharp, exp_dubois_prade_2d,
+    exp_fusion_trace, exp_averaging, exp_murphy, exp_yager,
+};
+pub (crate) use self::conditional::FusionTraceBuilder;
\ No newline at end of file
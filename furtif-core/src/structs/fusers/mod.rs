@@ -16,8 +16,11 @@
 
 /// Generic implementations of rules and fusion engines
 mod generic; pub use self::generic::{ 
-    Pcr6, PcrSharp, DiscountedFuser, DuboisPrade2D, Disjunctive, Conjunctive, DempsterShafer, EnumRule,
-    exp_pcr6, exp_conjunctive, exp_dempster_shafer, exp_disjunctive, exp_pcr_sharp, exp_dubois_prade_2d,
+    Pcr5, Pcr6, PcrSharp, DiscountedFuser, BbaProduct, DuboisPrade2D, Disjunctive, Conjunctive, DempsterShafer, Averaging, Murphy, Yager, EnumRule,
+    FusionTrace, TracedElement, TracedContribution,
+    exp_pcr5, exp_pcr6, exp_bba_product, exp_conjunctive, exp_dempster_shafer, exp_disjunctive, exp_pcr_sharp, exp_dubois_prade_2d,
+    exp_fusion_trace, exp_averaging, exp_murphy, exp_yager,
 };
+pub (crate) use self::generic::FusionTraceBuilder;
 /// Specific implementations of rules
 mod specific; // not implemented at this time
\ No newline at end of file
@@ -0,0 +1,237 @@
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the Lesser GNU General Public License as published
+// by the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// Lesser GNU General Public License for more details.
+
+// You should have received a copy of the Lesser GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+// Copyright 2024 Frederic Dambreville, Jean Dezert Developers.
+
+
+use nalgebra::{ DMatrix, SVector, };
+use rand::prelude::*;
+
+use crate::{
+    types::{ u128slx, f64slx, SlxInto, IntoSlx, },
+    structs::{ Assignment, Powerset, },
+    traits::{ Lattice, LatticeWithLeaves, },
+};
+
+/// Maximal number of clusters handled by evidential c-means (the number of focal elements is exponential)
+pub const ECM_MAX_CLUSTERS: usize = 12;
+
+#[derive(Clone, Debug)]
+/// Credal partition computed by evidential c-means
+/// * `D` : dimension of the features
+pub struct CredalPartition<const D: usize> {
+    /// Prototypes of the clusters, indexed by leaf rank
+    pub prototypes: Vec<SVector<f64slx,D>>,
+    /// Assignment of each sample over the powerset of clusters; the mass on bottom is the outlier mass
+    pub assignments: Vec<Assignment<u128slx>>,
+    /// Final value of the objective function
+    pub objective: f64slx,
+    /// Number of performed iterations
+    pub iterations: usize,
+}
+
+#[derive(Copy, Clone, Debug)]
+/// Evidential c-means (Masson & Denœux)
+/// * Clusters are the leaves of a powerset; each sample gets an assignment over the powerset:
+///   * mass on leaves are related to the clusters
+///   * mass on other non bottom elements are related to the ambiguity between clusters
+///   * mass on bottom is related to outliers
+pub struct EvidentialCMeans {
+    alpha: f64,
+    beta: f64,
+    delta: f64,
+    max_iterations: usize,
+    tolerance: f64,
+}
+
+impl EvidentialCMeans {
+    /// Constructor of evidential c-means
+    /// * `alpha: f64slx` : penalization exponent of the cardinal of imprecise elements (typically `1`)
+    /// * `beta: f64slx` : fuzzifier exponent, greater than `1` (typically `2`)
+    /// * `delta: f64slx` : distance of outliers to all the clusters
+    /// * `max_iterations: usize` : maximal number of iterations, at least `1`
+    /// * `tolerance: f64slx` : convergence tolerance on the objective function
+    /// * Output: the evidential c-means or an error
+    pub fn new(alpha: f64slx, beta: f64slx, delta: f64slx, max_iterations: usize, tolerance: f64slx) -> Result<Self,String> {
+        let (alpha, beta, delta, tolerance) = (alpha.unslx(), beta.unslx(), delta.unslx(), tolerance.unslx());
+        if !(alpha.is_finite() && alpha >= 0.0) { return Err(format!("alpha {alpha} should be non negative")); }
+        if !(beta.is_finite() && beta > 1.0) { return Err(format!("beta {beta} should be greater than 1")); }
+        if !(delta.is_finite() && delta > 0.0) { return Err(format!("delta {delta} should be positive")); }
+        if !(tolerance.is_finite() && tolerance >= 0.0) { return Err(format!("tolerance {tolerance} should be non negative")); }
+        if max_iterations == 0 { return Err("max_iterations should be positive".to_string()); }
+        Ok(Self { alpha, beta, delta, max_iterations, tolerance, })
+    }
+
+    /// For intern use: non bottom elements of the powerset, as leaf ranks and element code
+    fn focal_elements(powerset: &Powerset) -> Result<Vec<(Vec<usize>,u128slx)>,String> {
+        let nb_clusters = unsafe { powerset.unsafe_leaves() }?.count();
+        if nb_clusters == 0 || nb_clusters > ECM_MAX_CLUSTERS {
+            return Err(format!("number of clusters {nb_clusters} should be within [1,{ECM_MAX_CLUSTERS}]"));
+        }
+        let leaves = (0..nb_clusters).map(|u| powerset.leaf(u)).collect::<Result<Vec<_>,_>>()?;
+        (1usize..(1 << nb_clusters)).map(|code| {
+            let ranks = (0..nb_clusters).filter(|u| code & (1 << u) != 0).collect::<Vec<_>>();
+            let element = powerset.join_some(ranks.iter().map(|u| &leaves[*u]))?;
+            Ok((ranks, element.encoded()))
+        }).collect()
+    }
+
+    /// For intern use: masses of a sample on the focal elements and on bottom
+    fn masses(&self, focals: &[(Vec<usize>,u128slx)], centers: &[DMatrix<f64>], x: &DMatrix<f64>) -> (Vec<f64>,f64) {
+        let exponent = -1.0 / (self.beta - 1.0);
+        let weights = focals.iter().zip(centers).map(|((ranks,_),center)| {
+            let d2 = (x - center).norm_squared().max(f64::MIN_POSITIVE);
+            (ranks.len() as f64).powf(self.alpha * exponent) * d2.powf(exponent)
+        }).collect::<Vec<_>>();
+        let outlier = (self.delta * self.delta).powf(exponent);
+        let norm = weights.iter().sum::<f64>() + outlier;
+        (weights.into_iter().map(|w| w / norm).collect(), outlier / norm)
+    }
+
+    /// For intern use: barycenters of the prototypes for each focal element
+    fn centers(focals: &[(Vec<usize>,u128slx)], prototypes: &DMatrix<f64>) -> Vec<DMatrix<f64>> {
+        focals.iter().map(|(ranks,_)| {
+            let mut center = DMatrix::<f64>::zeros(1, prototypes.ncols());
+            for u in ranks { center += prototypes.row(*u); }
+            center / ranks.len() as f64
+        }).collect()
+    }
+
+    /// Compute the credal partition of samples
+    /// * `powerset: &Powerset` : powerset of the clusters; its leaves are the clusters
+    /// * `samples: &[SVector<f64slx,D>]` : feature vectors
+    /// * `rng: &mut R` : random number generator used for initializing the prototypes
+    /// * `D` : dimension of the features
+    /// * `R` : type of random number generator
+    /// * Output: the credal partition or an error
+    pub fn fit<R: Rng, const D: usize>(&self, powerset: &Powerset, samples: &[SVector<f64slx,D>], rng: &mut R) 
+                                                                            -> Result<CredalPartition<D>,String> {
+        let focals = Self::focal_elements(powerset)?;
+        let nb_clusters = focals.iter().filter(|(ranks,_)| ranks.len() == 1).count();
+        if samples.len() < nb_clusters { return Err("there are less samples than clusters".to_string()); }
+        let xs = samples.iter()
+            .map(|s| DMatrix::<f64>::from_iterator(1, D, s.iter().map(|v| (*v).unslx())))
+            .collect::<Vec<_>>();
+        // prototypes are initialized with randomly chosen samples
+        let mut prototypes = DMatrix::<f64>::zeros(nb_clusters, D);
+        for (u,i) in rand::seq::index::sample(rng, xs.len(), nb_clusters).into_iter().enumerate() {
+            prototypes.set_row(u, &xs[i].row(0));
+        }
+        let cards = focals.iter().map(|(ranks,_)| ranks.len() as f64).collect::<Vec<_>>();
+        let mut objective = f64::INFINITY;
+        let mut iterations = 0;
+        let mut masses = Vec::with_capacity(xs.len());
+        while iterations < self.max_iterations {
+            iterations += 1;
+            // update masses
+            let centers = Self::centers(&focals, &prototypes);
+            masses = xs.iter().map(|x| self.masses(&focals, &centers, x)).collect::<Vec<_>>();
+            // update prototypes by solving linear system `h . prototypes = b`
+            let mut h = DMatrix::<f64>::zeros(nb_clusters, nb_clusters);
+            let mut b = DMatrix::<f64>::zeros(nb_clusters, D);
+            for (x,(ms,_)) in xs.iter().zip(&masses) {
+                for (((ranks,_),card),m) in focals.iter().zip(&cards).zip(ms) {
+                    let mb = m.powf(self.beta);
+                    for l in ranks {
+                        for k in ranks { h[(*l,*k)] += card.powf(self.alpha - 2.0) * mb; }
+                        let mut row = b.row_mut(*l);
+                        row += x * (card.powf(self.alpha - 1.0) * mb);
+                    }
+                }
+            }
+            prototypes = match h.lu().solve(&b) {
+                Some(prototypes) => prototypes,
+                None => return Err("singular system for prototypes update".to_string()),
+            };
+            // objective function
+            let centers = Self::centers(&focals, &prototypes);
+            let new_objective = xs.iter().zip(&masses).map(|(x,(ms,m_bottom))| {
+                focals.iter().zip(&cards).zip(ms).zip(&centers).map(|(((_,card),m),center)| {
+                    card.powf(self.alpha) * m.powf(self.beta) * (x - center).norm_squared()
+                }).sum::<f64>() + self.delta * self.delta * m_bottom.powf(self.beta)
+            }).sum::<f64>();
+            let converged = (objective - new_objective).abs() <= self.tolerance;
+            objective = new_objective;
+            if converged { break; }
+        }
+        let bottom = powerset.bottom().encoded();
+        let assignments = masses.into_iter().map(|(ms,m_bottom)| {
+            let mut bba = powerset.assignment_with_capacity(focals.len() + 1);
+            for ((_,element),m) in focals.iter().zip(ms) { unsafe { bba.unsafe_push(*element, m.slx()) }?; }
+            unsafe { bba.unsafe_push(bottom, m_bottom.slx()) }?;
+            Ok(bba.into())
+        }).collect::<Result<Vec<_>,String>>()?;
+        let prototypes = prototypes.row_iter()
+            .map(|row| SVector::<f64slx,D>::from_iterator(row.iter().map(|v| v.slx())))
+            .collect();
+        Ok(CredalPartition { prototypes, assignments, objective: objective.slx(), iterations, })
+    }
+
+    /// Compute the assignment of a new sample from the prototypes of a credal partition
+    /// * `powerset: &Powerset` : powerset of the clusters, as used for computing the partition
+    /// * `partition: &CredalPartition<D>` : credal partition
+    /// * `x: &SVector<f64slx,D>` : feature vector
+    /// * `D` : dimension of the features
+    /// * Output: the assignment or an error
+    pub fn assign<const D: usize>(&self, powerset: &Powerset, partition: &CredalPartition<D>, x: &SVector<f64slx,D>) 
+                                                                            -> Result<Assignment<u128slx>,String> {
+        let focals = Self::focal_elements(powerset)?;
+        let nb_clusters = partition.prototypes.len();
+        if focals.iter().filter(|(ranks,_)| ranks.len() == 1).count() != nb_clusters {
+            return Err("mismatching number of clusters".to_string());
+        }
+        let mut prototypes = DMatrix::<f64>::zeros(nb_clusters, D);
+        for (u,p) in partition.prototypes.iter().enumerate() {
+            for (q,v) in p.iter().enumerate() { prototypes[(u,q)] = (*v).unslx(); }
+        }
+        let x = DMatrix::<f64>::from_iterator(1, D, x.iter().map(|v| (*v).unslx()));
+        let (ms,m_bottom) = self.masses(&focals, &Self::centers(&focals, &prototypes), &x);
+        let mut bba = powerset.assignment_with_capacity(focals.len() + 1);
+        for ((_,element),m) in focals.iter().zip(ms) { unsafe { bba.unsafe_push(*element, m.slx()) }?; }
+        unsafe { bba.unsafe_push(powerset.bottom().encoded(), m_bottom.slx()) }?;
+        Ok(bba.into())
+    }
+}
+
+pub mod experiment {
+    use nalgebra::SVector;
+    use rand::prelude::*;
+    use crate::{
+        types::IntoSlx,
+        structs::{ Powerset, EvidentialCMeans, }, 
+    };
+
+    /// Experimentation with evidential c-means
+    pub fn exp_ecm() -> Result<(),String> {
+        println!("=================================== ecm =====");
+        let powerset = Powerset::new_with_label(&["K1".to_string(), "K2".to_string()], 1024)?;
+        let vector = |x: f64, y: f64| SVector::<_,2>::new(x.slx(), y.slx());
+        let samples = vec![
+            vector(0.0, 0.0), vector(0.3, 0.1), vector(-0.2, 0.2), vector(0.1, -0.3),
+            vector(4.0, 0.0), vector(4.3, 0.2), vector(3.8, -0.1), vector(4.1, 0.3),
+            vector(2.0, 0.1), // ambiguous sample
+            vector(2.0, 8.0), // outlier
+        ];
+        let ecm = EvidentialCMeans::new(1.0.slx(), 2.0.slx(), 3.0.slx(), 100, 1e-6.slx())?;
+        let mut rng = StdRng::seed_from_u64(1);
+        let partition = ecm.fit(&powerset, &samples, &mut rng)?;
+        println!("prototypes -> {:?}", partition.prototypes.iter().map(|p| p.as_slice().to_vec()).collect::<Vec<_>>());
+        println!("objective -> {:.4}, iterations -> {}", partition.objective, partition.iterations);
+        for (x,m) in samples.iter().zip(&partition.assignments) {
+            println!("{:?} -> {m}", x.as_slice());
+        }
+        println!("new sample (0,0.5) -> {}", ecm.assign(&powerset, &partition, &vector(0.0, 0.5))?);
+        println!();
+        Ok(())
+    }
+}
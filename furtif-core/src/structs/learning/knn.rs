@@ -0,0 +1,219 @@
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the Lesser GNU General Public License as published
+// by the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// Lesser GNU General Public License for more details.
+
+// You should have received a copy of the Lesser GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+// Copyright 2024 Frederic Dambreville, Jean Dezert Developers.
+
+
+use std::{ hash::Hash, collections::HashMap, };
+
+use nalgebra::SVector;
+
+use crate::{
+    types::{ u128slx, f64slx, SlxInto, IntoSlx, },
+    structs::{ Assignment, SafeElement, },
+    traits::{ Lattice, Referee, DiscountedFusion, },
+};
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+/// Distances between feature vectors
+pub enum FeatureDistance {
+    /// Euclidean distance
+    Euclidean,
+    /// Manhattan distance
+    Manhattan,
+    /// Chebyshev distance
+    Chebyshev,
+}
+
+impl FeatureDistance {
+    /// Compute distance between two feature vectors
+    /// * `left: &SVector<f64slx,D>` : left feature vector
+    /// * `right: &SVector<f64slx,D>` : right feature vector
+    /// * `D` : dimension of the features
+    /// * Output: distance
+    pub fn distance<const D: usize>(&self, left: &SVector<f64slx,D>, right: &SVector<f64slx,D>) -> f64slx {
+        let diffs = left.iter().zip(right.iter()).map(|(l,r)| ((*l).unslx() - (*r).unslx()).abs());
+        match self {
+            FeatureDistance::Euclidean => diffs.map(|d| d * d).sum::<f64>().sqrt(),
+            FeatureDistance::Manhattan => diffs.sum::<f64>(),
+            FeatureDistance::Chebyshev => diffs.fold(0.0, f64::max),
+        }.slx()
+    }
+}
+
+#[derive(Clone, Debug)]
+/// Evidential k-nearest neighbours classifier (Denœux)
+/// * Each of the `k` nearest neighbours `xᵢ` of a feature vector `x`, labelled by `ωᵢ`, produces the assignment:
+///   * `m({ωᵢ}) = α exp(-γ(ωᵢ) d(x,xᵢ)²)`
+///   * `m(⊤) = 1 - m({ωᵢ})`
+/// * These assignments are then fused by means of a fusion engine and a referee function (typically Dempster-Shafer)
+/// * Labels are elements of the lattice, usually leaves; they are not required to be leaves however, so that imprecise labels are allowed
+/// * `X` : type of lattice element encoding
+/// * `D` : dimension of the features
+pub struct EvidentialKnn<X, const D: usize> where X: Eq + Hash, {
+    lattice_hash: u128slx,
+    k: usize,
+    alpha: f64slx,
+    distance: FeatureDistance,
+    training: Vec<(SVector<f64slx,D>,X)>,
+    gammas: HashMap<X,f64slx>,
+    user_gammas: HashMap<X,f64slx>,
+}
+
+impl<X, const D: usize> EvidentialKnn<X,D> where X: Clone + Eq + Ord + Hash, {
+    /// Constructor of the classifier with empty training set
+    /// * `lattice: &L` : lattice of the labels
+    /// * `k: usize` : number of neighbours
+    /// * `alpha: f64slx` : maximal weight assigned to the label of a neighbour, within `]0,1]`
+    /// * `distance: FeatureDistance` : distance between features
+    /// * `L` : type of lattice
+    /// * Output: the classifier or an error
+    pub fn new<L>(lattice: &L, k: usize, alpha: f64slx, distance: FeatureDistance) -> Result<Self,String> where L: Lattice<Item = X>, {
+        let native = alpha.unslx();
+        if k == 0 { return Err("number of neighbours should be positive".to_string()); }
+        if !(native > 0.0 && native <= 1.0) { return Err(format!("alpha {native} is not within ]0,1]")); }
+        Ok(Self { lattice_hash: lattice.lattice_hash(), k, alpha, distance, training: Vec::new(), gammas: HashMap::new(), user_gammas: HashMap::new(), })
+    }
+
+    /// Add labelled feature vectors to the training set
+    /// * Scale parameters `γ` are recomputed by means of the heuristic of Denœux: `γ(ω)` is the inverse of the mean squared distance between vectors labelled by `ω`
+    /// * Scale parameters set by `set_gamma(...)` are kept, since they take precedence over the heuristic
+    /// * `samples: I` : labelled feature vectors
+    /// * `I` : type of the collection of samples
+    /// * Output: nothing or an error
+    pub fn train<I>(&mut self, samples: I) -> Result<(), String> where I: IntoIterator<Item = (SVector<f64slx,D>,SafeElement<X>)>, {
+        for (u,(vector,SafeElement { code, lattice_hash })) in samples.into_iter().enumerate() {
+            if lattice_hash != self.lattice_hash { return Err(format!("label of sample {u} is not within lattice")); }
+            self.training.push((vector,code));
+        }
+        self.set_heuristic_gammas();
+        Ok(())
+    }
+
+    /// Set scale parameters by means of the heuristic of Denœux 
+    /// * `γ(ω)` is the inverse of the mean squared distance between vectors labelled by `ω`; it is set to `1` if undefined
+    /// * Scale parameters set by `set_gamma(...)` take precedence over the heuristic
+    pub fn set_heuristic_gammas(&mut self) {
+        let mut cumuls = HashMap::<&X,(f64,usize)>::new();
+        for (i,(xi,li)) in self.training.iter().enumerate() {
+            for (xj,_) in self.training[i+1..].iter().filter(|(_,lj)| lj == li) {
+                let d = self.distance.distance(xi, xj).unslx();
+                let cumul = cumuls.entry(li).or_insert((0.0,0));
+                cumul.0 += d * d; cumul.1 += 1;
+            }
+        }
+        let gammas = self.training.iter().map(|(_,l)| {
+            let gamma = match cumuls.get(l) {
+                Some((sum,count)) if *sum > 0.0 => *count as f64 / sum,
+                _ => 1.0,
+            };
+            (l.clone(), gamma.slx())
+        }).collect();
+        self.gammas = gammas;
+    }
+
+    /// Set scale parameter of a label
+    /// * This scale parameter takes precedence over the heuristic, and is kept by further trainings
+    /// * `label: &SafeElement<X>` : label
+    /// * `gamma: f64slx` : scale parameter, which should be positive
+    /// * Output: nothing or an error
+    pub fn set_gamma(&mut self, label: &SafeElement<X>, gamma: f64slx) -> Result<(),String> {
+        let SafeElement { code, lattice_hash } = label;
+        if *lattice_hash != self.lattice_hash { return Err("label is not within lattice".to_string()); }
+        let native = gamma.unslx();
+        if !(native.is_finite() && native > 0.0) { return Err(format!("gamma {native} should be positive and finite")); }
+        self.user_gammas.insert(code.clone(), gamma);
+        Ok(())
+    }
+
+    /// Size of the training set
+    pub fn len(&self) -> usize { self.training.len() }
+
+    /// Is the training set empty?
+    pub fn is_empty(&self) -> bool { self.training.is_empty() }
+
+    /// Assignments produced by the `k` nearest neighbours of a feature vector
+    /// * `lattice: &L` : lattice of the labels
+    /// * `x: &SVector<f64slx,D>` : feature vector
+    /// * `L` : type of lattice
+    /// * Output: neighbour assignments sorted by increasing distance, or an error
+    pub fn neighbour_assignments<L>(&self, lattice: &L, x: &SVector<f64slx,D>) -> Result<Vec<Assignment<X>>,String> where L: Lattice<Item = X>, {
+        if lattice.lattice_hash() != self.lattice_hash { return Err("classifier is not defined over lattice".to_string()); }
+        if self.training.is_empty() { return Err("training set is empty".to_string()); }
+        let mut distances = self.training.iter()
+            .map(|(xi,li)| (self.distance.distance(x, xi).unslx(),li)).collect::<Vec<_>>();
+        distances.sort_by(|(d1,_),(d2,_)| d1.total_cmp(d2));
+        let top = lattice.top().code;
+        distances.into_iter().take(self.k).map(|(d,label)| {
+            let gamma = self.user_gammas.get(label).or_else(|| self.gammas.get(label)).map(|g| (*g).unslx()).unwrap_or(1.0);
+            let weight = self.alpha.unslx() * (-gamma * d * d).exp();
+            let mut bba = lattice.assignment_with_capacity(2);
+            unsafe { 
+                bba.unsafe_push(label.clone(), weight.slx())?;
+                bba.unsafe_push(top.clone(), (1.0 - weight).slx())?;
+            }
+            Ok(bba.into())
+        }).collect()
+    }
+
+    /// Predict the assignment of a feature vector
+    /// * `lattice: &L` : lattice of the labels
+    /// * `engine: &E` : fusion engine
+    /// * `referee: &F` : referee function
+    /// * `x: &SVector<f64slx,D>` : feature vector
+    /// * `L` : type of lattice
+    /// * `E` : type of fusion engine
+    /// * `F` : type of referee function
+    /// * Output: an error or a pair composed of:
+    ///   * the fused assignment
+    ///   * the conflict
+    pub fn predict<L,E,F>(&self, lattice: &L, engine: &E, referee: &F, x: &SVector<f64slx,D>) 
+                        -> Result<(Assignment<X>,f64slx),String> where L: Lattice<Item = X>, E: DiscountedFusion, F: Referee, {
+        let bbas = self.neighbour_assignments(lattice, x)?;
+        let bbas = bbas.iter().collect::<Vec<_>>();
+        engine.fuse(lattice, referee, &bbas)
+    }
+}
+
+pub mod experiment {
+    use nalgebra::SVector;
+    use crate::{
+        types::IntoSlx,
+        structs::{ Powerset, DiscountedFuser, EnumRule, EvidentialKnn, FeatureDistance, }, 
+        traits::LatticeWithLeaves,
+    };
+
+    /// Experimentation with the evidential k-nearest neighbours classifier
+    pub fn exp_knn() -> Result<(),String> {
+        println!("=================================== knn =====");
+        let lattice = Powerset::new_with_label(
+            &["A".to_string(), "B".to_string(), "C".to_string()], 1024
+        )?;
+        let vector = |x: f64, y: f64| SVector::<_,2>::new(x.slx(), y.slx());
+        let (a, b, c) = (lattice.leaf(0)?, lattice.leaf(1)?, lattice.leaf(2)?);
+        let samples = vec![
+            (vector(0.0, 0.0), a), (vector(0.2, 0.1), a), (vector(-0.1, 0.3), a),
+            (vector(2.0, 0.0), b), (vector(2.2, 0.2), b), (vector(1.9, -0.2), b),
+            (vector(1.0, 2.0), c), (vector(1.2, 2.1), c), (vector(0.8, 1.8), c),
+        ];
+        let mut knn = EvidentialKnn::new(&lattice, 4, 0.95.slx(), FeatureDistance::Euclidean)?;
+        knn.train(samples)?;
+        let engine = DiscountedFuser::new(512..=1024);
+        for (x,y) in [(0.1,0.1), (1.0,0.0), (1.0,1.0), (5.0,5.0)] {
+            let (fused,z) = knn.predict(&lattice, &engine, &EnumRule::DempsterShafer, &vector(x,y))?;
+            println!("({x},{y}) -> {fused}, z -> {z:.3}");
+        }
+        println!();
+        Ok(())
+    }
+}
@@ -0,0 +1,30 @@
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the Lesser GNU General Public License as published
+// by the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// Lesser GNU General Public License for more details.
+
+// You should have received a copy of the Lesser GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+// Copyright 2024 Frederic Dambreville, Jean Dezert Developers.
+
+
+/// Estimation of source reliabilities from labelled histories
+mod reliability; pub use self::reliability::{ 
+    LabelledMass, Reliability, ReliabilityKind, ReliabilityLoss, ReliabilityEstimator, experiment::exp_reliability, 
+};
+#[cfg(feature = "use_nalgebra")]
+/// Evidential k-nearest neighbours classifier
+mod knn; 
+#[cfg(feature = "use_nalgebra")]
+pub use self::knn::{ EvidentialKnn, FeatureDistance, experiment::exp_knn, };
+#[cfg(feature = "use_nalgebra")]
+/// Evidential c-means clustering
+mod ecm; 
+#[cfg(feature = "use_nalgebra")]
+pub use self::ecm::{ EvidentialCMeans, CredalPartition, ECM_MAX_CLUSTERS, experiment::exp_ecm, };
@@ -0,0 +1,287 @@
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the Lesser GNU General Public License as published
+// by the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// Lesser GNU General Public License for more details.
+
+// You should have received a copy of the Lesser GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+// Copyright 2024 Frederic Dambreville, Jean Dezert Developers.
+
+
+use std::hash::Hash;
+
+use hashed_type_def::HashedTypeDef;
+#[cfg(feature = "rkyv")] use rkyv::{Archive, Serialize as RkyvSerialize, Deserialize as RkyvDeserialize, };
+#[cfg(feature = "serde")] use serde::{Serialize as SerdeSerialize, Deserialize as SerdeDeserialize};
+
+use crate::{
+    types::{ f64slx, SlxInto, IntoSlx, },
+    structs::{ Assignment, SafeElement, },
+    traits::{ LatticeWithLeaves, BeliefMetric, Discounting, },
+};
+
+/// Tolerance of the golden section search used by the numerical optimization
+const GOLDEN_TOLERANCE: f64 = 1e-6;
+
+/// Labelled mass: pair composed of a mass assignment and of the leaf which is the ground truth
+pub type LabelledMass<X> = (Assignment<X>, SafeElement<X>);
+
+#[derive(HashedTypeDef, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "rkyv", derive(Archive,RkyvSerialize,RkyvDeserialize))]
+#[cfg_attr(feature = "serde", derive(SerdeSerialize, SerdeDeserialize))]
+/// Reliability factors of a source
+/// * These factors are used by `Discounting::discount_with`
+pub enum Reliability {
+    /// Classical reliability: a single factor within `[0,1]`
+    Classical(f64slx),
+    /// Contextual reliabilities: one factor within `[0,1]` for each leaf, indexed by leaf rank
+    Contextual(Vec<f64slx>),
+}
+
+#[derive(HashedTypeDef, Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "rkyv", derive(Archive,RkyvSerialize,RkyvDeserialize))]
+#[cfg_attr(feature = "serde", derive(SerdeSerialize, SerdeDeserialize))]
+/// Kind of reliability factors to be estimated
+pub enum ReliabilityKind {
+    /// One factor for the source
+    Classical,
+    /// One factor for each leaf of the lattice
+    Contextual,
+}
+
+#[derive(HashedTypeDef, Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "rkyv", derive(Archive,RkyvSerialize,RkyvDeserialize))]
+#[cfg_attr(feature = "serde", derive(SerdeSerialize, SerdeDeserialize))]
+/// Loss minimized by the reliability estimation; losses are averaged over the labelled history
+pub enum ReliabilityLoss {
+    /// Squared error between the discounted plausibilities of the leaves and the truth indicator
+    /// * This loss is quadratic in the reliabilities, and is minimized in closed form
+    Plausibility,
+    /// Squared error between the discounted pignistic probabilities of the leaves and the truth indicator
+    Pignistic,
+    /// Jousselme distance between the discounted assignment and the categorical assignment of the truth
+    Jousselme,
+}
+
+#[derive(HashedTypeDef, Copy, Clone, Debug)]
+#[cfg_attr(feature = "rkyv", derive(Archive,RkyvSerialize,RkyvDeserialize))]
+#[cfg_attr(feature = "serde", derive(SerdeSerialize, SerdeDeserialize))]
+/// Estimator of reliability factors from a labelled history of a source
+/// * The labelled history is a sequence of pairs `(mass, truth)` where `truth` is a leaf of the lattice
+/// * Losses without closed form minimizer are minimized by coordinate descent with golden section search
+pub struct ReliabilityEstimator {
+    kind: ReliabilityKind,
+    loss: ReliabilityLoss,
+    sweeps: u32,
+}
+
+impl ReliabilityEstimator {
+    /// Constructor of the reliability estimator
+    /// * `kind: ReliabilityKind` : kind of the estimated factors
+    /// * `loss: ReliabilityLoss` : loss to be minimized
+    /// * `sweeps: u32` : number of coordinate descent sweeps (unused by closed form minimization)
+    /// * Output: reliability estimator
+    pub fn new(kind: ReliabilityKind, loss: ReliabilityLoss, sweeps: u32) -> Self {
+        Self { kind, loss, sweeps: sweeps.max(1), }
+    }
+
+    /// For intern use: leaves of the lattice sorted by rank, and leaf ranks of the truths
+    fn truth_ranks<L>(lattice: &L, history: &[LabelledMass<L::Item>]) -> Result<(Vec<L::Item>,Vec<usize>),String>
+                                                                    where L: LatticeWithLeaves, L::Item: Ord + Hash, {
+        let nb_leaves = unsafe { lattice.unsafe_leaves() }?.count();
+        let leaves = (0..nb_leaves).map(|u| unsafe { lattice.unsafe_leaf(u) }.cloned()).collect::<Result<Vec<_>,_>>()?;
+        let lattice_hash = lattice.lattice_hash();
+        let ranks = history.iter().enumerate().map(|(i,(mass,truth))| {
+            if mass.lattice_hash != lattice_hash || truth.lattice_hash != lattice_hash { 
+                return Err(format!("labelled mass of index {i} is not defined over lattice")); 
+            }
+            leaves.iter().position(|l| l == &truth.code)
+                .ok_or_else(|| format!("truth of index {i} is not a leaf"))
+        }).collect::<Result<Vec<_>,_>>()?;
+        Ok((leaves,ranks))
+    }
+
+    /// Average loss of the discounted history
+    /// * `lattice: &L` : lattice of definition of the assignments
+    /// * `history: &[LabelledMass<L::Item>]` : labelled history of the source
+    /// * `reliability: &Reliability` : reliability factors
+    /// * `L` : type of lattice
+    /// * Output: average loss or error
+    pub fn loss<L>(&self, lattice: &L, history: &[LabelledMass<L::Item>], reliability: &Reliability) 
+                                    -> Result<f64slx,String> where L: LatticeWithLeaves, L::Item: Ord + Hash, {
+        let (leaves,ranks) = Self::truth_ranks(lattice, history)?;
+        Ok(self.native_loss(lattice, history, &leaves, &ranks, reliability)?.slx())
+    }
+
+    /// For intern use: average loss in native type
+    fn native_loss<L>(&self, lattice: &L, history: &[LabelledMass<L::Item>], 
+        leaves: &[L::Item], ranks: &[usize], reliability: &Reliability,
+    ) -> Result<f64,String> where L: LatticeWithLeaves, L::Item: Ord + Hash, {
+        if history.is_empty() { return Err("labelled history is empty".to_string()); }
+        let mut cumul = 0.0;
+        for ((mass,truth),rank) in history.iter().zip(ranks) {
+            let discounted = lattice.discount_with(mass, reliability)?;
+            cumul += match self.loss {
+                ReliabilityLoss::Plausibility => leaves.iter().enumerate().map(|(k,l)| {
+                    let pl = discounted.elements.iter()
+                        .filter(|(x,_)| unsafe { lattice.unsafe_implied_join(x, l) })
+                        .map(|(_,w)| (*w).unslx()).sum::<f64>();
+                    let delta = if k == *rank { 1.0 } else { 0.0 };
+                    (pl - delta).powi(2)
+                }).sum::<f64>(),
+                ReliabilityLoss::Pignistic => {
+                    let pignistic = lattice.mass_to_pignistic(&discounted)?;
+                    leaves.iter().enumerate().map(|(k,l)| {
+                        let p = pignistic.elements.get(l).map(|w| (*w).unslx()).unwrap_or(0.0);
+                        let delta = if k == *rank { 1.0 } else { 0.0 };
+                        (p - delta).powi(2)
+                    }).sum::<f64>()
+                },
+                ReliabilityLoss::Jousselme => {
+                    let mut categorical = lattice.assignment();
+                    categorical.push(truth.clone(), 1.0.slx())?;
+                    lattice.jousselme_distance(&discounted, &categorical.into())?.unslx()
+                },
+            };
+        }
+        Ok(cumul / history.len() as f64)
+    }
+
+    /// For intern use: closed form minimization of the plausibility loss
+    /// * discounted plausibility of leaf `k` is `1 - rₖ aₖ` with `aₖ = 1 - pl(θₖ)`, so that the loss is quadratic in `rₖ`
+    fn plausibility_minimizer<L>(&self, lattice: &L, history: &[LabelledMass<L::Item>], 
+        leaves: &[L::Item], ranks: &[usize],
+    ) -> Reliability where L: LatticeWithLeaves, L::Item: Ord + Hash, {
+        let mut numerators = vec![0.0; leaves.len()];
+        let mut denominators = vec![0.0; leaves.len()];
+        for ((mass,_),rank) in history.iter().zip(ranks) {
+            for (k,l) in leaves.iter().enumerate() {
+                let pl = mass.elements.iter()
+                    .filter(|(x,_)| unsafe { lattice.unsafe_implied_join(x, l) })
+                    .map(|(_,w)| (*w).unslx()).sum::<f64>();
+                let a = 1.0 - pl;
+                if k != *rank { numerators[k] += a; }
+                denominators[k] += a * a;
+            }
+        }
+        let ratio = |n: f64, d: f64| if d > 0.0 { (n / d).clamp(0.0, 1.0) } else { 1.0 };
+        match self.kind {
+            ReliabilityKind::Classical => Reliability::Classical(
+                ratio(numerators.iter().sum(), denominators.iter().sum()).slx()
+            ),
+            ReliabilityKind::Contextual => Reliability::Contextual(
+                numerators.into_iter().zip(denominators).map(|(n,d)| ratio(n,d).slx()).collect()
+            ),
+        }
+    }
+
+    /// For intern use: golden section search of the minimum of a function over `[0,1]`
+    fn golden_section<F>(mut f: F) -> Result<f64,String> where F: FnMut(f64) -> Result<f64,String> {
+        let inv_phi = (5f64.sqrt() - 1.0) / 2.0;
+        let (mut a, mut b) = (0.0, 1.0);
+        let mut c = b - inv_phi * (b - a);
+        let mut d = a + inv_phi * (b - a);
+        let (mut fc, mut fd) = (f(c)?, f(d)?);
+        while b - a > GOLDEN_TOLERANCE {
+            if fc < fd { 
+                b = d; d = c; fd = fc; c = b - inv_phi * (b - a); fc = f(c)?;
+            } else { 
+                a = c; c = d; fc = fd; d = a + inv_phi * (b - a); fd = f(d)?;
+            }
+        }
+        // bounds are tested since the optimum is often reached at full reliability or full discounting 
+        let mut best = (0.5 * (a + b), f(0.5 * (a + b))?);
+        for r in [0.0, 1.0] { let fr = f(r)?; if fr < best.1 { best = (r,fr); } }
+        Ok(best.0)
+    }
+
+    /// Estimate the reliability factors of a source from its labelled history
+    /// * `lattice: &L` : lattice of definition of the assignments
+    /// * `history: &[LabelledMass<L::Item>]` : labelled history of the source; truths are leaves of the lattice
+    /// * `L` : type of lattice
+    /// * Output: reliability factors or error
+    pub fn estimate<L>(&self, lattice: &L, history: &[LabelledMass<L::Item>]) 
+                                    -> Result<Reliability,String> where L: LatticeWithLeaves, L::Item: Ord + Hash, {
+        if history.is_empty() { return Err("labelled history is empty".to_string()); }
+        let (leaves,ranks) = Self::truth_ranks(lattice, history)?;
+        if let ReliabilityLoss::Plausibility = self.loss {
+            return Ok(self.plausibility_minimizer(lattice, history, &leaves, &ranks));
+        }
+        let one = 1.0.slx();
+        match self.kind {
+            ReliabilityKind::Classical => {
+                let r = Self::golden_section(|r| self.native_loss(
+                    lattice, history, &leaves, &ranks, &Reliability::Classical(r.slx())
+                ))?;
+                Ok(Reliability::Classical(r.slx()))
+            },
+            ReliabilityKind::Contextual => {
+                let mut reliabilities = vec![one; leaves.len()];
+                for _ in 0..self.sweeps {
+                    for k in 0..leaves.len() {
+                        let r = Self::golden_section(|r| {
+                            let mut reliabilities = reliabilities.clone();
+                            reliabilities[k] = r.slx();
+                            self.native_loss(lattice, history, &leaves, &ranks, &Reliability::Contextual(reliabilities))
+                        })?;
+                        reliabilities[k] = r.slx();
+                    }
+                }
+                Ok(Reliability::Contextual(reliabilities))
+            },
+        }
+    }
+
+    /// Estimate the reliability factors of several sources from their labelled histories
+    /// * `lattice: &L` : lattice of definition of the assignments
+    /// * `histories: &[&[LabelledMass<L::Item>]]` : labelled histories of the sources
+    /// * `L` : type of lattice
+    /// * Output: reliability factors of each source or error
+    pub fn estimate_sources<L>(&self, lattice: &L, histories: &[&[LabelledMass<L::Item>]]) 
+                                    -> Result<Vec<Reliability>,String> where L: LatticeWithLeaves, L::Item: Ord + Hash, {
+        histories.iter().map(|history| self.estimate(lattice, history)).collect()
+    }
+}
+
+pub mod experiment {
+    use crate::{
+        structs::{ Powerset, ReliabilityEstimator, ReliabilityKind, ReliabilityLoss, }, 
+        traits::{ Lattice, LatticeWithLeaves, Discounting, }
+    };
+
+    /// Experimentation with the estimation of reliabilities
+    pub fn exp_reliability() -> Result<(),String> {
+        println!("=========================== reliability =====");
+        let lattice = Powerset::new_with_label(
+            &["A".to_string(), "B".to_string(), "C".to_string()], 1024
+        )?;
+        let (a, b, c) = (lattice.leaf(0)?, lattice.leaf(1)?, lattice.leaf(2)?);
+        let ab = lattice.join(&a, &b)?;
+        // the source is reliable when the truth is A, and often wrong when the truth is C
+        let history = vec![
+            (lattice.assignment() + (a, 0.8) + (ab, 0.2) + (), a),
+            (lattice.assignment() + (a, 0.7) + (ab, 0.3) + (), a),
+            (lattice.assignment() + (b, 0.9) + (ab, 0.1) + (), b),
+            (lattice.assignment() + (a, 0.6) + (b, 0.4) + (), c),
+            (lattice.assignment() + (b, 0.8) + (c, 0.2) + (), c),
+            (lattice.assignment() + (c, 0.9) + (ab, 0.1) + (), c),
+        ];
+        for kind in [ReliabilityKind::Classical, ReliabilityKind::Contextual] {
+            for loss in [ReliabilityLoss::Plausibility, ReliabilityLoss::Pignistic, ReliabilityLoss::Jousselme] {
+                let estimator = ReliabilityEstimator::new(kind, loss, 4);
+                let reliability = estimator.estimate(&lattice, &history)?;
+                let loss = estimator.loss(&lattice, &history, &reliability)?;
+                println!("{kind:?} / {:?} -> {reliability:?}, loss -> {loss:.4}", estimator.loss);
+                println!("  discounted -> {}", lattice.discount_with(&history[3].0, &reliability)?);
+            }
+        }
+        println!();
+        Ok(())
+    }
+}
@@ -16,19 +16,41 @@
 
 /// Assignments tools
 mod assignment_tools; pub use self::assignment_tools::{ 
-    SafeElement, SafeArray, AssignmentBuilder, Assignment, exp_hidden, ASSIGNMENT_EPSILON, 
+    SafeElement, SafeArray, AssignmentBuilder, Assignment, ImpreciseAssignment, Label, LinguisticScale, QualitativeAssignment, 
+    exp_hidden, ASSIGNMENT_EPSILON, 
 }; 
 pub (crate) use self::assignment_tools::{ hidden, zero_f64slx, one_f64slx, };
 /// Definition of fusion rules and fusion engines
 mod fusers; pub use self::fusers::{ 
-    Conjunctive, Disjunctive, DiscountedFuser, DuboisPrade2D, Pcr6, PcrSharp, DempsterShafer, EnumRule,
-    exp_pcr6, exp_conjunctive, exp_dempster_shafer, exp_disjunctive, exp_pcr_sharp, exp_dubois_prade_2d,
+    Conjunctive, Disjunctive, DiscountedFuser, BbaProduct, DuboisPrade2D, Pcr5, Pcr6, PcrSharp, DempsterShafer, Averaging, Murphy, Yager, EnumRule,
+    FusionTrace, TracedElement, TracedContribution,
+    exp_pcr5, exp_pcr6, exp_bba_product, exp_conjunctive, exp_dempster_shafer, exp_disjunctive, exp_pcr_sharp, exp_dubois_prade_2d,
+    exp_fusion_trace, exp_averaging, exp_murphy, exp_yager,
 };
+pub (crate) use self::fusers::FusionTraceBuilder;
 /// Definitions of lattices structures
 mod structures; pub use self::structures::{ 
-    Powerset, Taxon, TaxonCoder, Taxonomy, Taxons, TaxonomyBuilder, EnumLattice,
-    exp_taxonomy_1, exp_taxonomy_2,
+    Powerset, Taxon, TaxonCoder, Taxonomy, Taxons, TaxonomyBuilder, IntervalLattice, EnumLattice,
+    exp_taxonomy_1, exp_taxonomy_2, exp_interval,
 }; 
 #[allow(deprecated)] pub use self::structures::CombiLattice;
+/// Learning tools for belief functions
+mod learning; pub use self::learning::{ 
+    LabelledMass, Reliability, ReliabilityKind, ReliabilityLoss, ReliabilityEstimator, exp_reliability, 
+};
+#[cfg(feature = "use_nalgebra")]
+pub use self::learning::{ 
+    EvidentialKnn, FeatureDistance, EvidentialCMeans, CredalPartition, ECM_MAX_CLUSTERS, exp_knn, exp_ecm,
+};
+/// Valuation-based systems and evidential networks
+mod valuation; pub use self::valuation::{ 
+    Variable, ProductFrame, Valuation, ValuationSystem, JoinTree, Propagation, MAX_CONFIGURATIONS, exp_valuation,
+};
+/// Possibility distributions and their fusion rules
+mod possibility; pub use self::possibility::{ PossibilityDistribution, PossibilisticRule, };
+/// Conflict analysis between sources
+mod conflict; pub use self::conflict::{ 
+    ConflictMeasure, ConflictReport, ConflictAnalyzer, experiment::exp_conflict, 
+};
 /// Definitions of metrics
 mod metrics; // not implemented at this time
\ No newline at end of file
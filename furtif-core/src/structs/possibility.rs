@@ -0,0 +1,108 @@
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the Lesser GNU General Public License as published
+// by the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// Lesser GNU General Public License for more details.
+
+// You should have received a copy of the Lesser GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+// Copyright 2024 Frederic Dambreville, Jean Dezert Developers.
+
+
+use core::fmt::Debug;
+
+use hashed_type_def::HashedTypeDef;
+use crate::types::{ u128slx, f64slx, SlxInto, IntoSlx, };
+
+#[cfg(feature = "serde")] use serde::{ Serialize as SerdeSerialize, Deserialize as SerdeDeserialize, };
+#[cfg(feature = "rkyv")] use rkyv::{ Archive, Serialize as RkyvSerialize, Deserialize as RkyvDeserialize, };
+
+#[derive(Clone,HashedTypeDef,)]
+#[cfg_attr(feature = "rkyv", derive(Archive,RkyvSerialize,RkyvDeserialize))]
+/// Possibility distribution over the leaves of a lattice
+/// * Possibility distribution contains a lattice hash and the possibility degrees of the leaves, indexed by leaf rank (see `LatticeWithLeaves::leaf`)
+/// * Degrees are within `[0, 1]`; the distribution is normalized when its height (maximal degree) is `1`
+/// * There is no constructor for `PossibilityDistribution`: use methods of trait `PossibilityTheory`
+pub struct PossibilityDistribution {
+    pub degrees: Vec<f64slx>,
+    pub lattice_hash: u128slx,
+}
+
+impl PossibilityDistribution {
+    /// Height of the distribution, i.e. its maximal degree
+    /// * Output: height
+    pub fn height(&self) -> f64slx {
+        self.degrees.iter().map(|d| (*d).unslx()).fold(0.0, f64::max).slx()
+    }
+}
+
+impl Debug for PossibilityDistribution {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let degrees = self.degrees.iter().map(|d| (*d).unslx()).collect::<Vec<_>>();
+        f.debug_struct("PossibilityDistribution").field("degrees", &degrees)
+            .field("lattice_hash", &format!("{:x}", self.lattice_hash.unslx())).finish()
+    }
+}
+
+// implementation of Serde serialization
+#[cfg(feature = "serde")] 
+mod serding {
+    use super::{ PossibilityDistribution as SerdingPossibilityDistribution, SerdeSerialize, SerdeDeserialize, };
+    use crate::types::{ SlxInto, IntoSlx, };
+    #[derive(SerdeSerialize,SerdeDeserialize)]
+    pub struct PossibilityDistribution {
+        degrees: Vec<f64>,
+        lattice_hash: u128,
+    }
+
+    impl<'de> SerdeDeserialize<'de> for SerdingPossibilityDistribution {
+        fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where D: serde::Deserializer<'de> {
+            let PossibilityDistribution { degrees, lattice_hash, } = PossibilityDistribution::deserialize(deserializer)?;
+            let degrees = degrees.into_iter().map(|d| d.slx()).collect();
+            Ok(Self { degrees, lattice_hash: lattice_hash.slx(), })
+        }
+    }
+    
+    impl SerdeSerialize for SerdingPossibilityDistribution {
+        fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where S: serde::Serializer {
+            let SerdingPossibilityDistribution { degrees, lattice_hash, } = self;
+            let degrees = degrees.iter().map(|d| (*d).unslx()).collect();
+            let distribution = PossibilityDistribution { degrees, lattice_hash: (*lattice_hash).unslx(), };
+            distribution.serialize(serializer)
+        }
+    }
+}
+
+#[derive(HashedTypeDef, Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "rkyv", derive(Archive,RkyvSerialize,RkyvDeserialize))]
+#[cfg_attr(feature = "serde", derive(SerdeSerialize, SerdeDeserialize))]
+/// Enumeration of the fusion rules of possibility distributions
+pub enum PossibilisticRule {
+    /// Conjunctive fusion by means of the minimum
+    Minimum,
+    /// Conjunctive fusion by means of the product
+    Product,
+    /// Disjunctive fusion by means of the maximum
+    Maximum,
+}
+
+impl PossibilisticRule {
+    /// Combine the degrees of a leaf
+    /// * `degrees: I` : degrees of the leaf within each distribution
+    /// * `I` : type of degrees iterator
+    /// * Output: combined degree
+    pub fn combine<I>(&self, degrees: I) -> f64 where I: IntoIterator<Item = f64> {
+        match self {
+            PossibilisticRule::Minimum => degrees.into_iter().fold(1.0, f64::min),
+            PossibilisticRule::Product => degrees.into_iter().product(),
+            PossibilisticRule::Maximum => degrees.into_iter().fold(0.0, f64::max),
+        }
+    }
+}
@@ -18,7 +18,7 @@ use std::{collections::hash_map, vec};
 
 use crate::{
     types::{ u128slx, f64slx, },
-    structs::{ Powerset, Taxonomy, }, 
+    structs::{ Powerset, Taxonomy, IntervalLattice, }, 
     traits::{CollectionFamily1, IterableLattice, Lattice, LatticeWithLeaves}
 };
 
@@ -41,16 +41,18 @@ pub enum EnumLattice {
     Powerset{ powerset: Powerset, },
     /// Taxonomy
     Taxonomy{ taxonomy: Taxonomy, },
+    /// Interval lattice
+    Interval{ interval: IntervalLattice, },
 }
 
 impl Lattice for EnumLattice {
     type Item = u128slx;
 
     fn rand_lattice<R: rand::prelude::Rng>(rng: &mut R) -> Self {
-        if rng.gen::<bool>() {
-            Self::Powerset { powerset: Powerset::rand_lattice(rng) }
-        } else {
-            Self::Taxonomy { taxonomy: Taxonomy::rand_lattice(rng) }
+        match rng.gen_range(0..3) {
+            0 => Self::Powerset { powerset: Powerset::rand_lattice(rng) },
+            1 => Self::Taxonomy { taxonomy: Taxonomy::rand_lattice(rng) },
+            _ => Self::Interval { interval: IntervalLattice::rand_lattice(rng) },
         }
     }
 
@@ -58,6 +60,7 @@ impl Lattice for EnumLattice {
         match self {
             EnumLattice::Powerset { powerset } => powerset.rand_element(rng),
             EnumLattice::Taxonomy { taxonomy } => taxonomy.rand_element(rng),
+            EnumLattice::Interval { interval } => interval.rand_element(rng),
         }
     }
 
@@ -65,6 +68,7 @@ impl Lattice for EnumLattice {
         match self {
             EnumLattice::Powerset { powerset } => powerset.rand_elements::<R,I>(len, rng),
             EnumLattice::Taxonomy { taxonomy } => taxonomy.rand_elements::<R,I>(len, rng),
+            EnumLattice::Interval { interval } => interval.rand_elements::<R,I>(len, rng),
         }
     }
 
@@ -73,6 +77,7 @@ impl Lattice for EnumLattice {
         match self {
             EnumLattice::Powerset { powerset } => powerset.ref_lattice_hash(),
             EnumLattice::Taxonomy { taxonomy } => taxonomy.ref_lattice_hash(),
+            EnumLattice::Interval { interval } => interval.ref_lattice_hash(),
         }
     }
 
@@ -80,6 +85,7 @@ impl Lattice for EnumLattice {
         match self {
             EnumLattice::Powerset { powerset } => powerset.contains(element),
             EnumLattice::Taxonomy { taxonomy } => taxonomy.contains(element),
+            EnumLattice::Interval { interval } => interval.contains(element),
         }
     }
 
@@ -87,6 +93,7 @@ impl Lattice for EnumLattice {
         match self {
             EnumLattice::Powerset { powerset } => powerset.ref_bottom(),
             EnumLattice::Taxonomy { taxonomy } => taxonomy.ref_bottom(),
+            EnumLattice::Interval { interval } => interval.ref_bottom(),
         }
     }
 
@@ -94,6 +101,7 @@ impl Lattice for EnumLattice {
         match self {
             EnumLattice::Powerset { powerset } => powerset.ref_top(),
             EnumLattice::Taxonomy { taxonomy } => taxonomy.ref_top(),
+            EnumLattice::Interval { interval } => interval.ref_top(),
         }
     }
 
@@ -101,6 +109,7 @@ impl Lattice for EnumLattice {
         match self {
             EnumLattice::Powerset { powerset } => powerset.unsafe_meet(element_left, element_right),
             EnumLattice::Taxonomy { taxonomy } => taxonomy.unsafe_meet(element_left, element_right),
+            EnumLattice::Interval { interval } => interval.unsafe_meet(element_left, element_right),
         }
     }
 
@@ -108,6 +117,7 @@ impl Lattice for EnumLattice {
         match self {
             EnumLattice::Powerset { powerset } => powerset.unsafe_join(element_left, element_right),
             EnumLattice::Taxonomy { taxonomy } => taxonomy.unsafe_join(element_left, element_right),
+            EnumLattice::Interval { interval } => interval.unsafe_join(element_left, element_right),
         }
     }
 
@@ -115,6 +125,7 @@ impl Lattice for EnumLattice {
         match self {
             EnumLattice::Powerset { powerset } => powerset.from_str(s),
             EnumLattice::Taxonomy { taxonomy } => taxonomy.from_str(s),
+            EnumLattice::Interval { interval } => interval.from_str(s),
         }
     }
 
@@ -122,6 +133,7 @@ impl Lattice for EnumLattice {
         match self {
             EnumLattice::Powerset { powerset } => powerset.to_string(element),
             EnumLattice::Taxonomy { taxonomy } => taxonomy.to_string(element),
+            EnumLattice::Interval { interval } => interval.to_string(element),
         }
     }
 }
@@ -135,6 +147,7 @@ impl IterableLattice for EnumLattice {
         match self {
             EnumLattice::Powerset { powerset } => powerset.unsafe_bottom_to_top(),
             EnumLattice::Taxonomy { taxonomy } => taxonomy.unsafe_bottom_to_top(),
+            EnumLattice::Interval { interval } => interval.unsafe_bottom_to_top(),
         }
     }
 
@@ -142,6 +155,7 @@ impl IterableLattice for EnumLattice {
         match self {
             EnumLattice::Powerset { powerset } => powerset.unsafe_top_to_bottom(),
             EnumLattice::Taxonomy { taxonomy } => taxonomy.unsafe_top_to_bottom(),
+            EnumLattice::Interval { interval } => interval.unsafe_top_to_bottom(),
         }
     }
 }
@@ -153,6 +167,7 @@ impl LatticeWithLeaves for EnumLattice {
         match self {
             EnumLattice::Powerset { powerset } => powerset.unsafe_weighted_leaf(u),
             EnumLattice::Taxonomy { taxonomy } => taxonomy.unsafe_weighted_leaf(u),
+            EnumLattice::Interval { interval } => interval.unsafe_weighted_leaf(u),
         }
     }
 
@@ -160,6 +175,7 @@ impl LatticeWithLeaves for EnumLattice {
         match self {
             EnumLattice::Powerset { powerset } => powerset.unsafe_leaves(),
             EnumLattice::Taxonomy { taxonomy } => taxonomy.unsafe_leaves(),
+            EnumLattice::Interval { interval } => interval.unsafe_leaves(),
         }
     }
 }
@@ -0,0 +1,352 @@
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the Lesser GNU General Public License as published
+// by the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// Lesser GNU General Public License for more details.
+
+// You should have received a copy of the Lesser GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+// Copyright 2024 Frederic Dambreville, Jean Dezert Developers.
+
+
+use std::{ 
+    collections::{ HashMap, hash_map, }, vec,
+};
+use core::fmt::Debug;
+
+use hashed_type_def::{ HashedTypeDef, add_hash_fnv1a, };
+
+use crate::types::{ u128slx, f64slx, SlxInto, IntoSlx, };
+
+
+#[cfg(feature = "serde")] use serde::{Serialize as SerdeSerialize, Deserialize as SerdeDeserialize};
+#[cfg(feature = "rkyv")] use rkyv::{Archive, Serialize as RkyvSerialize, Deserialize as RkyvDeserialize};
+
+use rand::prelude::*;
+
+use crate::{
+    traits::{ Lattice, ComplementedLattice, IterableLattice, LatticeWithLeaves }, 
+    structs::{ SafeElement, Assignment, }, 
+};
+
+const DEFAULT_MAX_ITER_LEN : usize = 1024;
+
+#[derive(Clone, Debug, HashedTypeDef)]
+#[cfg_attr(feature = "rkyv", derive(Archive,RkyvSerialize,RkyvDeserialize))]
+/// Interval lattice on a discretised real axis
+/// * The axis is divided into at most 128 cells; the elements of the lattice are the unions of cells
+/// * Meet is the intersection and join is the union
+/// * Closed intervals are approximated by the smallest union of cells containing them
+/// * The leaves are the cells, weighted by their relative length, so that the pignistic transform is uniform over the axis
+pub struct IntervalLattice {
+    max_iter_len: u128slx,
+    top: SafeElement<u128slx>,
+    bottom: SafeElement<u128slx>,
+    bounds: Vec<f64slx>,
+    leaves: Vec<u128slx>,
+    weighted_leaves: HashMap<u128slx,f64slx>,
+    bottom_to_top: Option<Vec<u128slx>>,
+}
+
+// implementation of Serde serialization
+#[cfg(feature = "serde")] mod serding {
+    use super::{ 
+        IntervalLattice as SerdingIntervalLattice, SerdeSerialize, SerdeDeserialize, SlxInto,
+    };
+    #[derive(SerdeSerialize,SerdeDeserialize)]
+    pub struct IntervalLattice {
+        bounds: Vec<f64>, max_iter_len: usize,
+    }
+    impl<'de> SerdeDeserialize<'de> for SerdingIntervalLattice {
+        fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where D: serde::Deserializer<'de> {
+            let IntervalLattice { bounds, max_iter_len } = IntervalLattice::deserialize(deserializer)?;
+            SerdingIntervalLattice::with_bounds(&bounds, max_iter_len).map_err(serde::de::Error::custom)
+        }
+    }
+    impl SerdeSerialize for SerdingIntervalLattice {
+        fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where S: serde::Serializer {
+            let SerdingIntervalLattice { max_iter_len, bounds, .. } = self;
+            let bounds = bounds.iter().map(|b| (*b).unslx()).collect();
+            let max_iter_len = (*max_iter_len).unslx() as usize;
+            let intervals = IntervalLattice { bounds, max_iter_len };
+            intervals.serialize(serializer)
+        }
+    }
+}
+
+impl IntervalLattice {
+    /// Interval lattice constructor with regular cells
+    /// * `min: f64slx` : lower bound of the axis
+    /// * `max: f64slx` : upper bound of the axis
+    /// * `nb_cells: usize` : number of cells
+    /// * `max_iter_len: usize` : maximal size for an iterator on the lattice
+    /// * Output: the interval lattice or an error, when the number of cells is not within [1,128] or the bounds are not ordered
+    pub fn new(min: f64slx, max: f64slx, nb_cells: usize, max_iter_len: usize,) -> Result<IntervalLattice,String> {
+        let (min, max) = (min.unslx(), max.unslx());
+        let bounds = (0..=nb_cells).map(|u| {
+            if u == nb_cells { max } else { min + (max - min) * (u as f64) / (nb_cells as f64) }
+        }).collect::<Vec<_>>();
+        Self::with_bounds(&bounds, max_iter_len)
+    }
+
+    /// Interval lattice constructor with predefined cells bounds
+    /// * `bounds: &[f64]` : strictly increasing sequence of finite cells bounds; cell `u` is `[bounds[u], bounds[u+1]]`
+    /// * `max_iter_len: usize` : maximal size for an iterator on the lattice
+    /// * Output: the interval lattice or an error, when the number of cells is not within [1,128] or the bounds are not ordered
+    pub fn with_bounds(bounds: &[f64], max_iter_len: usize,) -> Result<IntervalLattice,String> {
+        let nb_cells = bounds.len().saturating_sub(1);
+        let s = std::mem::size_of::<u128>() << 3; // number of bits of u128
+        if nb_cells == 0 || nb_cells > s { return Err(format!("Number of cells should be within [1,{s}]")); }
+        if bounds.iter().any(|b| !b.is_finite()) || bounds.windows(2).any(|w| w[0] >= w[1]) {
+            return Err("Bounds should be finite and strictly increasing".to_string());
+        }
+        let top = if nb_cells == s { u128::MAX } else { (1u128 << nb_cells) - 1 }.slx();
+        let leaves = (0..nb_cells).map(|rank| (1u128 << rank).slx()).collect::<Vec<u128slx>>();
+        let length = bounds[nb_cells] - bounds[0];
+        let weighted_leaves = leaves.iter().enumerate()
+            .map(|(u,l)| (*l, ((bounds[u+1] - bounds[u]) / length).slx())).collect::<HashMap<_,_>>();
+        let lattice_hash = {
+            let mut lattice_hash = IntervalLattice::TYPE_HASH_NATIVE;
+            lattice_hash = add_hash_fnv1a(&nb_cells.to_le_bytes(), lattice_hash);
+            for b in bounds { lattice_hash = add_hash_fnv1a(&b.to_le_bytes(), lattice_hash); }
+            lattice_hash.slx()
+        };
+        let bounds = bounds.iter().map(|b| b.slx()).collect();
+        let max_iter_len = (max_iter_len as u128).slx();
+        let bottom = SafeElement { code: 0u128.slx(), lattice_hash, };
+        let top = SafeElement { code: top, lattice_hash, };
+        Ok(IntervalLattice { bottom, top, bounds, leaves, weighted_leaves, max_iter_len, bottom_to_top: None, })
+    }
+
+    /// Implement lattice iterators with a view to use methods `IterableLattice::unsafe_bottom_to_top` and `IterableLattice::unsafe_top_to_bottom`
+    /// * These iterators are not defined by the constructor due to the amount of resources required for some large lattices
+    /// * Output: interval lattice implementing the iterators
+    pub fn set_iterators(mut self) -> Self {
+        if self.top.code < self.max_iter_len && self.bottom_to_top.is_none() {
+            let mut bottom_to_top = (0..=self.top.code.unslx()).collect::<Vec<_>>();
+            bottom_to_top.sort_by_key(|u| u.count_ones());
+            self.bottom_to_top = Some(bottom_to_top.into_iter().map(|u| u.slx()).collect());
+        } self
+    }
+
+    /// Bounds of the cells
+    pub fn bounds(&self) -> &[f64slx] { &self.bounds }
+
+    /// Number of cells
+    pub fn nb_cells(&self) -> usize { self.leaves.len() }
+
+    /// Bounds of cell of rank `u`
+    /// * `u: usize` : cell rank
+    /// * Output: lower and upper bounds of the cell or an error
+    pub fn cell(&self, u: usize) -> Result<(f64slx,f64slx),String> {
+        match (self.bounds.get(u), self.bounds.get(u + 1)) {
+            (Some(lo), Some(hi)) => Ok((*lo, *hi)),
+            _ => Err(format!("Cell of index {u} is not found within lattice")),
+        }
+    }
+
+    /// Smallest union of cells containing a closed interval
+    /// * A degenerated interval `[x, x]` is mapped to the cell containing `x`
+    /// * `lo: f64slx` : lower bound of the interval
+    /// * `hi: f64slx` : upper bound of the interval
+    /// * Output: the element of the lattice or an error, when the interval is not ordered or does not meet the axis
+    pub fn interval(&self, lo: f64slx, hi: f64slx) -> Result<SafeElement<u128slx>,String> {
+        let (lo, hi) = (lo.unslx(), hi.unslx());
+        if lo.is_nan() || hi.is_nan() || lo > hi { return Err(format!("Interval [{lo}, {hi}] is not ordered")); }
+        let nb_cells = self.nb_cells();
+        let code = (0..nb_cells).filter(|u| {
+            let (clo, chi) = (self.bounds[*u].unslx(), self.bounds[u+1].unslx());
+            if lo < hi { clo < hi && chi > lo } else { clo <= lo && (lo < chi || (u + 1 == nb_cells && lo <= chi)) }
+        }).fold(0u128, |acc,u| acc | (1u128 << u));
+        if code == 0 { return Err(format!("Interval [{lo}, {hi}] does not meet the axis")); }
+        Ok(SafeElement { code: code.slx(), lattice_hash: self.bottom.lattice_hash, })
+    }
+
+    /// For intern use: maximal intervals of an element, given as ranges of cells
+    fn runs(element: u128) -> Vec<(usize,usize)> {
+        let mut runs = Vec::new();
+        let mut start = None;
+        for u in 0..=128 {
+            match (u < 128 && element & (1u128 << u) != 0, start) {
+                (true, None) => start = Some(u),
+                (false, Some(s)) => { runs.push((s, u)); start = None; },
+                _ => (),
+            }
+        }
+        runs
+    }
+
+    /// Pignistic density of a mass assignment over the axis
+    /// * The density is piecewise constant on the cells
+    /// * `mass: &Assignment<u128slx>` : mass assignment
+    /// * Output: sequence of triplets made of the lower bound, the upper bound and the density of each cell, or an error
+    pub fn pignistic_density(&self, mass: &Assignment<u128slx>) -> Result<Vec<(f64slx,f64slx,f64slx)>,String> {
+        let pignistic = self.mass_to_pignistic(mass)?;
+        Ok(self.leaves.iter().enumerate().map(|(u,l)| {
+            let (lo, hi) = (self.bounds[u], self.bounds[u+1]);
+            let p = pignistic.elements.get(l).copied().unwrap_or(0.0.slx());
+            (lo, hi, p / (hi - lo))
+        }).collect())
+    }
+}
+
+impl Lattice for IntervalLattice {
+    type Item = u128slx;
+
+    fn rand_lattice<R: Rng>(rng: &mut R) -> Self {
+        let nb_cells = rng.gen_range(1..=(std::mem::size_of::<u128>() << 3));
+        Self::new(0.0.slx(), 1.0.slx(), nb_cells, DEFAULT_MAX_ITER_LEN).expect("unexpected: None returned")
+    }
+
+    fn rand_element<R: Rng>(&self, rng: &mut R) -> SafeElement<Self::Item> {
+        let SafeElement { code: top, lattice_hash } = self.top;
+        let top = top.unslx();
+        let element = rng.gen_range(0..=top).slx();
+        SafeElement { code: element, lattice_hash }
+    }
+
+    fn ref_lattice_hash(&self) -> &u128slx { &self.bottom.lattice_hash }
+
+    fn contains(&self, element: &Self::Item) -> bool { element <= &self.top.code }
+
+    fn ref_bottom(&self) -> &SafeElement<Self::Item> { &self.bottom }
+
+    fn ref_top(&self) -> &SafeElement<Self::Item> { &self.top }
+
+    unsafe fn unsafe_meet(&self, element_left: &Self::Item, element_right: &Self::Item) -> Self::Item {
+        *element_left & *element_right
+    }
+
+    unsafe fn unsafe_join(&self, element_left: &Self::Item, element_right: &Self::Item) -> Self::Item {
+        *element_left | *element_right
+    }
+
+    /// Parse unions of closed intervals, e.g. `[10.5, 12.0] | [14, 15]`; `⊥` and `⊤` are also accepted
+    fn from_str(&self, s: &str) -> Result<SafeElement<Self::Item>,String> {
+        let SafeElement { code: mut element, lattice_hash } = self.bottom;
+        for token in s.split('|').map(|t| t.trim()) {
+            match token {
+                "\u{22A5}" => (), // case where token is bottom
+                "\u{22A4}" => element = self.top.code, // case where token is top
+                _ => {
+                    let bounds = token.strip_prefix('[').and_then(|t| t.strip_suffix(']'))
+                        .and_then(|t| t.split_once(','))
+                        .ok_or_else(|| format!("interval {token} is not of the form [lo, hi]"))?;
+                    let parse = |b: &str| b.trim().parse::<f64>().map_err(|e| format!("bound {b} is not parsed: {e}"));
+                    let (lo, hi) = (parse(bounds.0)?, parse(bounds.1)?);
+                    element |= self.interval(lo.slx(), hi.slx())?.code;
+                },
+            }
+        }
+        Ok(SafeElement { code: element, lattice_hash })
+    }
+
+    fn to_string(&self, element: &SafeElement<Self::Item>) -> Result<String,String> {
+        let SafeElement { code: element, lattice_hash } = element;
+        let element = *element;
+        if lattice_hash == &self.bottom.lattice_hash {
+            match (element == self.bottom.code,element == self.top.code) {
+                (true, true) => panic!("unexpected error: \u{22A5} == \u{22A4}"),
+                (true, false) => Ok("\u{22A5}".to_string()),
+                (false, true) => Ok("\u{22A4}".to_string()),
+                (false, false) => Ok(Self::runs(element.unslx()).into_iter()
+                    .map(|(s,e)| format!("[{}, {}]", self.bounds[s], self.bounds[e]))
+                    .collect::<Vec<_>>().join(" | ")),
+            }
+        } else { Err("lattice does not contain element".to_string()) }
+    }
+}
+
+impl ComplementedLattice for IntervalLattice {
+    unsafe fn unsafe_not(&self, element: &Self::Item) -> Self::Item { self.top.code ^ *element }
+}
+
+impl IterableLattice for IntervalLattice {
+    type IntoIterUp = vec::IntoIter<u128slx>;
+
+    type IntoIterDown = vec::IntoIter<u128slx>;
+
+    unsafe fn unsafe_bottom_to_top(&self) -> Result<Self::IntoIterUp,String> {
+        match &self.bottom_to_top {
+            Some(btt) => Ok(btt.clone().into_iter()),
+            None => Err("Iterator is not set or is exceeding allowed size".to_string()),            
+        }
+    }
+
+    unsafe fn unsafe_top_to_bottom(&self) -> Result<Self::IntoIterDown,String> {
+        match &self.bottom_to_top {
+            Some(btt) => Ok(btt.iter().copied().rev().collect::<Vec<_>>().into_iter()),
+            None => Err("Iterator is not set or is exceeding allowed size".to_string()),            
+        }
+    }
+}
+
+impl LatticeWithLeaves for IntervalLattice {
+    type IntoIterLeaves = hash_map::IntoIter<Self::Item, f64slx>;
+
+    unsafe fn unsafe_leaves(&self) -> Result<Self::IntoIterLeaves,String> {
+        let len_slx: u128slx = (self.weighted_leaves.len() as u128).slx();
+        if len_slx >= self.max_iter_len {
+            Err("Iterator is exceeding allowed size".to_string())
+        } else {
+            Ok(self.weighted_leaves.clone().into_iter())
+        }
+    }
+
+    unsafe fn unsafe_leaf(&self, u: usize) -> Result<&Self::Item,String> {
+        match self.leaves.get(u) {
+            Some(x) => Ok(x),
+            None => Err(format!("Leaf of index {u} is not found within lattice")),
+        }
+    }
+
+    unsafe fn unsafe_weighted_leaf(&self, u: usize) -> Result<(&Self::Item,&f64slx),String> {
+        let leaf = self.unsafe_leaf(u)?;
+        Ok((leaf,&self.weighted_leaves[leaf]))
+    }
+}
+
+pub mod experiment {
+    use crate::{
+        types::IntoSlx,
+        structs::{ IntervalLattice, DiscountedFuser, EnumRule, },
+        traits::{ Lattice, DiscountedFusion, },
+    };
+
+    /// Experimentation with interval lattices
+    pub fn exp_interval() -> Result<(),String> {
+        println!("============================== interval =====");
+        let lattice = IntervalLattice::new(10.0.slx(), 14.0.slx(), 8, 1024)?.set_iterators();
+        let a = lattice.from_str("[10.5, 12.0]")?;
+        let b = lattice.from_str("[11.2, 13.0]")?;
+        let c = lattice.from_str("[10.0, 10.4] | [13.5, 14.0]")?;
+        println!("a -> {}", lattice.to_string(&a)?);
+        println!("b -> {}", lattice.to_string(&b)?);
+        println!("c -> {}", lattice.to_string(&c)?);
+        println!("a & b -> {}", lattice.to_string(&lattice.meet(&a, &b)?)?);
+        println!("a | c -> {}", lattice.to_string(&lattice.join(&a, &c)?)?);
+        let m1 = lattice.assignment() + (a, 0.8) + (lattice.top(), 0.2) + ();
+        let m2 = lattice.assignment() + (b, 0.6) + (c, 0.1) + (lattice.top(), 0.3) + ();
+        let engine = DiscountedFuser::new(512..=1024);
+        for rule in [EnumRule::Conjunctive, EnumRule::DempsterShafer, EnumRule::Disjunctive, EnumRule::Pcr6] {
+            let (fused, z) = engine.fuse(&lattice, &rule, &[&m1, &m2])?;
+            let elements = fused.elements.iter().map(|(x,w)| {
+                Ok(format!("{} -> {w:.3}", lattice.to_string(&lattice.check_safe(*x)?)?))
+            }).collect::<Result<Vec<_>,String>>()?;
+            println!("{rule:?} -> {}, z -> {z:.3}", elements.join(", "));
+        }
+        let (fused, _) = engine.fuse(&lattice, &EnumRule::DempsterShafer, &[&m1, &m2])?;
+        for (lo, hi, d) in lattice.pignistic_density(&fused)? {
+            println!("density on [{lo}, {hi}] -> {d:.4}");
+        }
+        println!();
+        Ok(())
+    }
+}
@@ -21,6 +21,8 @@ mod taxonomy; pub use self::taxonomy::{
     Taxon, TaxonCoder, Taxons, Taxonomy, TaxonomyBuilder, 
     experiment::{ exp_taxonomy_1, exp_taxonomy_2, },
 };
+/// Interval lattice definitions
+mod interval; pub use self::interval::{ IntervalLattice, experiment::exp_interval, };
 /// Enumeration of different lattice implementations
 mod enum_lattice; pub use self::enum_lattice::EnumLattice;
 #[allow(deprecated)] pub use self::enum_lattice::CombiLattice;
@@ -92,7 +92,7 @@ impl TaxonCoder { // worst case for taxonomy depth depends on the number of subt
             ((0x7Fu8,0x0u128),a) => a,
             (a,(0x7Fu8,0x0u128)) => a,
             ((ah,al), (bh,bl)) => {
-                let depth_join = Self::min((((al^bl).leading_zeros() as i8) - 7i8) as u8, ah, bh);
+                let depth_join = Self::min(((al^bl).leading_zeros() - 7) as u8, ah, bh);
                 (depth_join, al | Self::top_u128()>>depth_join)        
             }
         }
@@ -16,8 +16,8 @@
 
 /// Lattice implementations
 mod lattice; pub use self::lattice::{ 
-    Powerset, Taxon, TaxonCoder, Taxons, Taxonomy, TaxonomyBuilder, EnumLattice,
-    exp_taxonomy_1, exp_taxonomy_2,
+    Powerset, Taxon, TaxonCoder, Taxons, Taxonomy, TaxonomyBuilder, IntervalLattice, EnumLattice,
+    exp_taxonomy_1, exp_taxonomy_2, exp_interval,
 }; 
 #[allow(deprecated)] pub use self::lattice::CombiLattice;
 
@@ -0,0 +1,284 @@
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the Lesser GNU General Public License as published
+// by the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// Lesser GNU General Public License for more details.
+
+// You should have received a copy of the Lesser GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+// Copyright 2024 Frederic Dambreville, Jean Dezert Developers.
+
+
+use std::collections::HashSet;
+
+use crate::{
+    types::{ u128slx, f64slx, SlxInto, IntoSlx, },
+    structs::{ Assignment, Powerset, SafeElement, },
+    traits::{ Lattice, LatticeWithLeaves, DiscountedFusion, Referee, },
+};
+
+/// Maximal number of configurations of a product frame (the configurations are the leaves of a powerset)
+pub const MAX_CONFIGURATIONS: usize = 128;
+
+/// For intern use: states of a configuration given by its mixed radix index
+fn mixed_radix_states(radices: &[usize], mut index: usize) -> Vec<usize> {
+    radices.iter().map(|r| { let s = index % r; index /= r; s }).collect()
+}
+
+#[derive(Clone, Debug)]
+/// Variable of a valuation-based system
+pub struct Variable {
+    name: String,
+    states: Vec<String>,
+}
+
+impl Variable {
+    /// Name of the variable
+    pub fn name(&self) -> &str { &self.name }
+
+    /// States of the variable
+    pub fn states(&self) -> &[String] { &self.states }
+}
+
+#[derive(Clone, Debug)]
+/// Product frame of a domain, i.e. a set of variables
+/// * The configurations of the variables are the leaves of a powerset
+/// * The variables of the domain are sorted by index
+/// * The index of a configuration is a mixed radix number, the first variable being the least significant digit
+/// * The label of a configuration is made of the labels of the states separated by `.`
+pub struct ProductFrame {
+    domain: Vec<usize>,
+    radices: Vec<usize>,
+    powerset: Powerset,
+}
+
+impl ProductFrame {
+    /// Domain of the product frame (sorted variable indices)
+    pub fn domain(&self) -> &[usize] { &self.domain }
+
+    /// Powerset of the configurations
+    pub fn powerset(&self) -> &Powerset { &self.powerset }
+
+    /// Number of configurations
+    pub fn nb_configurations(&self) -> usize { self.radices.iter().product() }
+
+    /// For intern use: index of the configuration given by the states of the domain variables
+    fn index(&self, states: &[usize]) -> usize {
+        self.radices.iter().zip(states).rev().fold(0, |acc,(r,s)| acc * r + s)
+    }
+
+    /// Configuration of the variables
+    /// * `states: &[(usize,usize)]` : pairs made of a variable index and a state rank; each variable of the domain should be given once
+    /// * Output: the configuration as a leaf of the powerset or an error
+    pub fn configuration(&self, states: &[(usize,usize)]) -> Result<SafeElement<u128slx>,String> {
+        if states.len() != self.domain.len() { return Err("Configuration does not match domain".to_string()); }
+        let states = self.domain.iter().zip(&self.radices).map(|(v,r)| {
+            match states.iter().find(|(w,_)| w == v) {
+                Some((_,s)) if s < r => Ok(*s),
+                Some((_,s)) => Err(format!("State {s} of variable {v} is out of range")),
+                None => Err(format!("Variable {v} is missing in configuration")),
+            }
+        }).collect::<Result<Vec<_>,_>>()?;
+        self.powerset.leaf(self.index(&states))
+    }
+
+    /// Set of configurations of the variables
+    /// * `configurations: &[&[(usize,usize)]]` : sequence of configurations
+    /// * Output: the set of configurations as an element of the powerset or an error
+    pub fn configurations(&self, configurations: &[&[(usize,usize)]]) -> Result<SafeElement<u128slx>,String> {
+        let configurations = configurations.iter().map(|c| self.configuration(c)).collect::<Result<Vec<_>,_>>()?;
+        self.powerset.join_some(configurations.iter())
+    }
+
+    /// For intern use: map the configurations of this frame to the configurations of a subframe
+    fn projection(&self, sub: &ProductFrame) -> Result<Vec<usize>,String> {
+        let positions = sub.domain.iter().map(|v| self.domain.iter().position(|w| w == v)
+            .ok_or_else(|| format!("Variable {v} is not within domain"))
+        ).collect::<Result<Vec<_>,_>>()?;
+        Ok((0..self.nb_configurations()).map(|index| {
+            let states = mixed_radix_states(&self.radices, index);
+            sub.index(&positions.iter().map(|p| states[*p]).collect::<Vec<_>>())
+        }).collect())
+    }
+}
+
+#[derive(Clone, Debug)]
+/// Valuation of a valuation-based system: mass assignment defined on the product frame of a domain
+pub struct Valuation {
+    domain: Vec<usize>,
+    mass: Assignment<u128slx>,
+}
+
+impl Valuation {
+    /// Domain of the valuation (sorted variable indices)
+    pub fn domain(&self) -> &[usize] { &self.domain }
+
+    /// Mass assignment of the valuation, defined on the powerset of the product frame
+    pub fn mass(&self) -> &Assignment<u128slx> { &self.mass }
+}
+
+#[derive(Clone, Debug)]
+/// Valuation-based system: collection of variables with finite frames
+/// * Valuations are mass assignments defined on the product frames of the variables
+/// * Valuations are combined by means of a fusion engine and a referee, and marginalized by projection
+pub struct ValuationSystem {
+    variables: Vec<Variable>,
+    max_iter_len: usize,
+}
+
+impl ValuationSystem {
+    /// Constructor of valuation-based system
+    /// * `max_iter_len: usize` : maximal size for an iterator on the powersets of the product frames
+    /// * Output: an empty valuation-based system
+    pub fn new(max_iter_len: usize) -> Self { Self { variables: Vec::new(), max_iter_len, } }
+
+    /// Add a variable to the system
+    /// * `name: &str` : name of the variable; should be unique
+    /// * `states: &[String]` : labels of the states of the variable; should be unique and without `|` or `.`
+    /// * Output: the index of the variable or an error
+    pub fn add_variable(&mut self, name: &str, states: &[String]) -> Result<usize,String> {
+        if self.variables.iter().any(|v| v.name == name) { return Err(format!("Variable {name} is already defined")); }
+        if states.is_empty() || states.len() > MAX_CONFIGURATIONS {
+            return Err(format!("Number of states of {name} should be within [1,{MAX_CONFIGURATIONS}]"));
+        }
+        if states.iter().collect::<HashSet<_>>().len() != states.len() {
+            return Err(format!("States of {name} should be unique"));
+        }
+        if states.iter().any(|s| s.contains(['|','.'])) {
+            return Err(format!("States of {name} should not contain `|` or `.`"));
+        }
+        self.variables.push(Variable { name: name.to_string(), states: states.to_vec(), });
+        Ok(self.variables.len() - 1)
+    }
+
+    /// Variables of the system, indexed by variable index
+    pub fn variables(&self) -> &[Variable] { &self.variables }
+
+    /// Index of a variable given by name
+    pub fn variable_index(&self, name: &str) -> Option<usize> { self.variables.iter().position(|v| v.name == name) }
+
+    /// For intern use: sorted domain without duplicates
+    fn normalized_domain(&self, domain: &[usize]) -> Result<Vec<usize>,String> {
+        let mut domain = domain.to_vec();
+        domain.sort(); domain.dedup();
+        match domain.last() {
+            Some(v) if *v >= self.variables.len() => Err(format!("Variable {v} is unknown")),
+            _ => Ok(domain),
+        }
+    }
+
+    /// Product frame of a domain
+    /// * The product frame of a single variable has the same lattice hash as `Powerset::new_with_label(states, max_iter_len)`
+    /// * `domain: &[usize]` : sequence of variable indices
+    /// * Output: the product frame or an error, when the number of configurations exceeds `MAX_CONFIGURATIONS`
+    pub fn frame(&self, domain: &[usize]) -> Result<ProductFrame,String> {
+        let domain = self.normalized_domain(domain)?;
+        let radices = domain.iter().map(|v| self.variables[*v].states.len()).collect::<Vec<_>>();
+        let nb_configurations = radices.iter().try_fold(1usize, |acc,r| acc.checked_mul(*r).filter(|n| *n <= MAX_CONFIGURATIONS));
+        if nb_configurations.is_none() {
+            return Err(format!("Number of configurations of domain {domain:?} exceeds {MAX_CONFIGURATIONS}"));
+        }
+        let labels = (0..radices.iter().product()).map(|index| {
+            let labels = domain.iter().zip(mixed_radix_states(&radices, index))
+                .map(|(v,s)| self.variables[*v].states[s].as_str()).collect::<Vec<_>>();
+            if labels.is_empty() { "*".to_string() } else { labels.join(".") }
+        }).collect::<Vec<_>>();
+        let powerset = Powerset::new_with_label(&labels, self.max_iter_len)?;
+        Ok(ProductFrame { domain, radices, powerset, })
+    }
+
+    /// Build a valuation
+    /// * `domain: &[usize]` : sequence of variable indices
+    /// * `mass: Assignment<u128slx>` : mass assignment defined on the powerset of the product frame of the domain
+    /// * Output: the valuation or an error
+    pub fn valuation(&self, domain: &[usize], mass: Assignment<u128slx>) -> Result<Valuation,String> {
+        let frame = self.frame(domain)?;
+        if &mass.lattice_hash != frame.powerset.ref_lattice_hash() { return Err("Mismatching lattice hash".to_string()); }
+        Ok(Valuation { domain: frame.domain, mass, })
+    }
+
+    /// Build the vacuous valuation of a domain
+    /// * `domain: &[usize]` : sequence of variable indices
+    /// * Output: the vacuous valuation or an error
+    pub fn vacuous(&self, domain: &[usize]) -> Result<Valuation,String> {
+        let frame = self.frame(domain)?;
+        let mass = frame.powerset.assignment() + (frame.powerset.top(), 1.0) + ();
+        Ok(Valuation { domain: frame.domain, mass, })
+    }
+
+    /// For intern use: check the valuation and build its frame
+    fn valuation_frame(&self, valuation: &Valuation) -> Result<ProductFrame,String> {
+        let frame = self.frame(&valuation.domain)?;
+        if &valuation.mass.lattice_hash != frame.powerset.ref_lattice_hash() {
+            return Err("Mismatching lattice hash".to_string());
+        }
+        Ok(frame)
+    }
+
+    /// Vacuous extension of a valuation to a larger domain
+    /// * Each focal element is replaced by its cylindrical extension
+    /// * `valuation: &Valuation` : valuation to be extended
+    /// * `domain: &[usize]` : sequence of variable indices; should contain the domain of the valuation
+    /// * Output: the extended valuation or an error
+    pub fn extend(&self, valuation: &Valuation, domain: &[usize]) -> Result<Valuation,String> {
+        let frame = self.valuation_frame(valuation)?;
+        let domain = self.normalized_domain(&[&valuation.domain, domain].concat())?;
+        let target = self.frame(&domain)?;
+        let projection = target.projection(&frame)?;
+        let mut mass = target.powerset.assignment_with_capacity(valuation.mass.elements.len());
+        for (element,weight) in &valuation.mass.elements {
+            let element = element.unslx();
+            let extended = projection.iter().enumerate()
+                .filter(|(_,p)| element & (1u128 << *p) != 0)
+                .fold(0u128, |acc,(u,_)| acc | (1u128 << u));
+            unsafe { mass.unsafe_push(extended.slx(), *weight) }?;
+        }
+        Ok(Valuation { domain: target.domain, mass: mass.into(), })
+    }
+
+    /// Marginalization of a valuation on a smaller domain
+    /// * Each focal element is replaced by its projection
+    /// * `valuation: &Valuation` : valuation to be marginalized
+    /// * `domain: &[usize]` : sequence of variable indices; variables outside the domain of the valuation are ignored
+    /// * Output: the marginalized valuation or an error
+    pub fn marginalize(&self, valuation: &Valuation, domain: &[usize]) -> Result<Valuation,String> {
+        let frame = self.valuation_frame(valuation)?;
+        let domain = domain.iter().copied().filter(|v| valuation.domain.contains(v)).collect::<Vec<_>>();
+        let target = self.frame(&domain)?;
+        let projection = frame.projection(&target)?;
+        let mut mass = target.powerset.assignment_with_capacity(valuation.mass.elements.len());
+        for (element,weight) in &valuation.mass.elements {
+            let element = element.unslx();
+            let projected = projection.iter().enumerate()
+                .filter(|(u,_)| element & (1u128 << *u) != 0)
+                .fold(0u128, |acc,(_,p)| acc | (1u128 << p));
+            unsafe { mass.unsafe_push(projected.slx(), *weight) }?;
+        }
+        Ok(Valuation { domain: target.domain, mass: mass.into(), })
+    }
+
+    /// Combination of valuations
+    /// * The valuations are extended to the union of their domains and fused
+    /// * `engine: &E` : fusion engine
+    /// * `referee: &F` : referee function; Shenoy-Shafer propagation requires a commutative and associative rule (e.g. conjunctive or Dempster-Shafer)
+    /// * `valuations: &[&Valuation]` : sequence of valuations
+    /// * `E` : type of fusion engine
+    /// * `F` : type of referee function
+    /// * Output: an error or a pair composed of:
+    ///   * the combined valuation
+    ///   * the conflict
+    pub fn combine<E,F>(&self, engine: &E, referee: &F, valuations: &[&Valuation]) -> Result<(Valuation,f64slx),String>
+                                                                            where E: DiscountedFusion, F: Referee, {
+        let domain = valuations.iter().flat_map(|v| v.domain.iter().copied()).collect::<Vec<_>>();
+        let frame = self.frame(&domain)?;
+        let extended = valuations.iter().map(|v| self.extend(v, &frame.domain)).collect::<Result<Vec<_>,_>>()?;
+        let masses = extended.iter().map(|v| &v.mass).collect::<Vec<_>>();
+        let (mass, z) = engine.fuse(&frame.powerset, referee, &masses)?;
+        Ok((Valuation { domain: frame.domain, mass, }, z))
+    }
+}
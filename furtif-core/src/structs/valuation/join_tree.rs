@@ -0,0 +1,216 @@
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the Lesser GNU General Public License as published
+// by the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// Lesser GNU General Public License for more details.
+
+// You should have received a copy of the Lesser GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+// Copyright 2024 Frederic Dambreville, Jean Dezert Developers.
+
+
+use std::collections::HashMap;
+
+use crate::{
+    structs::{ ValuationSystem, Valuation, },
+    traits::{ DiscountedFusion, Referee, },
+};
+
+#[derive(Clone, Debug)]
+/// Join tree of a valuation-based system, for Shenoy-Shafer message passing
+/// * The tree is built by variable elimination, so that it satisfies the running intersection property
+/// * Each variable of the system is contained by at least one node, so that all the single variable marginals are available
+pub struct JoinTree {
+    domains: Vec<Vec<usize>>,
+    neighbours: Vec<Vec<usize>>,
+    potentials: Vec<Vec<Valuation>>,
+}
+
+#[derive(Clone, Debug)]
+/// Beliefs of the nodes of a join tree, as computed by Shenoy-Shafer message passing
+pub struct Propagation {
+    beliefs: Vec<Valuation>,
+}
+
+impl JoinTree {
+    /// Join tree constructor
+    /// * Variables are eliminated one by one, choosing first the variable producing the smallest product frame
+    /// * `system: &ValuationSystem` : valuation-based system
+    /// * `valuations: Vec<Valuation>` : valuations of the system
+    /// * Output: the join tree or an error
+    pub fn new(system: &ValuationSystem, valuations: Vec<Valuation>) -> Result<Self,String> {
+        let nb_variables = system.variables().len();
+        let mut domains = (0..nb_variables).map(|v| vec![v]).collect::<Vec<_>>();
+        let mut potentials = vec![Vec::new(); nb_variables];
+        for valuation in valuations {
+            domains.push(valuation.domain().to_vec());
+            potentials.push(vec![valuation]);
+        }
+        let mut edges = Vec::new();
+        let mut active = (0..domains.len()).collect::<Vec<_>>();
+        let mut remaining = (0..nb_variables).collect::<Vec<_>>();
+        let union = |nodes: &[usize], domains: &[Vec<usize>]| {
+            let mut union = nodes.iter().flat_map(|n| domains[*n].iter().copied()).collect::<Vec<_>>();
+            union.sort(); union.dedup(); union
+        };
+        while !remaining.is_empty() {
+            // choose the variable which elimination produces the smallest frame
+            let (rank, variable) = remaining.iter().copied().enumerate().min_by_key(|(_,v)| {
+                let nodes = active.iter().copied().filter(|n| domains[*n].contains(v)).collect::<Vec<_>>();
+                union(&nodes, &domains).iter().map(|w| system.variables()[*w].states().len()).product::<usize>()
+            }).expect("unexpected: empty remaining variables");
+            remaining.swap_remove(rank);
+            let (nodes, others): (Vec<_>, Vec<_>) = active.into_iter().partition(|n| domains[*n].contains(&variable));
+            active = others;
+            let node = match nodes.as_slice() {
+                [node] => *node,
+                _ => {
+                    domains.push(union(&nodes, &domains)); potentials.push(Vec::new());
+                    let node = domains.len() - 1;
+                    edges.extend(nodes.into_iter().map(|n| (n, node)));
+                    node
+                },
+            };
+            domains.push(domains[node].iter().copied().filter(|w| *w != variable).collect());
+            potentials.push(Vec::new());
+            edges.push((node, domains.len() - 1));
+            active.push(domains.len() - 1);
+        }
+        // remaining nodes have empty domains and are chained together
+        edges.extend(active.windows(2).map(|w| (w[0], w[1])));
+        let mut neighbours = vec![Vec::new(); domains.len()];
+        for (n,m) in edges { neighbours[n].push(m); neighbours[m].push(n); }
+        Ok(Self { domains, neighbours, potentials, })
+    }
+
+    /// Domains of the nodes of the join tree
+    pub fn domains(&self) -> &[Vec<usize>] { &self.domains }
+
+    /// Edges of the join tree
+    pub fn edges(&self) -> Vec<(usize,usize)> {
+        self.neighbours.iter().enumerate()
+            .flat_map(|(n,ms)| ms.iter().filter(move |m| n < **m).map(move |m| (n, *m))).collect()
+    }
+
+    /// For intern use: combine the potentials of a node with messages, and extend the result to the node domain
+    fn node_valuation<E,F>(&self, system: &ValuationSystem, engine: &E, referee: &F, node: usize, messages: &[&Valuation]) 
+                                                    -> Result<Valuation,String> where E: DiscountedFusion, F: Referee, {
+        let valuations = self.potentials[node].iter().chain(messages.iter().copied()).collect::<Vec<_>>();
+        match valuations.as_slice() {
+            [] => system.vacuous(&self.domains[node]),
+            [valuation] => system.extend(valuation, &self.domains[node]),
+            _ => {
+                let (valuation, _) = system.combine(engine, referee, &valuations)?;
+                system.extend(&valuation, &self.domains[node])
+            },
+        }
+    }
+
+    /// For intern use: message from a node to a neighbour
+    fn message<E,F>(&self, system: &ValuationSystem, engine: &E, referee: &F, from: usize, to: usize, 
+                memo: &mut HashMap<(usize,usize),Valuation>) -> Result<Valuation,String> where E: DiscountedFusion, F: Referee, {
+        if let Some(message) = memo.get(&(from,to)) { return Ok(message.clone()); }
+        let incoming = self.neighbours[from].iter().filter(|n| **n != to)
+            .map(|n| self.message(system, engine, referee, *n, from, memo)).collect::<Result<Vec<_>,_>>()?;
+        let valuation = self.node_valuation(system, engine, referee, from, &incoming.iter().collect::<Vec<_>>())?;
+        let separator = self.domains[from].iter().copied().filter(|v| self.domains[to].contains(v)).collect::<Vec<_>>();
+        let message = system.marginalize(&valuation, &separator)?;
+        memo.insert((from,to), message.clone());
+        Ok(message)
+    }
+
+    /// Shenoy-Shafer message passing
+    /// * Messages are computed once for each direction of each edge
+    /// * The normalization of the combinations is done by the fusion engine
+    /// * `system: &ValuationSystem` : valuation-based system
+    /// * `engine: &E` : fusion engine
+    /// * `referee: &F` : referee function; should be commutative and associative (e.g. conjunctive or Dempster-Shafer)
+    /// * `E` : type of fusion engine
+    /// * `F` : type of referee function
+    /// * Output: the beliefs of the nodes or an error
+    pub fn propagate<E,F>(&self, system: &ValuationSystem, engine: &E, referee: &F) -> Result<Propagation,String>
+                                                                            where E: DiscountedFusion, F: Referee, {
+        let mut memo = HashMap::new();
+        let beliefs = (0..self.domains.len()).map(|node| {
+            let incoming = self.neighbours[node].iter()
+                .map(|n| self.message(system, engine, referee, *n, node, &mut memo)).collect::<Result<Vec<_>,_>>()?;
+            self.node_valuation(system, engine, referee, node, &incoming.iter().collect::<Vec<_>>())
+        }).collect::<Result<Vec<_>,String>>()?;
+        Ok(Propagation { beliefs, })
+    }
+}
+
+impl Propagation {
+    /// Beliefs of the nodes of the join tree, indexed by node
+    pub fn beliefs(&self) -> &[Valuation] { &self.beliefs }
+
+    /// Marginal of the beliefs on a domain
+    /// * The domain should be contained by the domain of a node of the join tree
+    /// * `system: &ValuationSystem` : valuation-based system
+    /// * `domain: &[usize]` : sequence of variable indices
+    /// * Output: the marginal valuation or an error
+    pub fn marginal(&self, system: &ValuationSystem, domain: &[usize]) -> Result<Valuation,String> {
+        match self.beliefs.iter().filter(|b| domain.iter().all(|v| b.domain().contains(v)))
+                                            .min_by_key(|b| b.domain().len()) {
+            Some(belief) => system.marginalize(belief, domain),
+            None => Err(format!("Domain {domain:?} is not contained by a node of the join tree")),
+        }
+    }
+}
+
+pub mod experiment {
+    use crate::{
+        structs::{ ValuationSystem, JoinTree, DiscountedFuser, EnumRule, },
+        traits::Lattice,
+    };
+
+    /// Experimentation with valuation-based systems
+    pub fn exp_valuation() -> Result<(),String> {
+        println!("============================= valuation =====");
+        let mut system = ValuationSystem::new(1024);
+        let labels = |states: &[&str]| states.iter().map(|s| s.to_string()).collect::<Vec<_>>();
+        let rain = system.add_variable("rain", &labels(&["rain", "norain"]))?;
+        let sprinkler = system.add_variable("sprinkler", &labels(&["on", "off"]))?;
+        let grass = system.add_variable("grass", &labels(&["wet", "dry"]))?;
+        // relation: the grass is wet if and only if it rains or the sprinkler is on
+        let relation = {
+            let frame = system.frame(&[rain, sprinkler, grass])?;
+            let configurations = [(0,0,0), (0,1,0), (1,0,0), (1,1,1)].map(|(r,s,g)| [(rain,r), (sprinkler,s), (grass,g)]);
+            let configurations = configurations.iter().map(|c| c.as_slice()).collect::<Vec<_>>();
+            let element = frame.configurations(&configurations)?;
+            let mass = frame.powerset().assignment() + (element, 0.9) + (frame.powerset().top(), 0.1) + ();
+            system.valuation(frame.domain(), mass)?
+        };
+        let grass_evidence = {
+            let frame = system.frame(&[grass])?;
+            let (wet, top) = (frame.powerset().from_str("wet")?, frame.powerset().top());
+            system.valuation(&[grass], frame.powerset().assignment() + (wet, 0.8) + (top, 0.2) + ())?
+        };
+        let sprinkler_evidence = {
+            let frame = system.frame(&[sprinkler])?;
+            let (off, top) = (frame.powerset().from_str("off")?, frame.powerset().top());
+            system.valuation(&[sprinkler], frame.powerset().assignment() + (off, 0.7) + (top, 0.3) + ())?
+        };
+        let engine = DiscountedFuser::new(512..=1024);
+        let referee = EnumRule::DempsterShafer;
+        let valuations = vec![relation, grass_evidence, sprinkler_evidence];
+        let tree = JoinTree::new(&system, valuations.clone())?;
+        println!("join tree domains -> {:?}", tree.domains());
+        println!("join tree edges -> {:?}", tree.edges());
+        let propagation = tree.propagate(&system, &engine, &referee)?;
+        let (joint, z) = system.combine(&engine, &referee, &valuations.iter().collect::<Vec<_>>())?;
+        println!("conflict of the flat combination -> {z:.4}");
+        for variable in [rain, sprinkler, grass] {
+            let name = system.variables()[variable].name();
+            println!("{name} (join tree) -> {}", propagation.marginal(&system, &[variable])?.mass());
+            println!("{name} (flat combination) -> {}", system.marginalize(&joint, &[variable])?.mass());
+        }
+        println!();
+        Ok(())
+    }
+}
@@ -0,0 +1,20 @@
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the Lesser GNU General Public License as published
+// by the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// Lesser GNU General Public License for more details.
+
+// You should have received a copy of the Lesser GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+// Copyright 2024 Frederic Dambreville, Jean Dezert Developers.
+
+
+/// Variables, product frames and valuations
+mod frame; pub use self::frame::{ Variable, ProductFrame, Valuation, ValuationSystem, MAX_CONFIGURATIONS, };
+/// Join trees and Shenoy-Shafer message passing
+mod join_tree; pub use self::join_tree::{ JoinTree, Propagation, experiment::exp_valuation, };
@@ -0,0 +1,190 @@
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the Lesser GNU General Public License as published
+// by the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// Lesser GNU General Public License for more details.
+
+// You should have received a copy of the Lesser GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+// Copyright 2024 Frederic Dambreville, Jean Dezert Developers.
+
+
+use std::{ collections::HashSet, hash::Hash, };
+use core::fmt::Debug;
+
+use crate::{
+    types::{ SlxInto, IntoSlx, },
+    structs::{ Assignment, SafeElement, EnumRule, PcrSharp, },
+    traits::{ Lattice, ComplementedLattice, BeliefTransform, DiscountedFusion, PossibilityTheory, Referee, },
+};
+
+/// All the rules implemented by `EnumRule`
+/// * `PcrSharp` is set with 16 maximal entries
+pub fn all_rules() -> Vec<EnumRule> {
+    vec![
+        EnumRule::Conjunctive, EnumRule::DempsterShafer, EnumRule::Disjunctive, EnumRule::DuboisPrade2D, EnumRule::Pcr5, EnumRule::Pcr6,
+        EnumRule::PcrSharp(PcrSharp::new(16).expect("unexpected PCR# failure")),
+        EnumRule::Averaging, EnumRule::Murphy, EnumRule::Yager,
+    ]
+}
+
+/// For intern use: check equality of two elements
+fn check_eq<L>(lattice: &L, law: &str, left: &SafeElement<L::Item>, right: &SafeElement<L::Item>) -> Result<(),String> 
+                                                                                    where L: Lattice, L::Item: Debug, {
+    if left == right { Ok(()) } else { 
+        Err(format!("{law} failed: {} != {}", 
+            lattice.to_string(left).unwrap_or(format!("{left:?}")), lattice.to_string(right).unwrap_or(format!("{right:?}"))
+        )) 
+    }
+}
+
+/// Check the lattice axioms on three elements
+/// * Idempotence, commutativity, associativity and absorption of `meet` and `join`
+/// * Bottom and top are the neutral and absorbing elements
+/// * Consistency of the order: `a ∧ b = a` if and only if `a ∨ b = b`
+/// * `lattice: &L` : the lattice
+/// * `a, b, c: &SafeElement<L::Item>` : elements of the lattice
+/// * `L` : type of lattice
+/// * Output: nothing or an error describing the first violated axiom
+pub fn check_lattice_axioms<L>(lattice: &L, a: &SafeElement<L::Item>, b: &SafeElement<L::Item>, c: &SafeElement<L::Item>) 
+                                                                    -> Result<(),String> where L: Lattice, L::Item: Debug, {
+    let (bottom, top) = (lattice.bottom(), lattice.top());
+    check_eq(lattice, "meet idempotence", &lattice.meet(a, a)?, a)?;
+    check_eq(lattice, "join idempotence", &lattice.join(a, a)?, a)?;
+    check_eq(lattice, "meet commutativity", &lattice.meet(a, b)?, &lattice.meet(b, a)?)?;
+    check_eq(lattice, "join commutativity", &lattice.join(a, b)?, &lattice.join(b, a)?)?;
+    check_eq(lattice, "meet associativity", &lattice.meet(&lattice.meet(a, b)?, c)?, &lattice.meet(a, &lattice.meet(b, c)?)?)?;
+    check_eq(lattice, "join associativity", &lattice.join(&lattice.join(a, b)?, c)?, &lattice.join(a, &lattice.join(b, c)?)?)?;
+    check_eq(lattice, "meet absorption", &lattice.meet(a, &lattice.join(a, b)?)?, a)?;
+    check_eq(lattice, "join absorption", &lattice.join(a, &lattice.meet(a, b)?)?, a)?;
+    check_eq(lattice, "bottom neutrality", &lattice.join(a, &bottom)?, a)?;
+    check_eq(lattice, "top neutrality", &lattice.meet(a, &top)?, a)?;
+    check_eq(lattice, "bottom absorption", &lattice.meet(a, &bottom)?, &bottom)?;
+    check_eq(lattice, "top absorption", &lattice.join(a, &top)?, &top)?;
+    if (&lattice.meet(a, b)? == a) != (&lattice.join(a, b)? == b) {
+        return Err("order consistency failed: a ∧ b = a and a ∨ b = b disagree".to_string());
+    }
+    Ok(())
+}
+
+/// Check the complement axioms on an element
+/// * `a ∧ ¬a = ⊥`, `a ∨ ¬a = ⊤` and `¬¬a = a`
+/// * `lattice: &L` : the lattice
+/// * `a: &SafeElement<L::Item>` : element of the lattice
+/// * `L` : type of lattice
+/// * Output: nothing or an error describing the first violated axiom
+pub fn check_complement_axioms<L>(lattice: &L, a: &SafeElement<L::Item>) -> Result<(),String> 
+                                                        where L: ComplementedLattice, L::Item: Debug, {
+    let not_a = lattice.not(a)?;
+    check_eq(lattice, "complement meet", &lattice.meet(a, &not_a)?, &lattice.bottom())?;
+    check_eq(lattice, "complement join", &lattice.join(a, &not_a)?, &lattice.top())?;
+    check_eq(lattice, "involution", &lattice.not(&not_a)?, a)
+}
+
+/// Check that two assignments are equal up to a tolerance; missing elements have a null weight
+/// * `left: &Assignment<X>` : first assignment
+/// * `right: &Assignment<X>` : second assignment
+/// * `tolerance: f64` : tolerance on the weights
+/// * `X` : type of encoded elements
+/// * Output: nothing or an error describing the first mismatching element
+pub fn check_assignments_eq<X>(left: &Assignment<X>, right: &Assignment<X>, tolerance: f64) -> Result<(),String> 
+                                                                                    where X: Eq + Hash + Debug, {
+    if left.lattice_hash != right.lattice_hash { return Err("Mismatching lattice hash".to_string()); }
+    let keys = left.elements.keys().chain(right.elements.keys()).collect::<HashSet<_>>();
+    for x in keys {
+        let l = left.elements.get(x).map(|w| (*w).unslx()).unwrap_or(0.0);
+        let r = right.elements.get(x).map(|w| (*w).unslx()).unwrap_or(0.0);
+        if (l - r).abs() > tolerance { return Err(format!("weights of {x:?} differ: {l} vs {r}")); }
+    }
+    Ok(())
+}
+
+/// Check the round-trip `mass_to_commonality` then `mass_from_commonality`
+/// * `lattice: &L` : the lattice; its iterators should be implemented
+/// * `mass: &Assignment<L::Item>` : mass assignment
+/// * `tolerance: f64` : tolerance on the weights
+/// * `L` : type of lattice
+/// * Output: nothing or an error describing the mismatch
+pub fn check_commonality_round_trip<L>(lattice: &L, mass: &Assignment<L::Item>, tolerance: f64) -> Result<(),String> 
+                                                                where L: BeliefTransform, L::Item: Ord + Hash + Debug, {
+    let commonality = lattice.mass_to_commonality(mass)?;
+    let round_trip = lattice.mass_from_commonality(&commonality)?;
+    check_assignments_eq(mass, &round_trip, tolerance).map_err(|e| format!("commonality round-trip failed: {e}"))
+}
+
+/// Check the round-trip `possibility_to_assignment` then `possibility_from_assignment`
+/// * `lattice: &L` : the lattice
+/// * `degrees: Vec<f64>` : degrees within `[0, 1]`, indexed by leaf rank
+/// * `tolerance: f64` : tolerance on the degrees
+/// * `L` : type of lattice
+/// * Output: nothing or an error describing the mismatch
+pub fn check_possibility_round_trip<L>(lattice: &L, degrees: Vec<f64>, tolerance: f64) -> Result<(),String> 
+                                                            where L: PossibilityTheory, L::Item: Ord + Hash + Debug, {
+    let distribution = lattice.possibility(degrees.into_iter().map(|d| d.slx()).collect())?;
+    let mass = lattice.possibility_to_assignment(&distribution)?;
+    let round_trip = lattice.possibility_from_assignment(&mass)?;
+    for (u,(d,r)) in distribution.degrees.iter().zip(&round_trip.degrees).enumerate() {
+        let (d, r) = ((*d).unslx(), (*r).unslx());
+        if (d - r).abs() > tolerance { return Err(format!("possibility round-trip failed: degree of leaf {u} {d} vs {r}")); }
+    }
+    Ok(())
+}
+
+/// Check the laws of a rule on two assignments
+/// * Commutativity: fusing `[m1, m2]` and `[m2, m1]` gives the same assignment and conflict
+/// * Mass conservation: the fused assignment is normalized, and the conflict is the conjunctive mass of bottom 
+///   (computed on the pre-processed assignments, see `Referee::pre_process`) for the Dempster-Shafer and Murphy rules and zero for the other rules
+/// * The Dempster-Shafer and Murphy rules are allowed to fail when the conflict is total
+/// * `lattice: &L` : the lattice
+/// * `engine: &E` : fusion engine; should neither prune the product of the assignments nor the fused assignment
+/// * `rule: &EnumRule` : the rule
+/// * `m1, m2: &Assignment<L::Item>` : normalized mass assignments
+/// * `tolerance: f64` : tolerance on the weights
+/// * `L` : type of lattice
+/// * `E` : type of fusion engine
+/// * Output: nothing or an error describing the first violated law
+pub fn check_rule_laws<L,E>(lattice: &L, engine: &E, rule: &EnumRule, m1: &Assignment<L::Item>, m2: &Assignment<L::Item>, 
+                    tolerance: f64) -> Result<(),String> where L: Lattice, L::Item: Ord + Hash + Debug, E: DiscountedFusion, {
+    let conflict = {
+        let processed = rule.pre_process(lattice, &[m1, m2])?;
+        let processed = match &processed {
+            Some(processed) => processed.iter().collect::<Vec<_>>(), None => vec![m1, m2],
+        };
+        let (conjunctive, _) = engine.fuse(lattice, &EnumRule::Conjunctive, &processed)?;
+        conjunctive.elements.get(&lattice.bottom().code).map(|w| (*w).unslx()).unwrap_or(0.0)
+    };
+    let (fused12, fused21) = (engine.fuse(lattice, rule, &[m1, m2]), engine.fuse(lattice, rule, &[m2, m1]));
+    let ((fused12, z12), (fused21, z21)) = match (fused12, fused21, rule) {
+        (Ok(f12), Ok(f21), _) => (f12, f21),
+        (Err(_), Err(_), EnumRule::DempsterShafer | EnumRule::Murphy) if conflict > 1.0 - tolerance => return Ok(()),
+        (Err(e), _, _) | (_, Err(e), _) => return Err(format!("{rule:?}: fusion failed: {e}")),
+    };
+    check_assignments_eq(&fused12, &fused21, tolerance).map_err(|e| format!("{rule:?}: commutativity failed: {e}"))?;
+    let (z12, z21) = (z12.unslx(), z21.unslx());
+    if (z12 - z21).abs() > tolerance { return Err(format!("{rule:?}: commutativity of conflict failed: {z12} vs {z21}")); }
+    let total = fused12.elements.values().map(|w| (*w).unslx()).sum::<f64>();
+    if (total - 1.0).abs() > tolerance { return Err(format!("{rule:?}: fused assignment is not normalized: {total}")); }
+    let expected = if let EnumRule::DempsterShafer | EnumRule::Murphy = rule { conflict } else { 0.0 };
+    if (z12 - expected).abs() > tolerance {
+        return Err(format!("{rule:?}: mass conservation failed: conflict {z12} instead of {expected}"));
+    }
+    Ok(())
+}
+
+/// Check the laws of all the rules of `EnumRule` on two assignments (see `check_rule_laws`)
+/// * `lattice: &L` : the lattice
+/// * `engine: &E` : fusion engine; should neither prune the product of the assignments nor the fused assignment
+/// * `m1, m2: &Assignment<L::Item>` : normalized mass assignments
+/// * `tolerance: f64` : tolerance on the weights
+/// * `L` : type of lattice
+/// * `E` : type of fusion engine
+/// * Output: nothing or an error describing the first violated law
+pub fn check_all_rules_laws<L,E>(lattice: &L, engine: &E, m1: &Assignment<L::Item>, m2: &Assignment<L::Item>, tolerance: f64) 
+                                            -> Result<(),String> where L: Lattice, L::Item: Ord + Hash + Debug, E: DiscountedFusion, {
+    all_rules().iter().try_for_each(|rule| check_rule_laws(lattice, engine, rule, m1, m2, tolerance))
+}
@@ -0,0 +1,24 @@
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the Lesser GNU General Public License as published
+// by the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// Lesser GNU General Public License for more details.
+
+// You should have received a copy of the Lesser GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+// Copyright 2024 Frederic Dambreville, Jean Dezert Developers.
+
+
+//! Property-based testing tools (feature `testing`)
+//! * `strategies` : proptest strategies for lattices, elements and assignments
+//! * `laws` : reusable checkers of algebraic laws for lattices, transforms and rules
+
+/// Proptest strategies
+pub mod strategies;
+/// Law checkers
+pub mod laws;
@@ -0,0 +1,128 @@
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the Lesser GNU General Public License as published
+// by the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// Lesser GNU General Public License for more details.
+
+// You should have received a copy of the Lesser GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+// Copyright 2024 Frederic Dambreville, Jean Dezert Developers.
+
+
+use std::hash::Hash;
+use core::fmt::Debug;
+
+use proptest::prelude::*;
+use rand::prelude::*;
+
+use crate::{
+    types::{ f64slx, IntoSlx, },
+    structs::{ Assignment, SafeElement, Powerset, Taxonomy, IntervalLattice, EnumLattice, },
+    traits::Lattice,
+};
+
+/// Maximal size of the lattice iterators built by the strategies
+pub const STRATEGY_MAX_ITER_LEN: usize = 1 << 12;
+
+/// For intern use: seeded random number generator
+fn seeded(seed: u64) -> StdRng { StdRng::seed_from_u64(seed) }
+
+/// Strategy producing powersets with iterators
+/// * `max_leaves: usize` : maximal number of leaves (at most 11, so that the iterators are implemented)
+/// * Output: the strategy
+pub fn powerset(max_leaves: usize) -> impl Strategy<Value = Powerset> {
+    (1..=max_leaves.clamp(1, 11)).prop_map(|nb_leaves| {
+        Powerset::new(nb_leaves, STRATEGY_MAX_ITER_LEN).expect("unexpected powerset failure").set_iterators()
+    })
+}
+
+/// Strategy producing random taxonomies, by means of `Taxonomy::rand_lattice`
+/// * Output: the strategy
+pub fn taxonomy() -> impl Strategy<Value = Taxonomy> {
+    any::<u64>().prop_map(|seed| Taxonomy::rand_lattice(&mut seeded(seed)))
+}
+
+/// Strategy producing interval lattices with iterators on the axis `[0, 1]`
+/// * `max_cells: usize` : maximal number of cells (at most 11, so that the iterators are implemented)
+/// * Output: the strategy
+pub fn interval_lattice(max_cells: usize) -> impl Strategy<Value = IntervalLattice> {
+    (1..=max_cells.clamp(1, 11)).prop_map(|nb_cells| {
+        IntervalLattice::new(0.0.slx(), 1.0.slx(), nb_cells, STRATEGY_MAX_ITER_LEN)
+            .expect("unexpected interval lattice failure").set_iterators()
+    })
+}
+
+/// Strategy producing lattices of any kind implemented by `EnumLattice`
+/// * `max_leaves: usize` : maximal number of leaves of the powersets and of cells of the interval lattices
+/// * Output: the strategy
+pub fn enum_lattice(max_leaves: usize) -> impl Strategy<Value = EnumLattice> {
+    prop_oneof![
+        powerset(max_leaves).prop_map(|powerset| EnumLattice::Powerset { powerset, }),
+        taxonomy().prop_map(|taxonomy| EnumLattice::Taxonomy { taxonomy, }),
+        interval_lattice(max_leaves).prop_map(|interval| EnumLattice::Interval { interval, }),
+    ]
+}
+
+/// Strategy producing elements of a lattice, by means of `Lattice::rand_element`
+/// * `lattice: L` : the lattice
+/// * `L` : type of lattice
+/// * Output: the strategy
+pub fn element<L>(lattice: L) -> impl Strategy<Value = SafeElement<L::Item>> 
+                                                where L: Lattice + Debug + 'static, L::Item: Debug, {
+    any::<u64>().prop_map(move |seed| lattice.rand_element(&mut seeded(seed)))
+}
+
+/// Strategy producing normalized assignments on a lattice
+/// * Focal elements are drawn by means of `Lattice::rand_element`; bottom may be a focal element
+/// * `lattice: L` : the lattice
+/// * `max_len: usize` : maximal number of drawn focal elements
+/// * `L` : type of lattice
+/// * Output: the strategy
+pub fn assignment<L>(lattice: L, max_len: usize) -> impl Strategy<Value = Assignment<L::Item>> 
+                                                where L: Lattice + Debug + 'static, L::Item: Ord + Hash + Debug, {
+    prop::collection::vec((any::<u64>(), 0.01f64..1.0), 1..=max_len.max(1)).prop_map(move |weighted| {
+        let mut mass = lattice.assignment_with_capacity(weighted.len());
+        for (seed,weight) in weighted {
+            mass.push(lattice.rand_element(&mut seeded(seed)), weight.slx()).expect("unexpected push failure");
+        }
+        mass.normalize().expect("unexpected normalization failure");
+        mass.into()
+    })
+}
+
+/// Strategy producing a lattice together with assignments on this lattice
+/// * `lattices: S` : strategy producing the lattices
+/// * `nb_assignments: usize` : number of assignments
+/// * `max_len: usize` : maximal number of drawn focal elements of each assignment
+/// * `L` : type of lattice
+/// * `S` : type of lattice strategy
+/// * Output: the strategy
+pub fn lattice_with_assignments<L,S>(lattices: S, nb_assignments: usize, max_len: usize) 
+        -> impl Strategy<Value = (L, Vec<Assignment<L::Item>>)> 
+            where L: Lattice + Clone + Debug + 'static, L::Item: Ord + Hash + Debug, S: Strategy<Value = L>, {
+    lattices.prop_flat_map(move |lattice| {
+        let masses = prop::collection::vec(assignment(lattice.clone(), max_len), nb_assignments);
+        (Just(lattice), masses)
+    })
+}
+
+/// Strategy producing a lattice together with three elements of this lattice
+/// * `lattices: S` : strategy producing the lattices
+/// * `L` : type of lattice
+/// * `S` : type of lattice strategy
+/// * Output: the strategy
+pub fn lattice_with_elements<L,S>(lattices: S) -> impl Strategy<Value = (L, [SafeElement<L::Item>; 3])> 
+            where L: Lattice + Clone + Debug + 'static, L::Item: Debug, S: Strategy<Value = L>, {
+    lattices.prop_flat_map(|lattice| {
+        let elements = [element(lattice.clone()), element(lattice.clone()), element(lattice.clone())];
+        (Just(lattice), elements)
+    })
+}
+
+/// Strategy producing weights within `[0, 1]`
+pub fn weight() -> impl Strategy<Value = f64slx> { (0.0f64..=1.0).prop_map(|w| w.slx()) }
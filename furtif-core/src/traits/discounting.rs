@@ -0,0 +1,85 @@
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the Lesser GNU General Public License as published
+// by the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// Lesser GNU General Public License for more details.
+
+// You should have received a copy of the Lesser GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+// Copyright 2024 Frederic Dambreville, Jean Dezert Developers.
+
+
+use std::hash::Hash;
+
+use crate::{
+    types::{ f64slx, SlxInto, },
+    structs::{ Assignment, Reliability, one_f64slx, zero_f64slx, },
+    traits::LatticeWithLeaves,
+};
+
+/// Trait implementing the discounting of assignments by means of source reliabilities
+pub trait Discounting: LatticeWithLeaves where Self::Item: Ord + Hash, {
+    /// Classical (Shafer) discounting
+    /// * Mass is weakened by factor `reliability`, and the remaining mass `1 - reliability` is assigned to top
+    /// * `mass: &Assignment<Self::Item>` : mass assignment
+    /// * `reliability: f64slx` : reliability of the source, within `[0,1]`
+    /// * Output: discounted assignment or error
+    fn discount(&self, mass: &Assignment<Self::Item>, reliability: f64slx) -> Result<Assignment<Self::Item>,String> {
+        let Assignment { lattice_hash, elements, } = mass;
+        if lattice_hash != self.ref_lattice_hash() { return Err("Mismatching lattice hash".to_string()); }
+        let native = reliability.unslx();
+        if !(0.0..=1.0).contains(&native) { return Err(format!("reliability {native} is not within [0,1]")); }
+        let mut discounted = self.assignment_with_capacity(elements.len() + 1);
+        for (x,w) in elements { unsafe { discounted.unsafe_push(x.clone(), *w * reliability) }?; }
+        unsafe { discounted.unsafe_push(self.top().code, *one_f64slx() - reliability) }?;
+        Ok(discounted.into())
+    }
+
+    /// Contextual discounting (Mercier, Quost & Denœux)
+    /// * Discounted assignment is the disjunctive combination of `mass` with the assignments `rₖ ⊥ + (1 - rₖ) θₖ`, where `θₖ` is the leaf of rank `k` and `rₖ` its reliability 
+    /// * The discounted plausibility of leaf `θₖ` is then `1 - rₖ (1 - pl(θₖ))`
+    /// * `mass: &Assignment<Self::Item>` : mass assignment
+    /// * `reliabilities: &[f64slx]` : reliabilities of the source in the context of each leaf, indexed by leaf rank
+    /// * Output: discounted assignment or error
+    fn contextual_discount(&self, mass: &Assignment<Self::Item>, reliabilities: &[f64slx]) -> Result<Assignment<Self::Item>,String> {
+        let Assignment { lattice_hash, elements, } = mass;
+        if lattice_hash != self.ref_lattice_hash() { return Err("Mismatching lattice hash".to_string()); }
+        let nb_leaves = unsafe { self.unsafe_leaves() }?.count();
+        if reliabilities.len() != nb_leaves {
+            return Err(format!("mismatching lengths: {} reliabilities vs {nb_leaves} leaves", reliabilities.len()));
+        }
+        let mut discounted = elements.iter().map(|(x,w)| (x.clone(),*w)).collect::<Vec<_>>();
+        for (u,reliability) in reliabilities.iter().enumerate() {
+            let native = (*reliability).unslx();
+            if !(0.0..=1.0).contains(&native) { return Err(format!("reliability {native} is not within [0,1]")); }
+            if reliability == one_f64slx() { continue; }
+            let leaf = unsafe { self.unsafe_leaf(u) }?;
+            let mut builder = self.assignment_with_capacity(2 * discounted.len());
+            for (x,w) in discounted {
+                let joined = unsafe { self.unsafe_join(&x, leaf) };
+                unsafe { builder.unsafe_push(joined, w * (*one_f64slx() - *reliability)) }?;
+                if reliability > zero_f64slx() { unsafe { builder.unsafe_push(x, w * *reliability) }?; }
+            }
+            discounted = Assignment::from(builder).elements.into_iter().collect();
+        }
+        Ok(Assignment { elements: discounted.into_iter().collect(), lattice_hash: *lattice_hash, })
+    }
+
+    /// Discounting by means of reliability factors, typically estimated by `ReliabilityEstimator`
+    /// * `mass: &Assignment<Self::Item>` : mass assignment
+    /// * `reliability: &Reliability` : reliability factors
+    /// * Output: discounted assignment or error
+    fn discount_with(&self, mass: &Assignment<Self::Item>, reliability: &Reliability) -> Result<Assignment<Self::Item>,String> {
+        match reliability {
+            Reliability::Classical(reliability) => self.discount(mass, *reliability),
+            Reliability::Contextual(reliabilities) => self.contextual_discount(mass, reliabilities),
+        }
+    }
+}
+
+impl<L> Discounting for L where L: LatticeWithLeaves, Self::Item: Ord + Hash, { }
@@ -14,40 +14,103 @@
 // Copyright 2024 Frederic Dambreville, Jean Dezert Developers.
 
 
-use std::{ hash::Hash, iter::once, ops::RangeInclusive, };
+use std::{ hash::Hash, ops::RangeInclusive, };
 
 #[cfg(feature = "silx-types")] use silx_types::Float;
 // #[cfg(feature = "silx-types")]use silx_types::{ f64slx, IntoSlx, Float, };
 // #[cfg(not(feature = "silx-types"))] use crate::fake_slx::{f64slx, FakeSlx};
 use crate::{
-    types::{ f64slx, IntoSlx, },
-    structs::{ Assignment, SafeArray, one_f64slx, zero_f64slx, },
+    types::{ f64slx, SlxInto, },
+    structs::{ Assignment, AssignmentBuilder, SafeArray, BbaProduct, FusionTrace, FusionTraceBuilder, one_f64slx, zero_f64slx, },
     traits::{ Lattice, Referee, CollectionFamily1, },
 };
 
-/// For intern use: produce tensor product combination of the bbas
-/// * `lattice: &'a L` : lattice of definition of the assignments
-/// * `bbas: &'a[&'a Assignment<L::Item>]` : collection of assignments
-/// * `L` : type of lattice
-/// * Output: tensor product combination of the bbas or an error
-fn product_bba<'a,L>(lattice: &'a L, bbas: &'a[&'a Assignment<L::Item>]) -> Result<Vec<(SafeArray<'a,L::Item>,f64slx)>, String> 
-                                                                                                where L: Lattice, L::Item: Eq + Ord + Hash, {
-    let lattice_hash = lattice.lattice_hash();
-    // Compatibility tests:
-    for (u,bba) in bbas.into_iter().enumerate() {
-        if bba.lattice_hash != lattice_hash { return Err(format!("bbas of index {u} is not defined over lattice")); } 
+#[cfg(feature = "parallel")]
+/// Number of conditions within the chunks of the product processed by `DiscountedFusion::par_fuse`
+pub const PARALLEL_CHUNK_LEN: usize = 1024;
+
+/// For intern use: fold weighted conditions within an assignment builder by means of a referee
+/// * The builder is not pruned, so that the folding does not depend on how the conditions are split
+/// * `lattice: &L` : lattice of definition of the assignments
+/// * `referee: &F` : referee function
+/// * `bbas: &[&Assignment<L::Item>]` : assignments sequence
+/// * `products: I` : weighted conditions
+/// * `bba: &mut AssignmentBuilder<L::Item>` : assignment builder receiving the folded conditions
+/// * `L` : type of the lattice
+/// * `F` : type of the referee function
+/// * `I` : type of the weighted conditions collection
+/// * Output: nothing or an error
+fn fold_products<'a,L,F,I>(lattice: &L, referee: &F, bbas: &[&Assignment<L::Item>], products: I, bba: &mut AssignmentBuilder<L::Item>)
+        -> Result<(),String> 
+            where L: Lattice, L::Item: 'a + Eq + Ord + Hash, F: Referee, I: IntoIterator<Item = (SafeArray<'a,L::Item>,f64slx)>, {
+    for (conditions,weight)  in products {
+        let output = referee.from_conditions(lattice, bbas, conditions)?;
+        // optimizable ==>
+        for (safe_element, sub_weight) in output {
+            bba.push(safe_element, sub_weight * weight)?;
+        } // <== 
     }
-    let mut products = vec![(Vec::<&L::Item>::with_capacity(bbas.len()),1.0f64.slx())];
-    for bba in bbas {
-        products = products.iter().flat_map(|(left,left_w)| {
-            bba.elements.iter().map(move |(right,right_w)|
-                (left.iter().map(|l|*l).chain(once(right)).collect(), *left_w * *right_w)
-            )
-        }).collect::<Vec<_>>();
+    Ok(())
+}
+
+/// For intern use: apply the hook `Referee::pre_process` to the assignments and run a process on the resulting assignments
+/// * If the referee does not pre-process the assignments, the process is run on the original assignments
+/// * `lattice: &L` : lattice of definition of the assignments
+/// * `referee: &F` : referee function
+/// * `bbas: &[&Assignment<L::Item>]` : assignments sequence
+/// * `process: P` : process run on the pre-processed assignments
+/// * `L` : type of the lattice
+/// * `F` : type of the referee function
+/// * `P` : type of the process
+/// * `T` : type of the process output
+/// * Output: the process output or an error
+fn with_pre_processed<L,F,P,T>(lattice: &L, referee: &F, bbas: &[&Assignment<L::Item>], process: P) -> Result<T,String>
+        where L: Lattice, L::Item: Eq + Ord + Hash, F: Referee, P: FnOnce(&[&Assignment<L::Item>]) -> Result<T,String>, {
+    let processed = referee.pre_process(lattice, bbas)?;
+    let processed = processed.as_ref().map(|processed| processed.iter().collect::<Vec<_>>());
+    process(processed.as_deref().unwrap_or(bbas))
+}
+
+/// For intern use: combine assignments by means of a referee, without pruning nor normalization
+/// * The hooks `Referee::pre_process` and `Referee::post_process` are applied
+/// * This is used by the fusion of assignments whose weights are not normalized (e.g. bounds of imprecise assignments)
+/// * `lattice: &L` : lattice of definition of the assignments
+/// * `referee: &F` : referee function
+/// * `bbas: &[&Assignment<L::Item>]` : assignments sequence
+/// * `L` : type of the lattice
+/// * `F` : type of the referee function
+/// * Output: the combined assignment or an error
+pub (crate) fn combine_unnormalized<L,F>(lattice: &L, referee: &F, bbas: &[&Assignment<L::Item>]) -> Result<Assignment<L::Item>,String> 
+                                                                    where L: Lattice, L::Item: Eq + Ord + Hash, F: Referee, {
+    let mut combined = lattice.assignment();
+    with_pre_processed(lattice, referee, bbas, |inner_bbas| {
+        for (conditions, weight) in BbaProduct::new(lattice, inner_bbas, *zero_f64slx())? {
+            for (safe_element, sub_weight) in referee.from_conditions(lattice, inner_bbas, conditions)? {
+                combined.push(safe_element, sub_weight * weight)?;
+            }
+        }
+        Ok(())
+    })?;
+    referee.post_process(lattice, bbas, &mut combined)?;
+    Ok(combined.into())
+}
+
+/// For intern use: normalize a fused assignment and compute the conflict
+/// * `bba: AssignmentBuilder<X>` : fused assignment builder
+/// * `pruned: f64slx` : weight of the conditions pruned from the product, which is not accounted as conflict
+/// * `X` : type of the encoded elements
+/// * Output: an error or a pair composed of:
+///   * the fused assignment
+///   * the conflict
+fn normalize_fused<X>(mut bba: AssignmentBuilder<X>, pruned: f64slx) -> Result<(Assignment<X>,f64slx),String> where X: Eq + Ord + Hash + Clone, {
+    let norm = bba.cumul_weight()?;
+    let z = *one_f64slx() - norm - pruned;
+    if &norm == zero_f64slx() {
+        Err("Cumulative weight is zero, cannot be normalized".to_string())
+    } else { 
+        bba.scale(norm.recip())?;
+        Ok((bba.into(),z))
     }
-    Ok(products.into_iter().map(|(product,weight)| 
-        (SafeArray{ lattice_hash, product,}, weight)
-    ).collect())
 }
 
 
@@ -61,7 +124,16 @@ pub trait DiscountedFusion {
     /// * Reduction strategy is defined by means of `AssignmentBuilder` mechanisms
     fn size_range(&self) -> RangeInclusive<usize>;
 
+    /// Threshold for the early pruning of the product of the assignments
+    /// * Conditions of the product with weight below the threshold are discarded before any referee call (see `BbaProduct`)
+    /// * By default, the threshold is zero, and there is no pruning
+    fn product_threshold(&self) -> f64slx { *zero_f64slx() }
+
     /// Fusing bbas returning fused assignment and conflict
+    /// * The conditions of the product of the bbas are streamed by means of `BbaProduct` and folded one by one: 
+    ///   * the product is never materialized, and its memory footprint is `O(∑|bba_i|)`
+    ///   * the fused assignment is only reduced at the end, so that its size is bounded by the number of distinct elements produced by the referee
+    /// * Conditions pruned by `product_threshold()` are not accounted within the conflict
     /// * `lattice: &L` : lattice of definition of the assignments
     /// * `referee: &F` : referee function
     /// * `bbas: &[&Assignment<L::Item>]` : assignments sequence
@@ -72,28 +144,126 @@ pub trait DiscountedFusion {
     ///   * the conflict
     fn fuse<L,F>(&self, lattice: &L, referee: &F, bbas: &[&Assignment<L::Item>])
             -> Result<(Assignment<L::Item>,f64slx),String> where L: Lattice, L::Item: Eq + Ord + Hash, F: Referee {
-        let (length_mid, length_max) = {
-            let range = self.size_range();
-            (*range.start() as u32,*range.end() as u32)
-        };
+        let range = self.size_range();
+        let mut bba = lattice.prunable(*range.start() as u32, *range.end() as u32);
+        let pruned = with_pre_processed(lattice, referee, bbas, |inner_bbas| {
+            let mut products = BbaProduct::new(lattice, inner_bbas, self.product_threshold())?;
+            fold_products(lattice, referee, inner_bbas, products.by_ref(), &mut bba)?;
+            Ok(products.pruned_weight())
+        })?;
+        referee.post_process(lattice, bbas, &mut bba)?;
+        bba.prune(|x,y| unsafe{ lattice.unsafe_meet(&x, &y) });
+        normalize_fused(bba, pruned)
+    }
+
+    /// Fusing bbas returning fused assignment, conflict and trace of the fusion
+    /// * The fused assignment and the conflict are the same as for `fuse`
+    /// * The trace records, for each fused element, the contributing conditions and the shares given by the referee,
+    ///   and accounts for the conflict (see `FusionTrace`)
+    /// * Tracing stores every contribution, so that its memory footprint is `O(∏|bba_i|)`
+    /// * `lattice: &L` : lattice of definition of the assignments
+    /// * `referee: &F` : referee function
+    /// * `bbas: &[&Assignment<L::Item>]` : assignments sequence
+    /// * `L` : type of the lattice
+    /// * `F` : type of the referee function
+    /// * Output: an error or a triple composed of:
+    ///   * the fused assignment
+    ///   * the conflict
+    ///   * the trace of the fusion
+    fn fuse_traced<L,F>(&self, lattice: &L, referee: &F, bbas: &[&Assignment<L::Item>])
+            -> Result<(Assignment<L::Item>,f64slx,FusionTrace<L::Item>),String> where L: Lattice, L::Item: Eq + Ord + Hash, F: Referee {
+        let range = self.size_range();
+        let (length_mid, length_max) = (*range.start() as u32,*range.end() as u32);
         let mut bba = lattice.prunable(length_mid, length_max);
-        let products = product_bba(lattice,bbas)?;
-        for (conditions,weight)  in products {
-            let output = referee.from_conditions(lattice, bbas, conditions)?;
-            // optimizable ==>
-            for (safe_element, sub_weight) in output {
-                bba.push(safe_element, sub_weight * weight)?;
-            } // <== 
-        }
+        let mut trace = FusionTraceBuilder::new();
+        let pruned = with_pre_processed(lattice, referee, bbas, |inner_bbas| {
+            let mut products = BbaProduct::new(lattice, inner_bbas, self.product_threshold())?;
+            for (conditions,weight) in products.by_ref() {
+                let codes = conditions.product.iter().map(|x| (*x).clone()).collect::<Vec<_>>();
+                let top = lattice.top().code;
+                let meet = codes.iter().fold(top, |acc,x| unsafe { lattice.unsafe_meet(&acc,x) });
+                let is_conflicting = unsafe { lattice.unsafe_is_bottom(&meet) };
+                let output = referee.from_conditions(lattice, inner_bbas, conditions)?;
+                let mut shares = Vec::with_capacity(output.elements.len());
+                for (safe_element, sub_weight) in output {
+                    shares.push((safe_element.code.clone(), sub_weight.unslx()));
+                    bba.push(safe_element, sub_weight * weight)?;
+                }
+                trace.record(codes, &meet, is_conflicting, weight.unslx(), shares);
+            }
+            Ok(products.pruned_weight())
+        })?;
+        referee.post_process(lattice, bbas, &mut bba)?;
         bba.prune(|x,y| unsafe{ lattice.unsafe_meet(&x, &y) });
-        let norm = bba.cumul_weight()?;
-        let z = *one_f64slx() - norm;
-        if &norm == zero_f64slx() {
-            Err("Cumulative weight is zero, cannot be normalized".to_string())
-        } else { 
-            bba.scale(norm.recip())?;
-            Ok((bba.into(),z))
+        let normalization = bba.cumul_weight()?.unslx();
+        let (fused, z) = normalize_fused(bba, pruned)?;
+        let trace = trace.build(&fused, pruned.unslx(), normalization, z.unslx());
+        Ok((fused, z, trace))
+    }
+
+    #[cfg(feature = "parallel")]
+    /// Number of worker threads used by `par_fuse`
+    /// * By default, this is the available parallelism of the system
+    fn nb_threads(&self) -> usize { std::thread::available_parallelism().map(|n| n.get()).unwrap_or(1) }
+
+    #[cfg(feature = "parallel")]
+    /// Fusing bbas in parallel returning fused assignment and conflict
+    /// * The product of the bbas is split into chunks of at most `PARALLEL_CHUNK_LEN` conditions, which are dispatched over `nb_threads()` worker threads
+    ///   * the chunks are sub-products obtained by fixing the conditions of the first bbas (see `BbaProduct::chunk`), and are streamed lazily
+    /// * Each worker thread folds its chunks within its own assignment, which is not pruned; 
+    ///   the memory footprint is thus `O(nb_threads() · n)` for the thread assignments, where `n` is the number of distinct elements produced by the referee
+    /// * The thread assignments are then merged in thread order and pruned once according to `size_range()`, as for `fuse`
+    /// * The result is deterministic for a given number of threads and a given ordering of the inputs; 
+    ///   it may only differ from the result of `fuse` by rounding errors, since the weights are summed in a different order
+    /// * `lattice: &L` : lattice of definition of the assignments
+    /// * `referee: &F` : referee function
+    /// * `bbas: &[&Assignment<L::Item>]` : assignments sequence
+    /// * `L` : type of the lattice
+    /// * `F` : type of the referee function
+    /// * Output: an error or a pair composed of:
+    ///   * the fused assignment
+    ///   * the conflict
+    fn par_fuse<L,F>(&self, lattice: &L, referee: &F, bbas: &[&Assignment<L::Item>])
+            -> Result<(Assignment<L::Item>,f64slx),String> 
+                where L: Lattice + Sync, L::Item: Eq + Ord + Hash + Send + Sync, F: Referee + Sync {
+        let range = self.size_range();
+        let (length_mid, length_max) = (*range.start() as u32,*range.end() as u32);
+        let folded = with_pre_processed(lattice, referee, bbas, |inner_bbas| {
+            // split the product into chunks
+            let products = BbaProduct::new(lattice, inner_bbas, self.product_threshold())?;
+            let (depth, nb_chunks) = products.chunk_depth(PARALLEL_CHUNK_LEN)
+                .ok_or_else(|| "Number of chunks overflows".to_string())?;
+            // dispatch the chunks over the threads and fold them
+            let nb_threads = self.nb_threads().clamp(1, nb_chunks.max(1));
+            std::thread::scope(|scope| {
+                let products = &products;
+                let handles = (0..nb_threads).map(|thread| scope.spawn(move || {
+                    // one assignment per thread, receiving all the chunks of the thread
+                    let mut bba = lattice.prunable(length_mid, length_max);
+                    let mut pruned = *zero_f64slx();
+                    for rank in (thread..nb_chunks).step_by(nb_threads) {
+                        let mut chunk = products.chunk(depth, rank)?;
+                        fold_products(lattice, referee, inner_bbas, chunk.by_ref(), &mut bba)?;
+                        pruned += chunk.pruned_weight();
+                    }
+                    Ok((bba, pruned))
+                })).collect::<Vec<_>>();
+                handles.into_iter().map(|handle| match handle.join() {
+                    Ok(folded) => folded,
+                    Err(_) => Err("Worker thread panicked".to_string()),
+                }).collect::<Result<Vec<_>,String>>()
+            })
+        })?;
+        // merge the thread assignments in thread order
+        let mut bba = lattice.prunable(length_mid, length_max);
+        let mut pruned = *zero_f64slx();
+        for (mut thread_bba,thread_pruned) in folded {
+            while let Some((x,w)) = thread_bba.elements.pop_first() { bba.elements.push(x, w); }
+            pruned += thread_pruned;
         }
+        referee.post_process(lattice, bbas, &mut bba)?;
+        bba.prune(|x,y| unsafe{ lattice.unsafe_meet(&x, &y) });
+        normalize_fused(bba, pruned)
     }
 
     /// fusing bbas sequentially returning collected fused assignments and conflicts
@@ -119,4 +289,54 @@ pub trait DiscountedFusion {
         }
         Ok(results.into_iter().collect())
     }
-}
\ No newline at end of file
+}
+pub mod experiment {
+    #[cfg(feature = "parallel")]
+    /// Experimentation with parallel fusion
+    pub fn exp_par_fuse() -> Result<(),String> {
+        use std::{ ops::RangeInclusive, time::Instant, };
+        use rand::prelude::*;
+        use crate::{
+            types::{ IntoSlx, SlxInto, },
+            structs::{ Powerset, DiscountedFuser, EnumRule, Assignment, },
+            traits::{ Lattice, DiscountedFusion, },
+            types::u128slx,
+        };
+
+        /// Fusion engine with a given number of threads
+        struct ThreadedFuser { fuser: DiscountedFuser, nb_threads: usize, }
+        impl DiscountedFusion for ThreadedFuser {
+            fn size_range(&self) -> RangeInclusive<usize> { self.fuser.size_range() }
+            fn nb_threads(&self) -> usize { self.nb_threads }
+        }
+
+        println!("============================= par_fuse =====");
+        let lattice = Powerset::new(6, 1024)?;
+        let mut rng = StdRng::seed_from_u64(0);
+        let bbas = (0..5).map(|_| {
+            let mut bba = lattice.assignment();
+            for _ in 0..8 { bba.push(lattice.rand_element(&mut rng), rng.gen_range(0.1..1.0).slx())?; }
+            bba.normalize()?;
+            Ok(bba.into())
+        }).collect::<Result<Vec<Assignment<u128slx>>,String>>()?;
+        let bbas = bbas.iter().collect::<Vec<_>>();
+        let fuser = DiscountedFuser::new(32..=48);
+        let time = Instant::now();
+        let (sequential, z) = fuser.fuse(&lattice, &EnumRule::Pcr6, &bbas)?;
+        println!("fuse -> size {}, z -> {z:.6}, time -> {:?}", sequential.elements.len(), time.elapsed());
+        let mut results = Vec::new();
+        for nb_threads in [1, 2, 4, 8] {
+            let engine = ThreadedFuser { fuser, nb_threads, };
+            let time = Instant::now();
+            let (parallel, z) = engine.par_fuse(&lattice, &EnumRule::Pcr6, &bbas)?;
+            println!("par_fuse ({nb_threads} threads) -> size {}, z -> {z:.6}, time -> {:?}", parallel.elements.len(), time.elapsed());
+            results.push(parallel);
+        }
+        let max_gap = results.iter().flat_map(|parallel| sequential.elements.iter().map(|(x,w)| {
+            (parallel.elements.get(x).map(|v| v.unslx()).unwrap_or(0.0) - w.unslx()).abs()
+        })).fold(0.0f64, f64::max);
+        println!("max gap with fuse for any number of threads -> {max_gap:e}");
+        println!();
+        Ok(())
+    }
+}
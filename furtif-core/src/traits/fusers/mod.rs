@@ -16,5 +16,7 @@
 
 /// Discounted fusion
 mod discounted; pub use self::discounted::DiscountedFusion;
+pub (crate) use self::discounted::combine_unnormalized;
+#[cfg(feature = "parallel")] pub use self::discounted::{ PARALLEL_CHUNK_LEN, experiment::exp_par_fuse, };
 /// Sampled fusion
 mod sampled;
\ No newline at end of file
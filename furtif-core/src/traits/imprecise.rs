@@ -0,0 +1,151 @@
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the Lesser GNU General Public License as published
+// by the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// Lesser GNU General Public License for more details.
+
+// You should have received a copy of the Lesser GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+// Copyright 2024 Frederic Dambreville, Jean Dezert Developers.
+
+
+use std::{ collections::HashMap, hash::Hash, };
+
+use crate::{
+    types::{ f64slx, SlxInto, IntoSlx, },
+    structs::{ ImpreciseAssignment, SafeElement, },
+    traits::{ Lattice, Referee, combine_unnormalized, },
+};
+
+/// For intern use: bounds of the sum of the masses of the elements satisfying a predicate, among admissible assignments
+fn bounded_sum<X,P>(mass: &ImpreciseAssignment<X>, predicate: P) -> Result<(f64slx,f64slx),String> 
+                                                                    where X: Clone + Eq + Hash, P: Fn(&X) -> bool, {
+    if !mass.is_admissible() { return Err("Imprecise assignment is not admissible".to_string()); }
+    let (mut inner, mut outer) = ((0.0, 0.0), (0.0, 0.0));
+    for (x,(lo,hi)) in &mass.elements {
+        let sums = if predicate(x) { &mut inner } else { &mut outer };
+        sums.0 += (*lo).unslx(); sums.1 += (*hi).unslx();
+    }
+    let lower = inner.0.max(1.0 - outer.1).clamp(0.0, 1.0);
+    let upper = inner.1.min(1.0 - outer.0).clamp(0.0, 1.0);
+    Ok((lower.slx(), upper.slx()))
+}
+
+/// Trait implementing imprecise belief functions, i.e. belief functions defined by interval-valued masses
+/// * Combination follows the imprecise DSmT of Smarandache & Dezert: the rule is applied to the lower bounds 
+///   and to the upper bounds separately, without normalization
+/// * Credibility and plausibility are bounded by their minimum and maximum over the admissible assignments
+pub trait ImpreciseBelief: Lattice where Self::Item: Ord + Hash, {
+    /// Build an imprecise assignment from weighted intervals
+    /// * Intervals of a same element are summed
+    /// * `intervals: &[(SafeElement<Self::Item>,f64slx,f64slx)]` : sequence of elements with lower and upper bounds of mass
+    /// * Output: imprecise assignment or error
+    fn imprecise_assignment(&self, intervals: &[(SafeElement<Self::Item>,f64slx,f64slx)]) 
+                                                                -> Result<ImpreciseAssignment<Self::Item>,String> {
+        let mut elements = HashMap::<Self::Item,(f64slx,f64slx)>::with_capacity(intervals.len());
+        for (SafeElement { code, lattice_hash }, lo, hi) in intervals {
+            if lattice_hash != self.ref_lattice_hash() { return Err("Mismatching lattice hash".to_string()); }
+            let (l, h) = ((*lo).unslx(), (*hi).unslx());
+            if !(0.0 <= l && l <= h && h <= 1.0) { return Err(format!("[{l}, {h}] is not an interval within [0, 1]")); }
+            let entry = elements.entry(code.clone()).or_insert((0.0.slx(), 0.0.slx()));
+            entry.0 += *lo; entry.1 += *hi;
+        }
+        Ok(ImpreciseAssignment { elements, lattice_hash: self.lattice_hash(), })
+    }
+
+    /// Fuse imprecise assignments by means of a referee function
+    /// * The lower (resp. upper) bounds of the result are obtained by applying the referee to the lower (resp. upper) bounds of the entries
+    /// * The result is not normalized: the conflict is kept on bottom when the referee produces it (e.g. conjunctive rule); 
+    ///   use `ImpreciseAssignment::normalize` to tighten the intervals
+    /// * Rules which rely on the normalization by the fusion engine (e.g. Dempster-Shafer) are thus not meaningful here
+    /// * `referee: &F` : referee function
+    /// * `bbas: &[&ImpreciseAssignment<Self::Item>]` : sequence of imprecise assignments
+    /// * `F` : type of the referee function
+    /// * Output: fused imprecise assignment or error
+    fn imprecise_fuse<F>(&self, referee: &F, bbas: &[&ImpreciseAssignment<Self::Item>]) 
+                                            -> Result<ImpreciseAssignment<Self::Item>,String> where F: Referee, {
+        if bbas.is_empty() { return Err("No imprecise assignment to fuse".to_string()); }
+        for (u,bba) in bbas.iter().enumerate() {
+            if &bba.lattice_hash != self.ref_lattice_hash() { return Err(format!("Bba with index {u} is not defined over lattice")); }
+        }
+        let (lowers, uppers) = (
+            bbas.iter().map(|bba| bba.lower()).collect::<Vec<_>>(), bbas.iter().map(|bba| bba.upper()).collect::<Vec<_>>()
+        );
+        let lowers = combine_unnormalized(self, referee, &lowers.iter().collect::<Vec<_>>())?.elements;
+        let mut uppers = combine_unnormalized(self, referee, &uppers.iter().collect::<Vec<_>>())?.elements;
+        let mut elements = lowers.into_iter().map(|(x,lo)| {
+            let hi = uppers.remove(&x).unwrap_or(lo);
+            (x,(lo,hi))
+        }).collect::<HashMap<_,_>>();
+        elements.extend(uppers.into_iter().map(|(x,hi)| (x,(0.0.slx(),hi))));
+        Ok(ImpreciseAssignment { elements, lattice_hash: self.lattice_hash(), })
+    }
+
+    /// Lower and upper credibility of an element
+    /// * `mass: &ImpreciseAssignment<Self::Item>` : admissible imprecise assignment
+    /// * `element: &SafeElement<Self::Item>` : element
+    /// * Output: pair of lower and upper credibilities or error
+    fn imprecise_credibility(&self, mass: &ImpreciseAssignment<Self::Item>, element: &SafeElement<Self::Item>) 
+                                                                                -> Result<(f64slx,f64slx),String> {
+        if &mass.lattice_hash != self.ref_lattice_hash() || &element.lattice_hash != self.ref_lattice_hash() { 
+            return Err("Mismatching lattice hash".to_string()); 
+        }
+        bounded_sum(mass, |x| unsafe { !self.unsafe_is_bottom(x) && self.unsafe_implied_join(&element.code, x) })
+    }
+
+    /// Lower and upper plausibility of an element
+    /// * `mass: &ImpreciseAssignment<Self::Item>` : admissible imprecise assignment
+    /// * `element: &SafeElement<Self::Item>` : element
+    /// * Output: pair of lower and upper plausibilities or error
+    fn imprecise_plausibility(&self, mass: &ImpreciseAssignment<Self::Item>, element: &SafeElement<Self::Item>) 
+                                                                                -> Result<(f64slx,f64slx),String> {
+        if &mass.lattice_hash != self.ref_lattice_hash() || &element.lattice_hash != self.ref_lattice_hash() { 
+            return Err("Mismatching lattice hash".to_string()); 
+        }
+        bounded_sum(mass, |x| unsafe { !self.unsafe_disjoint(&element.code, x) })
+    }
+}
+
+impl<L> ImpreciseBelief for L where L: Lattice, Self::Item: Ord + Hash, { }
+
+pub mod experiment {
+    use crate::{
+        types::IntoSlx,
+        structs::{ Powerset, Conjunctive, Pcr6, },
+        traits::{ Lattice, ImpreciseBelief, },
+    };
+
+    /// Experimentation with imprecise assignments
+    pub fn exp_imprecise() -> Result<(),String> {
+        println!("===================== imprecise =====");
+        let lattice = Powerset::new_with_label(&["A".to_string(), "B".to_string()], 1024)?;
+        let (a, b, ab) = (lattice.from_str("A")?, lattice.from_str("B")?, lattice.from_str("A | B")?);
+        let m1 = lattice.imprecise_assignment(&[
+            (a, 0.4.slx(), 0.6.slx()), (b, 0.1.slx(), 0.3.slx()), (ab, 0.1.slx(), 0.3.slx()),
+        ])?;
+        let m2 = lattice.imprecise_assignment(&[
+            (a, 0.2.slx(), 0.4.slx()), (b, 0.3.slx(), 0.5.slx()), (ab, 0.2.slx(), 0.3.slx()),
+        ])?;
+        for (m,nm) in [(&m1,"m1"),(&m2,"m2")] {
+            println!("{nm} -> {m} ; admissible -> {}", m.is_admissible());
+            let (bel, pl) = (lattice.imprecise_credibility(m, &a)?, lattice.imprecise_plausibility(m, &a)?);
+            println!("{nm}: Bel(A) -> [{:.4}, {:.4}], Pl(A) -> [{:.4}, {:.4}]", bel.0, bel.1, pl.0, pl.1);
+        }
+        let conjunctive = lattice.imprecise_fuse(&Conjunctive, &[&m1, &m2])?;
+        println!("conjunctive -> {conjunctive}");
+        let pcr6 = lattice.imprecise_fuse(&Pcr6, &[&m1, &m2])?;
+        println!("pcr6 -> {pcr6}");
+        let mut normalized = pcr6.clone();
+        normalized.normalize()?;
+        println!("normalized pcr6 -> {normalized}");
+        let (bel, pl) = (lattice.imprecise_credibility(&normalized, &a)?, lattice.imprecise_plausibility(&normalized, &a)?);
+        println!("pcr6: Bel(A) -> [{:.4}, {:.4}], Pl(A) -> [{:.4}, {:.4}]", bel.0, bel.1, pl.0, pl.1);
+        println!();
+        Ok(())
+    }
+}
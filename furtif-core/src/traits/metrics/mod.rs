@@ -14,4 +14,36 @@
 // Copyright 2024 Frederic Dambreville, Jean Dezert Developers.
 
 
-// TO BE DONE
\ No newline at end of file
+use std::{ hash::Hash, collections::{ BTreeMap, BTreeSet, }, };
+
+use crate::{
+    types::{ f64slx, IntoSlx, },
+    structs::Assignment,
+    traits::{ LatticeWithLeaves, focal_leaf_sets, },
+};
+
+/// Trait implementing distances between assignments
+/// * The cardinal of an element is the number of leaves it contains
+pub trait BeliefMetric: LatticeWithLeaves where Self::Item: Ord + Hash, {
+    /// Jousselme distance: `sqrt( (m1 - m2)ᵀ D (m1 - m2) / 2 )` where `D(A,B) = |A ∩ B| / |A ∪ B|`
+    /// * Mass assigned to elements without leaves (typically bottom) is discarded
+    /// * `left: &Assignment<Self::Item>` : left mass assignment
+    /// * `right: &Assignment<Self::Item>` : right mass assignment
+    /// * Output: Jousselme distance or error
+    fn jousselme_distance(&self, left: &Assignment<Self::Item>, right: &Assignment<Self::Item>) -> Result<f64slx,String> {
+        let mut differences = BTreeMap::<BTreeSet<usize>,f64>::new();
+        for (a,w) in focal_leaf_sets(self, left)? { *differences.entry(a).or_default() += w; }
+        for (a,w) in focal_leaf_sets(self, right)? { *differences.entry(a).or_default() -= w; }
+        let differences = differences.into_iter().collect::<Vec<_>>();
+        let mut square = 0.0;
+        for (a,wa) in &differences {
+            for (b,wb) in &differences {
+                let jaccard = a.intersection(b).count() as f64 / a.union(b).count() as f64;
+                square += wa * jaccard * wb;
+            }
+        }
+        Ok((0.5 * square).max(0.0).sqrt().slx())
+    }
+}
+
+impl<L> BeliefMetric for L where L: LatticeWithLeaves, Self::Item: Ord + Hash, { }
@@ -22,6 +22,8 @@ mod structures; pub use self::structures::{ ComplementedLattice, Lattice, };
 
 /// Definition of fusion engines
 mod fusers; pub use self::fusers::DiscountedFusion;
+pub (crate) use self::fusers::combine_unnormalized;
+#[cfg(feature = "parallel")] pub use self::fusers::{ PARALLEL_CHUNK_LEN, exp_par_fuse, };
 
 /// Definition of referee function
 mod referee; pub use self::referee::Referee;
@@ -32,5 +34,23 @@ mod transforms; pub use transforms::{
     experiment::exp_transform,
 };
 
+/// Definition of uncertainty measures
+mod uncertainty; pub use self::uncertainty::{ 
+    UncertaintyMeasure, AU_MAX_FOCAL_ELEMENTS, experiment::exp_uncertainty, 
+};
+pub (crate) use self::uncertainty::focal_leaf_sets;
+
+/// Definition of possibility theory
+mod possibility; pub use self::possibility::{ PossibilityTheory, experiment::exp_possibility, };
+
+/// Definition of imprecise belief functions
+mod imprecise; pub use self::imprecise::{ ImpreciseBelief, experiment::exp_imprecise, };
+
+/// Definition of qualitative belief functions
+mod qualitative; pub use self::qualitative::{ QualitativeBelief, experiment::exp_qualitative, };
+
 /// Definition of metrics
-mod metrics; // not implemented at this time
+mod metrics; pub use self::metrics::BeliefMetric;
+
+/// Definition of discounting
+mod discounting; pub use self::discounting::Discounting;
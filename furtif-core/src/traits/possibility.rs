@@ -0,0 +1,222 @@
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the Lesser GNU General Public License as published
+// by the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// Lesser GNU General Public License for more details.
+
+// You should have received a copy of the Lesser GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+// Copyright 2024 Frederic Dambreville, Jean Dezert Developers.
+
+
+use std::hash::Hash;
+
+use crate::{
+    types::{ f64slx, SlxInto, IntoSlx, },
+    structs::{ Assignment, SafeElement, PossibilityDistribution, PossibilisticRule, ASSIGNMENT_EPSILON, },
+    traits::LatticeWithLeaves,
+};
+
+/// For intern use: codes of the leaves of a lattice, sorted by leaf rank
+fn leaf_codes<L>(lattice: &L) -> Result<Vec<L::Item>,String> where L: LatticeWithLeaves, L::Item: Ord + Hash, {
+    let nb_leaves = unsafe { lattice.unsafe_leaves() }?.count();
+    (0..nb_leaves).map(|u| unsafe { lattice.unsafe_leaf(u) }.cloned()).collect()
+}
+
+/// For intern use: check that a possibility distribution is defined on a lattice
+fn check_distribution<L>(lattice: &L, distribution: &PossibilityDistribution, nb_leaves: usize) -> Result<(),String> 
+                                                                    where L: LatticeWithLeaves, L::Item: Ord + Hash, {
+    if &distribution.lattice_hash != lattice.ref_lattice_hash() { return Err("Mismatching lattice hash".to_string()); }
+    if distribution.degrees.len() != nb_leaves { 
+        return Err(format!("Number of degrees {} differs from number of leaves {nb_leaves}", distribution.degrees.len())); 
+    }
+    Ok(())
+}
+
+/// Trait implementing possibility theory on lattices with leaves
+/// * Possibility distributions are defined on the leaves of the lattice, and are indexed by leaf rank
+/// * The possibility of an element is the maximal degree of the leaves it contains; its necessity is `1 - Π(¬A)`, 
+///   where `Π(¬A)` is the maximal degree of the leaves it does not contain
+/// * A possibility distribution is equivalent to a consonant mass assignment, i.e. whose focal elements are nested; 
+///   the possibility degrees are then the plausibilities of the leaves (contour function)
+/// * Consonant assignments make possibilistic and evidential sources fusable by the same engines
+pub trait PossibilityTheory: LatticeWithLeaves where Self::Item: Ord + Hash, {
+    /// Build a possibility distribution from the degrees of the leaves
+    /// * `degrees: Vec<f64slx>` : degrees within `[0, 1]`, indexed by leaf rank
+    /// * Output: possibility distribution or error
+    fn possibility(&self, degrees: Vec<f64slx>) -> Result<PossibilityDistribution,String> {
+        let nb_leaves = unsafe { self.unsafe_leaves() }?.count();
+        if degrees.len() != nb_leaves { 
+            return Err(format!("Number of degrees {} differs from number of leaves {nb_leaves}", degrees.len())); 
+        }
+        if let Some(d) = degrees.iter().map(|d| (*d).unslx()).find(|d| !(0.0..=1.0).contains(d)) {
+            return Err(format!("Degree {d} is not within [0, 1]"));
+        }
+        Ok(PossibilityDistribution { degrees, lattice_hash: self.lattice_hash(), })
+    }
+
+    /// Build a possibility distribution from the membership degrees of some leaves
+    /// * Leaves which are not listed have a null degree
+    /// * `memberships: &[(SafeElement<Self::Item>,f64slx)]` : sequence of leaves and membership degrees within `[0, 1]`
+    /// * Output: possibility distribution or error
+    fn possibility_from_memberships(&self, memberships: &[(SafeElement<Self::Item>,f64slx)]) 
+                                                                        -> Result<PossibilityDistribution,String> {
+        let leaves = leaf_codes(self)?;
+        let mut degrees = vec![0.0.slx(); leaves.len()];
+        for (SafeElement { code, lattice_hash }, d) in memberships {
+            if lattice_hash != self.ref_lattice_hash() { return Err("Mismatching lattice hash".to_string()); }
+            match leaves.iter().position(|l| l == code) {
+                Some(u) => degrees[u] = *d,
+                None => return Err("Membership is only defined for leaves".to_string()),
+            }
+        }
+        self.possibility(degrees)
+    }
+
+    /// Possibility measure of an element
+    /// * `distribution: &PossibilityDistribution` : possibility distribution
+    /// * `element: &SafeElement<Self::Item>` : element
+    /// * Output: possibility or error
+    fn possibility_measure(&self, distribution: &PossibilityDistribution, element: &SafeElement<Self::Item>) 
+                                                                                    -> Result<f64slx,String> {
+        let leaves = leaf_codes(self)?;
+        check_distribution(self, distribution, leaves.len())?;
+        if &element.lattice_hash != self.ref_lattice_hash() { return Err("Mismatching lattice hash".to_string()); }
+        Ok(leaves.iter().zip(&distribution.degrees).filter(|(l,_)| unsafe { self.unsafe_implied_join(&element.code, l) })
+            .map(|(_,d)| (*d).unslx()).fold(0.0, f64::max).slx())
+    }
+
+    /// Necessity measure of an element
+    /// * `distribution: &PossibilityDistribution` : possibility distribution
+    /// * `element: &SafeElement<Self::Item>` : element
+    /// * Output: necessity or error
+    fn necessity_measure(&self, distribution: &PossibilityDistribution, element: &SafeElement<Self::Item>) 
+                                                                                    -> Result<f64slx,String> {
+        let leaves = leaf_codes(self)?;
+        check_distribution(self, distribution, leaves.len())?;
+        if &element.lattice_hash != self.ref_lattice_hash() { return Err("Mismatching lattice hash".to_string()); }
+        let outer = leaves.iter().zip(&distribution.degrees).filter(|(l,_)| !unsafe { self.unsafe_implied_join(&element.code, l) })
+            .map(|(_,d)| (*d).unslx()).fold(0.0, f64::max);
+        Ok((1.0 - outer).slx())
+    }
+
+    /// Fuse possibility distributions
+    /// * The fused distribution is normalized by its height, and the conflict is `1 - height`
+    /// * `rule: &PossibilisticRule` : fusion rule
+    /// * `distributions: &[&PossibilityDistribution]` : sequence of possibility distributions
+    /// * Output: an error or a pair composed of:
+    ///   * the fused distribution
+    ///   * the conflict
+    fn fuse_possibilities(&self, rule: &PossibilisticRule, distributions: &[&PossibilityDistribution]) 
+                                                        -> Result<(PossibilityDistribution,f64slx),String> {
+        if distributions.is_empty() { return Err("No possibility distribution to fuse".to_string()); }
+        let nb_leaves = unsafe { self.unsafe_leaves() }?.count();
+        for distribution in distributions { check_distribution(self, distribution, nb_leaves)?; }
+        let degrees = (0..nb_leaves).map(|u| {
+            rule.combine(distributions.iter().map(|distribution| distribution.degrees[u].unslx()))
+        }).collect::<Vec<_>>();
+        let height = degrees.iter().copied().fold(0.0, f64::max);
+        if height <= ASSIGNMENT_EPSILON { return Err("Possibility distributions are in total conflict".to_string()); }
+        let degrees = degrees.into_iter().map(|d| (d / height).slx()).collect();
+        Ok((PossibilityDistribution { degrees, lattice_hash: self.lattice_hash(), }, (1.0 - height).slx()))
+    }
+
+    /// Convert a possibility distribution into a consonant mass assignment
+    /// * The focal elements are the joins of the leaves whose degree exceeds each level of the distribution;
+    ///   if the distribution is not normalized, the mass `1 - height` is assigned to bottom
+    /// * Conversion fails when such a join contains leaves of lower degree (e.g. within some taxonomies), 
+    ///   since the conversion would then be lossy
+    /// * `distribution: &PossibilityDistribution` : possibility distribution
+    /// * Output: consonant mass assignment or error
+    fn possibility_to_assignment(&self, distribution: &PossibilityDistribution) -> Result<Assignment<Self::Item>,String> {
+        let leaves = leaf_codes(self)?;
+        check_distribution(self, distribution, leaves.len())?;
+        let mut ranked = distribution.degrees.iter().map(|d| (*d).unslx()).enumerate().collect::<Vec<_>>();
+        ranked.sort_by(|(_,d1),(_,d2)| d2.total_cmp(d1));
+        let mut mass = self.assignment_with_capacity(ranked.len() + 1);
+        let mut cut = self.bottom();
+        let height = ranked.first().map(|(_,d)| *d).unwrap_or(0.0);
+        if height < 1.0 { mass.push(self.bottom(), (1.0 - height).slx())?; }
+        for (i,(u,d)) in ranked.iter().enumerate() {
+            cut = self.join(&cut, &self.leaf(*u)?)?;
+            let next = ranked.get(i + 1).map(|(_,d)| *d).unwrap_or(0.0);
+            if d - next > ASSIGNMENT_EPSILON {
+                let nb_implied = leaves.iter().filter(|l| unsafe { self.unsafe_implied_join(&cut.code, l) }).count();
+                if nb_implied != i + 1 {
+                    return Err("Possibility distribution is not representable by a consonant assignment on this lattice".to_string());
+                }
+                mass.push(cut.clone(), (d - next).slx())?;
+            }
+        }
+        mass.normalize()?; Ok(mass.into())
+    }
+
+    /// Convert a consonant mass assignment into a possibility distribution
+    /// * Possibility degrees are the plausibilities of the leaves; the mass of bottom reduces the height of the distribution
+    /// * Conversion fails when the assignment is not consonant, since the conversion would then be lossy
+    /// * `mass: &Assignment<Self::Item>` : consonant mass assignment
+    /// * Output: possibility distribution or error
+    fn possibility_from_assignment(&self, mass: &Assignment<Self::Item>) -> Result<PossibilityDistribution,String> {
+        let Assignment { lattice_hash, elements, } = mass;
+        if lattice_hash != self.ref_lattice_hash() { return Err("Mismatching lattice hash".to_string()); }
+        let leaves = leaf_codes(self)?;
+        let mut focals = elements.iter().filter(|(_,w)| (**w).unslx() > ASSIGNMENT_EPSILON).map(|(x,w)| {
+            let set = leaves.iter().map(|l| unsafe { self.unsafe_implied_join(x, l) }).collect::<Vec<_>>();
+            (set, (*w).unslx())
+        }).filter(|(set,_)| set.iter().any(|b| *b)).collect::<Vec<_>>();
+        focals.sort_by_key(|(set,_)| set.iter().filter(|b| **b).count());
+        let is_consonant = focals.windows(2).all(|pair| pair[0].0.iter().zip(&pair[1].0).all(|(b0,b1)| !b0 || *b1));
+        if !is_consonant { return Err("Assignment is not consonant".to_string()); }
+        let degrees = (0..leaves.len()).map(|u| {
+            focals.iter().filter(|(set,_)| set[u]).map(|(_,w)| *w).sum::<f64>().min(1.0).slx()
+        }).collect();
+        Ok(PossibilityDistribution { degrees, lattice_hash: *lattice_hash, })
+    }
+}
+
+impl<L> PossibilityTheory for L where L: LatticeWithLeaves, Self::Item: Ord + Hash, { }
+
+pub mod experiment {
+    use crate::{
+        types::IntoSlx,
+        structs::{ Powerset, PossibilisticRule, DiscountedFuser, EnumRule, },
+        traits::{ Lattice, LatticeWithLeaves, PossibilityTheory, DiscountedFusion, },
+    };
+
+    /// Experimentation with possibility theory
+    pub fn exp_possibility() -> Result<(),String> {
+        println!("===================== possibility =====");
+        let lattice = Powerset::new_with_label(
+            &["A".to_string(), "B".to_string(), "C".to_string()], 1024
+        )?;
+        let (a, b, c) = (lattice.from_str("A")?, lattice.from_str("B")?, lattice.from_str("C")?);
+        let ab = lattice.from_str("A | B")?;
+        let pi1 = lattice.possibility_from_memberships(&[(a, 1.0.slx()), (b, 0.7.slx()), (c, 0.2.slx())])?;
+        let pi2 = lattice.possibility_from_memberships(&[(a, 0.6.slx()), (b, 1.0.slx()), (c, 0.4.slx())])?;
+        println!("pi1 -> {pi1:?}");
+        println!("pi2 -> {pi2:?}");
+        println!("Π1(A | B) -> {:.4}, N1(A | B) -> {:.4}", lattice.possibility_measure(&pi1, &ab)?, lattice.necessity_measure(&pi1, &ab)?);
+        println!("Π1(C) -> {:.4}, N1(C) -> {:.4}", lattice.possibility_measure(&pi1, &c)?, lattice.necessity_measure(&pi1, &c)?);
+        for rule in [PossibilisticRule::Minimum, PossibilisticRule::Product, PossibilisticRule::Maximum] {
+            let (pi, z) = lattice.fuse_possibilities(&rule, &[&pi1, &pi2])?;
+            println!("{rule:?} -> {pi:?} ; conflict -> {z:.4}");
+        }
+        println!("-------------------- consonant conversion -----");
+        let m1 = lattice.possibility_to_assignment(&pi1)?;
+        println!("m1 -> {m1}");
+        println!("pi1 from m1 -> {:?}", lattice.possibility_from_assignment(&m1)?);
+        println!("pignistic m1 -> {}", lattice.mass_to_pignistic(&m1)?);
+        println!("-------------------- fusion with an evidential source -----");
+        let m2 = lattice.assignment() + (b, 0.5) + (lattice.from_str("B | C")?, 0.3) + (lattice.top(), 0.2) + ();
+        let engine = DiscountedFuser::new(512..=1024);
+        let (m, z) = engine.fuse(&lattice, &EnumRule::DempsterShafer, &[&m1, &m2])?;
+        println!("m1 ⊕ m2 -> {m} ; conflict -> {z:.4}");
+        println!();
+        Ok(())
+    }
+}
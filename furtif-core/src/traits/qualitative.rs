@@ -0,0 +1,112 @@
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the Lesser GNU General Public License as published
+// by the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// Lesser GNU General Public License for more details.
+
+// You should have received a copy of the Lesser GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+// Copyright 2024 Frederic Dambreville, Jean Dezert Developers.
+
+
+use std::{ collections::HashMap, hash::Hash, };
+
+use crate::{
+    types::{ SlxInto, IntoSlx, },
+    structs::{ QualitativeAssignment, LinguisticScale, SafeElement, },
+    traits::{ Lattice, Referee, combine_unnormalized, },
+};
+
+/// Trait implementing qualitative belief functions, i.e. belief functions whose masses are linguistic labels (qualitative DSmT)
+/// * Qualitative rules are obtained from the numerical rules by means of the label arithmetic
+/// * Since the label arithmetic is isomorphic to the arithmetic of the numbers `index / max_index`, 
+///   the referee functions of the numerical rules are reused (e.g. `Conjunctive`, `Pcr5`, `Pcr6`)
+pub trait QualitativeBelief: Lattice where Self::Item: Ord + Hash, {
+    /// Build a qualitative assignment from labelled elements
+    /// * Labels of a same element are added
+    /// * `scale: &LinguisticScale` : linguistic scale
+    /// * `labelled: &[(SafeElement<Self::Item>,&str)]` : sequence of elements with label names
+    /// * Output: qualitative assignment or error
+    fn qualitative_assignment(&self, scale: &LinguisticScale, labelled: &[(SafeElement<Self::Item>,&str)]) 
+                                                                -> Result<QualitativeAssignment<Self::Item>,String> {
+        let mut elements = HashMap::with_capacity(labelled.len());
+        for (SafeElement { code, lattice_hash }, name) in labelled {
+            if lattice_hash != self.ref_lattice_hash() { return Err("Mismatching lattice hash".to_string()); }
+            let label = scale.label(name)?;
+            *elements.entry(code.clone()).or_insert(0.0.slx()) += label.index.slx();
+        }
+        Ok(QualitativeAssignment { elements, max_index: scale.max_index().slx(), lattice_hash: self.lattice_hash(), })
+    }
+
+    /// Fuse qualitative assignments by means of a referee function
+    /// * The result is not normalized: the conflict is kept on bottom when the referee produces it (e.g. conjunctive rule); 
+    ///   use `QualitativeAssignment::normalize` for the qualitative normalization
+    /// * The labels of the result are generally refined; use `QualitativeAssignment::approximate` to get labels of the scale
+    /// * `referee: &F` : referee function
+    /// * `bbas: &[&QualitativeAssignment<Self::Item>]` : sequence of qualitative assignments defined on the same scale
+    /// * `F` : type of the referee function
+    /// * Output: fused qualitative assignment or error
+    fn qualitative_fuse<F>(&self, referee: &F, bbas: &[&QualitativeAssignment<Self::Item>]) 
+                                            -> Result<QualitativeAssignment<Self::Item>,String> where F: Referee, {
+        let max_index = match bbas.first() {
+            Some(bba) => bba.max_index.unslx(), None => return Err("No qualitative assignment to fuse".to_string()),
+        };
+        for (u,bba) in bbas.iter().enumerate() {
+            if &bba.lattice_hash != self.ref_lattice_hash() { return Err(format!("Bba with index {u} is not defined over lattice")); }
+            if bba.max_index.unslx() != max_index { return Err(format!("Bba with index {u} is not defined over the same scale")); }
+        }
+        let numerics = bbas.iter().map(|bba| bba.to_numeric()).collect::<Vec<_>>();
+        let fused = combine_unnormalized(self, referee, &numerics.iter().collect::<Vec<_>>())?;
+        Ok(QualitativeAssignment::from_numeric(&fused, max_index))
+    }
+}
+
+impl<L> QualitativeBelief for L where L: Lattice, Self::Item: Ord + Hash, { }
+
+pub mod experiment {
+    use crate::{
+        structs::{ Powerset, Conjunctive, Pcr5, Pcr6, LinguisticScale, },
+        traits::{ Lattice, QualitativeBelief, },
+    };
+
+    /// Experimentation with qualitative assignments
+    pub fn exp_qualitative() -> Result<(),String> {
+        println!("===================== qualitative =====");
+        let scale = LinguisticScale::new(&["none", "low", "medium", "high", "certain"])?;
+        let (low, medium, high) = (scale.label("low")?, scale.label("medium")?, scale.label("high")?);
+        println!("low + medium -> {}", scale.name(&(low + medium))?);
+        println!("high - low -> {}", scale.name(&(high - low))?);
+        println!("medium × high -> {}", scale.name(&(medium * high))?);
+        println!("low ÷ medium -> {}", scale.name(&(low / medium))?);
+        let lattice = Powerset::new_with_label(&["A".to_string(), "B".to_string(), "C".to_string()], 1024)?;
+        let (a, b, c) = (lattice.from_str("A")?, lattice.from_str("B")?, lattice.from_str("C")?);
+        let ab = lattice.from_str("A | B")?;
+        let q1 = lattice.qualitative_assignment(&scale, &[(a, "medium"), (ab, "low"), (lattice.top(), "low")])?;
+        let q2 = lattice.qualitative_assignment(&scale, &[(b, "medium"), (c, "low"), (lattice.top(), "low")])?;
+        for (q,nm) in [(&q1,"q1"),(&q2,"q2")] {
+            println!("{nm} -> {q} ; normalized -> {}", q.is_normalized());
+        }
+        let conjunctive = lattice.qualitative_fuse(&Conjunctive, &[&q1, &q2])?;
+        println!("conjunctive -> {conjunctive} ; sum -> {}", conjunctive.sum());
+        let pcr = lattice.qualitative_fuse(&Pcr6, &[&q1, &q2])?;
+        println!("pcr5/pcr6 -> {pcr} ; sum -> {}", pcr.sum());
+        let approximated = pcr.approximate();
+        for (x,i) in &approximated.elements {
+            let label = approximated.label(x);
+            println!("approximated pcr5/pcr6: {} -> {} (L{i})", lattice.to_string(&lattice.check_safe(*x)?)?, scale.name(&label)?);
+        }
+        // PCR5 and PCR6 differ for more than two sources
+        let q3 = lattice.qualitative_assignment(&scale, &[(b, "high"), (lattice.top(), "low")])?;
+        let pcr5 = lattice.qualitative_fuse(&Pcr5, &[&q1, &q2, &q3])?;
+        println!("pcr5 of q1, q2, q3 -> {pcr5} ; sum -> {}", pcr5.sum());
+        let pcr6 = lattice.qualitative_fuse(&Pcr6, &[&q1, &q2, &q3])?;
+        println!("pcr6 of q1, q2, q3 -> {pcr6} ; sum -> {}", pcr6.sum());
+        println!();
+        Ok(())
+    }
+}
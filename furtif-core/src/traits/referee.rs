@@ -16,9 +16,11 @@
 
 use std::hash::Hash;
 
-use crate::{ structs::{Assignment, SafeArray}, traits::Lattice, };
+use crate::{ structs::{Assignment, AssignmentBuilder, SafeArray}, traits::Lattice, };
 
 /// Trait defining Referee functions
+/// * A referee function mainly decides how the weight of each condition (tuple of focal elements) of the product of the bbas is assigned
+/// * Rules needing global information may also use the hooks `pre_process` and `post_process`, which are called once per fusion
 pub trait Referee {
     /// Test if fusion is allowed
     /// * does not concern lattice coherence, which is tested by `from_conditions`
@@ -53,4 +55,27 @@ pub trait Referee {
             unsafe { self.unsafe_from_conditions(lattice, bbas, conditions) }
         } else { Err("Entries not allowed".to_string()) } 
     }
+
+    /// Global pre-processing of the bbas, called by the fusion engines before the product of the bbas
+    /// * The replacing bbas are then used for the product and passed to `from_conditions` (e.g. averaged bbas)
+    /// * By default, the bbas are not replaced
+    /// * `lattice: &L` : reference lattice
+    /// * `bbas: &[&Assignment<L::Item>]` : sequence of bbas to be fused
+    /// * Output: `None` if the bbas are not replaced, replacing bbas, or error
+    fn pre_process<L>(&self, _lattice: &L, _bbas: &[&Assignment<L::Item>]) -> Result<Option<Vec<Assignment<L::Item>>>,String> 
+                                                                            where L: Lattice, L::Item: Eq + Ord + Hash, {
+        Ok(None)
+    }
+
+    /// Global post-processing of the accumulated assignment, called by the fusion engines after the product of the bbas and 
+    /// before the reduction and the normalization (e.g. reassignment of the global conflict)
+    /// * By default, the accumulated assignment is not modified
+    /// * `lattice: &L` : reference lattice
+    /// * `bbas: &[&Assignment<L::Item>]` : sequence of bbas to be fused, as received by the fusion engine
+    /// * `accumulated: &mut AssignmentBuilder<L::Item>` : accumulated assignment
+    /// * Output: nothing or error
+    fn post_process<L>(&self, _lattice: &L, _bbas: &[&Assignment<L::Item>], _accumulated: &mut AssignmentBuilder<L::Item>) 
+                                                            -> Result<(),String> where L: Lattice, L::Item: Eq + Ord + Hash, {
+        Ok(())
+    }
 }
\ No newline at end of file
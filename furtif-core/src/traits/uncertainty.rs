@@ -0,0 +1,182 @@
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the Lesser GNU General Public License as published
+// by the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// Lesser GNU General Public License for more details.
+
+// You should have received a copy of the Lesser GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+// Copyright 2024 Frederic Dambreville, Jean Dezert Developers.
+
+
+use std::{ hash::Hash, collections::BTreeSet, };
+
+use crate::{
+    types::{ f64slx, SlxInto, IntoSlx, },
+    structs::{ Assignment, ASSIGNMENT_EPSILON, },
+    traits::LatticeWithLeaves,
+};
+
+/// Maximal number of focal elements accepted by the computation of the aggregate uncertainty
+/// * the computation enumerates the unions of focal elements, so that its cost is exponential with this number
+pub const AU_MAX_FOCAL_ELEMENTS: usize = 20;
+
+/// For intern use: focal elements of an assignment described as sets of leaf ranks
+/// * mass on elements without leaves (typically bottom) is discarded
+/// * `lattice: &L` : lattice of definition of the assignment
+/// * `mass: &Assignment<L::Item>` : mass assignment
+/// * `L` : type of lattice
+/// * Output: sequence of focal elements given as leaf ranks and native weight, or error
+pub (crate) fn focal_leaf_sets<L>(lattice: &L, mass: &Assignment<L::Item>) -> Result<Vec<(BTreeSet<usize>,f64)>,String>
+                                                                    where L: LatticeWithLeaves, L::Item: Ord + Hash, {
+    let Assignment { lattice_hash, elements, } = mass;
+    if lattice_hash != lattice.ref_lattice_hash() { return Err("Mismatching lattice hash".to_string()); }
+    let mut leaves = unsafe { lattice.unsafe_leaves() }?.map(|(l,_)| l).collect::<Vec<_>>();
+    leaves.sort();
+    Ok(elements.iter().map(|(x,w)| {
+        let set = leaves.iter().enumerate()
+            .filter(|(_,l)| unsafe { lattice.unsafe_implied_join(x, l) })
+            .map(|(u,_)| u).collect::<BTreeSet<_>>();
+        (set, (*w).unslx())
+    }).filter(|(set,_)| !set.is_empty()).collect())
+}
+
+/// For intern use: binary entropy term `-w log2(w)`, with the convention `0 log2(0) = 0`
+#[inline] fn entropy_term(w: f64) -> f64 { if w > ASSIGNMENT_EPSILON { - w * w.log2() } else { 0.0 } }
+
+/// Trait implementing uncertainty measures of belief functions
+/// * Measures are computed in bits (logarithms in base 2)
+/// * The cardinal of an element is the number of leaves it contains; leaves weights are only used by the pignistic transform
+/// * Mass assigned to elements without leaves (typically bottom) is discarded, without renormalisation
+pub trait UncertaintyMeasure: LatticeWithLeaves where Self::Item: Ord + Hash, {
+    /// Deng entropy: `-∑ m(A) log2( m(A) / (2^|A| - 1) )`
+    /// * `mass: &Assignment<Self::Item>` : mass assignment
+    /// * Output: Deng entropy or error
+    fn deng_entropy(&self, mass: &Assignment<Self::Item>) -> Result<f64slx,String> {
+        let focals = focal_leaf_sets(self, mass)?;
+        Ok(focals.iter().map(|(a,w)| {
+            let card = 2f64.powi(a.len() as i32) - 1.0;
+            entropy_term(*w) + w * card.log2()
+        }).sum::<f64>().slx())
+    }
+
+    /// Nonspecificity (generalized Hartley measure of Dubois & Prade): `∑ m(A) log2 |A|`
+    /// * `mass: &Assignment<Self::Item>` : mass assignment
+    /// * Output: nonspecificity or error
+    fn nonspecificity(&self, mass: &Assignment<Self::Item>) -> Result<f64slx,String> {
+        let focals = focal_leaf_sets(self, mass)?;
+        Ok(focals.iter().map(|(a,w)| w * (a.len() as f64).log2()).sum::<f64>().slx())
+    }
+
+    /// Yager's dissonance: `-∑ m(A) log2 Pl(A)`
+    /// * `mass: &Assignment<Self::Item>` : mass assignment
+    /// * Output: dissonance or error
+    fn dissonance(&self, mass: &Assignment<Self::Item>) -> Result<f64slx,String> {
+        let focals = focal_leaf_sets(self, mass)?;
+        Ok(focals.iter().map(|(a,w)| {
+            let pl = focals.iter().filter(|(b,_)| !a.is_disjoint(b)).map(|(_,v)| *v).sum::<f64>();
+            w * pl.recip().log2()
+        }).sum::<f64>().slx())
+    }
+
+    /// Höhle's confusion: `-∑ m(A) log2 Bel(A)`
+    /// * `mass: &Assignment<Self::Item>` : mass assignment
+    /// * Output: confusion or error
+    fn confusion(&self, mass: &Assignment<Self::Item>) -> Result<f64slx,String> {
+        let focals = focal_leaf_sets(self, mass)?;
+        Ok(focals.iter().map(|(a,w)| {
+            let bel = focals.iter().filter(|(b,_)| b.is_subset(a)).map(|(_,v)| *v).sum::<f64>();
+            w * bel.recip().log2()
+        }).sum::<f64>().slx())
+    }
+
+    /// Klir's aggregate uncertainty (AU): maximal Shannon entropy among the probabilities dominating the credibility
+    /// * Computation is done by means of the algorithm of Meyerowitz, Richman & Walker
+    /// * The unions of focal elements are enumerated, so that the number of focal elements should not exceed `AU_MAX_FOCAL_ELEMENTS`
+    /// * `mass: &Assignment<Self::Item>` : mass assignment
+    /// * Output: aggregate uncertainty or error
+    fn aggregate_uncertainty(&self, mass: &Assignment<Self::Item>) -> Result<f64slx,String> {
+        let focals = focal_leaf_sets(self, mass)?;
+        if focals.len() > AU_MAX_FOCAL_ELEMENTS {
+            return Err(format!("Number of focal elements {} exceeds {AU_MAX_FOCAL_ELEMENTS}", focals.len()));
+        }
+        let mut removed = BTreeSet::<usize>::new();
+        let mut remaining = focals;
+        let mut au = 0.0;
+        while !remaining.is_empty() {
+            // candidate sets are the unions of the remaining focal elements, deprived of the removed leaves
+            let reduced = remaining.iter().map(|(b,_)| b.difference(&removed).copied().collect::<BTreeSet<_>>())
+                .collect::<BTreeSet<_>>().into_iter().collect::<Vec<_>>();
+            let mut best: Option<(BTreeSet<usize>,f64)> = None;
+            for code in 1u32..(1u32 << reduced.len()) {
+                let candidate = reduced.iter().enumerate().filter(|(u,_)| code & (1 << u) != 0)
+                    .flat_map(|(_,b)| b.iter().copied()).collect::<BTreeSet<_>>();
+                let bel = remaining.iter().filter(|(b,_)| b.iter().all(|u| candidate.contains(u) || removed.contains(u)))
+                    .map(|(_,w)| *w).sum::<f64>();
+                let ratio = bel / candidate.len() as f64;
+                let is_better = match &best {
+                    None => true,
+                    Some((set,r)) => ratio > r + ASSIGNMENT_EPSILON
+                        || (ratio > r - ASSIGNMENT_EPSILON && candidate.len() > set.len()),
+                };
+                if is_better { best = Some((candidate,ratio)); }
+            }
+            let (set,ratio) = match best { Some(best) => best, None => break, };
+            au += set.len() as f64 * entropy_term(ratio);
+            removed.extend(set);
+            remaining.retain(|(b,_)| !b.is_subset(&removed));
+        }
+        Ok(au.slx())
+    }
+
+    /// Jousselme's ambiguity measure: Shannon entropy of the pignistic probability
+    /// * The pignistic probability is computed by means of `LatticeWithLeaves::mass_to_pignistic`, and thus depends on the leaves weights
+    /// * `mass: &Assignment<Self::Item>` : mass assignment
+    /// * Output: ambiguity measure or error
+    fn ambiguity(&self, mass: &Assignment<Self::Item>) -> Result<f64slx,String> {
+        let pignistic = self.mass_to_pignistic(mass)?;
+        Ok(pignistic.elements.values().map(|p| entropy_term((*p).unslx())).sum::<f64>().slx())
+    }
+}
+
+impl<L> UncertaintyMeasure for L where L: LatticeWithLeaves, Self::Item: Ord + Hash, { }
+
+pub mod experiment {
+    use crate::{
+        structs::Powerset,
+        traits::{ Lattice, UncertaintyMeasure, }
+    };
+
+    /// Experimentation with uncertainty measures
+    pub fn exp_uncertainty() -> Result<(),String> {
+        println!("===================== uncertainty =====");
+        let lattice = Powerset::new_with_label(
+            &["A".to_string(), "B".to_string(), "C".to_string()], 1024
+        )?;
+        let a = lattice.from_str("A")?;
+        let b = lattice.from_str("B")?;
+        let c = lattice.from_str("C")?;
+        let ab = lattice.from_str("A | B")?;
+        let top = lattice.top();
+        let bayesian = lattice.assignment() + (a, 1.0) + (b, 1.0) + (c, 1.0) + ();
+        let vacuous = lattice.assignment() + (top, 1.0) + ();
+        let mixed = lattice.assignment() + (a, 0.5) + (ab, 0.3) + (top, 0.2) + ();
+        for (m,nm) in [(bayesian,"bayesian"),(vacuous,"vacuous"),(mixed,"mixed")] {
+            println!("-------------------- {nm} -----");
+            println!("m -> {m}");
+            println!("deng entropy -> {:.4}", lattice.deng_entropy(&m)?);
+            println!("nonspecificity -> {:.4}", lattice.nonspecificity(&m)?);
+            println!("dissonance -> {:.4}", lattice.dissonance(&m)?);
+            println!("confusion -> {:.4}", lattice.confusion(&m)?);
+            println!("aggregate uncertainty -> {:.4}", lattice.aggregate_uncertainty(&m)?);
+            println!("ambiguity -> {:.4}", lattice.ambiguity(&m)?);
+        }
+        println!();
+        Ok(())
+    }
+}
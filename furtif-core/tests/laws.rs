@@ -0,0 +1,179 @@
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the Lesser GNU General Public License as published
+// by the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// Lesser GNU General Public License for more details.
+
+// You should have received a copy of the Lesser GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+// Copyright 2024 Frederic Dambreville, Jean Dezert Developers.
+
+
+use proptest::prelude::*;
+
+use std::hash::Hash;
+
+use furtif_core::{
+    structs::{ Assignment, AssignmentBuilder, Conjunctive, DiscountedFuser, EnumLattice, Label, Powerset, SafeArray, },
+    traits::{ DiscountedFusion, ImpreciseBelief, Lattice, LatticeWithLeaves, Referee, },
+    types::IntoSlx,
+    testing::{ strategies::*, laws::*, },
+};
+#[cfg(feature = "parallel")]
+use {
+    std::ops::RangeInclusive,
+    rand::{ rngs::StdRng, SeedableRng, },
+    furtif_core::{ structs::EnumRule, types::u128slx, },
+};
+
+/// Tolerance used for comparing weights
+const TOLERANCE: f64 = 1e-6;
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(64))]
+
+    #[test]
+    /// lattice axioms on any kind of lattice
+    fn lattice_axioms((lattice, [a, b, c]) in lattice_with_elements(enum_lattice(8))) {
+        check_lattice_axioms(&lattice, &a, &b, &c).map_err(TestCaseError::fail)?;
+    }
+
+    #[test]
+    /// complement axioms on powersets and interval lattices
+    fn complement_axioms(
+        (powerset, [a, _, _]) in lattice_with_elements(powerset(8)),
+        (interval, [b, _, _]) in lattice_with_elements(interval_lattice(8)),
+    ) {
+        check_complement_axioms(&powerset, &a).map_err(TestCaseError::fail)?;
+        check_complement_axioms(&interval, &b).map_err(TestCaseError::fail)?;
+    }
+
+    #[test]
+    /// commonality round-trip on any kind of lattice
+    fn commonality_round_trip((lattice, masses) in lattice_with_assignments(enum_lattice(6), 1, 5)) {
+        check_commonality_round_trip(&lattice, &masses[0], TOLERANCE).map_err(TestCaseError::fail)?;
+    }
+
+    #[test]
+    /// consonant round-trip of possibility distributions on powersets
+    fn possibility_round_trip(
+        (lattice, degrees) in powerset(8).prop_flat_map(|lattice| {
+            let nb_leaves = lattice.leaves().map(|leaves| leaves.len()).unwrap_or(0);
+            (Just(lattice), prop::collection::vec(0.0f64..=1.0, nb_leaves))
+        })
+    ) {
+        check_possibility_round_trip(&lattice, degrees, TOLERANCE).map_err(TestCaseError::fail)?;
+    }
+}
+
+/// Conjunctive rule discounting the fused assignment toward top during the post-processing
+struct TopDiscounted;
+
+impl Referee for TopDiscounted {
+    fn is_allowed<L>(&self, lattice: &L, bbas: &[&Assignment<L::Item>]) -> bool where L: Lattice, L::Item: Eq + Ord + Hash, {
+        Conjunctive.is_allowed(lattice, bbas)
+    }
+
+    unsafe fn unsafe_from_conditions<L>(&self, lattice: &L, bbas: &[&Assignment<L::Item>], conditions: SafeArray<L::Item>)
+            -> Result<Assignment<L::Item>,String> where L: Lattice, L::Item: Eq + Ord + Hash, {
+        Conjunctive.unsafe_from_conditions(lattice, bbas, conditions)
+    }
+
+    fn post_process<L>(&self, lattice: &L, _bbas: &[&Assignment<L::Item>], accumulated: &mut AssignmentBuilder<L::Item>) 
+                                                            -> Result<(),String> where L: Lattice, L::Item: Eq + Ord + Hash, {
+        accumulated.push(lattice.top(), 0.5.slx())?;
+        Ok(())
+    }
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(32))]
+
+    #[test]
+    /// commutativity and mass conservation of the rules
+    fn rules_laws((lattice, masses) in lattice_with_assignments::<EnumLattice,_>(enum_lattice(6), 2, 4)) {
+        let engine = DiscountedFuser::new(4096..=4096);
+        check_all_rules_laws(&lattice, &engine, &masses[0], &masses[1], TOLERANCE).map_err(TestCaseError::fail)?;
+    }
+
+    #[test]
+    /// the fused assignment is reduced after the post-processing of the referee
+    fn post_process_reduced((lattice, masses) in lattice_with_assignments::<EnumLattice,_>(enum_lattice(6), 3, 4)) {
+        let engine = DiscountedFuser::new(3..=3);
+        let masses = masses.iter().collect::<Vec<_>>();
+        let (fused, _) = engine.fuse(&lattice, &TopDiscounted, &masses).map_err(TestCaseError::fail)?;
+        prop_assert!(fused.elements.len() <= 3);
+        let (fused, _, _) = engine.fuse_traced(&lattice, &TopDiscounted, &masses).map_err(TestCaseError::fail)?;
+        prop_assert!(fused.elements.len() <= 3);
+    }
+}
+
+#[cfg(feature = "parallel")]
+/// Fusion engine with a given number of threads
+struct ThreadedFuser { fuser: DiscountedFuser, nb_threads: usize, }
+
+#[cfg(feature = "parallel")]
+impl DiscountedFusion for ThreadedFuser {
+    fn size_range(&self) -> RangeInclusive<usize> { self.fuser.size_range() }
+    fn nb_threads(&self) -> usize { self.nb_threads }
+}
+
+#[cfg(feature = "parallel")]
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(8))]
+
+    #[test]
+    /// parallel fusion is identical to sequential fusion when there is no pruning
+    /// * weights are dyadic, so that their sums are exact whatever their order
+    /// * 6 assignments with 4 focal elements produce 4 chunks of the product
+    fn par_fuse_eq_fuse((lattice, seeds) in powerset(6).prop_flat_map(|lattice| (Just(lattice), prop::collection::vec([any::<u64>(); 4], 6)))) {
+        let masses = seeds.iter().map(|seeds| {
+            let mut mass = lattice.assignment();
+            for (seed,weight) in seeds.iter().zip([0.5, 0.25, 0.125, 0.125]) {
+                mass.push(lattice.rand_element(&mut StdRng::seed_from_u64(*seed)), weight.slx())?;
+            }
+            Ok(mass.into())
+        }).collect::<Result<Vec<Assignment<u128slx>>,String>>().map_err(TestCaseError::fail)?;
+        let masses = masses.iter().collect::<Vec<_>>();
+        let fuser = DiscountedFuser::new(4096..=4096);
+        let (sequential, z) = fuser.fuse(&lattice, &EnumRule::Conjunctive, &masses).map_err(TestCaseError::fail)?;
+        for nb_threads in [1, 2, 3, 4] {
+            let engine = ThreadedFuser { fuser, nb_threads, };
+            let (parallel, pz) = engine.par_fuse(&lattice, &EnumRule::Conjunctive, &masses).map_err(TestCaseError::fail)?;
+            prop_assert_eq!(&parallel.elements, &sequential.elements);
+            prop_assert_eq!(pz, z);
+        }
+    }
+}
+
+#[test]
+/// an imprecise assignment with an inverted interval is not admissible, even when the sums of its bounds are
+fn inverted_interval_not_admissible() -> Result<(),String> {
+    let lattice = Powerset::new_with_label(&["A".to_string(), "B".to_string()], 1024)?;
+    let (a, b) = (lattice.from_str("A")?, lattice.from_str("B")?);
+    let mut mass = lattice.imprecise_assignment(&[(a, 0.2.slx(), 0.5.slx()), (b, 0.0.slx(), 0.8.slx())])?;
+    assert!(mass.is_admissible());
+    for (lo,hi) in mass.elements.values_mut().filter(|(lo,_)| *lo == 0.2.slx()) { (*lo, *hi) = (0.5.slx(), 0.2.slx()); }
+    assert_eq!(mass.bounds_sums(), (0.5, 1.0));
+    assert!(!mass.is_admissible());
+    assert!(mass.normalize().is_err());
+    Ok(())
+}
+
+#[test]
+/// division by the minimal label is an error, and the quotient of labels is a finite label
+fn label_division_by_minimal_label() -> Result<(),String> {
+    let (low, medium, none) = (Label::new(1.0, 4)?, Label::new(2.0, 4)?, Label::min_label(4));
+    assert_eq!(low.checked_div(medium)?, Label::new(2.0, 4)?);
+    assert_eq!(low / medium, Label::new(2.0, 4)?);
+    assert!(low.checked_div(none).is_err());
+    assert!(none.checked_div(none).is_err());
+    assert!(low.checked_div(Label::new(1.0, 3)?).is_err());
+    assert!(std::panic::catch_unwind(|| low / none).is_err());
+    Ok(())
+}
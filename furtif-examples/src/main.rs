@@ -22,6 +22,7 @@ use std::env;
 use self::crate_main::{ exp_dsmtbook, exp_dsmtbook_mono, exp_load_start, };
 
 fn _main_exp_referee() {
+    println!("{:?}",furtif_core::structs::exp_pcr5());
     println!("{:?}",furtif_core::structs::exp_pcr6());
     println!("{:?}",furtif_core::structs::exp_conjunctive());
     println!("{:?}",furtif_core::structs::exp_dempster_shafer());
@@ -34,6 +35,65 @@ fn _main_exp_transform() {
     println!("{:?}",furtif_core::traits::exp_transform());
 }
 
+fn _main_exp_uncertainty() {
+    println!("{:?}",furtif_core::traits::exp_uncertainty());
+}
+
+fn _main_exp_imprecise() {
+    println!("{:?}",furtif_core::traits::exp_imprecise());
+}
+
+fn _main_exp_qualitative() {
+    println!("{:?}",furtif_core::traits::exp_qualitative());
+}
+
+fn _main_exp_fusion_trace() {
+    println!("{:?}",furtif_core::structs::exp_fusion_trace());
+}
+
+fn _main_exp_conflict() {
+    println!("{:?}",furtif_core::structs::exp_conflict());
+}
+
+fn _main_exp_global_rules() {
+    println!("{:?}",furtif_core::structs::exp_averaging());
+    println!("{:?}",furtif_core::structs::exp_murphy());
+    println!("{:?}",furtif_core::structs::exp_yager());
+}
+
+fn _main_exp_possibility() {
+    println!("{:?}",furtif_core::traits::exp_possibility());
+}
+
+fn _main_exp_reliability() {
+    println!("{:?}",furtif_core::structs::exp_reliability());
+}
+
+fn _main_exp_knn() {
+    println!("{:?}",furtif_core::structs::exp_knn());
+}
+
+fn _main_exp_ecm() {
+    println!("{:?}",furtif_core::structs::exp_ecm());
+}
+
+fn _main_exp_valuation() {
+    println!("{:?}",furtif_core::structs::exp_valuation());
+}
+
+fn _main_exp_interval() {
+    println!("{:?}",furtif_core::structs::exp_interval());
+}
+
+#[cfg(feature = "parallel")]
+fn _main_exp_par_fuse() {
+    println!("{:?}",furtif_core::traits::exp_par_fuse());
+}
+
+fn _main_exp_bba_product() {
+    println!("{:?}",furtif_core::structs::exp_bba_product());
+}
+
 fn _main_exp_taxonomy() {
     println!("{:?}",furtif_core::structs::exp_taxonomy_1());
     println!("{:?}",furtif_core::structs::exp_taxonomy_2());
@@ -49,6 +109,20 @@ fn _main_exp_taxonomy() {
 /// * exp_referee : some referee function examples
 /// * exp_transform : some transform examples
 /// * exp_taxonomy : some taxonomy examples
+/// * exp_uncertainty : some uncertainty measures examples
+/// * exp_reliability : some reliability estimation examples
+/// * exp_knn : some evidential k-nearest neighbours examples
+/// * exp_ecm : some evidential c-means examples
+/// * exp_valuation : some valuation-based system examples
+/// * exp_interval : some interval lattice examples
+/// * exp_par_fuse : some parallel fusion examples (requires feature `parallel`)
+/// * exp_bba_product : some lazy product of assignments examples
+/// * exp_possibility : some possibility theory examples
+/// * exp_imprecise : some imprecise assignments examples
+/// * exp_qualitative : some qualitative assignments examples
+/// * exp_fusion_trace : some fusion trace examples
+/// * exp_conflict : some conflict analysis examples
+/// * exp_global_rules : some examples of global rules (averaging, Murphy, Yager)
 pub async fn main() {
     println!("Available paralelism -> {:?}",std::thread::available_parallelism());
     let args: Vec<String> = env::args().collect();
@@ -61,6 +135,21 @@ pub async fn main() {
                 "exp_referee" => _main_exp_referee(),
                 "exp_transform" => _main_exp_transform(),
                 "exp_taxonomy" => _main_exp_taxonomy(),
+                "exp_uncertainty" => _main_exp_uncertainty(),
+                "exp_reliability" => _main_exp_reliability(),
+                "exp_knn" => _main_exp_knn(),
+                "exp_ecm" => _main_exp_ecm(),
+                "exp_valuation" => _main_exp_valuation(),
+                "exp_interval" => _main_exp_interval(),
+                #[cfg(feature = "parallel")]
+                "exp_par_fuse" => _main_exp_par_fuse(),
+                "exp_bba_product" => _main_exp_bba_product(),
+                "exp_possibility" => _main_exp_possibility(),
+                "exp_imprecise" => _main_exp_imprecise(),
+                "exp_qualitative" => _main_exp_qualitative(),
+                "exp_fusion_trace" => _main_exp_fusion_trace(),
+                "exp_conflict" => _main_exp_conflict(),
+                "exp_global_rules" => _main_exp_global_rules(),
                 _ => panic!("bad argument"),
             }    
         }, 
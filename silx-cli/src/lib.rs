@@ -0,0 +1,195 @@
+//! This is part of [**Silx**](https://crates.io/crates/silx-core) project
+//!
+//! `silx-cli` contains the `silx` command-line tool, which works on the network definitions saved by `StarterProducer` and `RecFiled<FiledStarter>`
+//!
+//! # Commands
+//! ```txt
+//! silx validate <starter.yaml> [--dir <directory>]
+//! silx inspect <starter.yaml> [--dir <directory>]
+//! silx graph <starter.yaml> [--dir <directory>] [--format dot|mermaid] [--metrics <admin address>]...
+//! silx run <starter.yaml> [--dir <directory>]
+//! ```
+//! * `validate`: load the starter and check the topology of the network; the exit code is non-zero if an error is found
+//! * `inspect`: print the clusters, servants and channels, with the type hash codes of the channels
+//! * `graph`: export the topology in DOT (default) or Mermaid format; the admin endpoints given by `--metrics` (socket address or Unix socket path) annotate the graph with live telemetry
+//! * `run`: launch a `Main` or `Listener` starter
+//!
+//! `inspect` and `graph` only read the starter, builder and channel files, so that they work for any network.
+//! `validate` and `run` need the servant builders, and thus are restricted to the servant types linked within the executable (see below).
+//!
+//! The starter file path is relative to the directory of the saved network (option `--dir`, default is the current directory).
+//! Traces are filtered at runtime by the `RUST_LOG` environment variable (default is `info`).
+//!
+//! # Servant types
+//! Servant builders are deserialized by means of `typetag`, so that the servant types have to be linked within the executable for `validate` and `run`.
+//! The `silx` binary only knows the servants of `silx-core`; for validating or running a network with its own servants, a binary is built which links these servants and calls `silx_cli::main()`:
+//! ```txt
+//! use my_servants as _; // link the servant builders
+//!
+//! #[tokio::main]
+//! async fn main() -> std::process::ExitCode { silx_cli::main().await }
+//! ```
+use std::{ collections::BTreeMap, net::SocketAddr, path::PathBuf, process::ExitCode, };
+
+use silx_core::{
+    error::SilxError,
+    utils::{ AdminAddress, AdminClient, Channel, ChannelKind, Starter, StarterOutline, },
+};
+use tracing_subscriber::EnvFilter;
+
+static USAGE: &str = "\
+Usage:
+    silx validate <starter.yaml> [--dir <directory>]
+    silx inspect <starter.yaml> [--dir <directory>]
+    silx graph <starter.yaml> [--dir <directory>] [--format dot|mermaid] [--metrics <admin address>]...
+    silx run <starter.yaml> [--dir <directory>]
+
+validate and run unserialize the servant builders: the silx binary only knows the servants of silx-core,
+other servants require a binary which links them and calls silx_cli::main().
+inspect and graph do not unserialize the servant builders and work for any network.";
+
+#[derive(Clone, Copy, Debug, PartialEq,)]
+/// Export format of the topology
+pub enum GraphFormat {
+    /// Graphviz DOT
+    Dot,
+    /// Mermaid flowchart
+    Mermaid,
+}
+
+#[derive(Clone, Debug,)]
+/// Command of the `silx` tool
+pub enum Command {
+    /// Check the topology of the network
+    Validate { starter: PathBuf, dir: PathBuf, },
+    /// Print the clusters, servants and channels of the network
+    Inspect { starter: PathBuf, dir: PathBuf, },
+    /// Export the topology of the network, possibly annotated by the metrics of running clusters
+    Graph { starter: PathBuf, dir: PathBuf, format: GraphFormat, metrics: Vec<AdminAddress>, },
+    /// Launch the starter
+    Run { starter: PathBuf, dir: PathBuf, },
+}
+
+impl Command {
+    /// Parse the command from the arguments, without the name of the executable
+    /// * `args: I` : arguments
+    /// * `I` : type of arguments iterator
+    /// * Output: the command or a configuration error with the usage
+    pub fn parse<I>(args: I) -> Result<Self,SilxError> where I: IntoIterator<Item=String> {
+        let usage = |message: String| SilxError::config(format!("{message}\n{USAGE}"));
+        let mut args = args.into_iter();
+        let command = args.next().ok_or_else(|| usage("missing command".to_string()))?;
+        let starter = args.next().map(PathBuf::from).ok_or_else(|| usage("missing starter file".to_string()))?;
+        let mut dir = PathBuf::from(".");
+        let mut format = GraphFormat::Dot;
+        let mut metrics = Vec::new();
+        while let Some(option) = args.next() {
+            let mut value = || args.next().ok_or_else(|| usage(format!("missing value of option {option}")));
+            match option.as_str() {
+                "--dir" => dir = PathBuf::from(value()?),
+                "--format" if command == "graph" => format = match value()?.as_str() {
+                    "dot" => GraphFormat::Dot,
+                    "mermaid" => GraphFormat::Mermaid,
+                    other => return Err(usage(format!("unknown format {other}"))),
+                },
+                "--metrics" if command == "graph" => {
+                    let address = value()?;
+                    metrics.push(match address.parse::<SocketAddr>() {
+                        Ok(socket) => AdminAddress::Tcp(socket), Err(_) => AdminAddress::Unix(PathBuf::from(address)),
+                    });
+                },
+                _ => return Err(usage(format!("unexpected argument {option}"))),
+            }
+        }
+        match command.as_str() {
+            "validate" => Ok(Self::Validate { starter, dir, }),
+            "inspect"  => Ok(Self::Inspect { starter, dir, }),
+            "graph"    => Ok(Self::Graph { starter, dir, format, metrics, }),
+            "run"      => Ok(Self::Run { starter, dir, }),
+            _          => Err(usage(format!("unknown command {command}"))),
+        }
+    }
+
+    /// Execute the command
+    /// * Output: nothing or error
+    pub async fn execute(self) -> Result<(),SilxError> {
+        match self {
+            Self::Validate { starter, dir, } => {
+                let report = Starter::load(starter, dir)?.validate();
+                println!("{report}");
+                if report.is_valid() { Ok(()) } else { Err(SilxError::config("the network is not valid")) }
+            },
+            Self::Inspect { starter, dir, } => { print!("{}", inspect(&StarterOutline::load(starter, dir)?)); Ok(()) },
+            Self::Graph { starter, dir, format, metrics, } => {
+                let starter = StarterOutline::load(starter, dir)?;
+                let mut snapshots = Vec::new();
+                for address in &metrics { snapshots.push(AdminClient::connect(address).await?.metrics().await?); }
+                print!("{}", match format {
+                    GraphFormat::Dot => starter.to_dot(&snapshots)?,
+                    GraphFormat::Mermaid => starter.to_mermaid(&snapshots)?,
+                });
+                Ok(())
+            },
+            Self::Run { starter, dir, } => Starter::load(starter, dir)?.run().await,
+        }
+    }
+}
+
+// data types label of a channel
+fn data_types(channel: &Channel) -> String {
+    match (channel.kind(), channel.data_types()) {
+        (ChannelKind::Query | ChannelKind::NetQuery, (query_type, reply_type)) => format!("query type {query_type}, reply type {reply_type}"),
+        (_, (data_type, _)) => format!("data type {data_type}"),
+    }
+}
+
+/// Describe the clusters, servants and channels of a starter
+/// * `starter: &StarterOutline` : outline of the starter
+/// * Output: description of the starter
+pub fn inspect(starter: &StarterOutline) -> String {
+    let (main, builders, flow) = match starter {
+        StarterOutline::Listener { main, this, } => return format!("listener cluster {this}, awaiting its definition from main cluster {main}\n"),
+        StarterOutline::Main { main, builders, flow, } => (main, builders, flow),
+    };
+    let mut out = String::new();
+    // channels connected to each servant
+    let mut connections: BTreeMap<(SocketAddr,&String),Vec<String>> = BTreeMap::new();
+    for (name,channel) in flow {
+        let [(icluster,inames), (ocluster,onames)] = channel.sides();
+        for servant in inames { connections.entry((icluster,servant)).or_default().push(format!("{name} (input)")); }
+        for servant in onames { connections.entry((ocluster,servant)).or_default().push(format!("{name} (output)")); }
+    }
+    for (cluster,builder) in builders {
+        out.push_str(&format!("cluster {cluster}{}\n", if cluster == main { " (main)" } else { "" }));
+        let net_size = builder.net_size.map(|size| size.to_string()).unwrap_or_else(|| "unbounded".to_string());
+        out.push_str(&format!("    net size: {net_size}, control capacity: {}\n", builder.ctrl_ch_capacity));
+        if let Some(admin) = &builder.admin { out.push_str(&format!("    admin: {admin:?}\n")); }
+        if let Some(metrics) = &builder.metrics { out.push_str(&format!("    metrics: {metrics}\n")); }
+        for name in &builder.servants {
+            let supervision = builder.supervision.get(name).copied().unwrap_or_default();
+            let channels = connections.get(&(*cluster,name)).map(|channels| channels.join(", ")).unwrap_or_default();
+            out.push_str(&format!("    servant {name}, supervision {supervision:?}, channels: {channels}\n"));
+        }
+    }
+    for (name,channel) in flow {
+        let [(icluster,inames), (ocluster,onames)] = channel.sides();
+        out.push_str(&format!("channel {name}: {}, {}\n", channel.kind(), data_types(channel)));
+        out.push_str(&format!("    input: {icluster} {inames:?}\n    output: {ocluster} {onames:?}\n"));
+    }
+    out
+}
+
+/// Main function of the `silx` tool: install the tracing subscriber, then parse and execute the command given by the arguments of the process
+/// * Output: exit code
+pub async fn main() -> ExitCode {
+    let filter = EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new("info"));
+    tracing_subscriber::fmt().with_env_filter(filter).init();
+    let command = match Command::parse(std::env::args().skip(1)) {
+        Ok(command) => command,
+        Err(e) => { eprintln!("{}", String::from(e)); return ExitCode::from(2); },
+    };
+    match command.execute().await {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => { eprintln!("{}", String::from(e)); ExitCode::FAILURE },
+    }
+}
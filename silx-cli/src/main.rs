@@ -0,0 +1,6 @@
+//! `silx` command-line tool, for the networks built on the servants of `silx-core`
+//! * see crate documentation for building the tool with other servants
+use std::process::ExitCode;
+
+#[tokio::main]
+async fn main() -> ExitCode { silx_cli::main().await }
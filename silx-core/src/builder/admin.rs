@@ -0,0 +1,289 @@
+use std::{ collections::BTreeMap, net::SocketAddr, path::PathBuf, time::Duration, };
+
+use serde::{ Serialize, Deserialize, };
+use tokio::{ net::{ TcpListener, TcpStream, }, io::{ AsyncRead, AsyncWrite, }, sync::{ mpsc as msc, oneshot as osh, }, task::JoinHandle, time::{ sleep, timeout, }, };
+use tracing::{ error, warn, };
+
+use crate::{ net::SilxProtocols, builder::telemetry::{ MetricsSnapshot, ACCEPT_BACKOFF, }, shared::{ trace::spawn, error::SilxError, }, };
+
+/// Default maximal duration of the round-trip of an admin request
+pub const ADMIN_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Channel sender of the admin requests to the master of a cluster
+pub (crate) type SendAdmin = msc::Sender<(AdminRequest, osh::Sender<AdminReply>)>;
+/// Channel receiver of the admin requests by the master of a cluster
+pub (crate) type RecvAdmin = msc::Receiver<(AdminRequest, osh::Sender<AdminReply>)>;
+
+#[derive(Clone, Serialize, Deserialize, Debug, PartialEq,)]
+/// Address of the admin endpoint of a cluster
+pub enum AdminAddress {
+    /// Local TCP socket address; the endpoint is unauthenticated, so that only loopback addresses are accepted
+    Tcp(SocketAddr),
+    /// Path of a Unix domain socket (available on unix platforms only)
+    Unix(PathBuf),
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug, PartialEq,)]
+/// Request sent to the admin endpoint of a cluster
+pub enum AdminRequest {
+    /// List the servants and channels of the cluster with their status
+    List,
+    /// Ping a named channel for all the servants of the cluster which are connected to it
+    Ping { channel: String, },
+    /// Pause (turn off) a named channel for all the servants of the cluster which are connected to it
+    Pause { channel: String, },
+    /// Resume (turn on) a named channel for all the servants of the cluster which are connected to it
+    Resume { channel: String, },
+    /// Get a snapshot of the metrics of the cluster
+    Metrics,
+    /// Gracefully shut the cluster down
+    Shutdown,
+    /// Gracefully shut the whole network down; this request is only accepted by the main cluster
+    ShutdownNetwork,
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug, PartialEq,)]
+/// Reply of the admin endpoint of a cluster
+pub enum AdminReply {
+    /// Status of the cluster, as replied to `AdminRequest::List`
+    Status(ClusterStatus),
+    /// Ping results by servant name, as replied to `AdminRequest::Ping`; a servant fails the ping when its channel is staled or turned off
+    Ping(BTreeMap<String,bool>),
+    /// Snapshot of the metrics of the cluster, as replied to `AdminRequest::Metrics`
+    Metrics(MetricsSnapshot),
+    /// Request is done; shutdown requests are replied as soon as the shutdown is started
+    Done,
+    /// Request has failed
+    Error(String),
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug, PartialEq,)]
+/// Status of a cluster
+pub struct ClusterStatus {
+    /// Socket address of the cluster
+    pub cluster: SocketAddr,
+    /// Status of the servants by name
+    pub servants: BTreeMap<String,ServantStatus>,
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug, PartialEq,)]
+/// Status of a servant
+pub struct ServantStatus {
+    /// Supervision state of the servant
+    pub state: ServantState,
+    /// State of the channels of the servant by name
+    pub channels: BTreeMap<String,ChannelState>,
+}
+
+#[derive(Clone, Copy, Serialize, Deserialize, Debug, PartialEq,)]
+/// Supervision state of a servant
+pub enum ServantState {
+    /// Servant is running and has never failed
+    Running,
+    /// Servant has failed and has been restarted; the number of restarts within the supervision period is given
+    Restarted(usize),
+    /// Servant has failed and is stopped
+    Stopped,
+    /// Servant has failed and its failure has been escalated to the cluster
+    Escalated,
+}
+
+#[derive(Clone, Copy, Serialize, Deserialize, Debug, PartialEq,)]
+/// State of a channel of a servant
+pub enum ChannelState {
+    /// Channel is turned on
+    Active,
+    /// Channel has been paused by the admin endpoint
+    Paused,
+}
+
+// stream of an admin connection
+trait AdminStream: AsyncRead + AsyncWrite + Unpin + Send {}
+impl<S> AdminStream for S where S: AsyncRead + AsyncWrite + Unpin + Send {}
+
+// process the admin requests of a connection
+async fn serve(mut stream: Box<dyn AdminStream>, send_admin: SendAdmin,) {
+    while let Ok(yaml) = SilxProtocols::pop_string(&mut stream).await {
+        let reply = match serde_yaml::from_str::<AdminRequest>(&yaml) {
+            Err(e) => AdminReply::Error(format!("Admin: failed to unserialize request -> {e}")),
+            Ok(request) => {
+                let (sender, receiver) = osh::channel();
+                if send_admin.send((request, sender)).await.is_err() { AdminReply::Error("Admin: master is not running".to_string()) }
+                else { receiver.await.unwrap_or_else(|_| AdminReply::Error("Admin: no reply from master".to_string())) }
+            },
+        };
+        let yaml = match serde_yaml::to_string(&reply) {
+            Ok(s) => s, Err(e) => { error!("admin: failed to serialize reply -> {e}"); break; },
+        };
+        if SilxProtocols::push_string(&mut stream, &yaml).await.is_err() { break; }
+    }
+}
+
+/// Listen to the admin connections and forward their requests to the master of a cluster
+/// * Output: handle of the listener or error
+pub (crate) async fn listen_admin(address: &AdminAddress, send_admin: SendAdmin,) -> Result<JoinHandle<()>,SilxError> {
+    match address {
+        AdminAddress::Tcp(socket) => {
+            if !socket.ip().is_loopback() {
+                return Err(SilxError::config(format!("Admin: {socket} is not a loopback address")));
+            }
+            let listener = match TcpListener::bind(socket).await {
+                Ok(listener) => listener, Err(e) => return Err(SilxError::io(format!("Admin: failed to bind {socket}"), e)),
+            };
+            Ok(spawn(async move {
+                loop {
+                    match listener.accept().await {
+                        Ok((stream,_)) => { spawn(serve(Box::new(stream), send_admin.clone())); },
+                        Err(e) => { warn!("Admin: failed to accept connection -> {e}"); sleep(ACCEPT_BACKOFF).await; },
+                    }
+                }
+            }))
+        },
+        #[cfg(unix)]
+        AdminAddress::Unix(path) => {
+            // remove a socket file left by a previous run, but never another kind of file
+            if let Ok(metadata) = std::fs::symlink_metadata(path) {
+                use std::os::unix::fs::FileTypeExt;
+                if !metadata.file_type().is_socket() {
+                    return Err(SilxError::config(format!("Admin: {} exists and is not a socket", path.display())));
+                }
+                if let Err(e) = std::fs::remove_file(path) {
+                    return Err(SilxError::io(format!("Admin: failed to remove socket {}", path.display()), e));
+                }
+            }
+            let listener = match tokio::net::UnixListener::bind(path) {
+                Ok(listener) => listener, Err(e) => return Err(SilxError::io(format!("Admin: failed to bind {}", path.display()), e)),
+            };
+            Ok(spawn(async move {
+                loop {
+                    match listener.accept().await {
+                        Ok((stream,_)) => { spawn(serve(Box::new(stream), send_admin.clone())); },
+                        Err(e) => { warn!("Admin: failed to accept connection -> {e}"); sleep(ACCEPT_BACKOFF).await; },
+                    }
+                }
+            }))
+        },
+        #[cfg(not(unix))]
+        AdminAddress::Unix(_) => Err(SilxError::config("Admin: Unix sockets are not available on this platform")),
+    }
+}
+
+/// Client of the admin endpoint of a cluster
+pub struct AdminClient {
+    stream: Box<dyn AdminStream>,
+    timeout: Duration,
+}
+
+impl AdminClient {
+    /// Connect to the admin endpoint of a cluster
+    /// * `address: &AdminAddress` : address of the admin endpoint
+    /// * Output: admin client or error
+    pub async fn connect(address: &AdminAddress) -> Result<Self,SilxError> {
+        let stream: Box<dyn AdminStream> = match address {
+            AdminAddress::Tcp(socket) => match TcpStream::connect(socket).await {
+                Ok(stream) => Box::new(stream), Err(e) => return Err(SilxError::io(format!("Admin: failed to connect {socket}"), e)),
+            },
+            #[cfg(unix)]
+            AdminAddress::Unix(path) => match tokio::net::UnixStream::connect(path).await {
+                Ok(stream) => Box::new(stream), Err(e) => return Err(SilxError::io(format!("Admin: failed to connect {}", path.display()), e)),
+            },
+            #[cfg(not(unix))]
+            AdminAddress::Unix(_) => return Err(SilxError::config("Admin: Unix sockets are not available on this platform")),
+        };
+        Ok(Self { stream, timeout: ADMIN_TIMEOUT, })
+    }
+
+    /// Set the maximal duration of the round-trip of the requests; by default, this is `ADMIN_TIMEOUT`
+    /// * `timeout: Duration` : maximal duration of a request
+    /// * Output: the client
+    pub fn with_timeout(mut self, timeout: Duration) -> Self { self.timeout = timeout; self }
+
+    /// Send a request to the admin endpoint and wait for the reply
+    /// * If there is no reply within the timeout of the client, a time out error is returned; the client should then be connected again,
+    ///   since a late reply would be taken for the reply of the next request
+    /// * `request: &AdminRequest` : admin request
+    /// * Output: reply or error
+    pub async fn request(&mut self, request: &AdminRequest) -> Result<AdminReply,SilxError> {
+        let yaml = match serde_yaml::to_string(request) {
+            Ok(s) => s, Err(e) => return Err(SilxError::protocol("Admin: failed to serialize request").with_source(e)),
+        };
+        let stream = &mut self.stream;
+        let round_trip = async {
+            SilxProtocols::push_string(&mut *stream, &yaml).await?;
+            SilxProtocols::pop_string(&mut *stream).await
+        };
+        let reply = match timeout(self.timeout, round_trip).await {
+            Ok(reply) => reply?,
+            Err(_) => return Err(SilxError::timeout(format!("Admin: no reply within {:?}", self.timeout))),
+        };
+        match serde_yaml::from_str(&reply) {
+            Ok(reply) => Ok(reply), Err(e) => Err(SilxError::protocol("Admin: failed to unserialize reply").with_source(e)),
+        }
+    }
+
+    // send a request which is expected to be replied by `AdminReply::Done`
+    async fn done(&mut self, request: &AdminRequest) -> Result<(),SilxError> {
+        match self.request(request).await? {
+            AdminReply::Done => Ok(()),
+            AdminReply::Error(e) => Err(SilxError::remote(e)),
+            reply => Err(SilxError::protocol(format!("Admin: unexpected reply {:?}", reply))),
+        }
+    }
+
+    /// List the servants and channels of the cluster with their status
+    /// * Output: status of the cluster or error
+    pub async fn list(&mut self) -> Result<ClusterStatus,SilxError> {
+        match self.request(&AdminRequest::List).await? {
+            AdminReply::Status(status) => Ok(status),
+            AdminReply::Error(e) => Err(SilxError::remote(e)),
+            reply => Err(SilxError::protocol(format!("Admin: unexpected reply {:?}", reply))),
+        }
+    }
+
+    /// Ping a named channel
+    /// * `channel: &str` : name of the channel
+    /// * Output: ping results by servant name or error
+    pub async fn ping(&mut self, channel: &str) -> Result<BTreeMap<String,bool>,SilxError> {
+        match self.request(&AdminRequest::Ping { channel: channel.to_string(), }).await? {
+            AdminReply::Ping(results) => Ok(results),
+            AdminReply::Error(e) => Err(SilxError::remote(e)),
+            reply => Err(SilxError::protocol(format!("Admin: unexpected reply {:?}", reply))),
+        }
+    }
+
+    /// Pause a named channel
+    /// * `channel: &str` : name of the channel
+    /// * Output: nothing or error
+    pub async fn pause(&mut self, channel: &str) -> Result<(),SilxError> {
+        self.done(&AdminRequest::Pause { channel: channel.to_string(), }).await
+    }
+
+    /// Resume a paused channel
+    /// * `channel: &str` : name of the channel
+    /// * Output: nothing or error
+    pub async fn resume(&mut self, channel: &str) -> Result<(),SilxError> {
+        self.done(&AdminRequest::Resume { channel: channel.to_string(), }).await
+    }
+
+    /// Get a snapshot of the metrics of the cluster
+    /// * Output: metrics snapshot or error
+    pub async fn metrics(&mut self) -> Result<MetricsSnapshot,SilxError> {
+        match self.request(&AdminRequest::Metrics).await? {
+            AdminReply::Metrics(snapshot) => Ok(snapshot),
+            AdminReply::Error(e) => Err(SilxError::remote(e)),
+            reply => Err(SilxError::protocol(format!("Admin: unexpected reply {:?}", reply))),
+        }
+    }
+
+    /// Gracefully shut the cluster down
+    /// * Output: nothing or error
+    pub async fn shutdown(&mut self) -> Result<(),SilxError> {
+        self.done(&AdminRequest::Shutdown).await
+    }
+
+    /// Gracefully shut the whole network down; the client should be connected to the main cluster
+    /// * Output: nothing or error
+    pub async fn shutdown_network(&mut self) -> Result<(),SilxError> {
+        self.done(&AdminRequest::ShutdownNetwork).await
+    }
+}
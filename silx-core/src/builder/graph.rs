@@ -0,0 +1,215 @@
+use std::{ collections::{ BTreeMap, HashMap, }, fmt::Write, net::SocketAddr, };
+
+use crate::{
+    traits::FullId,
+    builder::{ Channel, ChannelKind, outline::ClusterOutline, telemetry::{ MetricsSnapshot, ServantSnapshot, ChannelSnapshot, }, },
+};
+
+// node of the graph: id and label lines
+type Node = (String, Vec<String>);
+
+// edge of the graph: source id, target id, label lines, and true for a net channel
+type Edge = (String, String, Vec<String>, bool);
+
+/// Graph of the network topology, rendered in DOT or Mermaid format
+/// * clusters are subgraphs, servants are nodes, channels are edges from their input servants to their output servants
+/// * a channel without input or without output servant is a dashed node, linked to the servants of its other side
+pub (crate) struct Graph {
+    // cluster label and servants of each cluster
+    clusters: Vec<(String, Vec<Node>)>,
+    // servants connected to a channel, but undefined within their cluster
+    loose: Vec<Node>,
+    // channels without input or without output servant
+    stubs: Vec<Node>,
+    edges: Vec<Edge>,
+}
+
+// short form of a type hash code
+fn short(id: &FullId) -> String { id.simple().to_string()[..8].to_string() }
+
+// data type label of a channel
+fn data_type(channel: &Channel) -> String {
+    match (channel.kind(), channel.data_types()) {
+        (ChannelKind::Query | ChannelKind::NetQuery, (query_type, reply_type)) => format!("{} -> {}", short(&query_type), short(&reply_type)),
+        (_, (data_type, _)) => short(&data_type),
+    }
+}
+
+impl Graph {
+    /// Build the graph of a network definition
+    /// * `main: &SocketAddr` : socket address of the main cluster
+    /// * `builders: &BTreeMap<SocketAddr,ClusterOutline>` : outlines of the clusters definitions
+    /// * `flow: &BTreeMap<String,Channel>` : channels definitions
+    /// * `metrics: &[MetricsSnapshot]` : metrics snapshots of the running clusters, used for annotating the servants and channels
+    /// * Output: the graph
+    pub (crate) fn new(main: &SocketAddr, builders: &BTreeMap<SocketAddr,ClusterOutline>, flow: &BTreeMap<String,Channel>, metrics: &[MetricsSnapshot],) -> Self {
+        let servant_metrics: HashMap<(SocketAddr,&str),&ServantSnapshot> = metrics.iter().flat_map(|snapshot| {
+            snapshot.servants.iter().map(|servant| ((snapshot.cluster, servant.servant.as_str()), servant))
+        }).collect();
+        let channel_metrics: HashMap<(SocketAddr,&str,&str),&ChannelSnapshot> = metrics.iter().flat_map(|snapshot| {
+            snapshot.channels.iter().map(|channel| ((snapshot.cluster, channel.servant.as_str(), channel.channel.as_str()), channel))
+        }).collect();
+        let mut ids: BTreeMap<(SocketAddr,String),String> = BTreeMap::new();
+        let clusters = builders.iter().enumerate().map(|(c,(cluster,builder))| {
+            let label = if cluster == main { format!("{cluster} (main)") } else { cluster.to_string() };
+            let servants = builder.servants.iter().enumerate().map(|(s,name)| {
+                let id = format!("c{c}_s{s}");
+                ids.insert((*cluster,name.clone()), id.clone());
+                let mut label = vec![name.clone()];
+                if let Some(snapshot) = servant_metrics.get(&(*cluster,name.as_str())) {
+                    label.push(format!("{} tasks, {} timeouts", snapshot.tasks, snapshot.timeouts));
+                }
+                (id, label)
+            }).collect();
+            (label, servants)
+        }).collect();
+        let mut loose = Vec::new();
+        let mut stubs = Vec::new();
+        let mut edges = Vec::new();
+        for (name,channel) in flow {
+            let (kind, data_type) = (channel.kind(), data_type(channel));
+            let [(icluster, inames), (ocluster, onames)] = channel.sides();
+            let net = kind.is_net();
+            let mut node = |cluster: SocketAddr, servant: &String| -> String {
+                ids.entry((cluster,servant.clone())).or_insert_with(|| {
+                    let id = format!("u{}", loose.len());
+                    loose.push((id.clone(), vec![servant.clone(), format!("undefined within {cluster}")]));
+                    id
+                }).clone()
+            };
+            let inodes: Vec<_> = inames.iter().map(|servant| (servant, node(icluster, servant))).collect();
+            let onodes: Vec<_> = onames.iter().map(|servant| (servant, node(ocluster, servant))).collect();
+            if inodes.is_empty() || onodes.is_empty() {
+                let id = format!("x{}", stubs.len());
+                let side = if inodes.is_empty() { "input" } else { "output" };
+                stubs.push((id.clone(), vec![name.clone(), format!("{kind} {data_type}"), format!("no {side} servant")]));
+                for (_,inode) in &inodes { edges.push((inode.clone(), id.clone(), Vec::new(), true)); }
+                for (_,onode) in &onodes { edges.push((id.clone(), onode.clone(), Vec::new(), true)); }
+                continue;
+            }
+            for (iservant,inode) in &inodes {
+                for (oservant,onode) in &onodes {
+                    let mut label = vec![name.clone(), format!("{kind} {data_type}")];
+                    let sent = channel_metrics.get(&(icluster,iservant.as_str(),name.as_str()));
+                    let received = channel_metrics.get(&(ocluster,oservant.as_str(),name.as_str()));
+                    if sent.is_some() || received.is_some() {
+                        let sent = sent.map(|snapshot| snapshot.messages_sent.to_string()).unwrap_or_else(|| "?".to_string());
+                        let received = received.map(|snapshot| snapshot.messages_received.to_string()).unwrap_or_else(|| "?".to_string());
+                        label.push(format!("{sent} sent, {received} received"));
+                    }
+                    edges.push((inode.clone(), onode.clone(), label, net));
+                }
+            }
+        }
+        Self { clusters, loose, stubs, edges, }
+    }
+
+    /// Render the graph in Graphviz DOT format
+    /// * Output: DOT source
+    pub (crate) fn to_dot(&self) -> String {
+        fn quote(lines: &[String]) -> String {
+            let lines: Vec<_> = lines.iter().map(|line| line.replace('\\', "\\\\").replace('"', "\\\"")).collect();
+            format!("\"{}\"", lines.join("\\n"))
+        }
+        let mut dot = String::from("digraph silx {\n    rankdir=LR;\n    node [shape=box, style=rounded];\n");
+        for (c,(label,servants)) in self.clusters.iter().enumerate() {
+            let _ = writeln!(dot, "    subgraph cluster_{c} {{\n        label={};", quote(std::slice::from_ref(label)));
+            for (id,label) in servants { let _ = writeln!(dot, "        {id} [label={}];", quote(label)); }
+            dot.push_str("    }\n");
+        }
+        for (id,label) in &self.loose { let _ = writeln!(dot, "    {id} [label={}, style=dashed];", quote(label)); }
+        for (id,label) in &self.stubs { let _ = writeln!(dot, "    {id} [label={}, shape=ellipse, style=dashed];", quote(label)); }
+        for (source,target,label,net) in &self.edges {
+            let style = if *net { "style=dashed" } else { "" };
+            if label.is_empty() { let _ = writeln!(dot, "    {source} -> {target} [{style}];"); }
+            else { let _ = writeln!(dot, "    {source} -> {target} [label={}{}{style}];", quote(label), if *net { ", " } else { "" }); }
+        }
+        dot.push_str("}\n");
+        dot
+    }
+
+    /// Render the graph in Mermaid flowchart format
+    /// * Output: Mermaid source
+    pub (crate) fn to_mermaid(&self) -> String {
+        fn quote(lines: &[String]) -> String {
+            let lines: Vec<_> = lines.iter().map(|line| line.replace('"', "#quot;")).collect();
+            format!("\"{}\"", lines.join("<br/>"))
+        }
+        let mut mermaid = String::from("flowchart LR\n");
+        for (c,(label,servants)) in self.clusters.iter().enumerate() {
+            let _ = writeln!(mermaid, "    subgraph cluster_{c} [{}]", quote(std::slice::from_ref(label)));
+            for (id,label) in servants { let _ = writeln!(mermaid, "        {id}[{}]", quote(label)); }
+            mermaid.push_str("    end\n");
+        }
+        for (id,label) in &self.loose { let _ = writeln!(mermaid, "    {id}[{}]", quote(label)); }
+        for (id,label) in &self.stubs { let _ = writeln!(mermaid, "    {id}([{}])", quote(label)); }
+        for (source,target,label,net) in &self.edges {
+            let _ = match (label.is_empty(), *net) {
+                (true, true)   => writeln!(mermaid, "    {source} -.-> {target}"),
+                (true, false)  => writeln!(mermaid, "    {source} --> {target}"),
+                (false, true)  => writeln!(mermaid, "    {source} -. {} .-> {target}", quote(label)),
+                (false, false) => writeln!(mermaid, "    {source} -- {} --> {target}", quote(label)),
+            };
+        }
+        mermaid
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{ collections::BTreeSet, net::{ IpAddr, Ipv4Addr, }, time::Duration, };
+
+    use super::*;
+
+    fn address(port: u16) -> SocketAddr { SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), port) }
+
+    fn outline(servants: &[&str]) -> ClusterOutline {
+        ClusterOutline {
+            net_size: None, servants: servants.iter().map(|name| name.to_string()).collect(), ctrl_ch_capacity: 16,
+            supervision: BTreeMap::new(), admin: None, metrics: None,
+        }
+    }
+
+    fn names(names: &[&str]) -> BTreeSet<String> { names.iter().map(|name| name.to_string()).collect() }
+
+    fn broadcast(cluster: SocketAddr, data_type: FullId, input: &[&str], output: &[&str]) -> Channel {
+        Channel::Broadcast { cluster, max_ping: Duration::from_millis(100), data_type, size: 16, input: names(input), output: names(output), }
+    }
+
+    #[test]
+    /// a channel connected to an undefined servant links to a dashed node outside the clusters
+    fn dangling_channel() {
+        let main = address(8180);
+        let builders = [(main, outline(&["stop"]))].into_iter().collect();
+        let flow = [("Shutdown".to_string(), broadcast(main, FullId::nil(), &["ghost"], &["stop"]))].into_iter().collect();
+        let graph = Graph::new(&main, &builders, &flow, &[]);
+        assert_eq!(graph.loose, [("u0".to_string(), vec!["ghost".to_string(), format!("undefined within {main}")])]);
+        assert_eq!(graph.edges.len(), 1);
+        assert_eq!((graph.edges[0].0.as_str(), graph.edges[0].1.as_str()), ("u0", "c0_s0"));
+        assert!(graph.to_dot().contains("u0 [label=\"ghost\\nundefined within 127.0.0.1:8180\", style=dashed];"));
+    }
+
+    #[test]
+    /// a channel without input servant is a dashed stub linked to its output servants
+    fn unused_channel() {
+        let main = address(8180);
+        let builders = [(main, outline(&["stop"]))].into_iter().collect();
+        let flow = [("Shutdown".to_string(), broadcast(main, FullId::nil(), &[], &["stop"]))].into_iter().collect();
+        let graph = Graph::new(&main, &builders, &flow, &[]);
+        assert_eq!(graph.stubs, [("x0".to_string(), vec!["Shutdown".to_string(), "broadcast 00000000".to_string(), "no input servant".to_string()])]);
+        assert_eq!(graph.edges, [("x0".to_string(), "c0_s0".to_string(), Vec::new(), true)]);
+        assert!(graph.to_mermaid().contains("    x0 -.-> c0_s0\n"));
+    }
+
+    #[test]
+    /// servants with the same name within two clusters are distinct nodes
+    fn duplicate_servant() {
+        let (main, slave) = (address(8180), address(8181));
+        let builders = [(main, outline(&["stop"])), (slave, outline(&["stop"]))].into_iter().collect();
+        let graph = Graph::new(&main, &builders, &BTreeMap::new(), &[]);
+        assert_eq!(graph.clusters, [
+            (format!("{main} (main)"), vec![("c0_s0".to_string(), vec!["stop".to_string()])]),
+            (slave.to_string(), vec![("c1_s0".to_string(), vec!["stop".to_string()])]),
+        ]);
+    }
+}
@@ -1,22 +1,49 @@
 use std::{ 
-    collections::{ BTreeMap, BTreeSet, HashMap, HashSet, }, fmt::Debug, net::SocketAddr, path::{Path, PathBuf} 
+    collections::{ BTreeMap, BTreeSet, HashMap, HashSet, }, fmt::{ Debug, Display, }, net::SocketAddr, path::{Path, PathBuf}, sync::Arc, 
 };
 
 use serde::{ Serialize, Deserialize, };
 use fnv::FnvHashMap;
 use tokio::{
-    net::{ TcpStream, TcpListener, }, spawn, sync::mpsc as msc, time::{ sleep, Duration, },
+    net::{ TcpStream, TcpListener, }, sync::mpsc as msc, time::{ sleep, Duration, },
 };
+use tracing::{ debug, error, info, warn, info_span, Instrument, };
+
+/// Runtime updates of the network topology
+pub mod topology;
+use topology::{ RecvUpdates, Topology, TopologyManager, TopologyReceiver, };
+
+/// Admin endpoint of the clusters
+pub mod admin;
+use admin::AdminAddress;
+
+/// Telemetry of the clusters
+pub mod telemetry;
+use telemetry::{ Telemetry, SocketRole, };
+
+/// Static validation of the network definition
+pub mod validate;
+use validate::ValidationReport;
+
+/// Export of the network topology to DOT and Mermaid
+mod graph;
+use telemetry::MetricsSnapshot;
+
+/// Outline of the network definition, loaded without the servant builders
+pub mod outline;
+use outline::StarterOutline;
 
 use crate::{
     shared::{
         utils::{ SendToMaster, ProcessInstance, },
         id_tools::{ IdBuilder, TaskIdGenerator, },
+        trace::spawn,
+        error::SilxError,
     },
     net::SilxProtocols,
     ChannelIdType, ServantIdType,
     structs::cells::{
-        servant::Servant, master::Master,
+        servant::Servant, supervisor::Supervisor, master::{ Master, MasterTopology, },
         ctrl_message::{ RecvFromMaster, SendToServant, RecvFromServant, SetChannel as sch, },
     },
     traits::{
@@ -76,13 +103,13 @@ pub trait ServantBuilder: Send + tool::BoxClone + ServantBuilderParameters {
     ///  * Output: a servant instance or an error
     fn build(&self, servant_id: ServantIdType, servant_name: String, ch_naming: &FnvHashMap<ChannelIdType, String,>, 
         recv_from_master: RecvFromMaster, send_2_master: SendToMaster, task_id: IdBuilder,
-    ) -> Result<Servant,String> {
+    ) -> Result<Servant,SilxError> {
         let named_process = self.build_process(task_id,send_2_master).0;
         let oprocess_cell =  ProcessCell::new(named_process, ch_naming,);
         let max_cycle_time = self.max_cycle_time();
 
         match oprocess_cell {
-            None => Err("Failed to build process cell".to_string()),
+            None => Err(SilxError::config(format!("Failed to build process cell of servant {servant_name}"))),
             Some(pc) => {
                 let processes = Box::new(pc) as Box<dyn HasProcess + Send>;
                 Ok(Servant::new(servant_id, servant_name, processes, max_cycle_time, recv_from_master,))
@@ -94,6 +121,7 @@ pub trait ServantBuilder: Send + tool::BoxClone + ServantBuilderParameters {
 
 pub enum MasterBuilder {}
 impl MasterBuilder {
+    #[allow(clippy::too_many_arguments)]
     fn build(// Nota: Servant naming and id are unique through the responsible cluster only
         cluster_id: SocketAddr,
         server: ChannelServer, // server is stored within master, so as to be active until cluster is dropped
@@ -102,16 +130,43 @@ impl MasterBuilder {
         mut nsend_to_servants: BTreeMap<String,SendToServant>, 
         mut nrecv_from_servants: BTreeMap<String,RecvFromServant>, 
         task_id: IdBuilder,
-    ) -> Result<Master,String> {
+        topology: MasterTopology,
+    ) -> Result<Master,SilxError> {
         // on ne retient que la partie effectivement présente; Sv_naming peut contenir plus de label
         let send_to_servants = sv_naming.iter().filter_map(|(id,name)| nsend_to_servants.remove(name).map(|ch|(*id,ch))).collect();
         // par contre, erreur si des éléments de send_to_servants sont oubliés
-        if !nsend_to_servants.is_empty() { return Err("send_to_servants: some servant id are undefined".to_string()); }
+        if !nsend_to_servants.is_empty() { return Err(SilxError::config("send_to_servants: some servant id are undefined")); }
         let recv_from_servants = sv_naming.iter().filter_map(|(id,name)| nrecv_from_servants.remove(name).map(|ch|(*id,ch))).collect();
-        if !nrecv_from_servants.is_empty() { return Err("recv_from_servants: some servant id are undefined".to_string()); }
+        if !nrecv_from_servants.is_empty() { return Err(SilxError::config("recv_from_servants: some servant id are undefined")); }
         let channels: FnvHashMap<_,_> = sv_naming.iter().filter_map(|(id,name)| nchannels.remove(name).map(|ch|(*id,ch))).collect();
-        if !nchannels.is_empty() { return Err("channel: some servant id are undefined".to_string()); }
-        Ok(Master::new(cluster_id, server, send_to_servants, recv_from_servants, channels, task_id,))
+        if !nchannels.is_empty() { return Err(SilxError::config("channel: some servant id are undefined")); }
+        Ok(Master::new(cluster_id, server, send_to_servants, recv_from_servants, channels, task_id, topology,))
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq,)]
+/// Kind of a channel
+pub enum ChannelKind {
+    Query,
+    Broadcast,
+    Signal,
+    NetQuery,
+    NetBroadcast,
+    NetSignal,
+}
+
+impl ChannelKind {
+    /// Test if the channel connects two clusters
+    /// * Output: true for a net channel
+    pub fn is_net(&self) -> bool { matches!(self, Self::NetQuery | Self::NetBroadcast | Self::NetSignal) }
+}
+
+impl Display for ChannelKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Self::Query => "query", Self::Broadcast => "broadcast", Self::Signal => "signal",
+            Self::NetQuery => "net query", Self::NetBroadcast => "net broadcast", Self::NetSignal => "net signal",
+        })
     }
 }
 
@@ -157,37 +212,213 @@ pub enum Channel {
         input:  (SocketAddr,BTreeSet<String>,),
         output: (SocketAddr,BTreeSet<String>,),
     },
+    NetSignal {
+        max_ping: Duration,
+        data_type: FullId,
+        input:  (SocketAddr,BTreeSet<String>,),
+        output: (SocketAddr,BTreeSet<String>,),
+    },
 }
 
 impl Channel {
     pub fn has_cluster(&self, socket: &SocketAddr) -> bool {
-        use Channel::{Query, Broadcast, Signal, NetQuery, NetBroadcast, };
+        use Channel::{Query, Broadcast, Signal, NetQuery, NetBroadcast, NetSignal, };
         match self {
             Query { cluster, ..} | Broadcast { cluster, .. } | Signal { cluster, .. } => { socket == cluster },
-            NetQuery { input: (icluster,_), output: (ocluster,_), .. } | NetBroadcast { input: (icluster,_), output: (ocluster,_), .. } => {
+            NetQuery { input: (icluster,_), output: (ocluster,_), .. } | NetBroadcast { input: (icluster,_), output: (ocluster,_), .. } |
+                NetSignal { input: (icluster,_), output: (ocluster,_), .. } => {
                 icluster == socket || ocluster == socket
             },
         }
     } 
     pub fn has_input_servant(&self, name: &str) -> bool {
-        use Channel::{Query, Broadcast, Signal, NetQuery, NetBroadcast, };
+        use Channel::{Query, Broadcast, Signal, NetQuery, NetBroadcast, NetSignal, };
         match self {
             Query { input, ..} | Broadcast { input, .. } | Signal { input, .. } | 
-                NetQuery { input: (_,input), .. } | NetBroadcast { input: (_,input), .. } => {
+                NetQuery { input: (_,input), .. } | NetBroadcast { input: (_,input), .. } | NetSignal { input: (_,input), .. } => {
                     input.contains(name)
                 }
         }
     } 
     pub fn has_output_servant(&self, name: &str) -> bool {
-        use Channel::{Query, Broadcast, Signal, NetQuery, NetBroadcast, };
+        use Channel::{Query, Broadcast, Signal, NetQuery, NetBroadcast, NetSignal, };
         match self {
             Query { output, ..} | Broadcast { output, .. } | Signal { output, .. } | 
-                NetQuery { output: (_,output), .. } | NetBroadcast { output: (_,output), .. } => {
+                NetQuery { output: (_,output), .. } | NetBroadcast { output: (_,output), .. } | NetSignal { output: (_,output), .. } => {
                     output.contains(name)
                 },
         }
     } 
     pub fn has_servant(&self, name: &str) -> bool { self.has_input_servant(name) || self.has_output_servant(name) } 
+
+    /// Kind of the channel
+    /// * Output: the kind
+    pub fn kind(&self) -> ChannelKind {
+        match self {
+            Channel::Query { .. } => ChannelKind::Query, Channel::Broadcast { .. } => ChannelKind::Broadcast, Channel::Signal { .. } => ChannelKind::Signal,
+            Channel::NetQuery { .. } => ChannelKind::NetQuery, Channel::NetBroadcast { .. } => ChannelKind::NetBroadcast, Channel::NetSignal { .. } => ChannelKind::NetSignal,
+        }
+    }
+
+    /// Data types of the input and of the output of the channel
+    /// * Output: query type and reply type for a query channel, or data type twice for a broadcast or signal channel
+    pub fn data_types(&self) -> (FullId, FullId) {
+        use Channel::{Query, Broadcast, Signal, NetQuery, NetBroadcast, NetSignal, };
+        match self {
+            Query { query_type, reply_type, .. } | NetQuery { query_type, reply_type, .. } => (*query_type, *reply_type),
+            Broadcast { data_type, .. } | Signal { data_type, .. } | NetBroadcast { data_type, .. } | NetSignal { data_type, .. } => (*data_type, *data_type),
+        }
+    }
+
+    /// Input and output sides of the channel
+    /// * Output: cluster and servants names of the input side, then of the output side
+    pub fn sides(&self) -> [(SocketAddr, &BTreeSet<String>); 2] {
+        use Channel::{Query, Broadcast, Signal, NetQuery, NetBroadcast, NetSignal, };
+        match self {
+            Query { cluster, input, output, .. } | Broadcast { cluster, input, output, .. } | Signal { cluster, input, output, .. } => {
+                [(*cluster, input), (*cluster, output)]
+            },
+            NetQuery { input, output, .. } | NetBroadcast { input, output, .. } | NetSignal { input, output, .. } => {
+                [(input.0, &input.1), (output.0, &output.1)]
+            },
+        }
+    }
+
+    /// Build the settings of the channel for the servants of a cluster
+    /// * `id: ChannelIdType` : id of the channel
+    /// * `cluster_id: &SocketAddr` : address of the cluster
+    /// * `server: &mut ChannelServer` : channel server of the cluster
+    /// * `clients: &mut HashMap<SocketAddr, ChannelClient>` : channel clients of the cluster
+    /// * Output: the names of the servants of the cluster connected to the channel, with their channel setting, or an error
+    pub (crate) async fn settings(&self, id: ChannelIdType, cluster_id: &SocketAddr, server: &mut ChannelServer, 
+                                    clients: &mut HashMap<SocketAddr, ChannelClient>,) -> Result<Vec<(String,sch)>,SilxError> {
+        let mut settings = Vec::new();
+        match self {
+            Channel::Query { size, input, output, max_ping, query_type, reply_type, .. } => {
+                let (sender,receiver) = if let Some(size) = size { ArchQuery::bounded::<(),()>(*size) } else { ArchQuery::unbounded::<(),()>() };
+                let sender = sender.inner(); let receiver = receiver.inner();
+                for iname in input {
+                    let sender = sender.clone();
+                    settings.push((iname.clone(), sch::QuerySender { max_ping: *max_ping, query_type: *query_type, reply_type: *reply_type, sender, }));
+                }
+                for oname in output {
+                    let receiver = receiver.clone();
+                    settings.push((oname.clone(), sch::QueryReceiver { max_ping: *max_ping, query_type: *query_type, reply_type: *reply_type, receiver }));
+                }
+            },
+            Channel::Broadcast { size, input, output, max_ping, data_type, .. } => {
+                let (sender,receiver) = ArchBroadcast::channel::<()>(*size).await;
+                let sender = sender.inner(); let receiver = receiver.inner();
+                for iname in input {
+                    let sender = sender.clone();
+                    settings.push((iname.clone(), sch::BroadcastSender { max_ping: *max_ping, data_type: *data_type, sender, }));
+                }
+                for oname in output {
+                    let receiver = receiver.clone();
+                    settings.push((oname.clone(), sch::BroadcastReceiver { max_ping: *max_ping, data_type: *data_type, receiver }));
+                }          
+            },
+            Channel::Signal { input, output, max_ping, data_type, .. } => {
+                let (sender,receiver) = ArchSignal::channel::<()>();
+                let sender = sender.inner(); let receiver = receiver.inner();
+                for iname in input {
+                    let sender = sender.clone();
+                    settings.push((iname.clone(), sch::SignalSender { max_ping: *max_ping, data_type: *data_type, sender, }));
+                }
+                for oname in output {
+                    let receiver = receiver.clone();
+                    settings.push((oname.clone(), sch::SignalReceiver { max_ping: *max_ping, data_type: *data_type, receiver }));
+                }
+            },
+            Channel::NetQuery {
+                size,
+                max_ping,
+                query_type,
+                reply_type,
+                input:  (icluster,in_names,),
+                output: (ocluster,out_names,),
+            } => {
+                if icluster == cluster_id && !in_names.is_empty() {
+                    let client = clients.get_mut(ocluster).ok_or_else(|| SilxError::config(format!("no connection from cluster {cluster_id} to cluster {ocluster}")))?;
+                    let sender = client.sender_query::<(),()>(id, *size,).await.inner();    
+                    for iname in in_names {
+                        let sender = sender.clone();
+                        settings.push((iname.clone(), sch::NetQuerySender { max_ping: *max_ping, query_type: *query_type, reply_type: *reply_type, sender, }));
+                    }    
+                }
+                if ocluster == cluster_id && !out_names.is_empty() {
+                    let receiver = server.receiver_query::<(),()>(id, *size,).await.inner();
+                    for oname in out_names {
+                        let receiver = receiver.clone();
+                        settings.push((oname.clone(), sch::NetQueryReceiver { max_ping: *max_ping, query_type: *query_type, reply_type: *reply_type, receiver }));
+                    }
+                }
+            },
+            Channel::NetBroadcast {
+                size,
+                max_ping,
+                data_type,            
+                input:  (icluster,in_names,),
+                output: (ocluster,out_names,),
+            } => {
+                if icluster == cluster_id && !in_names.is_empty() {
+                    let client = clients.get_mut(ocluster).ok_or_else(|| SilxError::config(format!("no connection from cluster {cluster_id} to cluster {ocluster}")))?;
+                    let sender = client.sender_broadcast::<()>(id, *size,).await.inner();    
+                    for iname in in_names {
+                        let sender = sender.clone();
+                        settings.push((iname.clone(), sch::NetBroadcastSender { max_ping: *max_ping, data_type: *data_type, sender, }));
+                    }
+                }
+                if ocluster == cluster_id && !out_names.is_empty() {
+                    let receiver = server.receiver_broadcast::<()>(id, *size,).await.inner();
+                    for oname in out_names {
+                        let receiver = receiver.clone();
+                        settings.push((oname.clone(), sch::NetBroadcastReceiver { max_ping: *max_ping, data_type: *data_type, receiver }));
+                    }
+                }
+            },
+            Channel::NetSignal {
+                max_ping,
+                data_type,            
+                input:  (icluster,in_names,),
+                output: (ocluster,out_names,),
+            } => {
+                if icluster == cluster_id && !in_names.is_empty() {
+                    let client = clients.get_mut(ocluster).ok_or_else(|| SilxError::config(format!("no connection from cluster {cluster_id} to cluster {ocluster}")))?;
+                    let sender = client.sender_signal::<()>(id,).await.inner();    
+                    for iname in in_names {
+                        let sender = sender.clone();
+                        settings.push((iname.clone(), sch::NetSignalSender { max_ping: *max_ping, data_type: *data_type, sender, }));
+                    }
+                }
+                if ocluster == cluster_id && !out_names.is_empty() {
+                    let receiver = server.receiver_signal::<()>(id,).await.inner();
+                    for oname in out_names {
+                        let receiver = receiver.clone();
+                        settings.push((oname.clone(), sch::NetSignalReceiver { max_ping: *max_ping, data_type: *data_type, receiver }));
+                    }
+                }
+            },
+        }
+        Ok(settings)
+    }
+}
+
+#[derive(Clone, Copy, Serialize, Deserialize, Debug, Default, PartialEq,)]
+/// Supervision strategy of a servant
+/// * The strategy is applied when the servant fails, i.e. when its process, or the processing of a query or of a signal, panics
+pub enum Supervision {
+    /// The failed servant is stopped, while the other servants of the cluster continue
+    Stop,
+    /// The failed servant is rebuilt from its builder and its channels are set again
+    /// * If the servant has already been restarted `max_restarts` times within duration `within`, the failure is escalated
+    Restart { 
+        max_restarts: usize, 
+        within: Duration, 
+    },
+    /// The failure is escalated to the master, which then shuts the cluster down
+    #[default]
+    Escalate,
 }
 
 #[derive(Clone,Serialize, Deserialize, Debug,)]
@@ -196,25 +427,37 @@ pub struct FiledClusterBuilder {
     pub net_size: Option<usize>,
     pub named_servants: BTreeMap<String,Filed<Box<dyn ServantBuilder>>>,
     pub ctrl_ch_capacity: usize,
+    /// Supervision strategies of the servants; servants which are not listed are supervised with `Supervision::Escalate`
+    #[serde(default)]
+    pub supervision: BTreeMap<String,Supervision>,
+    /// Address of the admin endpoint of the cluster; no admin endpoint is started if undefined
+    #[serde(default)]
+    pub admin: Option<AdminAddress>,
+    /// Socket address of the HTTP endpoint serving the metrics of the cluster in Prometheus text format; no metrics endpoint is started if undefined
+    #[serde(default)]
+    pub metrics: Option<SocketAddr>,
     // A généraliser en mpmc et network
 }
 impl Filable for FiledClusterBuilder {
     type Unfiled = ClusterBuilder;
 
-    fn load<P: AsRef<Path>,>(&mut self, path: P,) -> Result<bool,String> {
+    fn load<P: AsRef<Path>,>(&mut self, path: P,) -> Result<bool,SilxError> {
         let path = path.as_ref();
         let mut updated = false;
         for (_,fserv) in self.named_servants.iter_mut() { updated |= fserv.load(path)?; }
         Ok(updated)
     }
 
-    fn unload(&mut self, opath: Option<&Path>,) -> Result<Self::Unfiled,String,> {
+    fn unload(&mut self, opath: Option<&Path>,) -> Result<Self::Unfiled,SilxError,> {
         let mut named_servants = vec![];
         for (rname,fserv) in self.named_servants.iter_mut() { named_servants.push((rname.clone(),fserv.unload(opath.clone())?)); }
         let named_servants = named_servants.into_iter().collect();
         let net_size = self.net_size;
         let ctrl_ch_capacity = self.ctrl_ch_capacity;
-        Ok(ClusterBuilder { net_size, named_servants, ctrl_ch_capacity, })
+        let supervision = self.supervision.clone();
+        let admin = self.admin.clone();
+        let metrics = self.metrics;
+        Ok(ClusterBuilder { net_size, named_servants, ctrl_ch_capacity, supervision, admin, metrics, })
     }
 }
 
@@ -224,6 +467,15 @@ pub struct ClusterBuilder {
     pub net_size: Option<usize>,
     pub named_servants: BTreeMap<String,Box<dyn ServantBuilder>>,
     pub ctrl_ch_capacity: usize,
+    /// Supervision strategies of the servants; servants which are not listed are supervised with `Supervision::Escalate`
+    #[serde(default)]
+    pub supervision: BTreeMap<String,Supervision>,
+    /// Address of the admin endpoint of the cluster; no admin endpoint is started if undefined
+    #[serde(default)]
+    pub admin: Option<AdminAddress>,
+    /// Socket address of the HTTP endpoint serving the metrics of the cluster in Prometheus text format; no metrics endpoint is started if undefined
+    #[serde(default)]
+    pub metrics: Option<SocketAddr>,
 }
 
 #[derive(Clone,Serialize, Deserialize, Debug,)]
@@ -251,7 +503,7 @@ pub enum FiledStarter {
 impl Filable for FiledStarter {
     type Unfiled = Starter;
 
-    fn load<P: AsRef<Path>,>(&mut self, path: P,) -> Result<bool,String> {
+    fn load<P: AsRef<Path>,>(&mut self, path: P,) -> Result<bool,SilxError> {
         let mut updated = false;
         let path = path.as_ref();
         if let Self::Main { builders, flow, .. } = self {
@@ -261,7 +513,7 @@ impl Filable for FiledStarter {
         Ok(updated)
     }
 
-    fn unload(&mut self, opath: Option<&Path>,) -> Result<Self::Unfiled,String,> {
+    fn unload(&mut self, opath: Option<&Path>,) -> Result<Self::Unfiled,SilxError,> {
         Ok(match self {
             Self::Listener { main: m, this: t, } => Starter::Listener { main: *m, this: *t, },
             Self::Main { builders, flow, main: m } => {
@@ -306,7 +558,7 @@ pub enum Starter {
 impl Starter {
     pub (crate) async fn build_all(main: &SocketAddr, 
         mut builders: BTreeMap<SocketAddr, ClusterBuilder>, flow: BTreeMap<String, Channel>,
-    ) -> Result<(FnvHashMap<ChannelIdType,(String,Channel)>,ClusterBuilder,), String> {
+    ) -> Result<(FnvHashMap<ChannelIdType,(String,Channel)>,ClusterBuilder,FnvHashMap<ChannelIdType,(String,Channel)>,BTreeMap<SocketAddr,TcpStream>,), SilxError> {
         let mut chan_id = 0;
         let named_flow: FnvHashMap<ChannelIdType, (String,Channel)> = flow.into_iter().map(move |named| { 
             let kv = (chan_id,named); chan_id += 1; kv
@@ -314,16 +566,16 @@ impl Starter {
         let main_id_name_flow: FnvHashMap<ChannelIdType, (String,Channel),> = named_flow.iter().filter(|(_,(_,ref rc))|rc.has_cluster(main))
             .map(|(&i,rc)| (i, rc.clone())).collect();
         let main_builder = if let Some(b) = builders.remove(main) { b } else { 
-            return Err(format!("Main cluster builder at address {} is undefined", main))
+            return Err(SilxError::config(format!("Main cluster builder at address {} is undefined", main)))
         };
         // building and running initilisation server:
-        let listener = TcpListener::bind(main).await.expect(&format!("Starter::build_all -> Failed to listen on {}", main));
+        let listener = TcpListener::bind(main).await.map_err(|e| SilxError::io(format!("Starter::build_all -> Failed to listen on {}", main), e))?;
         let mut sockets = Vec::new();
         while !builders.is_empty() {
             let named_flow = named_flow.clone();
-            let (mut socket, _) = listener.accept().await.expect(&format!("Starter::build_all -> Failed while listening on {}", main));
+            let (mut socket, _) = listener.accept().await.map_err(|e| SilxError::io(format!("Starter::build_all -> Failed while listening on {}", main), e))?;
             let socket_addr = match SilxProtocols::pop_socket(&mut socket).await { // commencer par obtenir l'identifiant
-                Ok(soa) => soa, Err(e) => return Err(format!("Starter::build_all - pop_socket failure -> {e}")),
+                Ok(soa) => soa, Err(e) => return Err(SilxError::protocol("Starter::build_all - pop_socket failure").with_source(e)),
             };
             if let Some(builder) = builders.remove(&socket_addr) {
                 let (mut reader, mut writer) = socket.split();
@@ -331,42 +583,45 @@ impl Starter {
                     .map(|(&i,rc)| (i, rc.clone())).collect();
                 let flow_n_builder = (sub_id_name_flow,builder);
                 let yaml: String = match serde_yaml::to_string(&flow_n_builder) {
-                    Ok(s) => s, Err(_) => return Err(format!("Starter::build_all -> Failed to serialize flow and builder")),
+                    Ok(s) => s, Err(e) => return Err(SilxError::config("Starter::build_all -> Failed to serialize flow and builder").with_source(e)),
                 };
                 let command = match SilxProtocols::pop_string(&mut reader).await {
                     Ok(s)                    => s,
-                    Err(_)                   => return Err(format!("Starter::build_all - pop_string failure  -> Failed to read command bytes")),
+                    Err(e)                   => return Err(SilxError::protocol("Starter::build_all - pop_string failure  -> Failed to read command bytes").with_source(e)),
                 };
                 if command.as_str() == REQUEST_COMMAND {
-                    if SilxProtocols::push_string(&mut writer, &yaml,).await.is_err() {
-                        return Err(format!("Starter::build_all - push_string failure  -> Failed to write Yaml sting"));
+                    if let Err(e) = SilxProtocols::push_string(&mut writer, &yaml,).await {
+                        return Err(SilxError::protocol("Starter::build_all - push_string failure  -> Failed to write Yaml sting").with_source(e));
                     }
-                } else { return Err(format!("Unknown command!")); }
+                } else { return Err(SilxError::protocol(format!("Unknown command {}", command.trim_end()))); }
             };
-            sockets.push(socket);
+            sockets.push((socket_addr,socket));
         }
-        for mut socket in sockets { // envoi du signal ready!
-            if SilxProtocols::push_string(&mut socket, READY_COMMAND).await.is_err() {
-                return Err(format!("Starter::build_all - push_string failure  -> Failed to send ready signal"));
+        let mut links = BTreeMap::new(); // sockets are kept for runtime updates of the topology
+        for (socket_addr, mut socket) in sockets { // envoi du signal ready!
+            if let Err(e) = SilxProtocols::push_string(&mut socket, READY_COMMAND).await {
+                return Err(SilxError::protocol("Starter::build_all - push_string failure  -> Failed to send ready signal").with_source(e));
             }    
+            links.insert(socket_addr, socket);
         }
-        Ok((main_id_name_flow, main_builder,))
+        Ok((main_id_name_flow, main_builder, named_flow, links,))
     }
 
     // instrumental function
     async fn build_server_clients(
         this: SocketAddr, rid_name_flow: &FnvHashMap<ChannelIdType, (String,Channel,),>, net_size: Option<usize>
-    ) -> Result<(ChannelServer, HashMap<SocketAddr, ChannelClient>),String> {
+    ) -> Result<(ChannelServer, HashMap<SocketAddr, ChannelClient>),SilxError> {
         let mut server = ChannelServer::bind(this).await?; // build channel server of the cluster
         let mut server_of_clients = HashSet::new(); // contains the address of all clients to be accepted by 'this' server
         let mut client_of_servers = HashSet::new(); // contains the address of all servers to connect by 'this' client
         for (_,(_,ref chan)) in rid_name_flow { // populate input_clients and output_clients
             match chan {
-                Channel::NetQuery {  input:  (ina,_,), output: (outa,_,), .. } | Channel::NetBroadcast { input:  (ina,_,), output: (outa,_,), .. } => {
+                Channel::NetQuery {  input:  (ina,_,), output: (outa,_,), .. } | Channel::NetBroadcast { input:  (ina,_,), output: (outa,_,), .. } |
+                        Channel::NetSignal { input:  (ina,_,), output: (outa,_,), .. } => {
                     match (ina == &this, outa == &this) {
                         (true,false,) => { client_of_servers.insert(outa.clone()); },
                         (false,true,) => { server_of_clients.insert(ina.clone()); },
-                        _ => panic!("Unexpected case"),
+                        _ => return Err(SilxError::config(format!("Starter::build_server_clients -> net channel does not connect cluster {this} to another cluster"))),
                     }
                 },
                 _ => (),
@@ -388,81 +643,132 @@ impl Starter {
             sleep(Duration::from_millis(100)).await; // sleep so as to avoid simultaneous
         }
         let server = match handle_accept.await { // wait until handle stop, and get back the server
-            Ok(s) => s, Err(_) => return Err(format!("Failed to get back server from handle")),
+            Ok(s) => s, Err(e) => return Err(SilxError::protocol("Failed to get back server from handle").with_source(e)),
         };
         Ok((server,clients))
     }
 
-    async fn listen_init(main: SocketAddr, this: SocketAddr, ) -> Result<(FnvHashMap<ChannelIdType, (String,Channel,),>, ClusterBuilder,), String> {
-        #[cfg(feature = "verbose2")]
-        println!("{this}: try to connect {main}");
+    async fn listen_init(main: SocketAddr, this: SocketAddr, ) -> Result<(FnvHashMap<ChannelIdType, (String,Channel,),>, ClusterBuilder, TcpStream,), SilxError> {
+        debug!(main = %main, "try to connect main cluster");
         let mut socket = match TcpStream::connect(&main).await {
             Ok(socket)  => socket,
-            Err(e)           => { return Err(format!("Starter::listen_init -> failed to connect to main address {main} => {e}")); },
+            Err(e)           => { return Err(SilxError::io(format!("Starter::listen_init -> failed to connect to main address {main}"), e)); },
         };
-        #[cfg(feature = "verbose2")]
-        println!("{this}: Listening connection established");
+        debug!(main = %main, "listening connection established");
         let (mut reader, mut writer) = socket.split();
-        if SilxProtocols::push_socket(&mut writer, &this).await.is_err() {
-            panic!("Starter::listen_init - push_socket -> failed to send identifier");
-        }
-        if SilxProtocols::push_string(&mut writer, REQUEST_COMMAND).await.is_err() {
-            panic!("Starter::listen_init - push_string -> failed to request yaml");
-        }
+        SilxProtocols::push_socket(&mut writer, &this).await
+            .map_err(|e| SilxError::protocol("Starter::listen_init - push_socket -> failed to send identifier").with_source(e))?;
+        SilxProtocols::push_string(&mut writer, REQUEST_COMMAND).await
+            .map_err(|e| SilxError::protocol("Starter::listen_init - push_string -> failed to request yaml").with_source(e))?;
 
         let yaml_str = match SilxProtocols::pop_string(&mut reader).await {
             Ok(s)  => s,
-            Err(e) => return Err(format!("Starter::listen_init - pop_string failure -> {e}")), 
+            Err(e) => return Err(SilxError::protocol("Starter::listen_init - pop_string failure").with_source(e)), 
         };
         let (id_name_flow,builder): (FnvHashMap<ChannelIdType, (String,Channel,),>, ClusterBuilder,) = match serde_yaml::from_str(&yaml_str) {
             Ok(builder) => builder,
             Err(e)      => {
-                println!("Error: {:?}",e);
-                return Err(format!("Failed to unserialize"))
+                error!("failed to unserialize cluster builder -> {e:?}");
+                return Err(SilxError::config("Failed to unserialize").with_source(e))
             },
         };
         let ready = match SilxProtocols::pop_string(&mut reader).await {
             Ok(s)  => s,
-            Err(e) => return Err(format!("Starter::listen_init - pop_string failure -> {e}")), 
+            Err(e) => return Err(SilxError::protocol("Starter::listen_init - pop_string failure").with_source(e)), 
         };
-        if ready.as_str() != READY_COMMAND { return Err(format!("Fail to receive READY signal")) }
-        Ok((id_name_flow,builder))
+        if ready.as_str() != READY_COMMAND { return Err(SilxError::protocol("Fail to receive READY signal")) }
+        Ok((id_name_flow,builder,socket))
     }
 
-    pub (crate) async fn listen(main: SocketAddr, this: SocketAddr, ) -> Result<(), String> {
-        let (id_name_flow,builder) = Self::listen_init(main, this).await?;
+    pub (crate) async fn listen(main: SocketAddr, this: SocketAddr, ) -> Result<(), SilxError> {
+        let (id_name_flow,builder,socket) = Self::listen_init(main, this).await?;
         //
         let net_size = builder.net_size;
         let (server,clients) = Self::build_server_clients(this, &id_name_flow, net_size).await?;
-        let (master,servants) = builder.build_cluster(this, server, clients, id_name_flow).await?;
-        ClusterBuilder::run_cluster(master, servants,).await;
-        Ok(())
+        let (send_updates, recv_updates) = msc::channel(builder.ctrl_ch_capacity);
+        let handle_relay = spawn(topology::relay_updates(socket, send_updates)); // relay topology updates from main
+        let (master,supervisors) = builder.build_cluster(this, server, clients, id_name_flow, recv_updates, None).await?;
+        let result = ClusterBuilder::run_cluster(master, supervisors,).await;
+        handle_relay.abort(); let _ = handle_relay.await;
+        result
     }
 
     /// Run the starter
     /// * Output: nothing or error
-    pub async fn run(self) -> Result<(), String> {
+    pub async fn run(self) -> Result<(), SilxError> {
         match self {
-            Self::Listener { main, this, }       => Self::listen(main, this,).await,
-            Self::Main { main, builders, flow, } => { 
-                let (id_name_flow, main_builder,) = Self::build_all(&main, builders, flow,).await?; // build all cluster; binding on main is necessary
-                let net_size = main_builder.net_size;
-                //
-                let (server,clients) = Self::build_server_clients(main, &id_name_flow, net_size).await?;
-                let (master,servants) = main_builder.build_cluster(main, server, clients, id_name_flow).await?;
-                ClusterBuilder::run_cluster(master, servants,).await;
-                Ok(())
-            },
+            Self::Listener { main, this, }       => Self::listen(main, this,).instrument(info_span!("cluster", cluster = %this)).await,
+            Self::Main { main, builders, flow, } => Self::run_main(main, builders, flow, None).instrument(info_span!("cluster", cluster = %main)).await,
+        }
+    }
+
+    // run the main cluster; the topology requests are processed by the main cluster, which also shuts the network down on admin request 
+    async fn run_main(main: SocketAddr, builders: BTreeMap<SocketAddr, ClusterBuilder>, flow: BTreeMap<String, Channel>, 
+                        receiver: Option<TopologyReceiver>,) -> Result<(), SilxError> {
+        let report = validate::validate(&main, &builders, &flow);
+        for warning in report.warnings() { warn!("validation: {warning}"); }
+        report.into_result()?; // nothing is bound if the network is not valid
+        let servants = builders.iter().flat_map(|(&cluster,builder)| builder.named_servants.iter().map(move |(name,servant)| {
+            (name.clone(),(cluster,validate::signature(servant.as_ref())))
+        })).collect();
+        let (id_name_flow, main_builder, named_flow, links,) = Self::build_all(&main, builders, flow,).await?; // build all cluster; binding on main is necessary
+        let net_size = main_builder.net_size;
+        //
+        let (server,clients) = Self::build_server_clients(main, &id_name_flow, net_size).await?;
+        let (send_updates, recv_updates) = msc::channel(main_builder.ctrl_ch_capacity);
+        let named_flow = named_flow.into_iter().map(|(id,(name,channel))| (name,(id,channel))).collect();
+        let manager = TopologyManager::new(main, named_flow, servants, send_updates, links);
+        let (network, network_receiver) = Topology::new(main_builder.ctrl_ch_capacity);
+        let handle_manager = spawn(manager.run(network_receiver));
+        let handle_forward = receiver.map(|receiver| spawn(network.clone().forward(receiver))); // user requests
+        let (master,supervisors) = main_builder.build_cluster(main, server, clients, id_name_flow, recv_updates, Some(network)).await?;
+        let result = ClusterBuilder::run_cluster(master, supervisors,).await;
+        if let Some(handle_forward) = handle_forward { handle_forward.abort(); let _ = handle_forward.await; }
+        handle_manager.abort(); let _ = handle_manager.await; // links are closed before returning
+        result
+    }
+
+    /// Run the main starter and process the topology requests at runtime
+    /// * `receiver: TopologyReceiver` : receiver of the topology requests, as built by `Topology::new(...)`
+    /// * Output: nothing or error
+    pub async fn run_with_topology(self, receiver: TopologyReceiver) -> Result<(), SilxError> {
+        match self {
+            Self::Listener { .. } => Err(SilxError::config("Topology requests are processed by the main starter only")),
+            Self::Main { main, builders, flow, } => Self::run_main(main, builders, flow, Some(receiver)).instrument(info_span!("cluster", cluster = %main)).await,
+        }
+    }
+
+    /// Validate the network definition statically, i.e. without binding any socket nor starting any servant
+    /// * The data types of the channels are checked against the signatures of the connected processes
+    /// * Dangling and unused channels, duplicate servant names, missing cluster builders and unused processes are reported
+    /// * The validation is also done by the main starter before running; a listener starter has nothing to validate
+    /// * Output: validation report
+    pub fn validate(&self) -> ValidationReport {
+        match self {
+            Self::Listener { .. } => ValidationReport::default(),
+            Self::Main { main, builders, flow, } => validate::validate(main, builders, flow),
         }
     }
 
+    /// Export the network topology in Graphviz DOT format
+    /// * Clusters are subgraphs, servants are nodes and channels are edges labelled with their name, kind and data type (short type hash code)
+    /// * `metrics: &[MetricsSnapshot]` : metrics snapshots of the running clusters (e.g. from `AdminClient::metrics()`) for annotating the servants and channels; may be empty
+    /// * Output: DOT source or error, if the starter is a listener
+    pub fn to_dot(&self, metrics: &[MetricsSnapshot]) -> Result<String,SilxError> { StarterOutline::from(self).to_dot(metrics) }
+
+    /// Export the network topology in Mermaid flowchart format
+    /// * Clusters are subgraphs, servants are nodes and channels are edges labelled with their name, kind and data type (short type hash code)
+    /// * `metrics: &[MetricsSnapshot]` : metrics snapshots of the running clusters (e.g. from `AdminClient::metrics()`) for annotating the servants and channels; may be empty
+    /// * Output: Mermaid source or error, if the starter is a listener
+    pub fn to_mermaid(&self, metrics: &[MetricsSnapshot]) -> Result<String,SilxError> { StarterOutline::from(self).to_mermaid(metrics) }
+
     /// Load starter from saved files
     /// * `starter_path: P` : starter file name
     /// * `dir_path: Q` : directory of network saved files
     /// * `P` : type of path
     /// * `Q` : type of path
     /// * Output: Starter or error
-    pub fn load<P,Q>(starter_path: P, dir_path: Q) -> Result<Self,String> where P: AsRef<Path>, Q: AsRef<Path> {
+    pub fn load<P,Q>(starter_path: P, dir_path: Q) -> Result<Self,SilxError> where P: AsRef<Path>, Q: AsRef<Path> {
         let mut unloaded = RecFiled::<FiledStarter>::new_unloaded(starter_path);
         let save_dir = PathBuf::from(dir_path.as_ref());
         unloaded.load(&save_dir)?;
@@ -472,8 +778,12 @@ impl Starter {
 
 impl ClusterBuilder {
     pub (crate) async fn build_cluster(self, cluster_id: SocketAddr, mut server: ChannelServer, mut clients: HashMap<SocketAddr, ChannelClient>, 
-                                     id_name_flow: FnvHashMap<ChannelIdType,(String,Channel)>) -> Result<(Master,Vec<Servant>),String> {
+                                     id_name_flow: FnvHashMap<ChannelIdType,(String,Channel)>, recv_updates: RecvUpdates, network: Option<Topology>,
+    ) -> Result<(Master,Vec<Supervisor>),SilxError> {
         let ctrl_ch_capacity = self.ctrl_ch_capacity;
+        if let Some(name) = self.supervision.keys().find(|name| !self.named_servants.contains_key(*name)) {
+            return Err(SilxError::config(format!("supervision is defined for unknown servant {name}")));
+        }
         // recupération de l'identification des canaux / trié par servant
         let ch_naming: BTreeMap<String,FnvHashMap<ChannelIdType, String,>> = self.named_servants.iter().map(|(serv_st,_)| {
             let sel_id_name_flow = id_name_flow.iter().filter(
@@ -484,6 +794,8 @@ impl ClusterBuilder {
        
         // création de l'identifer de tâche
         let task_id = TaskIdGenerator::new();
+        // création du registre des métriques
+        let telemetry = Arc::new(Telemetry::default());
         //création de l'identification des servant
         let mut sid: ServantIdType = 0;
         let sv_naming:FnvHashMap<ServantIdType, String,> = self.named_servants.iter().map(|(st,_)| {
@@ -500,18 +812,21 @@ impl ClusterBuilder {
             ((st.to_string(),sender,),(st.to_string(),receiver,))
         } ).unzip();
 
-        // création des servants
-        let mut servants : Vec<Servant> = Vec::new();
+        // création des servants et de leurs superviseurs
+        let mut supervisors : Vec<Supervisor> = Vec::new();
         for (&servant_id,rname,) in &sv_naming {
             let rbuilder = self.named_servants.get(rname).expect("unexpected error: missing servant builder");
             let recv_from_master = match nrecv_from_master.remove(rname) {
-                Some(r) => r, None => return Err(format!("failed to get recv_from_master for servant {}", rname)),
+                Some(r) => r, None => return Err(SilxError::config(format!("failed to get recv_from_master for servant {}", rname))),
             };
             let send_2_master = match nsend_2_master.remove(rname) {
-                Some(s) => s, None => return Err(format!("failed to get send_2_master for servant {}", rname)),
+                Some(s) => s, None => return Err(SilxError::config(format!("failed to get send_2_master for servant {}", rname))),
             };
-            let servant = rbuilder.build(servant_id, rname.clone(), ch_naming.get(rname).expect("unexpected error"), recv_from_master, send_2_master, task_id.clone())?;
-            servants.push(servant);
+            let supervision = self.supervision.get(rname).copied().unwrap_or_default();
+            let sv_ch_naming = ch_naming.get(rname).expect("unexpected error").clone();
+            let supervisor = Supervisor::new(servant_id, rname.clone(), rbuilder.clone(), sv_ch_naming, supervision, 
+                ctrl_ch_capacity, recv_from_master, send_2_master, task_id.clone(), telemetry.clone()).await?;
+            supervisors.push(supervisor);
         };
         // création du maître
         let mut nchannels: BTreeMap<String, Vec<(ChannelIdType, sch)>> = BTreeMap::new(); // clef =  noms de servant du cluster
@@ -522,141 +837,49 @@ impl ClusterBuilder {
                 Channel::NetQuery { output: (ocluster,out_names,), .. } if ocluster == &cluster_id => (out_names, None), 
                 Channel::NetBroadcast { input: (icluster,in_names,), .. } if icluster == &cluster_id => (in_names, None),
                 Channel::NetBroadcast { output: (ocluster,out_names,), .. } if ocluster == &cluster_id => (out_names, None),
-                _ => panic!("Unexpected case!"),
+                Channel::NetSignal { input: (icluster,in_names,), .. } if icluster == &cluster_id => (in_names, None),
+                Channel::NetSignal { output: (ocluster,out_names,), .. } if ocluster == &cluster_id => (out_names, None),
+                _ => return Err(SilxError::config(format!("ClusterBuilder::build_cluster -> channel does not operate within cluster {cluster_id}"))),
             };
             for name in first { if !nchannels.contains_key(name) { nchannels.insert(name.to_string(), Vec::new()); } }
             if let Some(second) = second {
                 for name in second { if !nchannels.contains_key(name) { nchannels.insert(name.to_string(), Vec::new()); } }    
             }
         }
+        let channel_names = id_name_flow.iter().map(|(id,(name,_))| (*id,name.clone())).collect();
         for (id,(_,chan)) in id_name_flow {
-            match chan {
-                Channel::Query { size, input, output, max_ping, query_type, reply_type, .. } => {
-                    let (sender,receiver) = if let Some(size) = size { ArchQuery::bounded::<(),()>(size) } else { ArchQuery::unbounded::<(),()>() };
-                    let sender = sender.inner(); let receiver = receiver.inner();
-                    for iname in &input {
-                        let query_type = query_type.clone();
-                        let reply_type = reply_type.clone();
-                        let sender = sender.clone();
-                        nchannels.get_mut(iname).expect("Unexpected: missing key").push((id, sch::QuerySender { max_ping, query_type, reply_type, sender, }));
-                    }
-                    for oname in &output {
-                        let query_type = query_type.clone();
-                        let reply_type = reply_type.clone();
-                        let receiver = receiver.clone();
-                        nchannels.get_mut(oname).expect("Unexpected: missing key").push((id, sch::QueryReceiver { max_ping, query_type, reply_type, receiver }));
-                    }
-                },
-                Channel::Broadcast { size, input, output, max_ping, data_type, .. } => {
-                    let (sender,receiver) = ArchBroadcast::channel::<()>(size).await;
-                    let sender = sender.inner(); let receiver = receiver.inner();
-                    for iname in &input {
-                        let data_type = data_type.clone();
-                        let sender = sender.clone();
-                        nchannels.get_mut(iname).expect("Unexpected: missing key").push((id, sch::BroadcastSender { max_ping, data_type, sender, }));
-                    }
-                    for oname in &output {
-                        let data_type = data_type.clone();
-                        let receiver = receiver.clone();
-                        nchannels.get_mut(oname).expect("Unexpected: missing key").push((id, sch::BroadcastReceiver { max_ping, data_type, receiver }));
-                    }          
-                },
-                Channel::Signal { input, output, max_ping, data_type, .. } => {
-                    let (sender,receiver) = ArchSignal::channel::<()>();
-                    let sender = sender.inner(); let receiver = receiver.inner();
-                    for iname in &input {
-                        let data_type = data_type.clone();
-                        let sender = sender.clone();
-                        nchannels.get_mut(iname).expect("Unexpected: missing key").push((id, sch::SignalSender { max_ping, data_type, sender, }));
-                    }
-                    for oname in &output {
-                        let data_type = data_type.clone();
-                        let receiver = receiver.clone();
-                        nchannels.get_mut(oname).expect("Unexpected: missing key").push((id, sch::SignalReceiver { max_ping, data_type, receiver }));
-                    }
-                },
-                Channel::NetQuery {
-                    size,
-                    max_ping,
-                    query_type,
-                    reply_type,
-                    input:  (icluster,in_names,),
-                    output: (ocluster,out_names,),
-                } => {
-                    if icluster == cluster_id {
-                        if !in_names.is_empty() {
-                            let sender = clients.get_mut(&ocluster).expect("Unexpected: client not found").sender_query::<(),()>(id, size,).await.inner();    
-                            for iname in &in_names {
-                                let query_type = query_type.clone();
-                                let reply_type = reply_type.clone();
-                                let sender = sender.clone();
-                                nchannels.get_mut(iname).expect("Unexpected: missing key").push((id, sch::NetQuerySender { max_ping, query_type, reply_type, sender, }));
-                            }    
-                        }
-                    }
-                    if ocluster == cluster_id {
-                        if !out_names.is_empty() {
-                            let receiver = server.receiver_query::<(),()>(id, size,).await.inner();
-                            for oname in &out_names {
-                                let query_type = query_type.clone();
-                                let reply_type = reply_type.clone();
-                                let receiver = receiver.clone();
-                                nchannels.get_mut(oname).expect("Unexpected: missing key").push((id, sch::NetQueryReceiver { max_ping, query_type, reply_type, receiver }));
-                            }
-                        }
-    
-                    }
-                },
-                Channel::NetBroadcast {
-                    size,
-                    max_ping,
-                    data_type,            
-                    input:  (icluster,in_names,),
-                    output: (ocluster,out_names,),
-                } => {
-                    if icluster == cluster_id {
-                        if !in_names.is_empty() {
-                            let sender = clients.get_mut(&ocluster).expect("Unexpected: client not found").sender_broadcast::<()>(id, size,).await.inner();    
-                            for iname in &in_names {
-                                let data_type = data_type.clone();
-                                let sender = sender.clone();
-                                nchannels.get_mut(iname).expect("Unexpected: missing key").push((id, sch::NetBroadcastSender { max_ping, data_type, sender, }));
-                            }
-                        }    
-                    }
-                    if ocluster == cluster_id {
-                        if !out_names.is_empty() {
-                            let receiver = server.receiver_broadcast::<()>(id, size,).await.inner();
-                            for oname in &out_names {
-                                let data_type = data_type.clone();
-                                let receiver = receiver.clone();
-                                nchannels.get_mut(oname).expect("Unexpected: missing key").push((id, sch::NetBroadcastReceiver { max_ping, data_type, receiver }));
-                            }
-                        }
-                    }
-                },
+            for (name,setting) in chan.settings(id, &cluster_id, &mut server, &mut clients).await? {
+                nchannels.get_mut(&name).expect("Unexpected: missing key").push((id, setting));
             }
         }
         // en principe, nchannels ne doit pas contenir de vecteurs vides
-        let master = MasterBuilder::build(cluster_id, server, &sv_naming, nchannels, nsend_to_servants, nrecv_from_servants, task_id.clone())?;
-        #[cfg(feature = "verbose1")]
-        println!("cluster {cluster_id} has been built");
-        Ok((master, servants))
+        let servant_ids = sv_naming.iter().map(|(&id,name)| (name.clone(),id)).collect();
+        for (peer,metrics) in server.socket_metrics() { telemetry.socket(SocketRole::Server, peer, metrics); }
+        for client in clients.values() { let (peer,metrics) = client.socket_metrics(); telemetry.socket(SocketRole::Client, peer, metrics); }
+        let admin = self.admin;
+        let metrics = self.metrics;
+        let topology = MasterTopology { recv_updates, clients, servant_ids, channel_names, ctrl_ch_capacity, admin, metrics, telemetry, network, };
+        let master = MasterBuilder::build(cluster_id, server, &sv_naming, nchannels, nsend_to_servants, nrecv_from_servants, task_id.clone(), topology)?;
+        info!("cluster has been built");
+        Ok((master, supervisors))
     }
 
-    pub (crate) async fn run_cluster(master: Master, servants: Vec<Servant>,) { // run the cluster
-        let cluster_id = master.cluster_id;
+    pub (crate) async fn run_cluster(master: Master, supervisors: Vec<Supervisor>,) -> Result<(), SilxError> { // run the cluster
         let mut handles = Vec::new();
-        for servant in servants { handles.push(spawn(servant.run())); }
-        handles.push(spawn(master.run()));
+        for supervisor in supervisors { handles.push(spawn(supervisor.run())); }
+        let handle_master = spawn(master.run());
         for handle in handles { 
             match handle.await {
                 Ok(_) => (),
-                Err(e) => println!("handle.await -> {}", e),
+                Err(e) => error!("handle.await -> {}", e),
             } 
         }
-        #[cfg(feature = "verbose1")]
-        println!("cluster {cluster_id} is ended");
+        let result = match handle_master.await {
+            Ok(result) => result,
+            Err(e) => Err(SilxError::protocol("ClusterBuilder::run_cluster - master has failed").with_source(e)),
+        };
+        info!("cluster is ended");
+        result
     }
 }
 
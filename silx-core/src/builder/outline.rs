@@ -0,0 +1,174 @@
+use std::{ collections::BTreeMap, net::SocketAddr, path::{ Path, PathBuf, }, };
+
+use serde::{ Serialize, Deserialize, };
+
+use crate::{
+    shared::error::SilxError,
+    traits::filable::{ Filable, Filed, RecFiled, },
+    builder::{ Channel, ClusterBuilder, Starter, Supervision, admin::AdminAddress, graph::Graph, telemetry::MetricsSnapshot, },
+};
+
+#[derive(Clone, Debug,)]
+/// Outline of a cluster definition: the cluster builder without its servant builders
+pub struct ClusterOutline {
+    /// Maximal size of the network messages, if bounded
+    pub net_size: Option<usize>,
+    /// Names of the servants of the cluster
+    pub servants: Vec<String>,
+    /// Capacity of the control channels
+    pub ctrl_ch_capacity: usize,
+    /// Supervision strategies of the servants
+    pub supervision: BTreeMap<String,Supervision>,
+    /// Address of the admin endpoint of the cluster
+    pub admin: Option<AdminAddress>,
+    /// Socket address of the HTTP endpoint serving the metrics of the cluster
+    pub metrics: Option<SocketAddr>,
+}
+
+impl From<&ClusterBuilder> for ClusterOutline {
+    fn from(builder: &ClusterBuilder) -> Self {
+        Self {
+            net_size: builder.net_size, servants: builder.named_servants.keys().cloned().collect(), ctrl_ch_capacity: builder.ctrl_ch_capacity,
+            supervision: builder.supervision.clone(), admin: builder.admin.clone(), metrics: builder.metrics,
+        }
+    }
+}
+
+#[derive(Clone, Debug,)]
+/// Outline of a starter: the network definition without the servant builders
+/// * The outline is loaded from the saved files without unserializing the servant builders, so that the servant types need not be linked
+/// * The outline is sufficient for inspecting and exporting the topology, but not for validating or running the network
+pub enum StarterOutline {
+    /// Outline of the main cluster
+    Main {
+        /// Outlines of the clusters definitions with their socket address
+        builders: BTreeMap<SocketAddr,ClusterOutline>,
+        /// List of the definitions of the channels with their names
+        flow: BTreeMap<String,Channel>,
+        /// Socket address of the main cluster
+        main: SocketAddr,
+    },
+    /// Definition of a slave cluster: this cluster will await parameters from the main
+    Listener {
+        /// Socket address of the main cluster
+        main: SocketAddr,
+        /// Socket address of this cluster
+        this: SocketAddr,
+    }
+}
+
+impl From<&Starter> for StarterOutline {
+    fn from(starter: &Starter) -> Self {
+        match starter {
+            Starter::Listener { main, this, } => Self::Listener { main: *main, this: *this, },
+            Starter::Main { builders, flow, main, } => Self::Main {
+                builders: builders.iter().map(|(cluster,builder)| (*cluster, builder.into())).collect(), flow: flow.clone(), main: *main,
+            },
+        }
+    }
+}
+
+impl StarterOutline {
+    /// Load the outline of a starter from saved files; the servant builders are not unserialized
+    /// * `starter_path: P` : starter file name
+    /// * `dir_path: Q` : directory of network saved files
+    /// * `P` : type of path
+    /// * `Q` : type of path
+    /// * Output: outline or error
+    pub fn load<P,Q>(starter_path: P, dir_path: Q) -> Result<Self,SilxError> where P: AsRef<Path>, Q: AsRef<Path> {
+        let mut unloaded = RecFiled::<FiledStarterOutline>::new_unloaded(starter_path);
+        let save_dir = PathBuf::from(dir_path.as_ref());
+        unloaded.load(&save_dir)?;
+        unloaded.unload(None)
+    }
+
+    /// Export the network topology in Graphviz DOT format
+    /// * See `Starter::to_dot(...)`
+    /// * `metrics: &[MetricsSnapshot]` : metrics snapshots of the running clusters for annotating the servants and channels; may be empty
+    /// * Output: DOT source or error, if the starter is a listener
+    pub fn to_dot(&self, metrics: &[MetricsSnapshot]) -> Result<String,SilxError> {
+        match self {
+            Self::Listener { .. } => Err(SilxError::config("Topology export is available for the main starter only")),
+            Self::Main { main, builders, flow, } => Ok(Graph::new(main, builders, flow, metrics).to_dot()),
+        }
+    }
+
+    /// Export the network topology in Mermaid flowchart format
+    /// * See `Starter::to_mermaid(...)`
+    /// * `metrics: &[MetricsSnapshot]` : metrics snapshots of the running clusters for annotating the servants and channels; may be empty
+    /// * Output: Mermaid source or error, if the starter is a listener
+    pub fn to_mermaid(&self, metrics: &[MetricsSnapshot]) -> Result<String,SilxError> {
+        match self {
+            Self::Listener { .. } => Err(SilxError::config("Topology export is available for the main starter only")),
+            Self::Main { main, builders, flow, } => Ok(Graph::new(main, builders, flow, metrics).to_mermaid()),
+        }
+    }
+}
+
+// filed cluster builder, whose servant builders are kept as raw yaml values
+#[derive(Clone, Serialize, Deserialize, Debug,)]
+struct FiledClusterOutline {
+    net_size: Option<usize>,
+    named_servants: BTreeMap<String,serde_yaml::Value>,
+    ctrl_ch_capacity: usize,
+    #[serde(default)]
+    supervision: BTreeMap<String,Supervision>,
+    #[serde(default)]
+    admin: Option<AdminAddress>,
+    #[serde(default)]
+    metrics: Option<SocketAddr>,
+}
+
+impl Filable for FiledClusterOutline {
+    type Unfiled = ClusterOutline;
+
+    fn load<P: AsRef<Path>,>(&mut self, _path: P,) -> Result<bool,SilxError> { Ok(false) }
+
+    fn unload(&mut self, _opath: Option<&Path>,) -> Result<Self::Unfiled,SilxError,> {
+        Ok(ClusterOutline {
+            net_size: self.net_size, servants: self.named_servants.keys().cloned().collect(), ctrl_ch_capacity: self.ctrl_ch_capacity,
+            supervision: self.supervision.clone(), admin: self.admin.clone(), metrics: self.metrics,
+        })
+    }
+}
+
+// filed starter, with the same format as `FiledStarter`
+#[derive(Clone, Serialize, Deserialize, Debug,)]
+enum FiledStarterOutline {
+    Main {
+        builders: BTreeMap<SocketAddr,RecFiled<FiledClusterOutline>>,
+        flow: BTreeMap<String,Filed<Channel>>,
+        main: SocketAddr,
+    },
+    Listener {
+        main: SocketAddr,
+        this: SocketAddr,
+    }
+}
+
+impl Filable for FiledStarterOutline {
+    type Unfiled = StarterOutline;
+
+    fn load<P: AsRef<Path>,>(&mut self, path: P,) -> Result<bool,SilxError> {
+        let mut updated = false;
+        let path = path.as_ref();
+        if let Self::Main { builders, flow, .. } = self {
+            for (_,lbuild) in builders.iter_mut() { updated |= lbuild.load(path)? }
+            for (_,lchan) in flow.iter_mut() { updated |= lchan.load(path)?; }
+        }
+        Ok(updated)
+    }
+
+    fn unload(&mut self, opath: Option<&Path>,) -> Result<Self::Unfiled,SilxError,> {
+        Ok(match self {
+            Self::Listener { main, this, } => StarterOutline::Listener { main: *main, this: *this, },
+            Self::Main { builders, flow, main, } => {
+                let mut outlines = BTreeMap::new();
+                for (cluster,fbuild) in builders.iter_mut() { outlines.insert(*cluster, fbuild.unload(opath)?); }
+                let mut channels = BTreeMap::new();
+                for (name,fchan) in flow.iter_mut() { channels.insert(name.clone(), fchan.unload(opath)?); }
+                StarterOutline::Main { builders: outlines, flow: channels, main: *main, }
+            },
+        })
+    }
+}
@@ -0,0 +1,328 @@
+use std::{
+    collections::BTreeMap, fmt::Write, net::SocketAddr, time::Duration,
+    sync::{ Arc, RwLock, atomic::{ AtomicU64, Ordering::Relaxed, }, },
+};
+
+use serde::{ Serialize, Deserialize, };
+use tokio::{ net::{ TcpListener, TcpStream, }, io::{ AsyncReadExt, AsyncWriteExt, }, task::JoinHandle, time::sleep, };
+use tracing::warn;
+
+use crate::shared::{ trace::spawn, error::SilxError, };
+
+#[derive(Default, Debug)]
+/// Counters of a channel of a servant
+pub (crate) struct ChannelMetrics {
+    messages_sent: AtomicU64,
+    messages_received: AtomicU64,
+    bytes_sent: AtomicU64,
+    bytes_received: AtomicU64,
+    dropped: AtomicU64,
+    lagged: AtomicU64,
+    handled: AtomicU64,
+    handling_nanos: AtomicU64,
+    queue: AtomicU64,
+}
+
+impl ChannelMetrics {
+    /// Count a message sent by the servant on the channel
+    pub (crate) fn sent(&self, bytes: usize) {
+        self.messages_sent.fetch_add(1, Relaxed); self.bytes_sent.fetch_add(bytes as u64, Relaxed);
+    }
+    /// Count a message received by the servant on the channel
+    pub (crate) fn received(&self, bytes: usize) {
+        self.messages_received.fetch_add(1, Relaxed); self.bytes_received.fetch_add(bytes as u64, Relaxed);
+    }
+    /// Count a message which has been dropped without ending the channel
+    pub (crate) fn dropped(&self) { self.dropped.fetch_add(1, Relaxed); }
+    /// Count the messages skipped by a lagging broadcast receiver
+    pub (crate) fn lagged(&self, skipped: u64) { self.lagged.fetch_add(skipped, Relaxed); }
+    /// Record the duration of the handling of a message (reply round trip, mapper or assert processing, forwarding)
+    pub (crate) fn handled(&self, elapsed: Duration) {
+        self.handled.fetch_add(1, Relaxed); self.handling_nanos.fetch_add(elapsed.as_nanos() as u64, Relaxed);
+    }
+    /// Record the number of messages waiting in the queue of the channel
+    pub (crate) fn queue(&self, len: usize) { self.queue.store(len as u64, Relaxed); }
+
+    fn snapshot(&self, servant: &str, channel: &str) -> ChannelSnapshot {
+        ChannelSnapshot {
+            servant: servant.to_string(), channel: channel.to_string(),
+            messages_sent: self.messages_sent.load(Relaxed), messages_received: self.messages_received.load(Relaxed),
+            bytes_sent: self.bytes_sent.load(Relaxed), bytes_received: self.bytes_received.load(Relaxed),
+            dropped: self.dropped.load(Relaxed), lagged: self.lagged.load(Relaxed),
+            handled: self.handled.load(Relaxed), handling_seconds: nanos_to_seconds(self.handling_nanos.load(Relaxed)),
+            queue: self.queue.load(Relaxed),
+        }
+    }
+}
+
+#[derive(Default, Debug)]
+/// Counters of the control tasks of a servant
+pub (crate) struct ServantMetrics {
+    tasks: AtomicU64,
+    task_nanos: AtomicU64,
+    timeouts: AtomicU64,
+}
+
+impl ServantMetrics {
+    /// Record the duration of a control task; `timeout` is true when the task has exceeded the max cycle time
+    pub (crate) fn task(&self, elapsed: Duration, timeout: bool) {
+        self.tasks.fetch_add(1, Relaxed); self.task_nanos.fetch_add(elapsed.as_nanos() as u64, Relaxed);
+        if timeout { self.timeouts.fetch_add(1, Relaxed); }
+    }
+
+    fn snapshot(&self, servant: &str) -> ServantSnapshot {
+        ServantSnapshot {
+            servant: servant.to_string(), tasks: self.tasks.load(Relaxed),
+            task_seconds: nanos_to_seconds(self.task_nanos.load(Relaxed)), timeouts: self.timeouts.load(Relaxed),
+        }
+    }
+}
+
+#[derive(Default, Debug)]
+/// Counters of a socket connection between two clusters
+pub (crate) struct SocketMetrics {
+    frames_sent: AtomicU64,
+    frames_received: AtomicU64,
+    bytes_sent: AtomicU64,
+    bytes_received: AtomicU64,
+    errors: AtomicU64,
+}
+
+impl SocketMetrics {
+    /// Count a frame written on the socket
+    pub (crate) fn sent(&self, bytes: usize) {
+        self.frames_sent.fetch_add(1, Relaxed); self.bytes_sent.fetch_add(bytes as u64, Relaxed);
+    }
+    /// Count a frame read from the socket
+    pub (crate) fn received(&self, bytes: usize) {
+        self.frames_received.fetch_add(1, Relaxed); self.bytes_received.fetch_add(bytes as u64, Relaxed);
+    }
+    /// Count a read or write error on the socket
+    pub (crate) fn error(&self) { self.errors.fetch_add(1, Relaxed); }
+
+    /// Snapshot of the counters
+    pub (crate) fn snapshot(&self, role: SocketRole, peer: SocketAddr) -> SocketSnapshot {
+        SocketSnapshot {
+            role, peer,
+            frames_sent: self.frames_sent.load(Relaxed), frames_received: self.frames_received.load(Relaxed),
+            bytes_sent: self.bytes_sent.load(Relaxed), bytes_received: self.bytes_received.load(Relaxed),
+            errors: self.errors.load(Relaxed),
+        }
+    }
+}
+
+#[derive(Default, Debug)]
+/// Registry of the metrics of a cluster
+/// * counters are kept by servant and channel names, so that they survive the restart of a servant
+pub (crate) struct Telemetry {
+    servants: RwLock<BTreeMap<String, Arc<ServantMetrics>>>,
+    channels: RwLock<BTreeMap<(String,String), Arc<ChannelMetrics>>>,
+    sockets: RwLock<BTreeMap<(SocketRole,SocketAddr), Arc<SocketMetrics>>>,
+}
+
+impl Telemetry {
+    /// Get the counters of a servant; counters are created if needed
+    pub (crate) fn servant(&self, servant: &str) -> Arc<ServantMetrics> {
+        self.servants.write().expect("unexpected error: poisoned lock").entry(servant.to_string()).or_default().clone()
+    }
+
+    /// Get the counters of a channel of a servant; counters are created if needed
+    pub (crate) fn channel(&self, servant: &str, channel: &str) -> Arc<ChannelMetrics> {
+        self.channels.write().expect("unexpected error: poisoned lock").entry((servant.to_string(),channel.to_string())).or_default().clone()
+    }
+
+    /// Register the counters of a socket connection; the counters of a previous connection with same role and peer are replaced
+    pub (crate) fn socket(&self, role: SocketRole, peer: SocketAddr, metrics: Arc<SocketMetrics>) {
+        self.sockets.write().expect("unexpected error: poisoned lock").insert((role, peer), metrics);
+    }
+
+    /// Snapshot of all the metrics of the cluster
+    pub (crate) fn snapshot(&self, cluster: SocketAddr) -> MetricsSnapshot {
+        let servants = self.servants.read().expect("unexpected error: poisoned lock").iter()
+            .map(|(servant,metrics)| metrics.snapshot(servant)).collect();
+        let channels = self.channels.read().expect("unexpected error: poisoned lock").iter()
+            .map(|((servant,channel),metrics)| metrics.snapshot(servant, channel)).collect();
+        let sockets = self.sockets.read().expect("unexpected error: poisoned lock").iter()
+            .map(|((role,peer),metrics)| metrics.snapshot(*role, *peer)).collect();
+        MetricsSnapshot { cluster, servants, channels, sockets, }
+    }
+}
+
+#[derive(Clone, Copy, Serialize, Deserialize, Debug, PartialEq, Eq, PartialOrd, Ord,)]
+/// Role of the cluster within a socket connection
+pub enum SocketRole {
+    /// Connection accepted by the channel server of the cluster
+    Server,
+    /// Connection opened by a channel client of the cluster
+    Client,
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug, PartialEq,)]
+/// Snapshot of the metrics of a cluster
+pub struct MetricsSnapshot {
+    /// Socket address of the cluster
+    pub cluster: SocketAddr,
+    /// Metrics of the servants
+    pub servants: Vec<ServantSnapshot>,
+    /// Metrics of the channels of the servants
+    pub channels: Vec<ChannelSnapshot>,
+    /// Metrics of the socket connections with the other clusters
+    pub sockets: Vec<SocketSnapshot>,
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug, PartialEq,)]
+/// Metrics of the control tasks of a servant
+pub struct ServantSnapshot {
+    /// Name of the servant
+    pub servant: String,
+    /// Number of control tasks processed by the servant
+    pub tasks: u64,
+    /// Cumulated duration of the control tasks in seconds
+    pub task_seconds: f64,
+    /// Number of control tasks which have exceeded the max cycle time
+    pub timeouts: u64,
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug, PartialEq,)]
+/// Metrics of a channel of a servant
+pub struct ChannelSnapshot {
+    /// Name of the servant
+    pub servant: String,
+    /// Name of the channel
+    pub channel: String,
+    /// Number of messages sent by the servant on the channel (queries, replies, broadcasts or signals)
+    pub messages_sent: u64,
+    /// Number of messages received by the servant on the channel
+    pub messages_received: u64,
+    /// Number of serialized bytes sent by the servant on the channel
+    pub bytes_sent: u64,
+    /// Number of serialized bytes received by the servant on the channel
+    pub bytes_received: u64,
+    /// Number of messages dropped without ending the channel (e.g. replies to a cancelled query)
+    pub dropped: u64,
+    /// Number of messages skipped by a lagging broadcast receiver
+    pub lagged: u64,
+    /// Number of handled messages
+    pub handled: u64,
+    /// Cumulated handling duration in seconds: reply round trip for query senders, mapper or assert processing for receivers, forwarding otherwise
+    pub handling_seconds: f64,
+    /// Number of messages waiting in the queue of the channel, as seen at the last handled message
+    pub queue: u64,
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug, PartialEq,)]
+/// Metrics of a socket connection between two clusters
+pub struct SocketSnapshot {
+    /// Role of the cluster within the connection
+    pub role: SocketRole,
+    /// Socket address of the other cluster
+    pub peer: SocketAddr,
+    /// Number of frames written on the socket
+    pub frames_sent: u64,
+    /// Number of frames read from the socket
+    pub frames_received: u64,
+    /// Number of serialized bytes written on the socket
+    pub bytes_sent: u64,
+    /// Number of serialized bytes read from the socket
+    pub bytes_received: u64,
+    /// Number of read or write errors
+    pub errors: u64,
+}
+
+fn nanos_to_seconds(nanos: u64) -> f64 { nanos as f64 * 1e-9 }
+
+// escape a label value of the Prometheus text format
+fn escape(value: &str) -> String { value.replace('\\', "\\\\").replace('"', "\\\"").replace('\n', "\\n") }
+
+// write a metric family of the Prometheus text format
+fn family<T>(out: &mut String, name: &str, kind: &str, help: &str, items: &[T], labels: impl Fn(&T) -> String, value: impl Fn(&T) -> String) {
+    if items.is_empty() { return; }
+    let _ = writeln!(out, "# HELP {name} {help}");
+    let _ = writeln!(out, "# TYPE {name} {kind}");
+    for item in items { let _ = writeln!(out, "{name}{{{}}} {}", labels(item), value(item)); }
+}
+
+impl MetricsSnapshot {
+    /// Render the snapshot in Prometheus text exposition format
+    /// * Output: text of the metrics
+    pub fn to_prometheus(&self) -> String {
+        let cluster = escape(&self.cluster.to_string());
+        let sv = |s: &ServantSnapshot| format!("cluster=\"{cluster}\",servant=\"{}\"", escape(&s.servant));
+        let ch = |c: &ChannelSnapshot| format!("cluster=\"{cluster}\",servant=\"{}\",channel=\"{}\"", escape(&c.servant), escape(&c.channel));
+        let so = |s: &SocketSnapshot| format!(
+            "cluster=\"{cluster}\",role=\"{}\",peer=\"{}\"", match s.role { SocketRole::Server => "server", SocketRole::Client => "client", }, s.peer
+        );
+        let mut out = String::new();
+        let (servants, channels, sockets) = (&self.servants[..], &self.channels[..], &self.sockets[..]);
+        family(&mut out, "silx_servant_tasks_total", "counter", "Control tasks processed by the servant", servants, sv, |s| s.tasks.to_string());
+        family(&mut out, "silx_servant_task_seconds_total", "counter", "Cumulated duration of the control tasks", servants, sv, |s| s.task_seconds.to_string());
+        family(&mut out, "silx_servant_task_timeouts_total", "counter", "Control tasks exceeding the max cycle time", servants, sv, |s| s.timeouts.to_string());
+        family(&mut out, "silx_channel_messages_sent_total", "counter", "Messages sent by the servant on the channel", channels, ch, |c| c.messages_sent.to_string());
+        family(&mut out, "silx_channel_messages_received_total", "counter", "Messages received by the servant on the channel", channels, ch, |c| c.messages_received.to_string());
+        family(&mut out, "silx_channel_bytes_sent_total", "counter", "Serialized bytes sent by the servant on the channel", channels, ch, |c| c.bytes_sent.to_string());
+        family(&mut out, "silx_channel_bytes_received_total", "counter", "Serialized bytes received by the servant on the channel", channels, ch, |c| c.bytes_received.to_string());
+        family(&mut out, "silx_channel_dropped_total", "counter", "Messages dropped without ending the channel", channels, ch, |c| c.dropped.to_string());
+        family(&mut out, "silx_channel_lagged_total", "counter", "Messages skipped by a lagging broadcast receiver", channels, ch, |c| c.lagged.to_string());
+        family(&mut out, "silx_channel_handled_total", "counter", "Messages handled on the channel", channels, ch, |c| c.handled.to_string());
+        family(&mut out, "silx_channel_handling_seconds_total", "counter", "Cumulated handling duration of the messages", channels, ch, |c| c.handling_seconds.to_string());
+        family(&mut out, "silx_channel_queue_length", "gauge", "Messages waiting in the queue of the channel", channels, ch, |c| c.queue.to_string());
+        family(&mut out, "silx_socket_frames_sent_total", "counter", "Frames written on the socket", sockets, so, |s| s.frames_sent.to_string());
+        family(&mut out, "silx_socket_frames_received_total", "counter", "Frames read from the socket", sockets, so, |s| s.frames_received.to_string());
+        family(&mut out, "silx_socket_bytes_sent_total", "counter", "Serialized bytes written on the socket", sockets, so, |s| s.bytes_sent.to_string());
+        family(&mut out, "silx_socket_bytes_received_total", "counter", "Serialized bytes read from the socket", sockets, so, |s| s.bytes_received.to_string());
+        family(&mut out, "silx_socket_errors_total", "counter", "Read or write errors on the socket", sockets, so, |s| s.errors.to_string());
+        out
+    }
+}
+
+/// Maximal duration for reading the head of an HTTP request
+const HTTP_READ_TIMEOUT: Duration = Duration::from_secs(5);
+
+// reply to an HTTP request with the metrics of the cluster
+async fn serve(mut stream: TcpStream, cluster: SocketAddr, telemetry: Arc<Telemetry>,) {
+    let mut head = Vec::new();
+    let mut buf = [0u8; 1024];
+    let read_head = async {
+        while !head.windows(4).any(|w| w == b"\r\n\r\n") {
+            match stream.read(&mut buf).await {
+                Ok(0) | Err(_) => return false,
+                Ok(n) => { head.extend_from_slice(&buf[..n]); if head.len() > 8192 { break; } },
+            }
+        }
+        true
+    };
+    if !matches!(tokio::time::timeout(HTTP_READ_TIMEOUT, read_head).await, Ok(true)) { return; } // closed, failed or too slow connection
+    let head = String::from_utf8_lossy(&head);
+    let mut request = head.lines().next().unwrap_or("").split_whitespace();
+    let method = request.next();
+    let path = request.next().map(|target| target.split('?').next().unwrap_or(target)); // the query string is ignored
+    let (status, body) = match (method, path) {
+        (Some("GET"), Some("/metrics")) => ("200 OK", telemetry.snapshot(cluster).to_prometheus()),
+        (Some("GET"), _)                => ("404 Not Found", "not found\n".to_string()),
+        _                               => ("405 Method Not Allowed", "method not allowed\n".to_string()),
+    };
+    let response = format!(
+        "HTTP/1.1 {status}\r\nContent-Type: text/plain; version=0.0.4\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}", body.len()
+    );
+    let _ = stream.write_all(response.as_bytes()).await;
+    let _ = stream.shutdown().await;
+}
+
+// delay before accepting again after a failed accept (e.g. too many open files)
+pub (crate) const ACCEPT_BACKOFF: Duration = Duration::from_millis(100);
+
+/// Serve the metrics of a cluster in Prometheus text format at `http://<address>/metrics`
+/// * Output: handle of the listener or error
+pub (crate) async fn listen_metrics(address: &SocketAddr, cluster: SocketAddr, telemetry: Arc<Telemetry>,) -> Result<JoinHandle<()>,SilxError> {
+    let listener = match TcpListener::bind(address).await {
+        Ok(listener) => listener, Err(e) => return Err(SilxError::io(format!("Metrics: failed to bind {address}"), e)),
+    };
+    Ok(spawn(async move {
+        loop {
+            match listener.accept().await {
+                Ok((stream,_)) => { spawn(serve(stream, cluster, telemetry.clone())); },
+                Err(e) => { warn!("Metrics: failed to accept connection -> {e}"); sleep(ACCEPT_BACKOFF).await; },
+            }
+        }
+    }))
+}
@@ -0,0 +1,323 @@
+use std::{ collections::{ BTreeMap, HashMap, }, net::SocketAddr, time::Duration, };
+
+use serde::{ Serialize, Deserialize, };
+use tokio::{ net::TcpStream, sync::{ mpsc as msc, oneshot as osh, }, time::timeout, };
+use tracing::error;
+
+use crate::{
+    ChannelIdType,
+    net::SilxProtocols,
+    builder::{ Channel, ServantBuilder, Supervision, validate, },
+    shared::error::SilxError,
+    traits::procell::ProcessSignature,
+};
+
+/// Maximal duration of the round-trip of a topology update to a cluster
+pub const UPDATE_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Channel sender of the topology updates to the master of a cluster
+pub (crate) type SendUpdates = msc::Sender<(ClusterUpdate, osh::Sender<Result<(),String>>)>;
+/// Channel receiver of the topology updates by the master of a cluster
+pub (crate) type RecvUpdates = msc::Receiver<(ClusterUpdate, osh::Sender<Result<(),String>>)>;
+
+#[derive(Serialize, Deserialize, Debug,)]
+/// Update of the topology of a cluster, as sent by the main cluster
+pub (crate) enum ClusterUpdate {
+    /// Add and start a new servant
+    AddServant {
+        name: String,
+        builder: Box<dyn ServantBuilder>,
+        supervision: Supervision,
+    },
+    /// Add the cluster part of a channel, and turn it on
+    AddChannel {
+        id: ChannelIdType,
+        name: String,
+        channel: Channel,
+    },
+    /// Turn off and kill the cluster part of a channel
+    KillChannel {
+        id: ChannelIdType,
+    },
+    /// Gracefully shut the cluster down; the update is acknowledged before the shutdown
+    Shutdown,
+}
+
+/// Request for updating the topology of the network
+enum TopologyRequest {
+    AddServant {
+        cluster: SocketAddr,
+        name: String,
+        builder: Box<dyn ServantBuilder>,
+        supervision: Supervision,
+    },
+    AddChannel {
+        name: String,
+        channel: Channel,
+    },
+    KillChannel {
+        name: String,
+    },
+    RewireChannel {
+        name: String,
+        channel: Channel,
+    },
+    Shutdown,
+}
+
+#[derive(Clone)]
+/// Handle for updating the topology of the network at runtime
+/// * The requests are processed by the main cluster, which is started by `Starter::run_with_topology(...)`
+/// * Channels between two clusters can only be added if these clusters were already connected by a channel at startup
+pub struct Topology {
+    sender: msc::Sender<(TopologyRequest, osh::Sender<Result<(),SilxError>>)>,
+}
+
+/// Receiver of the topology requests, to be given to `Starter::run_with_topology(...)`
+pub struct TopologyReceiver(msc::Receiver<(TopologyRequest, osh::Sender<Result<(),SilxError>>)>);
+
+impl Topology {
+    /// Build a topology handle and its receiver
+    /// * `capacity: usize` : capacity of the requests channel
+    /// * Output: topology handle and receiver
+    pub fn new(capacity: usize) -> (Self, TopologyReceiver) {
+        let (sender, receiver) = msc::channel(capacity);
+        (Self { sender }, TopologyReceiver(receiver))
+    }
+
+    async fn request(&self, request: TopologyRequest) -> Result<(),SilxError> {
+        let (sender, receiver) = osh::channel();
+        if self.sender.send((request, sender)).await.is_err() { return Err(SilxError::protocol("Topology: main cluster is not running")); }
+        match receiver.await {
+            Ok(result) => result,
+            Err(_) => Err(SilxError::protocol("Topology: no reply from main cluster")),
+        }
+    }
+
+    /// Add and start a new servant within a cluster
+    /// * `cluster: SocketAddr` : socket address of the cluster
+    /// * `name: String` : name of the servant; this name should be unique within the network
+    /// * `builder: B` : builder of the servant
+    /// * `supervision: Supervision` : supervision strategy of the servant
+    /// * `B` : type of servant builder
+    /// * Output: nothing or error
+    pub async fn add_servant<B>(&self, cluster: SocketAddr, name: String, builder: B, supervision: Supervision,) -> Result<(),SilxError>
+            where B: 'static + ServantBuilder {
+        let builder = Box::new(builder);
+        self.request(TopologyRequest::AddServant { cluster, name, builder, supervision, }).await
+    }
+
+    /// Add a named channel and turn it on
+    /// * `name: String` : name of the channel; this name should not be used by another channel
+    /// * `channel: Channel` : definition of the channel
+    /// * Output: nothing or error
+    pub async fn add_channel(&self, name: String, channel: Channel,) -> Result<(),SilxError> {
+        self.request(TopologyRequest::AddChannel { name, channel, }).await
+    }
+
+    /// Turn off and kill a named channel
+    /// * `name: String` : name of the channel
+    /// * Output: nothing or error
+    pub async fn kill_channel(&self, name: String,) -> Result<(),SilxError> {
+        self.request(TopologyRequest::KillChannel { name, }).await
+    }
+
+    /// Rewire a named channel to other servants: the channel is killed, and then added again with its new definition
+    /// * If the new definition cannot be added, the old definition of the channel is restored
+    /// * `name: String` : name of the channel
+    /// * `channel: Channel` : new definition of the channel
+    /// * Output: nothing or error
+    pub async fn rewire_channel(&self, name: String, channel: Channel,) -> Result<(),SilxError> {
+        self.request(TopologyRequest::RewireChannel { name, channel, }).await
+    }
+
+    /// Gracefully shut the whole network down: the listener clusters are shut down first, and then the main cluster
+    /// * Output: nothing or error
+    pub async fn shutdown(&self,) -> Result<(),SilxError> {
+        self.request(TopologyRequest::Shutdown).await
+    }
+
+    // forward the requests of another receiver to this handle
+    pub (crate) async fn forward(self, TopologyReceiver(mut receiver): TopologyReceiver) {
+        while let Some(request) = receiver.recv().await { if self.sender.send(request).await.is_err() { break; } }
+    }
+}
+
+/// Manager of the topology of the network, running within the main cluster
+pub (crate) struct TopologyManager {
+    main: SocketAddr,
+    flow: BTreeMap<String,(ChannelIdType,Channel)>,
+    // cluster and processes signatures of the servants
+    servants: BTreeMap<String,(SocketAddr,HashMap<String,ProcessSignature>)>,
+    next_id: ChannelIdType,
+    local: SendUpdates,
+    links: BTreeMap<SocketAddr,TcpStream>,
+}
+
+impl TopologyManager {
+    pub (crate) fn new(main: SocketAddr, flow: BTreeMap<String,(ChannelIdType,Channel)>, servants: BTreeMap<String,(SocketAddr,HashMap<String,ProcessSignature>)>,
+                        local: SendUpdates, links: BTreeMap<SocketAddr,TcpStream>,) -> Self {
+        let next_id = flow.values().map(|(id,_)| id + 1).max().unwrap_or(0);
+        Self { main, flow, servants, next_id, local, links, }
+    }
+
+    // clusters involved in a channel
+    fn clusters(channel: &Channel) -> Vec<SocketAddr> {
+        match channel {
+            Channel::Query { cluster, .. } | Channel::Broadcast { cluster, .. } | Channel::Signal { cluster, .. } => vec![*cluster],
+            Channel::NetQuery { input: (icluster,_), output: (ocluster,_), .. } | Channel::NetBroadcast { input: (icluster,_), output: (ocluster,_), .. } |
+                    Channel::NetSignal { input: (icluster,_), output: (ocluster,_), .. } => {
+                if icluster == ocluster { vec![*icluster] } else { vec![*ocluster, *icluster] }
+            },
+        }
+    }
+
+    // check that the servants of a channel are defined within the proper clusters, with processes of the kind and types of the channel
+    // * the checks are those of the static validation (see `Starter::validate`), so that nothing is sent to the clusters for an invalid channel
+    fn check(&self, name: &str, channel: &Channel) -> Result<(),SilxError> {
+        for ((cluster, servants), expected) in channel.sides().into_iter().zip(validate::expected(channel)) {
+            if servants.is_empty() { return Err(SilxError::config("Topology: channel has no input or no output servant")); }
+            for servant in servants {
+                match self.servants.get(servant) {
+                    Some((servant_cluster,_)) if servant_cluster != &cluster => {
+                        return Err(SilxError::config(format!("Topology: servant {} is within cluster {}, not {}", servant, servant_cluster, cluster)));
+                    },
+                    Some((_,processes)) => if let Some(issue) = validate::check_process(name, servant, processes, &expected) {
+                        return Err(SilxError::config(format!("Topology: {issue}")));
+                    },
+                    None => return Err(SilxError::config(format!("Topology: unknown servant {}", servant))),
+                }
+            }
+        }
+        Ok(())
+    }
+
+    // send an update to a cluster and wait for the result, within `UPDATE_TIMEOUT`
+    // * a link which has timed out is dropped, since a late reply would be taken for the reply of the next update
+    async fn send(&mut self, cluster: &SocketAddr, update: ClusterUpdate,) -> Result<(),SilxError> {
+        let result = if cluster == &self.main {
+            let (sender, receiver) = osh::channel();
+            if self.local.send((update, sender)).await.is_err() { return Err(SilxError::protocol("Topology: main master is not running")); }
+            match timeout(UPDATE_TIMEOUT, receiver).await {
+                Ok(reply) => reply.map_err(|e| SilxError::protocol("Topology: no reply from main master").with_source(e))?,
+                Err(_) => return Err(SilxError::timeout(format!("Topology: no reply from main master within {:?}", UPDATE_TIMEOUT))),
+            }
+        } else {
+            let socket = match self.links.get_mut(cluster) {
+                Some(socket) => socket, None => return Err(SilxError::protocol(format!("Topology: no link to cluster {}", cluster))),
+            };
+            let yaml = match serde_yaml::to_string(&update) {
+                Ok(s) => s, Err(e) => return Err(SilxError::protocol("Topology: failed to serialize update").with_source(e)),
+            };
+            let round_trip = async {
+                SilxProtocols::push_string(&mut *socket, &yaml).await?;
+                SilxProtocols::pop_string(&mut *socket).await
+            };
+            let reply = match timeout(UPDATE_TIMEOUT, round_trip).await {
+                Ok(reply) => reply?,
+                Err(_) => {
+                    self.links.remove(cluster);
+                    return Err(SilxError::timeout(format!("Topology: no reply from cluster {} within {:?}", cluster, UPDATE_TIMEOUT)));
+                },
+            };
+            match serde_yaml::from_str::<Result<(),String>>(&reply) {
+                Ok(result) => result, Err(e) => return Err(SilxError::protocol("Topology: failed to unserialize reply").with_source(e)),
+            }
+        };
+        result.map_err(SilxError::remote)
+    }
+
+    async fn add_channel(&mut self, name: String, channel: Channel,) -> Result<(),SilxError> {
+        if self.flow.contains_key(&name) { return Err(SilxError::config(format!("Topology: channel {} is already defined", name))); }
+        self.check(&name, &channel)?;
+        let id = self.next_id;
+        self.next_id += 1;
+        let mut done = Vec::new();
+        for cluster in Self::clusters(&channel) {
+            let update = ClusterUpdate::AddChannel { id, name: name.clone(), channel: channel.clone(), };
+            if let Err(e) = self.send(&cluster, update).await {
+                // roll back the clusters which have been updated, and the current one
+                done.push(cluster);
+                for cluster in done { let _ = self.send(&cluster, ClusterUpdate::KillChannel { id, }).await; }
+                return Err(e);
+            }
+            done.push(cluster);
+        }
+        self.flow.insert(name, (id, channel));
+        Ok(())
+    }
+
+    async fn kill_channel(&mut self, name: &str,) -> Result<(),SilxError> {
+        let (id, channel) = match self.flow.remove(name) {
+            Some(ic) => ic, None => return Err(SilxError::config(format!("Topology: unknown channel {}", name))),
+        };
+        for cluster in Self::clusters(&channel) { self.send(&cluster, ClusterUpdate::KillChannel { id, }).await?; }
+        Ok(())
+    }
+
+    async fn apply(&mut self, request: TopologyRequest) -> Result<(),SilxError> {
+        match request {
+            TopologyRequest::AddServant { cluster, name, builder, supervision, } => {
+                if self.servants.contains_key(&name) { return Err(SilxError::config(format!("Topology: servant {} is already defined", name))); }
+                if cluster != self.main && !self.links.contains_key(&cluster) { return Err(SilxError::config(format!("Topology: unknown cluster {}", cluster))); }
+                let processes = validate::signature(builder.as_ref());
+                self.send(&cluster, ClusterUpdate::AddServant { name: name.clone(), builder, supervision, }).await?;
+                self.servants.insert(name, (cluster, processes));
+                Ok(())
+            },
+            TopologyRequest::AddChannel { name, channel, } => self.add_channel(name, channel).await,
+            TopologyRequest::KillChannel { name, } => self.kill_channel(&name).await,
+            TopologyRequest::RewireChannel { name, channel, } => {
+                self.check(&name, &channel)?;
+                let old = match self.flow.get(&name) {
+                    Some((_, old)) => old.clone(), None => return Err(SilxError::config(format!("Topology: unknown channel {}", name))),
+                };
+                self.kill_channel(&name).await?;
+                match self.add_channel(name.clone(), channel).await {
+                    Ok(()) => Ok(()),
+                    Err(e) => match self.add_channel(name, old).await {
+                        // the old definition of the channel is restored
+                        Ok(()) => Err(e),
+                        Err(f) => Err(SilxError::protocol(format!("{e}; failed to restore the old channel")).with_source(f)),
+                    },
+                }
+            },
+            TopologyRequest::Shutdown => {
+                let clusters: Vec<_> = self.links.keys().copied().collect();
+                let mut errors = Vec::new();
+                for cluster in clusters {
+                    if let Err(e) = self.send(&cluster, ClusterUpdate::Shutdown).await { errors.push(format!("{cluster}: {e}")); }
+                }
+                let main = self.main;
+                if let Err(e) = self.send(&main, ClusterUpdate::Shutdown).await { errors.push(format!("{main}: {e}")); }
+                if errors.is_empty() { Ok(()) } else { Err(SilxError::remote(format!("Topology: shutdown failures -> {}", errors.join("; ")))) }
+            },
+        }
+    }
+
+    /// Process the topology requests until the topology handles are dropped
+    pub (crate) async fn run(mut self, TopologyReceiver(mut receiver): TopologyReceiver) {
+        while let Some((request, reply)) = receiver.recv().await {
+            let result = self.apply(request).await;
+            if reply.send(result).is_err() { error!("topology: failed to reply to request"); }
+        }
+    }
+}
+
+/// Relay the topology updates received from the main cluster to the master of a listener cluster
+pub (crate) async fn relay_updates(mut socket: TcpStream, send_updates: SendUpdates,) {
+    while let Ok(yaml) = SilxProtocols::pop_string(&mut socket).await {
+        let result = match serde_yaml::from_str::<ClusterUpdate>(&yaml) {
+            Err(e) => Err(format!("Topology: failed to unserialize update -> {e}")),
+            Ok(update) => {
+                let (sender, receiver) = osh::channel();
+                if send_updates.send((update, sender)).await.is_err() { Err("Topology: master is not running".to_string()) }
+                else { receiver.await.unwrap_or_else(|_| Err("Topology: no reply from master".to_string())) }
+            },
+        };
+        let yaml = match serde_yaml::to_string(&result) {
+            Ok(s) => s, Err(e) => { error!("topology: failed to serialize reply -> {e}"); break; },
+        };
+        if SilxProtocols::push_string(&mut socket, &yaml).await.is_err() { break; }
+    }
+}
@@ -0,0 +1,288 @@
+use std::{ collections::{ BTreeMap, BTreeSet, HashMap, }, fmt::{ Display, Formatter, Result as FmtResult, }, net::SocketAddr, };
+
+use tokio::sync::mpsc as msc;
+
+use crate::{
+    shared::{ id_tools::TaskIdGenerator, error::SilxError, },
+    traits::{ procell::ProcessSignature as ps, FullId, },
+    builder::{ Channel, ChannelKind, ClusterBuilder, ServantBuilder, },
+};
+
+#[derive(Clone, Debug, PartialEq,)]
+/// Issue found by the static validation of a starter
+/// * All issues are errors, except `UnusedProcess` which is a warning
+pub enum ValidationIssue {
+    /// The main cluster has no builder
+    MissingMainBuilder { main: SocketAddr, },
+    /// A channel operates within a cluster which has no builder
+    MissingClusterBuilder { channel: String, cluster: SocketAddr, },
+    /// A servant name is defined within several clusters
+    DuplicateServant { servant: String, clusters: Vec<SocketAddr>, },
+    /// A channel is connected to a servant which is not defined within the cluster of the connection (dangling channel)
+    UnknownServant { channel: String, cluster: SocketAddr, servant: String, },
+    /// A channel has no input servant or no output servant (unused channel)
+    UnusedChannel { channel: String, side: &'static str, },
+    /// A servant connected to a channel has no process for this channel
+    MissingProcess { channel: String, servant: String, expected: &'static str, },
+    /// A servant connected to a channel has a process of the wrong kind for this channel
+    WrongProcess { channel: String, servant: String, expected: &'static str, found: &'static str, },
+    /// The process of a servant does not have the data type of the channel
+    TypeMismatch { channel: String, servant: String, expected: FullId, found: FullId, },
+    /// The process of a servant is not connected to any channel (warning)
+    UnusedProcess { servant: String, process: String, },
+    /// A supervision strategy is defined for an unknown servant
+    UnknownSupervised { cluster: SocketAddr, servant: String, },
+}
+
+impl ValidationIssue {
+    /// Test if the issue is an error; otherwise, it is a warning
+    /// * Output: true if the issue is an error
+    pub fn is_error(&self) -> bool { !matches!(self, Self::UnusedProcess { .. }) }
+}
+
+impl Display for ValidationIssue {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        match self {
+            Self::MissingMainBuilder { main, } => write!(f, "main cluster {main} has no builder"),
+            Self::MissingClusterBuilder { channel, cluster, } => write!(f, "channel {channel}: cluster {cluster} has no builder"),
+            Self::DuplicateServant { servant, clusters, } => {
+                let clusters: Vec<_> = clusters.iter().map(|cluster| cluster.to_string()).collect();
+                write!(f, "servant {servant} is defined within several clusters: {}", clusters.join(", "))
+            },
+            Self::UnknownServant { channel, cluster, servant, } => write!(f, "channel {channel}: servant {servant} is not defined within cluster {cluster}"),
+            Self::UnusedChannel { channel, side, } => write!(f, "channel {channel} has no {side} servant"),
+            Self::MissingProcess { channel, servant, expected, } => write!(f, "channel {channel}: servant {servant} has no {expected} process for this channel"),
+            Self::WrongProcess { channel, servant, expected, found, } => {
+                write!(f, "channel {channel}: servant {servant} has a {found} process instead of a {expected} process")
+            },
+            Self::TypeMismatch { channel, servant, expected, found, } => {
+                write!(f, "channel {channel}: process of servant {servant} has type {found}, while channel has type {expected}")
+            },
+            Self::UnusedProcess { servant, process, } => write!(f, "servant {servant}: process {process} is not connected to any channel"),
+            Self::UnknownSupervised { cluster, servant, } => write!(f, "cluster {cluster}: supervision is defined for unknown servant {servant}"),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default,)]
+/// Report of the static validation of a starter
+pub struct ValidationReport {
+    issues: Vec<ValidationIssue>,
+}
+
+impl ValidationReport {
+    /// All the issues found by the validation
+    /// * Output: the issues
+    pub fn issues(&self) -> &[ValidationIssue] { &self.issues }
+
+    /// Issues which are errors
+    /// * Output: iterator of the errors
+    pub fn errors(&self) -> impl Iterator<Item = &ValidationIssue> { self.issues.iter().filter(|issue| issue.is_error()) }
+
+    /// Issues which are warnings
+    /// * Output: iterator of the warnings
+    pub fn warnings(&self) -> impl Iterator<Item = &ValidationIssue> { self.issues.iter().filter(|issue| !issue.is_error()) }
+
+    /// Test if the network is valid, i.e. if no error has been found
+    /// * Output: true if the network is valid
+    pub fn is_valid(&self) -> bool { self.errors().next().is_none() }
+
+    /// Convert the report into a result
+    /// * Output: nothing if the network is valid, or a configuration error listing the issues
+    pub fn into_result(self) -> Result<(),SilxError> {
+        if self.is_valid() { Ok(()) } else { Err(SilxError::config(format!("Validation failed:\n{self}"))) }
+    }
+}
+
+impl Display for ValidationReport {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        if self.issues.is_empty() { return write!(f, "no issue found"); }
+        for (i,issue) in self.issues.iter().enumerate() {
+            if i > 0 { writeln!(f)?; }
+            write!(f, "{}: {issue}", if issue.is_error() { "error" } else { "warning" })?;
+        }
+        Ok(())
+    }
+}
+
+// signature of the processes of a servant
+pub (crate) fn signature(builder: &dyn ServantBuilder) -> HashMap<String, ps> {
+    let (sender, _receiver) = msc::channel(1);
+    builder.build_process(TaskIdGenerator::new(), sender).0.signature().clone()
+}
+
+// kind and data types of a process signature
+fn describe(signature: &ps) -> (&'static str, Vec<FullId>) {
+    match signature {
+        ps::Query { in_type, out_type, } => ("query", vec![*in_type, *out_type]),
+        ps::Reply { in_type, out_type, } => ("reply", vec![*in_type, *out_type]),
+        ps::Emit { in_type, }            => ("emit", vec![*in_type]),
+        ps::Read { out_type, }           => ("read", vec![*out_type]),
+        ps::RefRead { out_type, }        => ("ref read", vec![*out_type]),
+    }
+}
+
+// signatures expected for the processes of the input and output sides of a channel
+pub (crate) fn expected(channel: &Channel) -> [ps; 2] {
+    let (in_type, out_type) = channel.data_types();
+    match channel.kind() {
+        ChannelKind::Query | ChannelKind::NetQuery => [ps::Query { in_type, out_type, }, ps::Reply { in_type, out_type, }],
+        ChannelKind::Broadcast | ChannelKind::NetBroadcast => [ps::Emit { in_type, }, ps::Read { out_type, }],
+        ChannelKind::Signal | ChannelKind::NetSignal => [ps::Emit { in_type, }, ps::RefRead { out_type, }],
+    }
+}
+
+// check the process of a servant connected to a side of a channel
+// * `expected: &ps` : signature expected for the side of the channel (see `expected(...)`)
+pub (crate) fn check_process(channel: &str, servant: &str, processes: &HashMap<String, ps>, expected: &ps) -> Option<ValidationIssue> {
+    let (expected, expected_types) = describe(expected);
+    match processes.get(channel).map(describe) {
+        None => Some(ValidationIssue::MissingProcess { channel: channel.to_string(), servant: servant.to_string(), expected, }),
+        Some((found,_)) if found != expected => {
+            Some(ValidationIssue::WrongProcess { channel: channel.to_string(), servant: servant.to_string(), expected, found, })
+        },
+        Some((_,found_types)) => expected_types.iter().zip(found_types).find(|(e,f)| *e != f).map(|(expected,found)| {
+            ValidationIssue::TypeMismatch { channel: channel.to_string(), servant: servant.to_string(), expected: *expected, found, }
+        }),
+    }
+}
+
+/// Validate a network definition without building it
+/// * `main: &SocketAddr` : socket address of the main cluster
+/// * `builders: &BTreeMap<SocketAddr,ClusterBuilder>` : clusters definitions
+/// * `flow: &BTreeMap<String,Channel>` : channels definitions
+/// * Output: validation report
+pub (crate) fn validate(main: &SocketAddr, builders: &BTreeMap<SocketAddr,ClusterBuilder>, flow: &BTreeMap<String,Channel>,) -> ValidationReport {
+    let mut issues = Vec::new();
+    if !builders.contains_key(main) { issues.push(ValidationIssue::MissingMainBuilder { main: *main, }); }
+    // servants signatures by cluster
+    let signatures: BTreeMap<SocketAddr,BTreeMap<&String,HashMap<String, ps>>> = builders.iter().map(|(cluster,builder)| {
+        (*cluster, builder.named_servants.iter().map(|(name,servant)| (name, signature(servant.as_ref()))).collect())
+    }).collect();
+    let mut clusters_by_servant: BTreeMap<&String,Vec<SocketAddr>> = BTreeMap::new();
+    for (cluster,builder) in builders {
+        for name in builder.named_servants.keys() { clusters_by_servant.entry(name).or_default().push(*cluster); }
+        for name in builder.supervision.keys().filter(|name| !builder.named_servants.contains_key(*name)) {
+            issues.push(ValidationIssue::UnknownSupervised { cluster: *cluster, servant: name.clone(), });
+        }
+    }
+    for (servant,clusters) in clusters_by_servant.into_iter().filter(|(_,clusters)| clusters.len() > 1) {
+        issues.push(ValidationIssue::DuplicateServant { servant: servant.clone(), clusters, });
+    }
+    // channels connections
+    let mut used: BTreeSet<(&String,&String)> = BTreeSet::new();
+    for (name,channel) in flow {
+        for ((side, (cluster, servant_names)), expected) in ["input", "output"].into_iter().zip(channel.sides()).zip(expected(channel)) {
+            if servant_names.is_empty() { issues.push(ValidationIssue::UnusedChannel { channel: name.clone(), side, }); }
+            let Some(servants) = signatures.get(&cluster) else {
+                issues.push(ValidationIssue::MissingClusterBuilder { channel: name.clone(), cluster, });
+                continue;
+            };
+            for servant in servant_names {
+                let Some(processes) = servants.get(servant) else {
+                    issues.push(ValidationIssue::UnknownServant { channel: name.clone(), cluster, servant: servant.clone(), });
+                    continue;
+                };
+                used.insert((servant, name));
+                issues.extend(check_process(name, servant, processes, &expected));
+            }
+        }
+    }
+    // processes not connected to any channel
+    for servants in signatures.values() {
+        for (servant,processes) in servants {
+            let mut unused: Vec<_> = processes.keys().filter(|process| !used.contains(&(*servant,*process))).collect();
+            unused.sort();
+            issues.extend(unused.into_iter().map(|process| ValidationIssue::UnusedProcess { servant: (*servant).clone(), process: process.clone(), }));
+        }
+    }
+    ValidationReport { issues, }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{ net::{ IpAddr, Ipv4Addr, }, time::Duration, };
+
+    use super::*;
+    use crate::shared::servants::shutdown::ShutdownBuilder;
+
+    fn address(port: u16) -> SocketAddr { SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), port) }
+
+    // cluster builder with shutdown servants reading channel `Shutdown`
+    fn builder(servants: &[&str]) -> ClusterBuilder {
+        let named_servants = servants.iter().map(|name| {
+            (name.to_string(), Box::new(ShutdownBuilder::new("Shutdown".to_string())) as Box<dyn ServantBuilder>)
+        }).collect();
+        ClusterBuilder { net_size: None, named_servants, ctrl_ch_capacity: 16, supervision: BTreeMap::new(), admin: None, metrics: None, }
+    }
+
+    // data type read by the shutdown servant
+    fn wake_type() -> FullId {
+        match signature(&ShutdownBuilder::new("Shutdown".to_string())).get("Shutdown") {
+            Some(ps::Read { out_type, }) => *out_type,
+            _ => panic!("shutdown servant should read channel Shutdown"),
+        }
+    }
+
+    fn broadcast(cluster: SocketAddr, data_type: FullId, input: &[&str], output: &[&str]) -> BTreeMap<String,Channel> {
+        let names = |names: &[&str]| names.iter().map(|name| name.to_string()).collect();
+        let channel = Channel::Broadcast { cluster, max_ping: Duration::from_millis(100), data_type, size: 16, input: names(input), output: names(output), };
+        [("Shutdown".to_string(), channel)].into_iter().collect()
+    }
+
+    #[test]
+    /// an unconnected process is only a warning
+    fn unused_process_is_warning() {
+        let main = address(8180);
+        let builders = [(main, builder(&["stop"]))].into_iter().collect();
+        let report = validate(&main, &builders, &BTreeMap::new());
+        assert!(report.is_valid());
+        assert_eq!(report.issues(), [ValidationIssue::UnusedProcess { servant: "stop".to_string(), process: "Shutdown".to_string(), }]);
+    }
+
+    #[test]
+    /// a channel connected to an undefined servant is dangling
+    fn dangling_channel() {
+        let main = address(8180);
+        let builders = [(main, builder(&["stop"]))].into_iter().collect();
+        let report = validate(&main, &builders, &broadcast(main, wake_type(), &["ghost"], &["stop"]));
+        assert!(!report.is_valid());
+        assert_eq!(report.issues(), [
+            ValidationIssue::UnknownServant { channel: "Shutdown".to_string(), cluster: main, servant: "ghost".to_string(), }
+        ]);
+    }
+
+    #[test]
+    /// a channel without input servant is unused
+    fn unused_channel() {
+        let main = address(8180);
+        let builders = [(main, builder(&["stop"]))].into_iter().collect();
+        let report = validate(&main, &builders, &broadcast(main, wake_type(), &[], &["stop"]));
+        assert!(!report.is_valid());
+        assert_eq!(report.issues(), [ValidationIssue::UnusedChannel { channel: "Shutdown".to_string(), side: "input", }]);
+    }
+
+    #[test]
+    /// a servant name defined within two clusters is a duplicate
+    fn duplicate_servant() {
+        let (main, slave) = (address(8180), address(8181));
+        let builders = [(main, builder(&["stop"])), (slave, builder(&["stop"]))].into_iter().collect();
+        let report = validate(&main, &builders, &BTreeMap::new());
+        assert!(!report.is_valid());
+        assert!(report.errors().eq([&ValidationIssue::DuplicateServant { servant: "stop".to_string(), clusters: vec![main, slave], }]));
+    }
+
+    #[test]
+    /// processes of the wrong kind or of the wrong data type are rejected
+    fn wrong_process_and_type_mismatch() {
+        let main = address(8180);
+        let builders = [(main, builder(&["start", "stop"]))].into_iter().collect();
+        let other = FullId::nil();
+        let report = validate(&main, &builders, &broadcast(main, other, &["start"], &["stop"]));
+        assert!(!report.is_valid());
+        assert_eq!(report.issues(), [
+            ValidationIssue::WrongProcess { channel: "Shutdown".to_string(), servant: "start".to_string(), expected: "emit", found: "read", },
+            ValidationIssue::TypeMismatch { channel: "Shutdown".to_string(), servant: "stop".to_string(), expected: other, found: wake_type(), },
+        ]);
+    }
+}
@@ -10,6 +10,11 @@
 //! * control the coherence of the channel data types thanks to type hash codes
 //! * implement serialization with zero-copy deserialization (rkyv) on the exchange channels
 //! * serialize the application's entire network definition in editable text format, then reload and execute it  
+//! * update the network topology at runtime, by adding servants and adding, killing or rewiring channels
+//! * administrate the running clusters through an optional admin endpoint
+//! * monitor the channels, servants and sockets through a metrics snapshot or a Prometheus endpoint
+//! * trace the clusters, servants and channels by means of structured `tracing` events, filtered at runtime
+//! * handle failures through a typed error, `SilxError`, which distinguishes io, protocol, configuration and type mismatch errors
 //!
 //! Silx remains a project under development.   
 //!
@@ -28,6 +33,7 @@
 //! tokio = "^1.36.0"
 //! serde = "^1.0.197"
 //! typetag = "^0.2.16"
+//! tracing-subscriber = "^0.3.18"
 //!
 //! silx-core = "0.1.2"
 //! silx-types = "0.1.2"
@@ -191,6 +197,7 @@
 //! /// * execute the loaded network
 //! #[tokio::main]
 //! pub async fn main() {
+//!     tracing_subscriber::fmt::init(); // print the traces of the clusters
 //!     // build network and save it in files
 //!     let main_addr = SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)), 8180);
 //!     let slave_addr = SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)), 8181);
@@ -223,23 +230,19 @@
 //! ```txt
 //! ==== first run -------------
 //!
-//! 127.0.0.1:8181: try to connect 127.0.0.1:8180
-//! 127.0.0.1:8181: Listening connection established
-//! cluster 127.0.0.1:8181 has been built
-//! cluster 127.0.0.1:8180 has been built
+//!  INFO cluster{cluster=127.0.0.1:8181}: silx_core::builder: cluster has been built
+//!  INFO cluster{cluster=127.0.0.1:8180}: silx_core::builder: cluster has been built
 //! Welcome John Doe
-//! cluster 127.0.0.1:8181 is ended
-//! cluster 127.0.0.1:8180 is ended
+//!  INFO cluster{cluster=127.0.0.1:8181}: silx_core::builder: cluster is ended
+//!  INFO cluster{cluster=127.0.0.1:8180}: silx_core::builder: cluster is ended
 //!
 //! ==== second run (loadind network) -------------
 //!
-//! 127.0.0.1:8181: try to connect 127.0.0.1:8180
-//! 127.0.0.1:8181: Listening connection established
-//! cluster 127.0.0.1:8181 has been built
-//! cluster 127.0.0.1:8180 has been built
+//!  INFO cluster{cluster=127.0.0.1:8181}: silx_core::builder: cluster has been built
+//!  INFO cluster{cluster=127.0.0.1:8180}: silx_core::builder: cluster has been built
 //! Welcome John Doe
-//! cluster 127.0.0.1:8180 is ended
-//! cluster 127.0.0.1:8181 is ended
+//!  INFO cluster{cluster=127.0.0.1:8180}: silx_core::builder: cluster is ended
+//!  INFO cluster{cluster=127.0.0.1:8181}: silx_core::builder: cluster is ended
 //! ```
 //! ## Servant definition
 //! Servants are built by implementing the `ServantBuilderParameters` trait and the `ServantBuilder` trait with the macro `#[typetag::serde]`.
@@ -429,7 +432,8 @@
 //!   The serialization file, name and input servants followed by output servants are provided.
 //!   Indeed, the channels may connect several servants to several servants.
 //!   Moreover, the cluster address is provided in case of channels within a cluster, and the input cluster address followed by output cluster address are provided in case of channels betweens two clusters.
-//!   The nature of the channel is determined by the used method, here `add_net_broadcast` and `add_query`:
+//!   The nature of the channel is determined by the used method, here `add_net_broadcast` and `add_query`
+//!   (available methods are `add_query`, `add_broadcast`, `add_signal` within a cluster and `add_net_query`, `add_net_broadcast`, `add_net_signal` between two clusters):
 //! ```txt
 //! let mut starters = start_prod.add_query(
 //!     "channel=QueryHello.yaml", "QueryHello".to_string(), main_addr, ["last_name".to_string()], ["hello".to_string()], max_ping, None
@@ -457,6 +461,106 @@
 //!     let main_starter = Starter::load("starter=main.yaml", &save_dir).unwrap();
 //!     main_starter.run().await.unwrap();
 //! ```
+//! ## Static validation
+//! Method `validate` checks the network definition of a main starter before anything is bound, and returns a `ValidationReport`:
+//! * errors: data types of the channels which do not match the signatures of the connected processes, servants lacking the process of a channel, channels connected to unknown servants (dangling) or without input or output servant (unused), duplicate servant names, missing cluster builders and supervision of unknown servants
+//! * warnings: processes of the servants which are not connected to any channel
+//! The same validation is done by the main starter before running: the run fails with a configuration error listing the issues, and the warnings are traced.
+//! ```txt
+//!     let report = main_starter.validate();
+//!     if !report.is_valid() { eprintln!("{report}"); }
+//! ```
+//! ## Topology export
+//! The topology of a main starter is exported in Graphviz DOT format by method `to_dot`, and in Mermaid flowchart format by method `to_mermaid`: clusters are subgraphs, servants are nodes, and channels are edges from their input servants to their output servants, labelled with their name, kind (query, broadcast, signal, or their net counterparts, drawn dashed) and data type (short type hash code); a channel without input or without output servant is drawn as a dashed node.
+//! The same export is available from a `StarterOutline`, which is loaded from the saved files without deserializing the servant builders.
+//! When the clusters are running, the metrics snapshots given by their admin endpoints annotate the servants with their tasks and the channels with their sent and received messages:
+//! ```txt
+//!     let main_starter = Starter::load("starter=main.yaml", &save_dir).unwrap();
+//!     std::fs::write("network.dot", main_starter.to_dot(&[])?).unwrap();
+//!     let snapshot = client.metrics().await?;
+//!     println!("{}", main_starter.to_mermaid(&[snapshot])?);
+//! ```
+//! ## Command-line tool
+//! Crate `silx-cli` provides the `silx` tool, which validates, inspects, exports (DOT or Mermaid) and runs the saved starters without writing a `main.rs`:
+//! ```txt
+//! silx validate starter=main.yaml --dir ./saved
+//! silx graph starter=main.yaml --dir ./saved --format mermaid
+//! silx run starter=main.yaml --dir ./saved
+//! ```
+//! Commands `inspect` and `graph` only read the starter, builder and channel files, by means of `StarterOutline::load(...)`, and work for any network.
+//! Since servant builders are deserialized by means of `typetag`, validating or running a network with its own servants needs a binary which links these servants and calls `silx_cli::main()`.
+//! ## Runtime topology updates
+//! The main starter can also be executed by method `run_with_topology`, together with a `Topology` handle.
+//! This handle is able to add servants and to add, kill or rewire named channels while the network is running, without restarting the other clusters.
+//! Channels between two clusters can only be added if these clusters were already connected at startup.
+//! ```txt
+//!     let (topology, receiver) = Topology::new(16);
+//!     let handle_main = spawn(async move { main_starter.run_with_topology(receiver).await.unwrap(); });
+//!     [...]
+//!     topology.add_servant(main_addr, "hello2".to_string(), Hello("Bonjour".to_string()), Supervision::Escalate).await.unwrap();
+//!     topology.rewire_channel("QueryHello".to_string(), new_channel).await.unwrap();
+//! ```
+//! ## Admin endpoint
+//! A cluster can start an admin endpoint, defined by field `admin` of its builder file or by method `set_admin` of the starter producer (`!Tcp` with a loopback socket address, or `!Unix` with the path of a Unix socket; the endpoint is not authenticated, so that other TCP addresses are rejected):
+//! ```txt
+//!     let start_prod = StarterProducer::new(main_addr, "starter=main.yaml", "builder=main.yaml", None, 16)
+//!         .add_cluster(slave_addr, "starter=slave.yaml", "builder=slave.yaml", None, 16)?
+//!         .set_admin(&main_addr, AdminAddress::Tcp("127.0.0.1:8190".parse().unwrap()))?.done();
+//! ```
+//! The endpoint is driven by `AdminClient`, which lists the servants and channels of the cluster with their status, pings, pauses or resumes a named channel, and shuts the cluster or the whole network down (network shutdown is only accepted by the main cluster):
+//! ```txt
+//!     let mut client = AdminClient::connect(&AdminAddress::Tcp("127.0.0.1:8190".parse().unwrap())).await?;
+//!     println!("{:?}", client.list().await?);
+//!     client.pause("QueryHello").await?;
+//!     client.resume("QueryHello").await?;
+//!     client.shutdown_network().await?;
+//! ```
+//! The protocol can also be implemented by other tools.
+//! Each request is a yaml serialized `AdminRequest` (`List`, `!Ping`, `!Pause` or `!Resume` with field `channel`, `Metrics`, `Shutdown`, `ShutdownNetwork`), and is answered by a yaml serialized `AdminReply` (`!Status`, `!Ping`, `!Metrics`, `Done` or `!Error`).
+//! Each message is framed by byte `1`, followed by the byte length of the yaml as a little endian `u32`, and then by the yaml string:
+//! ```yaml
+//! !Pause
+//! channel: QueryHello
+//! ```
+//! ## Telemetry
+//! Each cluster counts, by servant and channel name, the messages and serialized bytes sent and received, the dropped messages, the messages skipped by lagging broadcast receivers, the handling durations and the queue lengths; it also counts the control tasks of its servants and their durations, and the frames and bytes exchanged on its socket connections with other clusters.
+//! A snapshot of these metrics is replied to `AdminRequest::Metrics` by the admin endpoint:
+//! ```txt
+//!     let snapshot = client.metrics().await?;
+//!     println!("{}", snapshot.to_prometheus());
+//! ```
+//! A cluster can also serve its metrics in Prometheus text format at `http://<address>/metrics`, with the address defined by field `metrics` of its builder file or by method `set_metrics` of the starter producer:
+//! ```txt
+//!     let start_prod = StarterProducer::new(main_addr, "starter=main.yaml", "builder=main.yaml", None, 16)
+//!         .set_metrics(&main_addr, "127.0.0.1:9180".parse().unwrap())?.done();
+//! ```
+//! The statistics of a standalone `ChannelServer` or `ChannelClient` are given by their method `stats`.
+//! ## Tracing
+//! The clusters emit structured events by means of crate `tracing`; they are printed once a subscriber is installed by the application, e.g. `tracing_subscriber::fmt::init()`.
+//! The events are recorded within nested spans: `cluster` (field `cluster`), then `servant` (fields `servant_id` and `servant`) and `task` (fields `task` and `channel`), or `socket` (fields `role`, `peer` and, for the client senders, `channel`).
+//! Levels are used as follows: `error` and `warn` for failures, `info` for the life cycle of the clusters, `debug` for the control tasks of the masters and the socket connections, `trace` for the tasks of the servants.
+//! The filtering is configured at runtime, e.g. by the `RUST_LOG` environment variable with the `env-filter` feature of `tracing-subscriber`:
+//! ```txt
+//! RUST_LOG=silx_core=debug cargo run --release
+//! RUST_LOG="info,silx_core::structs::cells::servant=trace" cargo run --release
+//! ```
+//! ## Errors
+//! The fallible methods of the public API return a `SilxError` (module `error`, also reexported by `utils`), which implements `std::error::Error` and keeps the error which has caused it as its `source`:
+//! * `Io`: failure when binding, connecting, reading or writing a socket or a file
+//! * `Protocol`: unexpected or malformed message, closed connection or closed channel
+//! * `Config`: inconsistent network definition, e.g. unknown cluster, servant or channel, or unreadable yaml file
+//! * `TypeMismatch`: conflicting data types on a channel, with the hash codes of the expected and found types
+//! * `Archive`: failure when archiving or accessing archived data
+//! * `Timeout`: operation which has exceeded its time limit, i.e. a control task of a servant (maximal cycle time of its builder), an admin request (`ADMIN_TIMEOUT`, see `AdminClient::with_timeout`) or a topology update sent to a cluster (`UPDATE_TIMEOUT`)
+//! * `Remote`: failure reported by another cluster, e.g. by the main cluster for a topology update, or by an admin endpoint
+//! Errors can thus be matched by kind, and are converted into `String` (together with their chain of sources) for applications still returning `Result<_,String>`:
+//! ```txt
+//!     match client.pause("QueryHello").await {
+//!         Err(SilxError::Remote { context, }) => eprintln!("refused by the cluster: {context}"),
+//!         Err(e) => return Err(e.into()),
+//!         Ok(()) => (),
+//!     }
+//! ```
 //! ## Saved files from the network serialization
 //! After a run, 11 files are generated from the network serialization in directory `saved` of the project.
 //! ```txt
@@ -533,8 +637,15 @@
 //!   last_name: !unloaded
 //!     path: servant=last_name.yaml
 //! ctrl_ch_capacity: 16
+//! supervision: {}
+//! admin: null
+//! metrics: null
 //! ```
-//! This file informs that main cluster contains the servants `hello` and `last_name` which are respectively defined within files `servant=hello.yaml` and `servant=last_name.yaml`
+//! This file informs that main cluster contains the servants `hello` and `last_name` which are respectively defined within files `servant=hello.yaml` and `servant=last_name.yaml`.
+//! Field `supervision` maps servant names to their supervision strategy (`Stop`, `!Restart` with `max_restarts` and `within`, or `Escalate`); servants which are not listed escalate their failure, and the cluster is then shut down.
+//! A strategy can also be set by method `set_supervision` of the starter producer, before calling `done()` on the servants
+//! Field `admin` is the optional address of the admin endpoint of the cluster (see section Admin endpoint)
+//! Field `metrics` is the optional socket address of the Prometheus endpoint of the cluster (see section Telemetry)
 //! #### Builder file `builder=slave.yaml`
 //! ```yaml
 //! net_size: null
@@ -544,6 +655,9 @@
 //!   shutdown: !unloaded
 //!     path: servant=shutdown.yaml
 //! ctrl_ch_capacity: 16
+//! supervision: {}
+//! admin: null
+//! metrics: null
 //! ```
 //! This file informs that slave cluster contains the servants `first_name` and `shutdown` which are respectively defined within files `servant=first_name.yaml` and `servant=shutdown.yaml`
 //! ### Servants and Channels files
@@ -588,7 +702,7 @@
 
 /// Shared structures, traits and macros; reexport
 mod shared;
-pub use self::shared::{ types, servants, utils, channels, id_tools, };
+pub use self::shared::{ types, servants, utils, channels, id_tools, error, };
 
 /// silx structures
 pub (crate) mod structs;
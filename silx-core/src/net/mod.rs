@@ -2,7 +2,7 @@ use rkyv::AlignedVec;
 use tokio::io::{ AsyncReadExt, AsyncWriteExt, };
 use num_enum::{ IntoPrimitive, TryFromPrimitive, };
 use std::{ net::SocketAddr, str::FromStr, };
-use crate::{ ChannelIdType, QueryIdType, structs::archmod::ser_data::{ SerializedData, SerializedDataType, }, };
+use crate::{ ChannelIdType, QueryIdType, shared::error::SilxError, structs::archmod::ser_data::{ SerializedData, SerializedDataType, }, };
 
 #[derive(IntoPrimitive, TryFromPrimitive,)]
 #[repr(u8)]
@@ -13,6 +13,7 @@ enum NetCase {
     SizedBroadcast,
     SizedQuery,
     SizedReply,
+    SizedSignal,
 }
 
 #[derive(Debug,)]
@@ -23,6 +24,7 @@ pub(crate) enum NetTag {
     SizedBroadcast(ChannelIdType,u32,),
     SizedQuery(ChannelIdType, QueryIdType,u32,),
     SizedReply(ChannelIdType, QueryIdType,u32,),
+    SizedSignal(ChannelIdType,u32,),
 }
 
 #[derive(Debug,)]
@@ -31,150 +33,154 @@ pub(crate) enum NetTaggedSerializedData {
     Broadcast(ChannelIdType, SerializedData,),
     Query(ChannelIdType, QueryIdType, SerializedData,),
     Reply(ChannelIdType, QueryIdType,  SerializedData,),
+    Signal(ChannelIdType, SerializedData,),
+}
+
+impl NetTaggedSerializedData {
+    /// Number of serialized bytes carried by the tagged data
+    pub(crate) fn data_len(&self) -> usize {
+        match self { Self::Broadcast(_,s) | Self::Query(_,_,s) | Self::Reply(_,_,s) | Self::Signal(_,s) => s.data.len(), }
+    }
 }
 
 /// doc to be defined
 pub(crate) enum SilxProtocols {}
 
 impl SilxProtocols {
-    async fn push<W: AsyncWriteExt + Unpin> (mut writer: W, tag: NetTag, data: &[u8]) -> Result<(),String> {
+    async fn push<W: AsyncWriteExt + Unpin> (mut writer: W, tag: NetTag, data: &[u8]) -> Result<(),SilxError> {
         match tag {
             NetTag::Socket                           => {
-                if writer.write_u8(NetCase::Socket.into()).await.is_err() { return Err("Failed to write case".to_string()); }
-                if writer.write_u32_le(data.len() as u32).await.is_err() { return Err("Failed to write bytes length".to_string()); }
-                if writer.write_all(data).await.is_err() { return Err("Failed to write bytes".to_string()); }   
+                writer.write_u8(NetCase::Socket.into()).await.map_err(|e| SilxError::io("Failed to write case", e))?;
+                writer.write_u32_le(data.len() as u32).await.map_err(|e| SilxError::io("Failed to write bytes length", e))?;
+                writer.write_all(data).await.map_err(|e| SilxError::io("Failed to write bytes", e))?;   
             },
             NetTag::String                           => {
-                if writer.write_u8(NetCase::String.into()).await.is_err() { return Err("Failed to write case".to_string()); }
-                if writer.write_u32_le(data.len() as u32).await.is_err() { return Err("Failed to write bytes length".to_string()); }
-                if writer.write_all(data).await.is_err() { return Err("Failed to write bytes".to_string()); }   
+                writer.write_u8(NetCase::String.into()).await.map_err(|e| SilxError::io("Failed to write case", e))?;
+                writer.write_u32_le(data.len() as u32).await.map_err(|e| SilxError::io("Failed to write bytes length", e))?;
+                writer.write_all(data).await.map_err(|e| SilxError::io("Failed to write bytes", e))?;   
             },
             NetTag::SizedBroadcast(channel,root)     => {
-                if writer.write_u8(NetCase::SizedBroadcast.into()).await.is_err() { return Err("Failed to write case".to_string()); }
-                if writer.write_u64_le(channel).await.is_err() { return Err("Failed to write channel".to_string()); }
-                if writer.write_u32_le(root).await.is_err() { return Err("Failed to write root".to_string()); }
-                if writer.write_u32_le(data.len() as u32).await.is_err() { return Err("Failed to write bytes length".to_string()); }
-                if writer.write_all(data).await.is_err() { return Err("Failed to write bytes".to_string()); }   
+                writer.write_u8(NetCase::SizedBroadcast.into()).await.map_err(|e| SilxError::io("Failed to write case", e))?;
+                writer.write_u64_le(channel).await.map_err(|e| SilxError::io("Failed to write channel", e))?;
+                writer.write_u32_le(root).await.map_err(|e| SilxError::io("Failed to write root", e))?;
+                writer.write_u32_le(data.len() as u32).await.map_err(|e| SilxError::io("Failed to write bytes length", e))?;
+                writer.write_all(data).await.map_err(|e| SilxError::io("Failed to write bytes", e))?;   
             },
             NetTag::SizedQuery(channel,query,root)   => {
-                if writer.write_u8(NetCase::SizedQuery.into()).await.is_err() { return Err("Failed to write case".to_string()); }
-                if writer.write_u64_le(channel).await.is_err() { return Err("Failed to write channel".to_string()); }
-                if writer.write_u64_le(query).await.is_err() { return Err("Failed to write query".to_string()); }
-                if writer.write_u32_le(root).await.is_err() { return Err("Failed to write root".to_string()); }
-                if writer.write_u32_le(data.len() as u32).await.is_err() { return Err("Failed to write bytes length".to_string()); }
-                if writer.write_all(data).await.is_err() { return Err("Failed to write bytes".to_string()); }   
+                writer.write_u8(NetCase::SizedQuery.into()).await.map_err(|e| SilxError::io("Failed to write case", e))?;
+                writer.write_u64_le(channel).await.map_err(|e| SilxError::io("Failed to write channel", e))?;
+                writer.write_u64_le(query).await.map_err(|e| SilxError::io("Failed to write query", e))?;
+                writer.write_u32_le(root).await.map_err(|e| SilxError::io("Failed to write root", e))?;
+                writer.write_u32_le(data.len() as u32).await.map_err(|e| SilxError::io("Failed to write bytes length", e))?;
+                writer.write_all(data).await.map_err(|e| SilxError::io("Failed to write bytes", e))?;   
             },
             NetTag::SizedReply(channel,query,root)   => {
-                if writer.write_u8(NetCase::SizedReply.into()).await.is_err() { return Err("Failed to write case".to_string()); }
-                if writer.write_u64_le(channel).await.is_err() { return Err("Failed to write channel".to_string()); }
-                if writer.write_u64_le(query).await.is_err() { return Err("Failed to write query".to_string()); }
-                if writer.write_u32_le(root).await.is_err() { return Err("Failed to write root".to_string()); }
-                if writer.write_u32_le(data.len() as u32).await.is_err() { return Err("Failed to write bytes length".to_string()); }
-                if writer.write_all(data).await.is_err() { return Err("Failed to write bytes".to_string()); }   
+                writer.write_u8(NetCase::SizedReply.into()).await.map_err(|e| SilxError::io("Failed to write case", e))?;
+                writer.write_u64_le(channel).await.map_err(|e| SilxError::io("Failed to write channel", e))?;
+                writer.write_u64_le(query).await.map_err(|e| SilxError::io("Failed to write query", e))?;
+                writer.write_u32_le(root).await.map_err(|e| SilxError::io("Failed to write root", e))?;
+                writer.write_u32_le(data.len() as u32).await.map_err(|e| SilxError::io("Failed to write bytes length", e))?;
+                writer.write_all(data).await.map_err(|e| SilxError::io("Failed to write bytes", e))?;   
+            },
+            NetTag::SizedSignal(channel,root)        => {
+                writer.write_u8(NetCase::SizedSignal.into()).await.map_err(|e| SilxError::io("Failed to write case", e))?;
+                writer.write_u64_le(channel).await.map_err(|e| SilxError::io("Failed to write channel", e))?;
+                writer.write_u32_le(root).await.map_err(|e| SilxError::io("Failed to write root", e))?;
+                writer.write_u32_le(data.len() as u32).await.map_err(|e| SilxError::io("Failed to write bytes length", e))?;
+                writer.write_all(data).await.map_err(|e| SilxError::io("Failed to write bytes", e))?;   
             },
         };
         Ok(())
     } 
-    async fn pop<R: AsyncReadExt + Unpin>(mut reader: R) -> Result<(NetTag,AlignedVec),String> {
-        let cas: NetCase = match reader.read_u8().await { 
-            Ok(cas) => match NetCase::try_from(cas) { Ok(cas) => cas, _ => return Err("Unknown case".to_string()), },
-            _ => return Err("Failed to read case".to_string()), 
-        };
+    async fn pop<R: AsyncReadExt + Unpin>(mut reader: R) -> Result<(NetTag,AlignedVec),SilxError> {
+        let cas = reader.read_u8().await.map_err(|e| SilxError::io("Failed to read case", e))?;
+        let cas = NetCase::try_from(cas).map_err(|_| SilxError::protocol(format!("Unknown case {cas}")))?;
         let tag = match cas {
             NetCase::Socket           => NetTag::Socket,
             NetCase::String           => NetTag::String,
             NetCase::SizedBroadcast   => {
-                let channel = match reader.read_u64_le().await { Ok(c) => c, _ => return Err("Failed to read channel".to_string()), };
-                let root = match reader.read_u32_le().await { Ok(root) => root, _ => return Err("Failed to read root".to_string()), };
+                let channel = reader.read_u64_le().await.map_err(|e| SilxError::io("Failed to read channel", e))?;
+                let root = reader.read_u32_le().await.map_err(|e| SilxError::io("Failed to read root", e))?;
                 NetTag::SizedBroadcast(channel,root)
             },
             NetCase::SizedQuery       => {
-                let channel = match reader.read_u64_le().await { Ok(c) => c, _ => return Err("Failed to read channel".to_string()), };
-                let query = match reader.read_u64_le().await { Ok(c) => c, _ => return Err("Failed to read query".to_string()), };
-                let root = match reader.read_u32_le().await { Ok(root) => root, _ => return Err("Failed to read root".to_string()), };
+                let channel = reader.read_u64_le().await.map_err(|e| SilxError::io("Failed to read channel", e))?;
+                let query = reader.read_u64_le().await.map_err(|e| SilxError::io("Failed to read query", e))?;
+                let root = reader.read_u32_le().await.map_err(|e| SilxError::io("Failed to read root", e))?;
                 NetTag::SizedQuery(channel,query,root)
             },
             NetCase::SizedReply       => {
-                let channel = match reader.read_u64_le().await { Ok(c) => c, _ => return Err("Failed to read channel".to_string()), };
-                let query = match reader.read_u64_le().await { Ok(c) => c, _ => return Err("Failed to read query".to_string()), };
-                let root = match reader.read_u32_le().await { Ok(root) => root, _ => return Err("Failed to read root".to_string()), };
+                let channel = reader.read_u64_le().await.map_err(|e| SilxError::io("Failed to read channel", e))?;
+                let query = reader.read_u64_le().await.map_err(|e| SilxError::io("Failed to read query", e))?;
+                let root = reader.read_u32_le().await.map_err(|e| SilxError::io("Failed to read root", e))?;
                 NetTag::SizedReply(channel,query,root)
             },
+            NetCase::SizedSignal      => {
+                let channel = reader.read_u64_le().await.map_err(|e| SilxError::io("Failed to read channel", e))?;
+                let root = reader.read_u32_le().await.map_err(|e| SilxError::io("Failed to read root", e))?;
+                NetTag::SizedSignal(channel,root)
+            },
         };
-        let len = match reader.read_u32_le().await {
-            Ok(len) => len,
-            _       => return Err("Failed to read bytes length".to_string()),
-        };
+        let len = reader.read_u32_le().await.map_err(|e| SilxError::io("Failed to read bytes length", e))?;
         let len: usize = len as usize;
         let mut buffer = AlignedVec::with_capacity(len);
         unsafe { buffer.set_len(len); }
-        match reader.read_exact(&mut buffer).await {
-            Ok(_)  => (),
-            Err(_) => { return Err("Failed to read bytes".to_string()); },
-        }
+        reader.read_exact(&mut buffer).await.map_err(|e| SilxError::io("Failed to read bytes", e))?;
         Ok((tag,buffer,))
     }
 
-    pub(crate) async fn push_socket<W: AsyncWriteExt + Unpin>(mut writer: W, socket: &SocketAddr) -> Result<(),String> {
+    pub(crate) async fn push_socket<W: AsyncWriteExt + Unpin>(mut writer: W, socket: &SocketAddr) -> Result<(),SilxError> {
         let str_s = socket.to_string();
         let bytes = str_s.as_bytes();
         SilxProtocols::push(&mut writer, NetTag::Socket, bytes).await
     }
-    pub(crate) async fn pop_socket<R: AsyncReadExt + Unpin>(mut reader: R) -> Result<SocketAddr,String> {
+    pub(crate) async fn pop_socket<R: AsyncReadExt + Unpin>(mut reader: R) -> Result<SocketAddr,SilxError> {
         let bytes = match SilxProtocols::pop(&mut reader).await {
             Ok((NetTag::Socket,bytes,)) => bytes,
-            Ok(dat)                     => return Err(format!("Wrong net data : {:?}", dat)),
+            Ok(dat)                     => return Err(SilxError::protocol(format!("Wrong net data : {:?}", dat))),
             Err(e)                      => return Err(e),
         };
-        match String::from_utf8(bytes.iter().cloned().collect()) {
-            Ok(s)  => {
-                match SocketAddr::from_str(&s) {
-                    Ok(soa) => Ok(soa),
-                    Err(_)  => Err("Failed to parse into socket address".to_string()),
-                }
-            },
-            Err(_) => return Err("Found invalid UTF-8".to_string()), 
-        }
+        let s = String::from_utf8(bytes.iter().cloned().collect()).map_err(|e| SilxError::protocol("Found invalid UTF-8").with_source(e))?;
+        SocketAddr::from_str(&s).map_err(|e| SilxError::protocol("Failed to parse into socket address").with_source(e))
 
     }
 
-    pub(crate) async fn push_string<W: AsyncWriteExt + Unpin>(mut writer: W, s: &str) -> Result<(),String> {
+    pub(crate) async fn push_string<W: AsyncWriteExt + Unpin>(mut writer: W, s: &str) -> Result<(),SilxError> {
         let bytes = s.as_bytes();
         SilxProtocols::push(&mut writer, NetTag::String, bytes).await
     }
-    pub(crate) async fn pop_string<R: AsyncReadExt + Unpin>(mut reader: R) -> Result<String,String> {
+    pub(crate) async fn pop_string<R: AsyncReadExt + Unpin>(mut reader: R) -> Result<String,SilxError> {
         let bytes = match SilxProtocols::pop(&mut reader).await {
             Ok((NetTag::String,bytes,)) => bytes,
-            Ok(dat)                     => return Err(format!("Wrong net data : {:?}", dat)),
+            Ok(dat)                     => return Err(SilxError::protocol(format!("Wrong net data : {:?}", dat))),
             Err(e)                      => return Err(e),
         };
-        match String::from_utf8(bytes.iter().cloned().collect()) {
-            Ok(s)  => Ok(s),
-            Err(_) => return Err("Found invalid UTF-8".to_string()), 
-        }
+        String::from_utf8(bytes.iter().cloned().collect()).map_err(|e| SilxError::protocol("Found invalid UTF-8").with_source(e))
     }
 
-    pub(crate) async fn push_tagged_serialized_data<W: AsyncWriteExt + Unpin>(mut writer: W, s: &NetTaggedSerializedData) -> Result<(),String> {
-        use NetTaggedSerializedData::{ Broadcast as NBroadcast, Query as NQuery, Reply as NReply, };
+    pub(crate) async fn push_tagged_serialized_data<W: AsyncWriteExt + Unpin>(mut writer: W, s: &NetTaggedSerializedData) -> Result<(),SilxError> {
+        use NetTaggedSerializedData::{ Broadcast as NBroadcast, Query as NQuery, Reply as NReply, Signal as NSignal, };
         use SilxProtocols as sp;
         type S = SerializedData;
         use SerializedDataType::Sized;
-        use NetTag::{ SizedBroadcast, SizedQuery, SizedReply, };
+        use NetTag::{ SizedBroadcast, SizedQuery, SizedReply, SizedSignal, };
         match s {
             NBroadcast(channel, S { data_type: Sized, root, data, },)      => sp::push(&mut writer, SizedBroadcast(*channel,*root), data).await,
             NQuery(channel, query, S { data_type: Sized, root, data, },)   => sp::push(&mut writer, SizedQuery(*channel,*query, *root), data).await,
             NReply(channel, query, S { data_type: Sized, root, data, },)   => sp::push(&mut writer, SizedReply(*channel,*query, *root), data).await,
-            _ => Err(format!("Undefined data: forbidden!")),
+            NSignal(channel, S { data_type: Sized, root, data, },)         => sp::push(&mut writer, SizedSignal(*channel,*root), data).await,
+            _ => Err(SilxError::protocol("Undefined data: forbidden!")),
         }
     }
-    pub(crate) async fn pop_tagged_serialized_data<R: AsyncReadExt + Unpin>(mut reader: R) -> Result<NetTaggedSerializedData,String> {
-        use NetTaggedSerializedData::{ Broadcast as NBroadcast, Query as NQuery, Reply as NReply, };
+    pub(crate) async fn pop_tagged_serialized_data<R: AsyncReadExt + Unpin>(mut reader: R) -> Result<NetTaggedSerializedData,SilxError> {
+        use NetTaggedSerializedData::{ Broadcast as NBroadcast, Query as NQuery, Reply as NReply, Signal as NSignal, };
         match SilxProtocols::pop(&mut reader).await {
             Ok((NetTag::SizedBroadcast(channel,root),bytes,))     => Ok(NBroadcast(channel, SerializedData::sized_from(root, bytes,),)),
             Ok((NetTag::SizedQuery(channel,query,root),bytes,))   => Ok(NQuery(channel, query, SerializedData::sized_from(root, bytes,),)),
             Ok((NetTag::SizedReply(channel,query,root),bytes,))   => Ok(NReply(channel, query, SerializedData::sized_from(root, bytes,),)),
-            Ok((NetTag::Socket,_))                                    => Err(format!("Wrong net data : NetTag::Socket")),
-            Ok((NetTag::String,_))                                    => Err(format!("Wrong net data : NetTag::String")),
+            Ok((NetTag::SizedSignal(channel,root),bytes,))        => Ok(NSignal(channel, SerializedData::sized_from(root, bytes,),)),
+            Ok((NetTag::Socket,_))                                    => Err(SilxError::protocol("Wrong net data : NetTag::Socket")),
+            Ok((NetTag::String,_))                                    => Err(SilxError::protocol("Wrong net data : NetTag::String")),
             Err(e)                                                => Err(e),
         }
     }
@@ -0,0 +1,123 @@
+use std::{ error::Error, fmt::{ Display, Formatter, Result as FmtResult, }, io, };
+
+use uuid::Uuid as FullId;
+
+/// Boxed error, used as source of a silx error
+pub type BoxedError = Box<dyn Error + Send + Sync + 'static>;
+
+#[derive(Debug)]
+/// Error of silx
+/// * Each variant carries a context message, and possibly the error which has caused it (see `std::error::Error::source`)
+pub enum SilxError {
+    /// Input/output failure, e.g. when binding, connecting, reading or writing a socket or a file
+    Io { context: String, source: io::Error, },
+    /// Failure of a silx protocol, e.g. unexpected message, closed connection or closed channel
+    Protocol { context: String, source: Option<BoxedError>, },
+    /// Inconsistent configuration, e.g. unknown cluster, servant or channel, or malformed network definition
+    Config { context: String, source: Option<BoxedError>, },
+    /// Mismatch between the data types expected by a channel and the data types actually provided
+    TypeMismatch { context: String, expected: FullId, found: FullId, },
+    /// Failure when archiving or accessing archived data
+    Archive { context: String, },
+    /// Operation which has exceeded its time limit
+    Timeout { context: String, },
+    /// Failure reported by another cluster or by an admin endpoint
+    Remote { context: String, },
+}
+
+impl SilxError {
+    /// Build an input/output error
+    /// * `context: impl Into<String>` : context of the error
+    /// * `source: io::Error` : input/output error
+    /// * Output: the error
+    pub fn io(context: impl Into<String>, source: io::Error) -> Self { Self::Io { context: context.into(), source, } }
+
+    /// Build a protocol error
+    /// * `context: impl Into<String>` : context of the error
+    /// * Output: the error
+    pub fn protocol(context: impl Into<String>) -> Self { Self::Protocol { context: context.into(), source: None, } }
+
+    /// Build a configuration error
+    /// * `context: impl Into<String>` : context of the error
+    /// * Output: the error
+    pub fn config(context: impl Into<String>) -> Self { Self::Config { context: context.into(), source: None, } }
+
+    /// Build a type mismatch error
+    /// * `context: impl Into<String>` : context of the error
+    /// * `expected: FullId` : identifier of the expected data type
+    /// * `found: FullId` : identifier of the provided data type
+    /// * Output: the error
+    pub fn type_mismatch(context: impl Into<String>, expected: FullId, found: FullId) -> Self {
+        Self::TypeMismatch { context: context.into(), expected, found, }
+    }
+
+    /// Build an archive error
+    /// * `context: impl Into<String>` : context of the error
+    /// * Output: the error
+    pub fn archive(context: impl Into<String>) -> Self { Self::Archive { context: context.into(), } }
+
+    /// Build a time out error
+    /// * `context: impl Into<String>` : context of the error
+    /// * Output: the error
+    pub fn timeout(context: impl Into<String>) -> Self { Self::Timeout { context: context.into(), } }
+
+    /// Build an error reported by another cluster or by an admin endpoint
+    /// * `context: impl Into<String>` : reported error
+    /// * Output: the error
+    pub fn remote(context: impl Into<String>) -> Self { Self::Remote { context: context.into(), } }
+
+    /// Set the source of a protocol or configuration error; other errors are unchanged
+    /// * `source: E` : error which has caused this error
+    /// * `E: Into<BoxedError>` : type of the source
+    /// * Output: the error with its source
+    pub fn with_source<E>(self, source: E) -> Self where E: Into<BoxedError> {
+        match self {
+            Self::Protocol { context, .. } => Self::Protocol { context, source: Some(source.into()), },
+            Self::Config { context, .. }   => Self::Config { context, source: Some(source.into()), },
+            other                          => other,
+        }
+    }
+
+    /// Context message of the error
+    /// * Output: the context message
+    pub fn context(&self) -> &str {
+        match self {
+            Self::Io { context, .. } | Self::Protocol { context, .. } | Self::Config { context, .. } | Self::TypeMismatch { context, .. }
+                | Self::Archive { context, } | Self::Timeout { context, } | Self::Remote { context, } => context,
+        }
+    }
+}
+
+impl Display for SilxError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        match self {
+            Self::Io { context, .. }       => write!(f, "I/O error: {context}"),
+            Self::Protocol { context, .. } => write!(f, "protocol error: {context}"),
+            Self::Config { context, .. }   => write!(f, "configuration error: {context}"),
+            Self::TypeMismatch { context, expected, found, } => write!(f, "type mismatch: {context} (expected {expected}, found {found})"),
+            Self::Archive { context, }     => write!(f, "archive error: {context}"),
+            Self::Timeout { context, }     => write!(f, "time out: {context}"),
+            Self::Remote { context, }      => write!(f, "remote error: {context}"),
+        }
+    }
+}
+
+impl Error for SilxError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::Io { source, .. } => Some(source),
+            Self::Protocol { source, .. } | Self::Config { source, .. } => source.as_ref().map(|source| source.as_ref() as &(dyn Error + 'static)),
+            _ => None,
+        }
+    }
+}
+
+impl From<SilxError> for String {
+    /// Render the error and its chain of sources, separated by ` -> `
+    fn from(error: SilxError) -> Self {
+        let mut rendered = error.to_string();
+        let mut source = error.source();
+        while let Some(error) = source { rendered.push_str(" -> "); rendered.push_str(&error.to_string()); source = error.source(); }
+        rendered
+    }
+}
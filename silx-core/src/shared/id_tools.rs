@@ -3,6 +3,8 @@ use std::sync::Arc;
 use tokio::sync::Mutex;
 use slab::Slab;
 
+use super::error::SilxError;
+
 /// Id builder for task request between servants and cluster master; this is an alias for a task id generator shared between threads
 pub type IdBuilder = Arc<Mutex<TaskIdGenerator>>;
 
@@ -33,7 +35,7 @@ impl TaskIdGenerator {
     /// * Output: a new task id
     pub fn generate(&mut self) -> TaskId { TaskId::new(self.0.insert(()) as AcknowledgeId) }
 
-    pub(crate) fn delete(&mut self, TaskId(id): TaskId) -> Result<(),String> { 
-        if self.0.try_remove(id as usize).is_none() { Err("TaskId: deleting a free identifier".to_string()) } else { Ok(()) }    
+    pub(crate) fn delete(&mut self, TaskId(id): TaskId) -> Result<(),SilxError> { 
+        if self.0.try_remove(id as usize).is_none() { Err(SilxError::protocol("TaskId: deleting a free identifier")) } else { Ok(()) }    
     }
 }
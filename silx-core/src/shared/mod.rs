@@ -11,4 +11,10 @@ pub mod utils;
 pub mod channels;
 
 /// Id tools for tasking between servants and cluster master
-pub mod id_tools;
\ No newline at end of file
+pub mod id_tools;
+
+/// Errors of silx
+pub mod error;
+
+/// Tracing helpers
+pub (crate) mod trace;
\ No newline at end of file
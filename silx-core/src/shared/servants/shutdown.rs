@@ -1,6 +1,7 @@
 use std::time::Duration;
 
 use serde::{Deserialize, Serialize};
+use tracing::error;
 
 use crate::shared::{
     id_tools::IdBuilder,
@@ -48,7 +49,7 @@ impl ServantBuilderParameters for ShutdownBuilder {
             let _ = read_recv.recv().await; // Just wait for a message (Ok or Err imply both shutdown)
             let tid = task_id.lock().await.generate(); // generate a new task identifier to be sent to the master of the cluster
             match MsgFromServant::Shutdown(tid).send(&send_to_master).await { // send shutdown message to the master of the cluster
-                Err(e)       => eprintln!("Shutdown error: failed to send message to master:\n -> {}",e), // print error if any
+                Err(e)       => error!("shutdown: failed to send message to master -> {}",e), // trace error if any
                 Ok(receiver) => { let _ = receiver.await; }, // or receive master acknowledgment
             }  
         })
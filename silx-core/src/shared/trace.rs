@@ -0,0 +1,19 @@
+use std::future::Future;
+
+use tokio::task::JoinHandle;
+use tracing::{ Instrument, Span, };
+
+/// Spawn a task within the current tracing span, so that the events of the task carry the cluster, servant and channel of the spawner
+/// * `future: F` : future of the task
+/// * Output: handle of the task
+pub (crate) fn spawn<F>(future: F) -> JoinHandle<F::Output> where F: Future + Send + 'static, F::Output: Send + 'static, {
+    tokio::spawn(future.in_current_span())
+}
+
+/// Spawn a task within a given tracing span
+/// * `span: Span` : span of the task
+/// * `future: F` : future of the task
+/// * Output: handle of the task
+pub (crate) fn spawn_in<F>(span: Span, future: F) -> JoinHandle<F::Output> where F: Future + Send + 'static, F::Output: Send + 'static, {
+    tokio::spawn(future.instrument(span))
+}
@@ -1,6 +1,6 @@
 use std::{ pin::Pin, ops::{ Deref, DerefMut, }, };
 
-use crate::utils::{ArchData, SlxData};
+use crate::{ utils::{ ArchData, SlxData, }, error::SilxError, };
 
 
 /// Convert slx archive to dereferencing
@@ -8,7 +8,7 @@ use crate::utils::{ArchData, SlxData};
 pub trait ArchToDeref<D> where D: Deref{
     /// Convert slx archive to dereferencing
     /// * Output: dereferencing or error
-    fn arch_deref(self) -> Result<D,String>;
+    fn arch_deref(self) -> Result<D,SilxError>;
 }
 
 /// Convert slx archive to mutable dereferencing 
@@ -16,7 +16,7 @@ pub trait ArchToDeref<D> where D: Deref{
 pub trait ArchToDerefMut<D> where D: Deref + DerefMut {
     /// Convert slx archive to pinned mutable dereferencing
     /// * Output: pinned mutable dereferencing or error
-    fn arch_deref_mut(self) -> Result<Pin<D>,String>;
+    fn arch_deref_mut(self) -> Result<Pin<D>,SilxError>;
 }
 
 /// Convert slx archive to dereferencing
@@ -26,7 +26,7 @@ pub trait DerefArch<'a,T>: Sized + Deref where T: SlxData {
     /// Convert slx archive to dereferencing
     /// * `arch:  &'a ArchData<T>` : reference to slx archive
     /// * Output: dereferencing or error
-    fn deref_arch(arch:  &'a ArchData<T>) -> Result<Self,String>;
+    fn deref_arch(arch:  &'a ArchData<T>) -> Result<Self,SilxError>;
 }
 
 /// Convert slx archive to mutable dereferencing
@@ -36,17 +36,17 @@ pub trait DerefMutArch<'a,T>: Sized + Deref + DerefMut where T: SlxData {
     /// Convert slx archive to pinned mutable dereferencing
     /// * `arch:  Pin<&'a mut ArchData<T>>` : pinned mutable reference to slx archive
     /// * Output: pinned mutable dereferencing or error
-    fn deref_mut_arch(arch:  Pin<&'a mut ArchData<T>>) -> Result<Pin<Self>,String>;
+    fn deref_mut_arch(arch:  Pin<&'a mut ArchData<T>>) -> Result<Pin<Self>,SilxError>;
 }
 
 impl<'a,T,D> ArchToDeref<D> for &'a ArchData<T> where T: SlxData, D: DerefArch<'a,T> {
-    fn arch_deref(self) -> Result<D,String> {
+    fn arch_deref(self) -> Result<D,SilxError> {
         D::deref_arch(self)
     }
 }
 
 impl<'a,D,T> ArchToDerefMut<D> for Pin<&'a mut ArchData<T>> where T: SlxData, D: DerefMutArch<'a,T> {
-    fn arch_deref_mut(self) -> Result<Pin<D>,String> {
+    fn arch_deref_mut(self) -> Result<Pin<D>,SilxError> {
         D::deref_mut_arch(self)
     }
 }
@@ -1,14 +1,19 @@
 pub use crate::{
+    shared::error::SilxError,
     structs::{
         archmod::archdata::{ ArchData, PinArchData, SlxData, },
-        cells::ctrl_message::{ MsgFromServant, SendToMaster, ReplyToServant, }, 
+        cells::ctrl_message::{ MsgFromServant, SendToMaster, ReplyToServant, SupervisionEvent, }, 
         start_prod::{ StarterProducer, StarterProducerWithProcesses, StarterProducerWithFlow, },
     },
     traits::{
         procell::{ ProcessProducer, ProcessInstance, }, 
         filable::{ Filable, Filed, RecFiled, }, arch::ArchSized,
     },
-    builder::{ ServantBuilder, ServantBuilderParameters, FiledStarter, Starter, },
+    builder::{ ServantBuilder, ServantBuilderParameters, FiledStarter, Starter, Supervision, Channel, ChannelKind, topology::{ Topology, TopologyReceiver, UPDATE_TIMEOUT, },
+        admin::{ ADMIN_TIMEOUT, AdminAddress, AdminClient, AdminRequest, AdminReply, ClusterStatus, ServantStatus, ServantState, ChannelState, },
+        telemetry::{ MetricsSnapshot, ServantSnapshot, ChannelSnapshot, SocketSnapshot, SocketRole, },
+        validate::{ ValidationReport, ValidationIssue, }, outline::{ StarterOutline, ClusterOutline, },
+    },
 };
 
 #[macro_export]
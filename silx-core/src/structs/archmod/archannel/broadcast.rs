@@ -134,6 +134,7 @@ impl SerializedDataBroadcastSender {
 impl SerializedDataBroadcastReceiver {
     pub (crate) async fn recv(&mut self) -> Result<SerializedData, bca::error::RecvError> { self.receiver.recv().await }
     pub (crate) fn try_recv(&mut self) -> Result<SerializedData, bca::error::TryRecvError> { self.receiver.try_recv() }
+    pub (crate) fn len(&self) -> usize { self.receiver.len() }
 }
 impl<U> ArchBroadcastSender<U> where U: SlxData {
     /// Send archived data
@@ -4,20 +4,22 @@ use crate::{
     structs::archmod::archannel::{
         ArchQuerySender, ArchQueryReceiver, RootArchBroadcastSender, RootArchBroadcastReceiver, ArchQuery, ArchBroadcast,
         SerializedDataOneshot, SerializedDataOneshotSender, SerializedDataQuerySender, SerializedDataBroadcastSender,
+        ArchSignal, ArchSignalSender, ArchSignalReceiver, SerializedDataSignalSender,
     },
     net::{ SilxProtocols, NetTaggedSerializedData as NTSData, },
+    builder::telemetry::{ SocketMetrics, SocketRole, SocketSnapshot, },
     ChannelIdType, QueryIdType,
-    shared::utils::SlxData,
+    shared::{ utils::SlxData, trace::{ spawn, spawn_in, }, error::SilxError, },
 };
 use slab::Slab;
 use tokio::{
-    spawn, task::JoinHandle,
+    task::JoinHandle,
     sync::{ RwLock, Mutex, mpsc, }, 
     net::{ TcpStream, TcpListener, ToSocketAddrs, tcp::OwnedWriteHalf, },
 };
 use fnv::FnvHashMap;
+use tracing::{ debug, error, info_span, warn, };
 
-const VERBOSE: bool = false;
 
 /// doc to be defined
 enum MpscSender<T> {
@@ -25,17 +27,17 @@ enum MpscSender<T> {
     Unbounded(mpsc::UnboundedSender<T>),
 }
 impl<T> MpscSender<T> {
-    async fn send(&self, value: T) -> Result<(),String> {
+    async fn send(&self, value: T) -> Result<(),SilxError> {
         match self {
             Self::Bounded(sender)   => {
                 match sender.send(value).await {
-                    Err(_) => Err("Bounded: failed to send".to_string()),
+                    Err(_) => Err(SilxError::protocol("Bounded: failed to send")),
                     Ok(()) => Ok(()),
                 }
             },
             Self::Unbounded(sender)   => {
                 match sender.send(value) {
-                    Err(_) => Err("Unbounded: failed to send".to_string()),
+                    Err(_) => Err(SilxError::protocol("Unbounded: failed to send")),
                     Ok(()) => Ok(()),                    
                 }
             },
@@ -68,23 +70,27 @@ impl<T> MpscReceiver<T> {
 
 #[derive(Debug)]
 /// Channel server
-/// * channel server will handle query and broadcast transmissions between two machines
+/// * channel server will handle query, broadcast and signal transmissions between two machines
 pub struct ChannelServer {
     channels_query: Arc<RwLock<FnvHashMap<ChannelIdType, SerializedDataQuerySender>>>,
     channels_broadcast: Arc<RwLock<FnvHashMap<ChannelIdType, SerializedDataBroadcastSender>>>,
+    channels_signal: Arc<RwLock<FnvHashMap<ChannelIdType, SerializedDataSignalSender>>>,
     sockets: HashMap<SocketAddr,(JoinHandle<()>,JoinHandle<()>,)>,
+    stats: HashMap<SocketAddr,Arc<SocketMetrics>>,
     listener: TcpListener,
     alive: Arc<RwLock<bool>>,
 }
 
 #[derive(Debug)]
 /// Channel client
-/// * channel lient will connect to channel server for query and broadcast transmissions between two machines
+/// * channel lient will connect to channel server for query, broadcast and signal transmissions between two machines
 pub struct ChannelClient {
     channels_reply: Arc<Mutex<FnvHashMap<ChannelIdType, Slab<SerializedDataOneshotSender>>>>,
     handles: Arc<RwLock<FnvHashMap<ChannelIdType,JoinHandle<()>>>>,
     handle_reply: JoinHandle<()>,
     writer: Arc<Mutex<OwnedWriteHalf>>,
+    peer: SocketAddr,
+    stats: Arc<SocketMetrics>,
     do_loop: Arc<RwLock<bool>>,
     alive: Arc<RwLock<bool>>,
 }
@@ -94,16 +100,26 @@ impl ChannelServer {
     /// * `addr: A` : Socket address to be bound to
     /// * `A: ToSocketAddrs` : type of the socket address
     /// * Output: the channel server or an error
-    pub async fn bind<A: ToSocketAddrs>(addr: A) -> Result<Self,String,> { // creation du canal (le receiver est le binder)
-        let listener = match TcpListener::bind(addr).await {
-            Ok(l) => Ok(l),
-            Err(_) => Err("Failed to bind".to_string()),
-        }?;
+    pub async fn bind<A: ToSocketAddrs>(addr: A) -> Result<Self,SilxError,> { // creation du canal (le receiver est le binder)
+        let listener = TcpListener::bind(addr).await.map_err(|e| SilxError::io("Failed to bind", e))?;
         let channels_query = Arc::new(RwLock::new(Default::default()));
         let channels_broadcast = Arc::new(RwLock::new(Default::default()));
+        let channels_signal = Arc::new(RwLock::new(Default::default()));
         let alive = Arc::new(RwLock::new(true));
         let sockets = HashMap::new();
-        Ok(Self { listener, channels_query, channels_broadcast, sockets, alive, })
+        let stats = HashMap::new();
+        Ok(Self { listener, channels_query, channels_broadcast, channels_signal, sockets, stats, alive, })
+    }
+
+    /// Statistics of the accepted connections, by client
+    /// * Output: snapshot of the statistics of each connection
+    pub fn stats(&self) -> Vec<SocketSnapshot> {
+        self.stats.iter().map(|(peer,stats)| stats.snapshot(SocketRole::Server, *peer)).collect()
+    }
+
+    /// Counters of the accepted connections, by client
+    pub (crate) fn socket_metrics(&self) -> Vec<(SocketAddr,Arc<SocketMetrics>)> {
+        self.stats.iter().map(|(peer,stats)| (*peer,stats.clone())).collect()
     }
 
     /// Kill the server
@@ -113,20 +129,23 @@ impl ChannelServer {
         for (_,(h1,h2,)) in self.sockets.drain() { let _ = h1.await; let _ = h2.await; }
         self.channels_query.write().await.clear();
         self.channels_broadcast.write().await.clear();
+        self.channels_signal.write().await.clear();
     }
 
     /// Accept a list of clients
     /// * `clients: &mut HashSet<SocketAddr>` : list of clients identified with their socket address
     /// * `capacity: Option<usize>` : size parameter for defining bounded or unbounnded channels
     /// Nothing or an error
-    pub async fn accept(&mut self, clients: &mut HashSet<SocketAddr>, capacity: Option<usize>,) -> Result<(),String> {
+    pub async fn accept(&mut self, clients: &mut HashSet<SocketAddr>, capacity: Option<usize>,) -> Result<(),SilxError> {
         match self.listener.accept().await {
             Ok((mut stream,socket)) => {
-                let ok = match SilxProtocols::pop_socket(&mut stream).await {
-                    Ok(soa) => clients.remove(&soa),
-                    Err(_)  => return Err(format!("Failed to read client id")),
+                let peer = match SilxProtocols::pop_socket(&mut stream).await {
+                    Ok(soa) => soa,
+                    Err(e)  => return Err(SilxError::protocol("Failed to read client id").with_source(e)),
                 };
-                if !ok { return Err(format!("Connecting client is not allowed")); }
+                if !clients.remove(&peer) { return Err(SilxError::config(format!("Connecting client {peer} is not allowed"))); }
+                let stats = Arc::new(SocketMetrics::default());
+                self.stats.insert(peer, stats.clone());
                 let (mut reader, mut writer) = stream.into_split();
                 let (sender,mut receiver) = match capacity {
                     Some(size) => {
@@ -142,75 +161,83 @@ impl ChannelServer {
                 let do_loop = Arc::new(RwLock::new(true));
                 let channels_broadcast = self.channels_broadcast.clone();
                 let channels_query = self.channels_query.clone();
-                let handlein = spawn({ let do_loop = do_loop.clone(); let sender = sender.clone(); async move { 
+                let channels_signal = self.channels_signal.clone();
+                let span = info_span!("socket", role = "server", peer = %peer);
+                let handlein = spawn_in(span.clone(), { let do_loop = do_loop.clone(); let sender = sender.clone(); let stats = stats.clone(); async move { 
                     while *alive.read().await && *do_loop.read().await {
-                        match SilxProtocols::pop_tagged_serialized_data(&mut reader,).await {
+                        match SilxProtocols::pop_tagged_serialized_data(&mut reader,).await.inspect(|nts| stats.received(nts.data_len())) {
                             Err(st)         => { 
-                                if VERBOSE { eprintln!("Input net error: {}", st); }
+                                debug!("input net error: {}", st);
                                 *do_loop.write().await = false; 
                             },
                             Ok(NTSData::Broadcast(channel, data,)) => {
                                 match channels_broadcast.read().await.get(&channel) {
-                                    None         => { eprintln!("unknown channel -> Broadcast {channel}"); *do_loop.write().await = false; },
-                                    Some(brsend) => { match brsend.send(data) {
-                                        Err(e)  => eprintln!("Net error: failed to broadcast -> {}",e),
-                                        Ok(_)  => (),
-                                    } }
+                                    None         => { error!(channel, "unknown channel -> Broadcast"); *do_loop.write().await = false; },
+                                    Some(brsend) => if let Err(e) = brsend.send(data) {
+                                        warn!(channel, "net error: failed to broadcast -> {}",e);
+                                    },
                                 }
                             },
                             Ok(NTSData::Query(channel, query, data,)) => {
                                 match channels_query.read().await.get(&channel) {
-                                    None         => { eprintln!("unknown channel -> Query {channel}"); *do_loop.write().await = false; },
+                                    None         => { error!(channel, "unknown channel -> Query"); *do_loop.write().await = false; },
                                     Some(brsend) => {
                                         let (ossender,osreceiver) = SerializedDataOneshot::channel();
                                         spawn({ let sender = sender.clone(); async move {
                                             match osreceiver.await {
-                                                Err(e)    => eprintln!("Net error: failed to read reply -> {}",e),
+                                                Err(e)    => warn!(channel, "net error: failed to read reply -> {}",e),
                                                 Ok(data) => {
                                                     match sender.send(NTSData::Reply(channel,query,data,)).await {
                                                         Ok(()) => (),
                                                         Err(e) => {
-                                                            eprintln!("failed to send back data -> {}",e);// *do_loop.write().await = false;
+                                                            warn!(channel, "failed to send back data -> {}",e);// *do_loop.write().await = false;
                                                         },
                                                     }
                                                 },
                                             };
                                         }});
-                                        match brsend.send((data, ossender)).await {
-                                            Err(e) => { eprintln!("Net error: failed to send final query -> {}",e) },
-                                            Ok(()) => (),
+                                        if let Err(e) = brsend.send((data, ossender)).await {
+                                            warn!(channel, "net error: failed to send final query -> {}",e);
                                         } 
                                     }
                                 }
                             },
-                            Ok(NTSData::Reply(..)) => { eprintln!("Unexpected reply tag error"); *do_loop.write().await = false; },
+                            Ok(NTSData::Signal(channel, data,)) => {
+                                match channels_signal.read().await.get(&channel) {
+                                    None         => { error!(channel, "unknown channel -> Signal"); *do_loop.write().await = false; },
+                                    Some(sgsend) => { sgsend.send_replace(data); }, // replace, so that the signal is kept even without receiver
+                                }
+                            },
+                            Ok(NTSData::Reply(..)) => { error!("unexpected reply tag error"); *do_loop.write().await = false; },
                         }
                     } 
                 }});
                 let alive = self.alive.clone();
-                let handleout = spawn({ let do_loop = do_loop.clone(); async move { 
+                let handleout = spawn_in(span, { let do_loop = do_loop.clone(); async move { 
                     while *alive.read().await && *do_loop.read().await {
                         match receiver.recv().await {
                             None                                       => {
-                                if VERBOSE && *alive.read().await { eprintln!("output net error: channel closed"); }
+                                if *alive.read().await { debug!("output net error: channel closed"); }
                             },
                             Some(NTSData::Reply(channel,query,data,)) => {
-                                match SilxProtocols::push_tagged_serialized_data(&mut writer,&NTSData::Reply(channel,query,data,)).await {
-                                    Ok(()) => (),
+                                let nts_dat = NTSData::Reply(channel,query,data,);
+                                match SilxProtocols::push_tagged_serialized_data(&mut writer,&nts_dat).await {
+                                    Ok(()) => stats.sent(nts_dat.data_len()),
                                     Err(e) => {
-                                        eprintln!("output net error: failed to post reply -> {}",e); *do_loop.write().await = false;
+                                        stats.error();
+                                        error!("output net error: failed to post reply -> {}",e); *do_loop.write().await = false;
                                     },
                                 }
                             },
                             Some(_)                                    => {
-                                eprintln!("output net error: unexpected Query or Broadcast"); *do_loop.write().await = false;
+                                error!("output net error: unexpected Query, Broadcast or Signal"); *do_loop.write().await = false;
                             },
                         }
                     } 
                 }});
                 self.sockets.insert(socket, (handlein,handleout)); Ok(())
             },
-            Err(_) => Err("Failed to receive channel connections".to_string()),
+            Err(e) => Err(SilxError::io("Failed to receive channel connections", e)),
         }
     }
 
@@ -238,10 +265,26 @@ impl ChannelServer {
         let (sender,receiver) = ArchBroadcast::channel::<U>(capacity).await;
         self.channels_broadcast.write().await.insert(channel,sender.inner().instance()); receiver
     }
+
+    /// Register a signal receiver for archived data
+    /// * `channel: ChannelIdType` : channel identifier
+    /// * `U` : type of the data; needs to implement `SlxData`
+    /// * Output: signal receiver for archived data
+    pub async fn receiver_signal<U>(&mut self, channel: ChannelIdType,) -> ArchSignalReceiver<U> where U: SlxData {
+        let (sender,receiver) = ArchSignal::channel::<U>();
+        self.channels_signal.write().await.insert(channel,sender.inner()); receiver
+    }
 }
 
 impl ChannelClient {
 
+    /// Statistics of the connection to the channel server
+    /// * Output: snapshot of the statistics of the connection
+    pub fn stats(&self) -> SocketSnapshot { self.stats.snapshot(SocketRole::Client, self.peer) }
+
+    /// Counters of the connection to the channel server
+    pub (crate) fn socket_metrics(&self) -> (SocketAddr,Arc<SocketMetrics>) { (self.peer,self.stats.clone()) }
+
     /// Kill the client
     pub async fn kill(&mut self) {
         *self.alive.write().await = false;
@@ -256,45 +299,47 @@ impl ChannelClient {
     /// * `addr: A` : socket address of the channel server
     /// * `A: ToSocketAddrs` : type of the server socket address
     /// * Output: the channel client or an error
-    pub async fn connect<A: ToSocketAddrs>(id_client: SocketAddr, addr: A) -> Result<Self,String,> { 
+    pub async fn connect<A: ToSocketAddrs>(id_client: SocketAddr, addr: A) -> Result<Self,SilxError,> { 
         match TcpStream::connect(addr).await {
-            Err(e)  => Err(format!("Failed to connect channel client -> {e}")),
+            Err(e)  => Err(SilxError::io("Failed to connect channel client", e)),
             Ok(mut ts)  => {
-                if SilxProtocols::push_socket(&mut ts,&id_client).await.is_err() { return Err(format!("failed to send client id")); }
+                if let Err(e) = SilxProtocols::push_socket(&mut ts,&id_client).await { return Err(SilxError::protocol("failed to send client id").with_source(e)); }
+                let peer = ts.peer_addr().map_err(|e| SilxError::io("failed to get server address", e))?;
+                let stats = Arc::new(SocketMetrics::default());
                 let alive = Arc::new(RwLock::new(true));
                 let do_loop = Arc::new(RwLock::new(true));
                 let (mut reader,writer,) = ts.into_split();
                 let writer = Arc::new(Mutex::new(writer));
                 let channels_reply: Arc<Mutex<FnvHashMap<ChannelIdType, Slab<SerializedDataOneshotSender>>>> = Arc::new(Mutex::new(Default::default()));
                 let handles: Arc<RwLock<FnvHashMap<ChannelIdType,JoinHandle<()>>>> = Arc::new(RwLock::new(Default::default()));
-                let handle_reply = spawn({
+                let handle_reply = spawn_in(info_span!("socket", role = "client", peer = %peer), {
                     let alive = alive.clone(); 
-                    let do_loop = do_loop.clone(); let channels_reply = channels_reply.clone(); let handles = handles.clone(); async move {
+                    let do_loop = do_loop.clone(); let channels_reply = channels_reply.clone(); let handles = handles.clone(); let stats = stats.clone(); async move {
                         while *alive.read().await && *do_loop.read().await {
-                            match SilxProtocols::pop_tagged_serialized_data(&mut reader,).await {
+                            match SilxProtocols::pop_tagged_serialized_data(&mut reader,).await.inspect(|nts| stats.received(nts.data_len())) {
                                 Err(st)         => { 
-                                    if VERBOSE { eprintln!("Input net error: {}", st); }
+                                    debug!("input net error: {}", st);
                                     *do_loop.write().await = false; 
                                 },
                                 Ok(NTSData::Reply(channel,query,data,)) => {
                                     match channels_reply.lock().await.get_mut(&channel) {
-                                        None         => { eprintln!("unknown channel -> {channel}"); *do_loop.write().await = false; },
+                                        None         => { error!(channel, "unknown channel"); *do_loop.write().await = false; },
                                         Some(slab) => {
                                             match slab.try_remove(query as usize) {
                                                 None   => { 
-                                                    eprintln!("unknown oneshot reply channel"); *do_loop.write().await = false; 
+                                                    error!(channel, "unknown oneshot reply channel"); *do_loop.write().await = false; 
                                                 },
                                                 Some(os) => {
                                                     if os.send(data).is_err() {
-                                                        eprintln!("net oneshot reply: failed to send data"); // *do_loop.write().await = false; 
+                                                        warn!(channel, "net oneshot reply: failed to send data"); // *do_loop.write().await = false; 
                                                     }
                                                 },
                                             }
                                         }
                                     }
                                 },
-                                Ok(NTSData::Broadcast(..)) | Ok(NTSData::Query(..)) => {
-                                    eprintln!("Unexpected net teg error"); *do_loop.write().await = false; 
+                                Ok(NTSData::Broadcast(..)) | Ok(NTSData::Query(..)) | Ok(NTSData::Signal(..)) => {
+                                    error!("unexpected net tag error"); *do_loop.write().await = false; 
                                 },
                             }
                         }
@@ -302,7 +347,7 @@ impl ChannelClient {
                         handles.write().await.clear();
                     }
                 });
-                Ok(Self { channels_reply, handle_reply, handles, writer, do_loop, alive, })
+                Ok(Self { channels_reply, handle_reply, handles, writer, peer, stats, do_loop, alive, })
             }
         }
     }
@@ -322,7 +367,7 @@ impl ChannelClient {
         let receiver = receiver.inner();
         let channels_reply = self.channels_reply.clone();
         channels_reply.lock().await.insert(channel,Slab::new());
-        let handle = spawn({ let alive = self.alive.clone(); let do_loop = self.do_loop.clone(); let writer = self.writer.clone(); async move {
+        let handle = spawn_in(info_span!("socket", role = "client", peer = %self.peer, channel), { let alive = self.alive.clone(); let do_loop = self.do_loop.clone(); let writer = self.writer.clone(); let stats = self.stats.clone(); async move {
             while *alive.read().await && *do_loop.read().await {
                 match receiver.recv().await {
                     Ok((data,ossender)) => {
@@ -331,17 +376,17 @@ impl ChannelClient {
                                 let query = slab.insert(ossender) as QueryIdType;
                                 let writer = &mut *writer.lock().await;
                                 let nts_dat = NTSData::Query(channel,query,data,);
-                                if SilxProtocols::push_tagged_serialized_data(writer, &nts_dat).await.is_err() {
-                                    eprintln!("failed to push query {} on channel {}",query,channel); *do_loop.write().await = false;
+                                if SilxProtocols::push_tagged_serialized_data(writer, &nts_dat).await.inspect(|_| stats.sent(nts_dat.data_len())).is_err() {
+                                    stats.error(); error!(query, "failed to push query"); *do_loop.write().await = false;
                                 }
                             },
                             None       => {
-                                eprintln!("failed to find slab for channel {}",channel); *do_loop.write().await = false; 
+                                error!("failed to find slab for channel"); *do_loop.write().await = false; 
                             },
                         }
                     },
                     Err(_)               => {
-                        if VERBOSE { eprintln!("failed to receive from channel {}", channel); }
+                        debug!("failed to receive from channel");
                         *do_loop.write().await = false; 
                     },
                 }
@@ -358,21 +403,21 @@ impl ChannelClient {
     pub async fn sender_broadcast<U>(&mut self, channel: ChannelIdType, capacity: usize,) -> RootArchBroadcastSender<U> where U: SlxData {
         let (sender,receiver) = ArchBroadcast::channel::<U>(capacity).await;
         let mut receiver = receiver.inner().instance();
-        let handle = spawn({ let alive = self.alive.clone(); let do_loop = self.do_loop.clone(); let writer = self.writer.clone(); async move {
+        let handle = spawn_in(info_span!("socket", role = "client", peer = %self.peer, channel), { let alive = self.alive.clone(); let do_loop = self.do_loop.clone(); let writer = self.writer.clone(); let stats = self.stats.clone(); async move {
             while *alive.read().await && *do_loop.read().await {
                 match receiver.recv().await {
                     Ok(data) => {
                         let writer = &mut *writer.lock().await;
                         let nts_dat = NTSData::Broadcast(channel,data,);
                         match SilxProtocols::push_tagged_serialized_data(writer, &nts_dat).await {
-                            Ok(()) => (),
+                            Ok(()) => stats.sent(nts_dat.data_len()),
                             Err(e) => {
-                                eprintln!("net error: failed to post broadcast -> {}",e); *do_loop.write().await = false;
+                                stats.error(); error!("net error: failed to post broadcast -> {}",e); *do_loop.write().await = false;
                             },
                         }
                     },
                     Err(_)               => {
-                        if VERBOSE { eprintln!("failed to receive from channel {}", channel); }
+                        debug!("failed to receive from channel");
                         *do_loop.write().await = false; 
                     },
                 }
@@ -381,6 +426,38 @@ impl ChannelClient {
         self.handles.write().await.insert(channel,handle); sender
     }
 
+    /// Register a signal sender for archived data
+    /// * Only the last signal is transmitted: intermediate signals may be skipped when the net is slower than the sender
+    /// * `channel: ChannelIdType` : channel identifier
+    /// * `U` : type of the data; needs to implement `SlxData`
+    /// * Output: signal sender for archived data
+    pub async fn sender_signal<U>(&mut self, channel: ChannelIdType,) -> ArchSignalSender<U> where U: SlxData {
+        let (sender,receiver) = ArchSignal::channel::<U>();
+        let mut receiver = receiver.inner();
+        let handle = spawn_in(info_span!("socket", role = "client", peer = %self.peer, channel), { let alive = self.alive.clone(); let do_loop = self.do_loop.clone(); let writer = self.writer.clone(); let stats = self.stats.clone(); async move {
+            while *alive.read().await && *do_loop.read().await {
+                match receiver.changed().await {
+                    Ok(()) => {
+                        if let Some(data) = receiver.borrow_and_update() {
+                            let writer = &mut *writer.lock().await;
+                            let nts_dat = NTSData::Signal(channel,data,);
+                            match SilxProtocols::push_tagged_serialized_data(writer, &nts_dat).await {
+                                Ok(()) => stats.sent(nts_dat.data_len()),
+                                Err(e) => {
+                                    stats.error(); error!("net error: failed to post signal -> {}",e); *do_loop.write().await = false;
+                                },
+                            }
+                        }
+                    },
+                    Err(_)               => {
+                        debug!("failed to receive from channel");
+                        *do_loop.write().await = false; 
+                    },
+                }
+            }
+        }});
+        self.handles.write().await.insert(channel,handle); sender
+    }
 }
 
 /// Experimentation example of channel server
@@ -407,6 +484,8 @@ pub async fn exp_channel_server() {
     // root channels
     let root_broadcast_out = channel_server.receiver_broadcast::<[u32;3]>(123,size).await;
     let root_broadcast_in = channel_client.sender_broadcast::<[u32;3]>(123,size).await;
+    let mut signal_out = channel_server.receiver_signal::<[u32;3]>(133).await;
+    let signal_in = channel_client.sender_signal::<[u32;3]>(133).await;
     // active channels
     let query_out1 = channel_server.receiver_query::<[u32;5],[u32;5]>(113,osize).await;
     let query_in1 = channel_client.sender_query::<[u32;5],[u32;5]>(113,osize).await;
@@ -444,6 +523,16 @@ pub async fn exp_channel_server() {
     let arch_data = broadcast_out2.recv().await.expect("Failed to read bytes");
     let bytes = arch_data.archive_ref().expect("fail to get archive ref");
     println!("received bytes = {:?}",bytes);
+
+    // signal
+    println!("=== signal =========================");
+    let bytes = [7u32,5,3].arch_sized().expect("failed to serialize");
+    let _ = signal_in.send(bytes);
+    println!("signal sent");
+    signal_out.changed().await.expect("Failed to receive signal");
+    let arch_data = signal_out.borrow_and_update().expect("Signal is undefined");
+    let bytes = arch_data.archive_ref().expect("fail to get archive ref");
+    println!("received signal = {:?}",bytes);
    
     // query
     println!("=== query ==========================");
@@ -8,7 +8,7 @@ use rkyv::{
     Archive, Serialize, ser::serializers::AllocSerializer,  Deserialize,
 };
 
-use crate::NALLOC;
+use crate::{ NALLOC, shared::error::SilxError, };
 use super::ser_data::SerializedData;
 
 /// A marker for data exchanged through silx channels
@@ -34,7 +34,7 @@ impl<U> ArchData<U> where U: SlxData {
     pub(super) fn from_bytes(bytes: SerializedData) -> Self { Self { bytes, phantom: PhantomData, } } // allowed only for internal use
 } 
 impl<U> ArchData<U> where U: SlxData {
-    pub fn new_sized(data: &U) -> Result<Self, String> where U: Sized + Archive + Serialize<AllocSerializer<NALLOC>> {
+    pub fn new_sized(data: &U) -> Result<Self, SilxError> where U: Sized + Archive + Serialize<AllocSerializer<NALLOC>> {
         let bytes = SerializedData::new_sized(data)?;
         Ok(Self::from_bytes(bytes))
     }
@@ -45,24 +45,24 @@ impl<U> ArchData<U> where U: SlxData {
     
     /// Get reference to the archived data within archive data (zero-copy)
     /// * Output: the reference or an error
-    pub fn archive_ref(&self) -> Result<&<U as Archive>::Archived,String> where U: Archive {
+    pub fn archive_ref(&self) -> Result<&<U as Archive>::Archived,SilxError> where U: Archive {
         self.bytes.archive_ref::<U>()
     }
 
     /// Get pinned mutable reference to the archived data within archive data (zero-copy)
     /// * Output: the pinned mutable reference or an error
-    pub fn archive_mut(self: Pin<&mut Self>) -> Result<Pin<&mut <U as Archive>::Archived>,String> where U: Archive {
+    pub fn archive_mut(self: Pin<&mut Self>) -> Result<Pin<&mut <U as Archive>::Archived>,SilxError> where U: Archive {
         let bytes: Pin<&mut SerializedData> = unsafe { self.map_unchecked_mut(|s| &mut s.bytes) };
         bytes.archive_ref_mut::<U>()
     }
 
     /// Unarchive the archive data
     /// * Output: the data or an error
-    pub fn unarchive(&self) -> Result<U,String> where U: Archive, U::Archived: Deserialize<U,rkyv::Infallible> {
+    pub fn unarchive(&self) -> Result<U,SilxError> where U: Archive, U::Archived: Deserialize<U,rkyv::Infallible> {
         let ref_arc = self.bytes.archive_ref::<U>()?;
         Ok(match U::Archived::deserialize(ref_arc, &mut rkyv::Infallible){
              Ok(w) => w,
-             Err(e) => return Err(format!("Failed to unarchive -> {e}")),
+             Err(e) => return Err(SilxError::archive(format!("Failed to unarchive -> {e}"))),
         })
     }
 } 
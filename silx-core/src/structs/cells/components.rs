@@ -29,6 +29,8 @@ pub enum Channelling {
     NetQueryRecv(BinQyReceiver),
     NetBroadcastSend(BinBcSender),
     NetBroadcastRecv(BinBcReceiver),
+    NetSignalSend(BinSgSender),
+    NetSignalRecv(BinSgReceiver),
     QuerySend(BinQySender),
     QueryRecv(BinQyReceiver),
     BroadcastSend(BinBcSender),
@@ -56,13 +58,18 @@ impl Ping {
 /// doc to be defined
 pub struct Flag {
     activated: RwLock<bool>,
+    failed: RwLock<bool>,
     notifier: Notify,
 }
 impl Flag {
-    pub fn new() -> Self { Self { activated: RwLock::new(false), notifier: Notify::new(), } }
+    pub fn new() -> Self { Self { activated: RwLock::new(false), failed: RwLock::new(false), notifier: Notify::new(), } }
     pub async fn go(&self) { if !*self.activated.read().await { self.notifier.notified().await; } }
     pub async fn activate(&self) { *self.activated.write().await = true; self.notifier.notify_waiters(); }
     pub async fn desactivate(&self) { *self.activated.write().await = false; } 
+    // mark the channel as failed (done by the subprocess, when the servant process panics)
+    pub async fn fail(&self) { *self.failed.write().await = true; }
+    // check if the channel has failed (done by the supervisor)
+    pub async fn is_failed(&self) -> bool { *self.failed.read().await }
 }
 
 
@@ -3,7 +3,7 @@ use std::time::Duration;
 use tokio::sync::{ mpsc as msc, oneshot as osh, };
 
 use crate::{
-    shared::id_tools::{ TaskId, AcknowledgeId, }, 
+    shared::{ id_tools::{ TaskId, AcknowledgeId, }, error::SilxError, }, 
     ChannelIdType, traits::FullId,
     structs::archmod::archannel::{ 
         SerializedDataQuerySender as BinQySender, SerializedDataQueryReceiver as BinQyReceiver, 
@@ -35,6 +35,19 @@ pub enum MsgFromServant {
     StaledChl(TaskId,ChannelIdType),
     /// Request a new task for shuting down the cluster
     Shutdown(TaskId,),
+    /// Report of a supervision event by the supervisor of a servant
+    Supervision(TaskId,SupervisionEvent),
+}
+
+#[derive(Clone, Copy, Debug, PartialEq,)]
+/// Supervision event reported by the supervisor of a servant to the master of the cluster
+pub enum SupervisionEvent {
+    /// The failed servant has been stopped
+    Stopped,
+    /// The failed servant has been restarted; the number of restarts within the supervision period is given
+    Restarted(usize),
+    /// The failure of the servant is escalated: the cluster is shut down
+    Escalated,
 }
 
 /// doc to be defined
@@ -52,6 +65,7 @@ pub enum ReplyToMaster {
 
 /// doc to be defined
 pub enum CtrlCell {
+    NameChl(ChannelIdType, String),
     SetChl(ChannelIdType, SetChannel),
     TurnOnChl(ChannelIdType),
     TurnOffChl(ChannelIdType),
@@ -87,6 +101,17 @@ pub enum SetChannel {
         data_type: FullId, // data_type is the type of received data
         receiver: BinBcReceiver,
     },
+    // Nota: signal is multisender to multireceiver
+    NetSignalSender {
+        max_ping: Duration,
+        data_type: FullId, // data_type is the type of received data
+        sender: BinSgSender,
+    },
+    NetSignalReceiver {
+        max_ping: Duration,
+        data_type: FullId, // data_type is the type of received data
+        receiver: BinSgReceiver,
+    },
     // Nota: query is multisender to multireceiver
     QuerySender { // defining receiver of the channel
         max_ping: Duration,
@@ -133,19 +158,19 @@ pub type SendToServant = msc::Sender<(MsgFromMaster,SenderToMaster)>;
 pub type RecvFromMaster = msc::Receiver<(MsgFromMaster,SenderToMaster)>;
 
 impl MsgFromServant {
-    pub async fn send(self, sender: &SendToMaster,) -> Result<osh::Receiver<ReplyToServant>,String> {
+    pub async fn send(self, sender: &SendToMaster,) -> Result<osh::Receiver<ReplyToServant>,SilxError> {
         let (osender,oreceiver,) = osh::channel();
         match sender.send((self,osender)).await {
-            Ok(()) => Ok(oreceiver), Err(_) => Err(format!("MsgFromServant: failed to send message")),
+            Ok(()) => Ok(oreceiver), Err(_) => Err(SilxError::protocol("MsgFromServant: failed to send message")),
         }
     }
 }
 
 impl MsgFromMaster {
-    pub async fn send(self, sender: &SendToServant,) -> Result<osh::Receiver<ReplyToMaster>,String> {
+    pub async fn send(self, sender: &SendToServant,) -> Result<osh::Receiver<ReplyToMaster>,SilxError> {
         let (osender,oreceiver,) = osh::channel();
         match sender.send((self,osender)).await {
-            Ok(()) => Ok(oreceiver), Err(_) => Err(format!("MsgFromMaster: failed to send message")),
+            Ok(()) => Ok(oreceiver), Err(_) => Err(SilxError::protocol("MsgFromMaster: failed to send message")),
         }
     }
 }
\ No newline at end of file
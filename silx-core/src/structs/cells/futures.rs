@@ -1,26 +1,42 @@
 use crate::{
-    shared::id_tools::AcknowledgeId,
+    shared::{ id_tools::AcknowledgeId, trace::spawn, },
     ChannelIdType,
     traits::FullId,
+    builder::telemetry::ChannelMetrics,
     structs::{ 
         cells::{
             components::{ Channelling, Flag, Ping, MembraneType, Mapper, Assert, },
             ctrl_message::ReplyToMaster,
         },
-        archmod::archannel::{
+        archmod::{ ser_data::SerializedData, archannel::{
             SerializedDataQuerySender as BinQySender, SerializedDataQueryReceiver as BinQyReceiver, 
             RootSerializedDataBroadcastSender as BinBcSender, RootSerializedDataBroadcastReceiver as BinBcReceiver, 
             SerializedDataSignalSender as BinSgSender, SerializedDataSignalReceiver as BinSgReceiver,         
             SerializedDataDispatchSender as BinDpSender, SerializedDataDispatchReceiver as BinDpReceiver,
-        },
+        }, },
     },
 };
-use std::{ sync::Arc, time::Duration, future::Future, };
+use std::{ sync::Arc, time::{ Duration, Instant, }, future::Future, };
 use async_scoped::TokioScope;
-use tokio::{ spawn, sync::RwLock, };
+use tokio::{ sync::{ RwLock, broadcast::error::RecvError, }, };
+use tracing::{ error, warn, };
 
 // Channel setting
 
+/// Context of the futures setting a channel of a servant
+pub  (crate) struct ChannelContext {
+    /// id of the task
+    pub (crate) acknowledge_id: AcknowledgeId,
+    /// cell membrane (contains channels informations and status)
+    pub (crate) membrane: MembraneType,
+    /// channel identifier
+    pub (crate) channel: ChannelIdType,
+    /// counters of the channel
+    pub (crate) metrics: Arc<ChannelMetrics>,
+    /// max duration when pinging the channel
+    pub (crate) max_ping: Duration,
+}
+
 /// Future builder replying undefined status to the master
 pub  (crate) enum FutureUndefined {}
 
@@ -36,6 +52,12 @@ pub  (crate) enum FutureNetBroadCastSender {}
 /// Future builder implementing broadcast receving cycle from a servant of another cluster  
 pub  (crate) enum FutureNetBroadCastReceiver {}
 
+/// Future builder implementing signal (a reference to archived data) sending cycle to a servant of another cluster  
+pub  (crate) enum FutureNetSignalSender {}
+
+/// Future builder implementing signal (a reference to archived data) receving cycle from a servant of another cluster  
+pub  (crate) enum FutureNetSignalReceiver {}
+
 
 /// Future builder implementing sending query cycle to a servant of same cluster  
 pub  (crate) enum FutureQuerySender {}
@@ -80,22 +102,19 @@ impl FutureUndefined {
 
 impl FutureNetQuerySender {
     /// Build future implementing sending query cycle to a servant of another cluster  
-    /// * `acknowledge_id: AcknowledgeId` : id of the task
-    /// * `membrane: MembraneType` : cell membrane (contains channels informations and status)
-    /// * `channel: ChannelIdType` : channel identifier
+    /// * `context: ChannelContext` : id of the task, cell membrane, channel identifier, counters of the channel and max duration when pinging the channel
     /// * `in_chan: BinDpReceiver` : channel intern receiver
     /// * `in_type: FullId` : identifier of the data type of intern receiver
     /// * `out_chan: BinDpSender` : channel intern sender
     /// * `out_type: FullId` : identifier of the data type of intern sender
-    /// * `max_ping: Duration` : max duration when pinging the channel
-    /// * `query_type: FullId` : identifier of the data type of query
-    /// * `reply_type: FullId` :  identifier of the data type of reply
+    /// * `(query_type, reply_type): (FullId, FullId)` : identifiers of the data types of query and reply
     /// * `sender: BinQySender` : Query sender
     /// * Output: future replying to master
-    pub  (crate) fn new(acknowledge_id: AcknowledgeId, membrane: MembraneType, channel: ChannelIdType,
+    pub  (crate) fn new(context: ChannelContext,
         in_chan: BinDpReceiver, in_type: FullId, out_chan: BinDpSender, out_type: FullId,
-        max_ping: Duration, query_type: FullId, reply_type: FullId, sender: BinQySender,
+        (query_type, reply_type): (FullId, FullId), sender: BinQySender,
     ) -> impl Future<Output = ReplyToMaster> {
+        let ChannelContext { acknowledge_id, membrane, channel, metrics, max_ping, } = context;
         async move { 
             if in_type == query_type && out_type == reply_type {
                 let channelling = Channelling::NetQuerySend(sender.clone());
@@ -108,11 +127,16 @@ impl FutureNetQuerySender {
                         ping.unping(); //unping pingger
                         match in_chan.recv().await {
                             Ok(bytes)   => {
+                                metrics.queue(in_chan.len());
+                                let start = Instant::now();
+                                let len = bytes.data.len();
                                 match bytes.send(&sender).await { 
                                     Err(_)        => { alive = false; },
                                     Ok(oreceiver) => {
+                                        metrics.sent(len);
                                         match oreceiver.await {
                                             Ok(bytes) => {
+                                                metrics.received(bytes.data.len()); metrics.handled(start.elapsed());
                                                 if out_chan.send(bytes).await.is_err() { alive = false; }
                                             },
                                             Err(_)    => alive = false, // channel is closed : stop the future
@@ -133,20 +157,17 @@ impl FutureNetQuerySender {
 
 impl FutureNetQueryReceiver {
     /// Build future implementing receving query cycle from a servant of another cluster  
-    /// * `acknowledge_id: AcknowledgeId` : id of the task
-    /// * `membrane: MembraneType` : cell membrane (contains channels informations and status)
-    /// * `channel: ChannelIdType` : channel identifier
+    /// * `context: ChannelContext` : id of the task, cell membrane, channel identifier, counters of the channel and max duration when pinging the channel
     /// * `in_type: FullId` : identifier of the data type of intern recceiver
     /// * `out_type: FullId` : identifier of the data type of intern sender
     /// * `mapper: Mapper` : query to answer mapper
-    /// * `max_ping: Duration` : max duration when pinging the channel
-    /// * `query_type: FullId` : identifier of the data type of query
-    /// * `reply_type: FullId` :  identifier of the data type of reply
+    /// * `(query_type, reply_type): (FullId, FullId)` : identifiers of the data types of query and reply
     /// * `receiver: BinQyReceiver` : Query receiver
     /// * Output: future replying to master
-    pub  (crate) fn new(acknowledge_id: AcknowledgeId, membrane: MembraneType, channel: ChannelIdType,
-        in_type: FullId, out_type: FullId, mapper: Mapper, max_ping: Duration, query_type: FullId, reply_type: FullId, receiver: BinQyReceiver,
+    pub  (crate) fn new(context: ChannelContext,
+        in_type: FullId, out_type: FullId, mapper: Mapper, (query_type, reply_type): (FullId, FullId), receiver: BinQyReceiver,
     ) -> impl Future<Output = ReplyToMaster> {
+        let ChannelContext { acknowledge_id, membrane, channel, metrics, max_ping, } = context;
         async move { 
             let aknowledge_id = acknowledge_id;
             if in_type == query_type && out_type == reply_type {
@@ -160,6 +181,8 @@ impl FutureNetQueryReceiver {
                         ping.unping(); //unping pingger
                         match receiver.recv().await {
                             Ok((mut bytes,resender)) => {
+                                metrics.received(bytes.data.len()); metrics.queue(receiver.len());
+                                let start = Instant::now();
                                 match unsafe {
                                     TokioScope::scope_and_collect(|scope| {
                                         scope.spawn(mapper(std::mem::transmute(&mut bytes)));
@@ -167,13 +190,15 @@ impl FutureNetQueryReceiver {
                                 }.await.1[0] {
                                     Ok(_) => (),
                                     Err(_) => {
-                                        alive = false;
-                                        eprintln!("spawning failure for task {}",acknowledge_id);
+                                        alive = false; flag.fail().await;
+                                        error!(task = %acknowledge_id, "spawning failure");
                                     },
                                 };
+                                metrics.handled(start.elapsed());
+                                let len = bytes.data.len();
                                 if resender.send(bytes).is_err() { // resend error does not end the process
-                                    eprintln!("Failed to resend data");
-                                }
+                                    metrics.dropped(); warn!("failed to resend data");
+                                } else { metrics.sent(len); }
                             },
                             Err(_)    => alive = false, // channel is closed : stop the future
                         };
@@ -188,18 +213,16 @@ impl FutureNetQueryReceiver {
 
 impl FutureNetBroadCastSender {
     /// Build future implementing broadcast sending cycle to a servant of another cluster  
-    /// * `acknowledge_id: AcknowledgeId` : id of the task
-    /// * `membrane: MembraneType` : cell membrane (contains channels informations and status)
-    /// * `channel: ChannelIdType` : channel identifier
+    /// * `context: ChannelContext` : id of the task, cell membrane, channel identifier, counters of the channel and max duration when pinging the channel
     /// * `in_chan: BinDpReceiver` : channel intern receiver
     /// * `in_type: FullId` : identifier of the data type of intern receiver
-    /// * `max_ping: Duration` : max duration when pinging the channel
     /// * `data_type: FullId` : identifier of broadcasted data type
     /// * `sender: BinBcSender` : Broadcast sender
     /// * Output: future replying to master
-    pub  (crate) fn new(acknowledge_id: AcknowledgeId, membrane: MembraneType, channel: ChannelIdType,
-        in_chan: BinDpReceiver, in_type: FullId,  max_ping: Duration, data_type: FullId, sender: BinBcSender,
+    pub  (crate) fn new(context: ChannelContext,
+        in_chan: BinDpReceiver, in_type: FullId, data_type: FullId, sender: BinBcSender,
     ) -> impl Future<Output = ReplyToMaster> {
+        let ChannelContext { acknowledge_id, membrane, channel, metrics, max_ping, } = context;
         async move { 
             if in_type == data_type {
                 let asender = sender.instance();
@@ -212,7 +235,11 @@ impl FutureNetBroadCastSender {
                         flag.go().await; // block process until activation
                         ping.unping(); //unping pingger
                         match in_chan.recv().await {
-                            Ok(bytes) => if asender.send(bytes).is_err() { alive = false; },
+                            Ok(bytes) => {
+                                metrics.queue(in_chan.len());
+                                let len = bytes.data.len();
+                                if asender.send(bytes).is_err() { alive = false; } else { metrics.sent(len); }
+                            },
                             Err(_)    => alive = false, // channel is closed : stop the future
                         };
                     } 
@@ -226,18 +253,16 @@ impl FutureNetBroadCastSender {
 
 impl FutureNetBroadCastReceiver {
     /// Build future implementing broadcast receving cycle from a servant of another cluster  
-    /// * `acknowledge_id: AcknowledgeId` : id of the task
-    /// * `membrane: MembraneType` : cell membrane (contains channels informations and status)
-    /// * `channel: ChannelIdType` : channel identifier
+    /// * `context: ChannelContext` : id of the task, cell membrane, channel identifier, counters of the channel and max duration when pinging the channel
     /// * `out_chan: BinDpSender` : channel intern sender
     /// * `out_type: FullId` : identifier of the data type of intern sender
-    /// * `max_ping: Duration` : max duration when pinging the channel
     /// * `data_type: FullId` : identifier of broadcasted data type
     /// * `receiver: BinBcReceiver` : Broadcast receiver
     /// * Output: future replying to master
-    pub  (crate) fn new(acknowledge_id: AcknowledgeId, membrane: MembraneType, channel: ChannelIdType,
-        out_chan: BinDpSender, out_type: FullId, max_ping: Duration, data_type: FullId, receiver: BinBcReceiver,
+    pub  (crate) fn new(context: ChannelContext,
+        out_chan: BinDpSender, out_type: FullId, data_type: FullId, receiver: BinBcReceiver,
     ) -> impl Future<Output = ReplyToMaster> {
+        let ChannelContext { acknowledge_id, membrane, channel, metrics, max_ping, } = context;
         async move { 
             if out_type == data_type {
                 let mut areceiver = receiver.instance();
@@ -250,7 +275,14 @@ impl FutureNetBroadCastReceiver {
                         flag.go().await; // block process until activation
                         ping.unping(); //unping pingger
                         match areceiver.recv().await {
-                            Ok(bytes) =>  if out_chan.send(bytes).await.is_err() { alive = false; },
+                            Ok(bytes) => {
+                                metrics.received(bytes.data.len()); metrics.queue(areceiver.len());
+                                let start = Instant::now();
+                                if out_chan.send(bytes).await.is_err() { alive = false; } else { metrics.handled(start.elapsed()); }
+                            },
+                            Err(RecvError::Lagged(skipped)) => { // lagging receiver has skipped messages : stop the future
+                                warn!(skipped, "lagging receiver"); metrics.lagged(skipped); alive = false;
+                            },
                             Err(_)    => alive = false, // channel is closed : stop the future
                         };
                     } 
@@ -262,12 +294,111 @@ impl FutureNetBroadCastReceiver {
     }
 }
 
+impl FutureNetSignalSender {
+    /// Build future implementing signal sending cycle to a servant of another cluster  
+    /// * `context: ChannelContext` : id of the task, cell membrane, channel identifier, counters of the channel and max duration when pinging the channel
+    /// * `in_chan: BinDpReceiver` : channel intern receiver
+    /// * `in_type: FullId` : identifier of the data type of intern receiver
+    /// * `data_type: FullId` : identifier of signal data type
+    /// * `sender: BinSgSender` : Signal sender
+    /// * Output: future replying to master
+    pub  (crate) fn new(context: ChannelContext,
+        in_chan: BinDpReceiver, in_type: FullId, data_type: FullId, sender: BinSgSender,
+    ) -> impl Future<Output = ReplyToMaster> {
+        let ChannelContext { acknowledge_id, membrane, channel, metrics, max_ping, } = context;
+        async move { 
+            if in_type == data_type {
+                let channelling = Channelling::NetSignalSend(sender.clone());
+                let flag = Arc::new(Flag::new());
+                let ping = Arc::new(Ping::new(max_ping));
+                let handle =  spawn( { let flag = flag.clone(); let ping = ping.clone(); async move { 
+                    let mut alive = true;
+                    while alive {
+                        flag.go().await; // block process until activation
+                        ping.unping(); //unping pingger
+                        match in_chan.recv().await {
+                            Ok(bytes) => {
+                                metrics.queue(in_chan.len());
+                                let len = bytes.data.len();
+                                if sender.send(bytes).is_err() { alive = false; } else { metrics.sent(len); }
+                            },
+                            Err(_)    => alive = false, // channel is closed : stop the future
+                        };
+                    } 
+                } } );
+                membrane.write().await.insert(channel,(channelling,flag,ping,Arc::new(RwLock::new(handle))));
+                ReplyToMaster::Ok(acknowledge_id)
+            } else { ReplyToMaster::WrongType(acknowledge_id) }
+        }
+    }
+}
+
+impl FutureNetSignalReceiver {
+    /// Build future implementing signal receving cycle from a servant of another cluster  
+    /// * `context: ChannelContext` : id of the task, cell membrane, channel identifier, counters of the channel and max duration when pinging the channel
+    /// * `assert: Assert` : process applied to the reference of the received signal
+    /// * `out_type: FullId` : identifier of the data type of intern sender
+    /// * `data_type: FullId` : identifier of signal data type
+    /// * `receiver: BinSgReceiver` : Signal receiver
+    /// * Output: future replying to master
+    pub  (crate) fn new(context: ChannelContext,
+        assert: Assert, out_type: FullId, data_type: FullId, mut receiver: BinSgReceiver,
+    ) -> impl Future<Output = ReplyToMaster> {
+        let ChannelContext { acknowledge_id, membrane, channel, metrics, max_ping, } = context;
+        async move { 
+            let aknowledge_id = acknowledge_id;
+            if out_type == data_type {
+                let channelling = Channelling::NetSignalRecv(receiver.clone());
+                // Nota: no update at beginning, so that a signal received from the net before this setting is not skipped
+                let flag = Arc::new(Flag::new());
+                let ping = Arc::new(Ping::new(max_ping));
+                let handle =  spawn( { let flag = flag.clone(); let ping = ping.clone(); async move { 
+                    let mut alive = true;
+                    while alive {
+                        flag.go().await; // block process until activation
+                        ping.unping(); //unping pingger
+                        match receiver.changed().await {
+                            Ok(_)   => {
+                                if let Some(bor) = receiver.borrow_and_update() { 
+                                    metrics.received(bor.data.len());
+                                    let start = Instant::now();
+                                    // SAFETY: `scope_and_collect` awaits the spawned assert before returning, so that the
+                                    // reference to `bor`, extended to `'static`, does not outlive `bor`
+                                    match unsafe {
+                                        TokioScope::scope_and_collect(|scope| {
+                                            scope.spawn(assert(std::mem::transmute::<&SerializedData, &'static SerializedData>(&bor)));
+                                        })
+                                    }.await.1[0] {
+                                        Ok(_) => (),
+                                        Err(_) => {
+                                            alive = false; flag.fail().await;
+                                            error!(task = %acknowledge_id, "spawning failure");
+                                        },
+                                    };
+                                    metrics.handled(start.elapsed());
+                                } else { // undefined signal: stop the future and report the failure to the supervisor
+                                    alive = false; flag.fail().await;
+                                    error!(task = %acknowledge_id, "signal is not inited");
+                                }
+                            },
+                            Err(_)  => alive = false, // channel is closed : stop the future
+                        };
+                    } 
+                } } );
+                membrane.write().await.insert(channel,(channelling,flag,ping,Arc::new(RwLock::new(handle))));
+                ReplyToMaster::Ok(aknowledge_id)
+            } else { ReplyToMaster::WrongType(aknowledge_id) }
+        }
+    }
+}
+
 
 impl FutureQuerySender {
-    pub  (crate) fn new(acknowledge_id: AcknowledgeId, membrane: MembraneType, channel: ChannelIdType,
+    pub  (crate) fn new(context: ChannelContext,
         in_chan: BinDpReceiver, in_type: FullId, out_chan: BinDpSender, out_type: FullId,
-        max_ping: Duration, query_type: FullId, reply_type: FullId, sender: BinQySender,
+        (query_type, reply_type): (FullId, FullId), sender: BinQySender,
     ) -> impl Future<Output = ReplyToMaster> {
+        let ChannelContext { acknowledge_id, membrane, channel, metrics, max_ping, } = context;
         async move { 
             if in_type == query_type && out_type == reply_type {
                 let channelling = Channelling::QuerySend(sender.clone());
@@ -280,11 +411,16 @@ impl FutureQuerySender {
                         ping.unping(); //unping pingger
                         match in_chan.recv().await {
                             Ok(bytes)   => {
+                                metrics.queue(in_chan.len());
+                                let start = Instant::now();
+                                let len = bytes.data.len();
                                 match bytes.send(&sender).await { 
                                     Err(_)        => { alive = false; },
                                     Ok(oreceiver) => {
+                                        metrics.sent(len);
                                         match oreceiver.await {
                                             Ok(bytes) => {
+                                                metrics.received(bytes.data.len()); metrics.handled(start.elapsed());
                                                 if out_chan.send(bytes).await.is_err() { alive = false; }
                                             },
                                             Err(_)    => alive = false, // channel is closed : stop the future
@@ -304,9 +440,10 @@ impl FutureQuerySender {
 }
 
 impl FutureQueryReceiver {
-    pub  (crate) fn new(acknowledge_id: AcknowledgeId, membrane: MembraneType, channel: ChannelIdType,
-        in_type: FullId, out_type: FullId, mapper: Mapper, max_ping: Duration, query_type: FullId, reply_type: FullId, receiver: BinQyReceiver,
+    pub  (crate) fn new(context: ChannelContext,
+        in_type: FullId, out_type: FullId, mapper: Mapper, (query_type, reply_type): (FullId, FullId), receiver: BinQyReceiver,
     ) -> impl Future<Output = ReplyToMaster> {
+        let ChannelContext { acknowledge_id, membrane, channel, metrics, max_ping, } = context;
         async move { 
             let aknowledge_id = acknowledge_id;
             if in_type == query_type && out_type == reply_type {
@@ -320,6 +457,8 @@ impl FutureQueryReceiver {
                         ping.unping(); //unping pingger
                         match receiver.recv().await {
                             Ok((mut bytes,resender)) => {
+                                metrics.received(bytes.data.len()); metrics.queue(receiver.len());
+                                let start = Instant::now();
                                 match unsafe {
                                     TokioScope::scope_and_collect(|scope| {
                                         scope.spawn(mapper(std::mem::transmute(&mut bytes)));
@@ -327,13 +466,15 @@ impl FutureQueryReceiver {
                                 }.await.1[0] {
                                     Ok(_) => (),
                                     Err(_) => {
-                                        alive = false;
-                                        eprintln!("spawning failure for task {}", acknowledge_id);
+                                        alive = false; flag.fail().await;
+                                        error!(task = %acknowledge_id, "spawning failure");
                                     },
                                 };
+                                metrics.handled(start.elapsed());
+                                let len = bytes.data.len();
                                 if resender.send(bytes).is_err() { // resend error does not end the process
-                                    eprintln!("Failed to resend data");
-                                }
+                                    metrics.dropped(); warn!("failed to resend data");
+                                } else { metrics.sent(len); }
                             },
                             Err(_)    => alive = false, // channel is closed : stop the future
                         };
@@ -347,9 +488,10 @@ impl FutureQueryReceiver {
 }
 
 impl FutureBroadCastSender {
-    pub  (crate) fn new(acknowledge_id: AcknowledgeId, membrane: MembraneType, channel: ChannelIdType,
-        in_chan: BinDpReceiver, in_type: FullId,  max_ping: Duration, data_type: FullId, sender: BinBcSender,
+    pub  (crate) fn new(context: ChannelContext,
+        in_chan: BinDpReceiver, in_type: FullId, data_type: FullId, sender: BinBcSender,
     ) -> impl Future<Output = ReplyToMaster> {
+        let ChannelContext { acknowledge_id, membrane, channel, metrics, max_ping, } = context;
         async move { 
             if in_type == data_type {
                 let asender = sender.instance();
@@ -362,7 +504,11 @@ impl FutureBroadCastSender {
                         flag.go().await; // block process until activation
                         ping.unping(); //unping pingger
                         match in_chan.recv().await {
-                            Ok(bytes) => if asender.send(bytes).is_err() { alive = false; },
+                            Ok(bytes) => {
+                                metrics.queue(in_chan.len());
+                                let len = bytes.data.len();
+                                if asender.send(bytes).is_err() { alive = false; } else { metrics.sent(len); }
+                            },
                             Err(_)    => alive = false, // channel is closed : stop the future
                         };
                     } 
@@ -375,9 +521,10 @@ impl FutureBroadCastSender {
 }
 
 impl FutureBroadCastReceiver {
-    pub  (crate) fn new(acknowledge_id: AcknowledgeId, membrane: MembraneType, channel: ChannelIdType,
-        out_chan: BinDpSender, out_type: FullId, max_ping: Duration, data_type: FullId, receiver: BinBcReceiver,
+    pub  (crate) fn new(context: ChannelContext,
+        out_chan: BinDpSender, out_type: FullId, data_type: FullId, receiver: BinBcReceiver,
     ) -> impl Future<Output = ReplyToMaster> {
+        let ChannelContext { acknowledge_id, membrane, channel, metrics, max_ping, } = context;
         async move { 
             if out_type == data_type {
                 let mut areceiver = receiver.instance();
@@ -390,7 +537,14 @@ impl FutureBroadCastReceiver {
                         flag.go().await; // block process until activation
                         ping.unping(); //unping pingger
                         match areceiver.recv().await {
-                            Ok(bytes) =>  if out_chan.send(bytes).await.is_err() { alive = false; },
+                            Ok(bytes) => {
+                                metrics.received(bytes.data.len()); metrics.queue(areceiver.len());
+                                let start = Instant::now();
+                                if out_chan.send(bytes).await.is_err() { alive = false; } else { metrics.handled(start.elapsed()); }
+                            },
+                            Err(RecvError::Lagged(skipped)) => { // lagging receiver has skipped messages : stop the future
+                                warn!(skipped, "lagging receiver"); metrics.lagged(skipped); alive = false;
+                            },
                             Err(_)    => alive = false, // channel is closed : stop the future
                         };
                     } 
@@ -403,9 +557,10 @@ impl FutureBroadCastReceiver {
 }
 
 impl FutureSignalSender {
-    pub  (crate) fn new(acknowledge_id: AcknowledgeId, membrane: MembraneType, channel: ChannelIdType,
-        in_chan: BinDpReceiver, in_type: FullId, max_ping: Duration, data_type: FullId, sender: BinSgSender,
+    pub  (crate) fn new(context: ChannelContext,
+        in_chan: BinDpReceiver, in_type: FullId, data_type: FullId, sender: BinSgSender,
     ) -> impl Future<Output = ReplyToMaster> {
+        let ChannelContext { acknowledge_id, membrane, channel, metrics, max_ping, } = context;
         async move { 
             if in_type == data_type {
                 let channelling = Channelling::SignalSend(sender.clone());
@@ -417,7 +572,11 @@ impl FutureSignalSender {
                         flag.go().await; // block process until activation
                         ping.unping(); //unping pingger
                         match in_chan.recv().await {
-                            Ok(bytes) => if sender.send(bytes).is_err() { alive = false; }, // Nota: the economic send_replace is not used for the moment
+                            Ok(bytes) => { // Nota: the economic send_replace is not used for the moment
+                                metrics.queue(in_chan.len());
+                                let len = bytes.data.len();
+                                if sender.send(bytes).is_err() { alive = false; } else { metrics.sent(len); }
+                            },
                             Err(_)    => alive = false, // channel is closed : stop the future
                         };
                     } 
@@ -430,9 +589,10 @@ impl FutureSignalSender {
 }
 
 impl FutureSignalReceiver {
-    pub  (crate) fn new(acknowledge_id: AcknowledgeId, membrane: MembraneType, channel: ChannelIdType,
-        assert: Assert, out_type: FullId, max_ping: Duration, data_type: FullId, mut receiver: BinSgReceiver,
+    pub  (crate) fn new(context: ChannelContext,
+        assert: Assert, out_type: FullId, data_type: FullId, mut receiver: BinSgReceiver,
     ) -> impl Future<Output = ReplyToMaster> {
+        let ChannelContext { acknowledge_id, membrane, channel, metrics, max_ping, } = context;
         async move { 
             let aknowledge_id = acknowledge_id;
             if out_type == data_type {
@@ -448,6 +608,8 @@ impl FutureSignalReceiver {
                         match receiver.changed().await {
                             Ok(_)   => {
                                 if let Some(bor) = receiver.borrow_and_update() { 
+                                    metrics.received(bor.data.len());
+                                    let start = Instant::now();
                                     match unsafe {
                                         TokioScope::scope_and_collect(|scope| {
                                             scope.spawn(assert(std::mem::transmute(&bor)));
@@ -455,11 +617,15 @@ impl FutureSignalReceiver {
                                     }.await.1[0] {
                                         Ok(_) => (),
                                         Err(_) => {
-                                            alive = false;
-                                            eprintln!("spawning failure for task {}", acknowledge_id);
+                                            alive = false; flag.fail().await;
+                                            error!(task = %acknowledge_id, "spawning failure");
                                         },
                                     };
-                                } else { panic!("Unexpected: signal is not inited") }
+                                    metrics.handled(start.elapsed());
+                                } else { // undefined signal: stop the future and report the failure to the supervisor
+                                    alive = false; flag.fail().await;
+                                    error!(task = %acknowledge_id, "signal is not inited");
+                                }
                             },
                             Err(_)  => alive = false, // channel is closed : stop the future
                         };
@@ -504,6 +670,8 @@ impl FuturePingChannel {
                     Channelling::NetQueryRecv(amreceiv) => !amreceiv.is_closed(),
                     Channelling::NetBroadcastSend(_)    => true,
                     Channelling::NetBroadcastRecv(_)    => true,
+                    Channelling::NetSignalSend(amsender) => !amsender.is_closed(),
+                    Channelling::NetSignalRecv(amreceiv) => amreceiv.has_changed().is_ok(),
                     Channelling::QuerySend(amsender)    => !amsender.is_closed(),
                     Channelling::QueryRecv(amreceiv)    => !amreceiv.is_closed(),
                     Channelling::BroadcastSend(_)       => true,
@@ -527,7 +695,8 @@ impl FuturePingChannel {
 impl FutureKillChannel {
     pub  (crate) fn new(acknowledge_id: AcknowledgeId, membrane: MembraneType, channel: ChannelIdType,) -> impl Future<Output = ReplyToMaster> {
         async move { 
-            let the_membrane = membrane.read().await.get(&channel).cloned();
+            // the channel is removed from the membrane, so that its handle is not awaited twice
+            let the_membrane = membrane.write().await.remove(&channel);
             if let Some((_,_,_,handle,)) = the_membrane {
                 handle.read().await.abort();
                 let _ = (&mut *handle.write().await).await;
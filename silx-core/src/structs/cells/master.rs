@@ -1,23 +1,30 @@
 use crate::{
-    shared::id_tools::{TaskId, IdBuilder}, structs::{ 
-        archmod::archannel::ChannelServer,
-        cells::ctrl_message::{ 
-            CtrlCell, MsgFromMaster, MsgFromServant, RecvFromServant, ReplyToMaster, ReplyToServant, SendToServant, SetChannel as sch 
+    shared::{ id_tools::{TaskId, IdBuilder}, trace::spawn, error::SilxError, }, structs::{ 
+        archmod::archannel::{ ChannelServer, ChannelClient, },
+        cells::{
+            supervisor::Supervisor,
+            ctrl_message::{ 
+                CtrlCell, MsgFromMaster, MsgFromServant, RecvFromServant, ReplyToMaster, ReplyToServant, SendToServant, SetChannel as sch, SupervisionEvent, 
+            },
         },
-    }, ChannelIdType, ServantIdType
+    }, 
+    builder::{
+        topology::{ ClusterUpdate, RecvUpdates, Topology, },
+        admin::{ self, AdminAddress, AdminReply, AdminRequest, ChannelState, ClusterStatus, RecvAdmin, ServantState, ServantStatus, },
+        telemetry::{ self, Telemetry, },
+    },
+    ChannelIdType, ServantIdType
 };
 
-use std::{ sync::Arc, collections::VecDeque, fmt::Debug, fmt::{ Formatter, Error, }, net::SocketAddr, };
+use std::{ sync::Arc, collections::{ BTreeSet, HashMap, VecDeque, }, fmt::Debug, fmt::{ Formatter, Error, }, net::SocketAddr, };
 
-use tokio::{ spawn, sync::RwLock, };
+use tokio::{ task::JoinHandle, sync::{ mpsc as msc, Mutex, RwLock, }, };
 use fnv::FnvHashMap;
-
-const VERBOSE: bool = false;
+use tracing::{ debug, error, trace, warn, };
 
 /// doc to be defined
 pub struct Master {
     pub (crate) cluster_id: SocketAddr,
-    #[allow(dead_code)]
     server: ChannelServer,
     send_to_servants: FnvHashMap<ServantIdType, SendToServant>, 
     recv_from_servants: FnvHashMap<ServantIdType, RecvFromServant>, 
@@ -25,8 +32,45 @@ pub struct Master {
     full_alive: Arc<RwLock<bool>>, // false during shutdown process; useful for muting errors then
     alive: Arc<RwLock<bool>>,
     task_id: IdBuilder,
+    topology: MasterTopology,
+}
+
+/// Resources of the master for updating and administrating the cluster at runtime
+pub (crate) struct MasterTopology {
+    pub (crate) recv_updates: RecvUpdates,
+    pub (crate) clients: HashMap<SocketAddr, ChannelClient>,
+    pub (crate) servant_ids: FnvHashMap<String, ServantIdType>,
+    pub (crate) channel_names: FnvHashMap<ChannelIdType, String>,
+    pub (crate) ctrl_ch_capacity: usize,
+    /// address of the admin endpoint, if any
+    pub (crate) admin: Option<AdminAddress>,
+    /// address of the metrics endpoint, if any
+    pub (crate) metrics: Option<SocketAddr>,
+    /// metrics registry of the cluster
+    pub (crate) telemetry: Arc<Telemetry>,
+    /// topology handle of the network; defined for the main cluster only
+    pub (crate) network: Option<Topology>,
+}
+
+#[derive(Clone)]
+// State of the master shared between the listeners of the servants, the topology updates and the admin requests
+struct MasterState {
+    cluster_id: SocketAddr,
+    alive: Arc<RwLock<bool>>,
+    full_alive: Arc<RwLock<bool>>,
+    task_id: IdBuilder,
+    channels: SharedChannels,
+    send_to_servants: Arc<RwLock<FnvHashMap<ServantIdType, SendToServant>>>,
+    servant_ids: Arc<RwLock<FnvHashMap<String, ServantIdType>>>,
+    channel_names: Arc<RwLock<FnvHashMap<ChannelIdType, String>>>,
+    servant_states: Arc<RwLock<FnvHashMap<ServantIdType, ServantState>>>,
+    paused: Arc<RwLock<BTreeSet<ChannelIdType>>>,
+    telemetry: Arc<Telemetry>,
 }
 
+type DynamicHandles = Arc<Mutex<VecDeque<JoinHandle<()>>>>;
+type SharedChannels = Arc<RwLock<FnvHashMap<ServantIdType, Vec<(ChannelIdType, sch)>>>>;
+
 impl Debug for Master {
     fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), Error> {
         write!(f, "Servant {{ .. }}")
@@ -37,7 +81,7 @@ impl Master {
     pub  (crate) fn new (cluster_id: SocketAddr,
             server: ChannelServer, // server is stored within master, so as to be active until cluster is dropped
             send_to_servants: FnvHashMap<ServantIdType, SendToServant>, recv_from_servants: FnvHashMap<ServantIdType, RecvFromServant>, 
-            channels: FnvHashMap<ServantIdType, Vec<(ChannelIdType, sch)>>, task_id: IdBuilder,) -> Self {
+            channels: FnvHashMap<ServantIdType, Vec<(ChannelIdType, sch)>>, task_id: IdBuilder, topology: MasterTopology,) -> Self {
         Self { 
             cluster_id,
             server,
@@ -47,14 +91,392 @@ impl Master {
             full_alive: Arc::new(RwLock::new(true)),
             alive: Arc::new(RwLock::new(true)),
             task_id,
+            topology,
+        }
+    }
+
+    // send a control request to a servant and wait for the reply
+    async fn request(state: &MasterState, servant_id: ServantIdType, ctrl: CtrlCell,) -> Result<(),SilxError> {
+        let msender = match state.send_to_servants.read().await.get(&servant_id) {
+            Some(msender) => msender.clone(), None => return Err(SilxError::config(format!("servant {} is undefined", servant_id))),
+        };
+        let tid = state.task_id.lock().await.generate();
+        let acknowledge_id = tid.acknowledge_id();
+        let receiver = MsgFromMaster::Ctrl(tid, ctrl).send(&msender).await?;
+        let reply = receiver.await.map_err(|e| {
+            SilxError::protocol(format!("no reply from servant {} for task {}", servant_id, acknowledge_id)).with_source(e)
+        })?;
+        state.task_id.lock().await.delete(TaskId::new(acknowledge_id))?;
+        match reply {
+            ReplyToMaster::Ok(_) => Ok(()),
+            ReplyToMaster::Undefined(_) => Err(SilxError::config(format!("undefined channel for servant {}", servant_id))),
+            ReplyToMaster::WrongType(_) => Err(SilxError::config(format!("type mismatch for servant {}", servant_id))),
+            ReplyToMaster::OutOfTime(_) => Err(SilxError::timeout(format!("task {} is out of time for servant {}", acknowledge_id, servant_id))),
+            _ => Err(SilxError::protocol(format!("task {} has failed for servant {}", acknowledge_id, servant_id))),
+        }
+    }
+
+    // apply a topology update to the cluster
+    async fn update(state: &MasterState, server: &Arc<Mutex<ChannelServer>>, topology: &mut MasterTopology, handles: &DynamicHandles, 
+                        update: ClusterUpdate,) -> Result<(),String> {
+        match update {
+            ClusterUpdate::AddServant { name, builder, supervision, } => {
+                let mut servant_ids = state.servant_ids.write().await;
+                if servant_ids.contains_key(&name) { return Err(format!("servant {} is already defined", name)); }
+                let servant_id = servant_ids.values().max().map(|id| id + 1).unwrap_or(0);
+                let (send_to_servant, recv_from_master) = msc::channel(topology.ctrl_ch_capacity);
+                let (send_2_master, recv_from_servant) = msc::channel(topology.ctrl_ch_capacity);
+                let supervisor = Supervisor::new(servant_id, name.clone(), builder, FnvHashMap::default(), supervision, 
+                    topology.ctrl_ch_capacity, recv_from_master, send_2_master, state.task_id.clone(), state.telemetry.clone()).await?;
+                state.send_to_servants.write().await.insert(servant_id, send_to_servant);
+                state.channels.write().await.insert(servant_id, Vec::new());
+                state.servant_states.write().await.insert(servant_id, ServantState::Running);
+                servant_ids.insert(name, servant_id);
+                let mut handles = handles.lock().await;
+                handles.push_front(spawn(supervisor.run()));
+                handles.push_front(Self::listen_servant(servant_id, recv_from_servant, state.clone()));
+            },
+            ClusterUpdate::AddChannel { id, name, channel, } => {
+                let settings = channel.settings(id, &state.cluster_id, &mut *server.lock().await, &mut topology.clients).await?;
+                state.channel_names.write().await.insert(id, name.clone());
+                for (servant, setting) in settings {
+                    let servant_id = match state.servant_ids.read().await.get(&servant) {
+                        Some(servant_id) => *servant_id, None => return Err(format!("servant {} is undefined", servant)),
+                    };
+                    Self::request(state, servant_id, CtrlCell::NameChl(id, name.clone())).await?;
+                    Self::request(state, servant_id, CtrlCell::SetChl(id, setting.clone())).await?;
+                    state.channels.write().await.entry(servant_id).or_default().push((id, setting));
+                    Self::request(state, servant_id, CtrlCell::TurnOnChl(id)).await?;
+                }
+            },
+            ClusterUpdate::KillChannel { id, } => {
+                let servant_ids: Vec<_> = state.channels.read().await.iter()
+                    .filter(|(_,servant_channels)| servant_channels.iter().any(|(ch_i,_)| *ch_i == id)).map(|(servant_id,_)| *servant_id).collect();
+                for servant_id in servant_ids {
+                    Self::request(state, servant_id, CtrlCell::TurnOffChl(id)).await?;
+                    Self::request(state, servant_id, CtrlCell::KillChl(id)).await?;
+                    if let Some(servant_channels) = state.channels.write().await.get_mut(&servant_id) { servant_channels.retain(|(ch_i,_)| *ch_i != id); }
+                }
+                state.channel_names.write().await.remove(&id);
+                state.paused.write().await.remove(&id);
+            },
+            ClusterUpdate::Shutdown => (), // processed by update_topology
+        }
+        Ok(())
+    }
+
+    // apply the topology updates requested by the main cluster
+    async fn update_topology(state: MasterState, server: Arc<Mutex<ChannelServer>>, mut topology: MasterTopology, handles: DynamicHandles,) {
+        while let Some((update, reply)) = topology.recv_updates.recv().await {
+            if let ClusterUpdate::Shutdown = update { // acknowledged before the shutdown, which ends the cluster
+                if reply.send(Ok(())).is_err() { error!("failed to reply to topology update"); }
+                Self::shutdown(state).await;
+                break;
+            }
+            let result = Self::update(&state, &server, &mut topology, &handles, update).await;
+            if reply.send(result).is_err() { error!("failed to reply to topology update"); }
+        }
+    }
+
+    // identifiers of the servants of the cluster which are connected to a named channel
+    async fn connected(state: &MasterState, channel: &str,) -> Result<(ChannelIdType,Vec<(String,ServantIdType)>),String> {
+        let id = match state.channel_names.read().await.iter().find(|(_,name)| name.as_str() == channel) {
+            Some((id,_)) => *id, None => return Err(format!("unknown channel {}", channel)),
+        };
+        let channels = state.channels.read().await;
+        let servants = state.servant_ids.read().await.iter().filter(|(_,servant_id)| {
+            channels.get(servant_id).map(|servant_channels| servant_channels.iter().any(|(ch_i,_)| *ch_i == id)).unwrap_or(false)
+        }).map(|(name,servant_id)| (name.clone(),*servant_id)).collect();
+        Ok((id,servants))
+    }
+
+    // process an admin request
+    async fn admin(state: &MasterState, network: &Option<Topology>, request: AdminRequest,) -> Result<AdminReply,String> {
+        match request {
+            AdminRequest::List => {
+                let channels = state.channels.read().await;
+                let channel_names = state.channel_names.read().await;
+                let servant_states = state.servant_states.read().await;
+                let paused = state.paused.read().await;
+                let servants = state.servant_ids.read().await.iter().map(|(name,servant_id)| {
+                    let state = servant_states.get(servant_id).copied().unwrap_or(ServantState::Running);
+                    let channels = channels.get(servant_id).map(|servant_channels| servant_channels.iter().map(|(ch_i,_)| {
+                        let channel = channel_names.get(ch_i).cloned().unwrap_or_else(|| ch_i.to_string());
+                        (channel, if paused.contains(ch_i) { ChannelState::Paused } else { ChannelState::Active })
+                    }).collect()).unwrap_or_default();
+                    (name.clone(), ServantStatus { state, channels, })
+                }).collect();
+                Ok(AdminReply::Status(ClusterStatus { cluster: state.cluster_id, servants, }))
+            },
+            AdminRequest::Ping { channel, } => {
+                let (id,servants) = Self::connected(state, &channel).await?;
+                let mut results = std::collections::BTreeMap::new();
+                for (name,servant_id) in servants { results.insert(name, Self::request(state, servant_id, CtrlCell::PingChl(id)).await.is_ok()); }
+                Ok(AdminReply::Ping(results))
+            },
+            AdminRequest::Pause { channel, } => {
+                let (id,servants) = Self::connected(state, &channel).await?;
+                for (_,servant_id) in servants { Self::request(state, servant_id, CtrlCell::TurnOffChl(id)).await?; }
+                state.paused.write().await.insert(id);
+                Ok(AdminReply::Done)
+            },
+            AdminRequest::Resume { channel, } => {
+                let (id,servants) = Self::connected(state, &channel).await?;
+                for (_,servant_id) in servants { Self::request(state, servant_id, CtrlCell::TurnOnChl(id)).await?; }
+                state.paused.write().await.remove(&id);
+                Ok(AdminReply::Done)
+            },
+            AdminRequest::Metrics => Ok(AdminReply::Metrics(state.telemetry.snapshot(state.cluster_id))),
+            AdminRequest::Shutdown => { spawn(Self::shutdown(state.clone())); Ok(AdminReply::Done) },
+            AdminRequest::ShutdownNetwork => match network {
+                Some(network) => {
+                    let network = network.clone();
+                    spawn(async move { if let Err(e) = network.shutdown().await { error!("network shutdown -> {}", e); } });
+                    Ok(AdminReply::Done)
+                },
+                None => Err("network shutdown is only available from the main cluster".to_string()),
+            },
+        }
+    }
+
+    // process the requests received by the admin endpoint
+    async fn administrate(state: MasterState, network: Option<Topology>, mut recv_admin: RecvAdmin,) {
+        while let Some((request, reply)) = recv_admin.recv().await {
+            let result = Self::admin(&state, &network, request).await.unwrap_or_else(AdminReply::Error);
+            if reply.send(result).is_err() { error!("failed to reply to admin request"); }
         }
     }
 
-    pub (crate) async fn run(self, ) {
+    // gracefully shut the cluster down: turn off all channels, and then kill all servants
+    async fn shutdown(state: MasterState,) {
         use MsgFromMaster::Ctrl as Ctrl;
-        use MsgFromServant::{ FailureChl, StaledChl, Shutdown, };
-        let Self { cluster_id, send_to_servants, recv_from_servants, channels, alive, full_alive, task_id, .. } = self; 
+        let MasterState { alive, full_alive, task_id, channels: shared_channels, send_to_servants: shared_senders, .. } = state;
+        { // shutdown is processed only once
+            let mut full_alive = full_alive.write().await;
+            if !*full_alive { return; }
+            *full_alive = false; // muting error messages 
+        }
+        let channels = shared_channels.read().await.clone();
+        let send_to_servants = shared_senders.read().await.clone();
+        let nb_channels = channels.values().map(|sc| sc.len()).sum::<usize>();
+        debug!(channels = nb_channels, "turn off channels");
+        let mut receivers = VecDeque::new();
+        for (&servant_id, servant_channels) in &channels {
+            for &(ch_i,_) in servant_channels {
+                match send_to_servants.get(&servant_id) {
+                    None => {
+                        *alive.write().await = false;
+                        if *full_alive.read().await { error!("killing master: cluster channel is undefined"); } // mute during shutdown                                             
+                    },
+                    Some(msender) => {
+                        let tid = task_id.lock().await.generate();
+                        debug!(task = %tid.acknowledge_id(), channel = ch_i, servant_id, "turn off channel");
+                        match Ctrl(tid, CtrlCell::TurnOffChl(ch_i,)).send(msender).await {
+                            Err(_) => {
+                                *alive.write().await = false;
+                                if *full_alive.read().await { error!("killing master: cluster channel is closed"); } // mute during shutdown
+                            },
+                            Ok(receiver) => receivers.push_front(receiver),
+                        }                
+                    }
+                }
+            }    
+        }
+        let mut handles = VecDeque::new();
+        while let Some(receiver) = receivers.pop_back() {
+            let alive = alive.clone();
+            let full_alive = full_alive.clone(); 
+            let task_id = task_id.clone();
+            handles.push_front(spawn(async move {
+                match receiver.await {
+                    Ok(reply) => {
+                        let tid = match reply {
+                            ReplyToMaster::Ok(tid) => { 
+                                trace!(task = %tid, "task is done");
+                                tid 
+                            },
+                            ReplyToMaster::Undefined(tid,) => {
+                                *alive.write().await = false; 
+                                error!(task = %tid, "reply from servant: undefined channel"); tid
+                            },
+                            ReplyToMaster::PingFail(tid,) => {
+                                warn!(task = %tid, "reply from servant: ping fail"); tid
+                            },
+                            ReplyToMaster::Failure(tid,) => {
+                                error!(task = %tid, "reply from servant: failure"); tid
+                            },
+                            ReplyToMaster::WrongType(tid,) => {
+                                *alive.write().await = false; 
+                                error!(task = %tid, "reply from servant: type mismatch"); tid
+                            },
+                            ReplyToMaster::OutOfTime(tid,) => {
+                                warn!(task = %tid, "reply from servant: task is out of time"); tid
+                            },
+                        };
+                        match task_id.lock().await.delete(TaskId::new(tid)) { 
+                            Ok(()) => (), Err(msg) => { error!("reply from servant -> {}", msg); }, 
+                        }
+                    },
+                    Err(_)    => {
+                        *alive.write().await = false; 
+                        if *full_alive.read().await { error!("killing master: cluster channel is closed");  } // mute during shutdown
+                    }
+                } 
+            }));
+        }
+        for handle in handles { let _: Result<_,_> = handle.await; }
+        debug!(servants = send_to_servants.len(), "channels are turned off: kill servants");
+        let mut receivers = VecDeque::new();
+        for (servid, msender) in &send_to_servants {
+            let tid = task_id.lock().await.generate();
+            debug!(task = %tid.acknowledge_id(), servant_id = servid, "kill servant");
+            match Ctrl(tid, CtrlCell::Kill).send(msender).await {
+                Err(_) => {
+                    *alive.write().await = false; 
+                    if *full_alive.read().await { error!("killing master: cluster channel is closed"); } // mute during shutdown
+                },
+                Ok(receiver) => receivers.push_front(receiver),
+            }
+        };
+        let mut handles = VecDeque::new();
+        while let Some(receiver) = receivers.pop_back() {
+            let alive = alive.clone();
+            let full_alive = full_alive.clone(); 
+            let task_id = task_id.clone();
+            handles.push_front(spawn(async move {
+                match receiver.await {
+                    Ok(reply) => {
+                        let tid = match reply {
+                            ReplyToMaster::Ok(tid) => { 
+                                trace!(task = %tid, "task is done");
+                                tid 
+                            },
+                            ReplyToMaster::Undefined(tid,) => {
+                               *alive.write().await = false; 
+                                error!(task = %tid, "reply from servant: undefined channel"); tid
+                            },
+                            ReplyToMaster::PingFail(tid,) => {
+                                warn!(task = %tid, "reply from servant: ping fail"); tid
+                            },
+                            ReplyToMaster::Failure(tid,) => {
+                                error!(task = %tid, "reply from servant: failure"); tid
+                            },
+                            ReplyToMaster::WrongType(tid,) => {
+                                *alive.write().await = false; 
+                                error!(task = %tid, "reply from servant: type mismatch"); tid
+                            },
+                            ReplyToMaster::OutOfTime(tid,) => {
+                                warn!(task = %tid, "reply from servant: task is out of time"); tid
+                            },
+                        };
+                        match task_id.lock().await.delete(TaskId::new(tid)) { 
+                            Ok(()) => (), Err(msg) => { error!("reply from servant -> {}", msg); }, 
+                        }
+                    },
+                    Err(_)    => {
+                        *alive.write().await = false; 
+                        if *full_alive.read().await { error!("killing master: cluster channel is closed"); } // mute during shutdown                                             
+                    }
+                } 
+            }));
+        }
+        for handle in handles { let _: Result<_,_> = handle.await; }
+        *alive.write().await = false;
+        debug!("servants are killed");
+    }
+
+    // listen to the requests of a servant
+    fn listen_servant(servant_id: ServantIdType, mut mreceiver: RecvFromServant, state: MasterState,) -> JoinHandle<()> {
+        use MsgFromServant::{ FailureChl, StaledChl, Shutdown, Supervision, };
+        let MasterState { alive, full_alive, task_id, servant_states, .. } = state.clone();
+        spawn(async move {
+            while *alive.read().await {
+                match mreceiver.recv().await {
+                    None => { 
+                        *alive.write().await = false; 
+                        if *full_alive.read().await { error!(servant_id, "killing master: channel from servant is closed"); } // mute during shutdown
+                    },
+                    Some((FailureChl(tid,chan),cluster_reply)) => {
+                        error!(channel = chan, task = %tid.acknowledge_id(), servant_id, "failure on channel");
+                        if cluster_reply.send(ReplyToServant::Aknowledged(tid.acknowledge_id())).is_err() {
+                            error!(servant_id, task = %tid.acknowledge_id(), "killing: failed to reply to servant");
+                        }
+                    }
+                    Some((StaledChl(tid,chan),cluster_reply)) => {
+                        warn!(channel = chan, task = %tid.acknowledge_id(), servant_id, "staled channel");
+                        if cluster_reply.send(ReplyToServant::Aknowledged(tid.acknowledge_id())).is_err() {
+                            error!(servant_id, task = %tid.acknowledge_id(), "killing: failed to reply to servant");
+                        }
+                    }
+                    Some((Supervision(tid,event),cluster_reply)) => {
+                        let servant_state = match event {
+                            SupervisionEvent::Stopped => { error!(servant_id, "servant has failed and is stopped"); ServantState::Stopped },
+                            SupervisionEvent::Restarted(n) => {
+                                warn!(servant_id, restarts = n, "servant has failed and is restarted"); ServantState::Restarted(n)
+                            },
+                            SupervisionEvent::Escalated => { error!(servant_id, "killing: failure of servant is escalated"); ServantState::Escalated },
+                        };
+                        servant_states.write().await.insert(servant_id, servant_state);
+                        if cluster_reply.send(ReplyToServant::Aknowledged(tid.acknowledge_id())).is_err() {
+                            error!(servant_id, task = %tid.acknowledge_id(), "killing: failed to reply to servant");
+                        }
+                        match task_id.lock().await.delete(tid) { 
+                            Ok(()) => (), Err(msg) => { error!("supervision event from servant -> {}", msg); }, 
+                        }
+                    }
+                    Some((Shutdown(_tid,),_cluster_reply)) => Self::shutdown(state.clone()).await,
+                }
+            }    
+        })
+    }
+
+    pub (crate) async fn run(self, ) -> Result<(),SilxError> {
+        use MsgFromMaster::Ctrl as Ctrl;
+        let Self { cluster_id, server, send_to_servants, recv_from_servants, channels, alive, full_alive, task_id, mut topology, } = self; 
         let nb_channels = channels.iter().map(|(_,sc)| sc.len()).sum::<usize>();
+        let servant_states = channels.keys().map(|servant_id| (*servant_id, ServantState::Running)).collect();
+        let channels = Arc::new(RwLock::new(channels));
+        let send_to_servants = Arc::new(RwLock::new(send_to_servants));
+        let state = MasterState { 
+            cluster_id, alive: alive.clone(), full_alive: full_alive.clone(), task_id: task_id.clone(), 
+            channels: channels.clone(), send_to_servants: send_to_servants.clone(),
+            servant_ids: Arc::new(RwLock::new(std::mem::take(&mut topology.servant_ids))),
+            channel_names: Arc::new(RwLock::new(std::mem::take(&mut topology.channel_names))),
+            servant_states: Arc::new(RwLock::new(servant_states)), paused: Arc::new(RwLock::new(BTreeSet::new())),
+            telemetry: topology.telemetry.clone(),
+        };
+        //
+        // ADMIN ENDPOINT
+        //
+        let handles_admin = match topology.admin.take() {
+            None => None,
+            Some(address) => {
+                let (send_admin, recv_admin) = msc::channel(topology.ctrl_ch_capacity);
+                match admin::listen_admin(&address, send_admin).await {
+                    Ok(handle_listener) => {
+                        let handle_admin = spawn(Self::administrate(state.clone(), topology.network.take(), recv_admin));
+                        Some((handle_listener, handle_admin, address))
+                    },
+                    Err(e) => { error!("{}", e); None },
+                }
+            },
+        };
+        //
+        // METRICS ENDPOINT
+        //
+        let handle_metrics = match topology.metrics.take() {
+            None => None,
+            Some(address) => match telemetry::listen_metrics(&address, cluster_id, state.telemetry.clone()).await {
+                Ok(handle) => Some(handle),
+                Err(e) => { error!("{}", e); None },
+            },
+        };
+        //
+        // TOPOLOGY UPDATES
+        //
+        let server = Arc::new(Mutex::new(server)); // server is kept until the master is done
+        let dynamic_handles: DynamicHandles = Arc::new(Mutex::new(VecDeque::new()));
+        let handle_updates = spawn(Self::update_topology(state.clone(), server.clone(), topology, dynamic_handles.clone()));
         //
         // INITIALIZATION
         //
@@ -62,256 +484,111 @@ impl Master {
             let alive = alive.clone(); 
             let full_alive = full_alive.clone(); 
             let task_id = task_id.clone(); 
-            let channels = channels.clone(); 
-            let send_to_servants = send_to_servants.clone();
+            let channels = channels.read().await.clone(); 
+            let send_to_servants = send_to_servants.read().await.clone();
             spawn(async move {
-                if VERBOSE { println!("CLUSTER {} INITIALIZATION: SETTING {} CHANNELS", cluster_id, nb_channels); }
+                debug!(channels = nb_channels, "initialization: set channels");
                 let mut receivers = VecDeque::new();
                 for (servant_id, servant_channels) in channels {
                     for (ch_i,s_ch) in servant_channels {
                         match send_to_servants.get(&servant_id) {
                             None => {
                                 *alive.write().await = false;
-                                if *full_alive.read().await { eprintln!("Killing master! Custer channel is undefined!"); } // mute during shutdown
+                                if *full_alive.read().await { error!("killing master: cluster channel is undefined"); } // mute during shutdown
                             },
                             Some(msender) => {
                                 let tid = task_id.lock().await.generate();
-                                if VERBOSE { 
-                                    println!("CLUSTER {} INITIALIZATION: TASK {} -> SET CHANNEL {} FOR SERVANT {}", cluster_id, tid.acknowledge_id(), ch_i, servant_id,); 
-                                }
+                                debug!(task = %tid.acknowledge_id(), channel = ch_i, servant_id, "set channel");
                                 match Ctrl(tid, CtrlCell::SetChl(ch_i,s_ch)).send(msender).await {
                                     Err(_) => {
                                         *alive.write().await = false; 
-                                        if *full_alive.read().await { eprintln!("Killing master! Custer channel is closed!"); } // mute during shutdown
+                                        if *full_alive.read().await { error!("killing master: cluster channel is closed"); } // mute during shutdown
                                     },
-                                    Ok(receiver) => receivers.push_front(receiver),
+                                    Ok(receiver) => receivers.push_front((servant_id, ch_i, receiver)),
                                 }
                             }
                         }
                     }    
                 }
                 let mut handles = VecDeque::new();
-                while let Some(receiver) = receivers.pop_back() {
+                while let Some((servant_id, ch_i, receiver)) = receivers.pop_back() {
                     let alive = alive.clone();
                     let full_alive = full_alive.clone(); 
                     let task_id = task_id.clone();
                     handles.push_front(spawn(async move {
                         match receiver.await {
                             Ok(reply) => {
-                                let tid = match reply {
+                                let (tid, failure) = match reply {
                                     ReplyToMaster::Ok(tid) => { 
-                                        if VERBOSE { println!("CLUSTER {} INITIALIZATION: TASK {} IS DONE", cluster_id, tid); }
-                                        tid 
+                                        trace!(task = %tid, "task is done");
+                                        (tid, None)
                                     },
                                     ReplyToMaster::Undefined(tid,) => {
                                         *alive.write().await = false; 
-                                        eprintln!("Reply from servant: [31mUNDEFINED CHANNEL FOR TASK {}[0m",tid); tid
+                                        error!(task = %tid, "reply from servant: undefined channel"); 
+                                        (tid, Some(SilxError::config(format!("initialization: channel {} is undefined for servant {}", ch_i, servant_id))))
                                     },
                                     ReplyToMaster::PingFail(tid,) => {
-                                        eprintln!("Reply from servant: ping fail for task {}",tid); tid
+                                        warn!(task = %tid, "reply from servant: ping fail"); (tid, None)
                                     },
                                     ReplyToMaster::Failure(tid,) => {
-                                        eprintln!("Reply from servant: failure for task {}",tid); tid
+                                        error!(task = %tid, "reply from servant: failure"); (tid, None)
                                     },
                                     ReplyToMaster::WrongType(tid,) => {
                                         *alive.write().await = false; 
-                                        eprintln!("Reply from servant: [31mTYPE MISMATCH FOR TASK {}[0m",tid); tid
+                                        error!(task = %tid, "reply from servant: type mismatch"); 
+                                        (tid, Some(SilxError::config(format!("initialization: type mismatch of channel {} for servant {}", ch_i, servant_id))))
                                     },
                                     ReplyToMaster::OutOfTime(tid,) => {
-                                        eprintln!("Reply from servant: task {} is out of time",tid); tid
+                                        warn!(task = %tid, "reply from servant: task is out of time"); (tid, None)
                                     },
                                 };
                                 match task_id.lock().await.delete(TaskId::new(tid)) { 
-                                    Ok(()) => (), Err(msg) => { eprintln!("Reply from servant -> {}",msg); }, 
+                                    Ok(()) => (), Err(msg) => { error!("reply from servant -> {}", msg); }, 
                                 }
+                                failure
                             },
                             Err(_)    => {
                                 *alive.write().await = false; 
-                                if *full_alive.read().await { eprintln!("Killing master (servant restart not implemented for now)! Custer channel is closed!"); } // mute during shutdown
+                                if *full_alive.read().await { error!("killing master: cluster channel is closed"); } // mute during shutdown
+                                Some(SilxError::protocol(format!("initialization: channel from servant {} is closed", servant_id)))
                             }
                         } 
                     }));
                 }
-                if !*alive.read().await {
-                    panic!("Initialization failed!");
+                let mut failure = None;
+                for handle in handles { 
+                    let error = match handle.await {
+                        Ok(error) => error,
+                        Err(e) => Some(SilxError::protocol("initialization: task has failed").with_source(e)),
+                    };
+                    if failure.is_none() { failure = error; }
+                }
+                match failure {
+                    Some(error) => Err(error),
+                    None if !*alive.read().await => Err(SilxError::protocol("initialization: cluster channel is undefined or closed")),
+                    None => { debug!("initialization is done"); Ok(()) },
                 }
-                for handle in handles { let _: Result<_,_> = handle.await; }
-                if VERBOSE { println!("END: CLUSTER {} INITIALIZATION", cluster_id); }
             })
         };
         //
         // LISTEN TO SERVANT REQUESTS
         //
         let mut handles = VecDeque::new();
-        for (servant_id, mut mreceiver) in recv_from_servants {
-            let alive = alive.clone();
-            let full_alive = full_alive.clone(); 
-            let task_id = task_id.clone();
-            let channels = channels.clone(); 
-            let send_to_servants = send_to_servants.clone();
-            handles.push_front(spawn(async move {
-                while *alive.read().await {
-                    match mreceiver.recv().await {
-                        None => { 
-                            *alive.write().await = false; 
-                            if *full_alive.read().await { eprintln!("Killing! Custer_recv channel is closed for servant {}!", servant_id); } // mute during shutdown
-                        },
-                        Some((FailureChl(tid,chan),cluster_reply)) => {
-                            eprintln!("Failure on channel {} for task {}!", chan, tid.acknowledge_id(),);
-                            match cluster_reply.send(ReplyToServant::Aknowledged(tid.acknowledge_id())) {
-                                Err(_) =>  eprintln!("Killing! Failed to reply to servant {} on task {}!", servant_id, tid.acknowledge_id()),
-                                Ok(_)  => (),
-                            }
-                        }
-                        Some((StaledChl(tid,chan),cluster_reply)) => {
-                            eprintln!("Staled channel {} for task {} and servant {}!", chan, tid.acknowledge_id(), servant_id,);
-                            match cluster_reply.send(ReplyToServant::Aknowledged(tid.acknowledge_id())) {
-                                Err(_) =>  eprintln!("Killing! Failed to reply to servant {} on task {}!", servant_id, tid.acknowledge_id()),
-                                Ok(_)  => (),
-                            }
-                        }
-                        Some((Shutdown(_tid,),_cluster_reply)) => {
-                            *full_alive.write().await = false; // muting error messages 
-                            if VERBOSE { println!("CLUSTER {} : TURN OFF {} CHANNELS", cluster_id, nb_channels); }
-                            let mut receivers = VecDeque::new();
-                            for (&servant_id, servant_channels) in &channels {
-                                for &(ch_i,_) in servant_channels {
-                                    match send_to_servants.get(&servant_id) {
-                                        None => {
-                                            *alive.write().await = false;
-                                            if *full_alive.read().await { eprintln!("Killing master! Custer channel is undefined!"); } // mute during shutdown                                             
-                                        },
-                                        Some(msender) => {
-                                            let tid = task_id.lock().await.generate();
-                                            if VERBOSE { 
-                                                println!("CLUSTER {} : TASK {} -> TURN OFF CHANNEL {} FOR SERVANT {}", cluster_id, tid.acknowledge_id(), ch_i, servant_id,); 
-                                            }
-                                            match Ctrl(tid, CtrlCell::TurnOffChl(ch_i,)).send(msender).await {
-                                                Err(_) => {
-                                                    *alive.write().await = false;
-                                                    if *full_alive.read().await { eprintln!("Killing master! Custer channel is closed!"); } // mute during shutdown
-                                                },
-                                                Ok(receiver) => receivers.push_front(receiver),
-                                            }                
-                                        }
-                                    }
-                                }    
-                            }
-                            let mut handles = VecDeque::new();
-                            while let Some(receiver) = receivers.pop_back() {
-                                let alive = alive.clone();
-                                let full_alive = full_alive.clone(); 
-                                let task_id = task_id.clone();
-                                handles.push_front(spawn(async move {
-                                    match receiver.await {
-                                        Ok(reply) => {
-                                            let tid = match reply {
-                                                ReplyToMaster::Ok(tid) => { 
-                                                    if VERBOSE { println!("CLUSTER {} : TASK {} IS DONE", cluster_id, tid); }
-                                                    tid 
-                                                },
-                                                ReplyToMaster::Undefined(tid,) => {
-                                                    *alive.write().await = false; 
-                                                    eprintln!("Reply from servant: [31mUNDEFINED CHANNEL FOR TASK {}[0m",tid); tid
-                                                },
-                                                ReplyToMaster::PingFail(tid,) => {
-                                                    eprintln!("Reply from servant: ping fail for task {}",tid); tid
-                                                },
-                                                ReplyToMaster::Failure(tid,) => {
-                                                    eprintln!("Reply from servant: failure for task {}",tid); tid
-                                                },
-                                                ReplyToMaster::WrongType(tid,) => {
-                                                    *alive.write().await = false; 
-                                                    eprintln!("Reply from servant: [31mTYPE MISMATCH FOR TASK {}[0m",tid); tid
-                                                },
-                                                ReplyToMaster::OutOfTime(tid,) => {
-                                                    eprintln!("Reply from servant: task {} is out of time",tid); tid
-                                                },
-                                            };
-                                            match task_id.lock().await.delete(TaskId::new(tid)) { 
-                                                Ok(()) => (), Err(msg) => { eprintln!("Reply from servant -> {}",msg); }, 
-                                            }
-                                        },
-                                        Err(_)    => {
-                                            *alive.write().await = false; 
-                                            if *full_alive.read().await { eprintln!("Killing master (servant restart not implemented for now)! Custer channel is closed!");  } // mute during shutdown
-                                        }
-                                    } 
-                                }));
-                            }
-                            for handle in handles { let _: Result<_,_> = handle.await; }
-                            if VERBOSE { 
-                                println!("END: CLUSTER {} -> TURN OFF", cluster_id);
-
-                                println!("CLUSTER {} : KILL {} SERVANTS", cluster_id, send_to_servants.len());
-                            }
-                            let mut receivers = VecDeque::new();
-                            for (servid, msender) in &send_to_servants {
-                                let tid = task_id.lock().await.generate();
-                                if VERBOSE { println!("CLUSTER {}: TASK {} -> KILL SERVANT {}", cluster_id, tid.acknowledge_id(), servid,); }
-                                match Ctrl(tid, CtrlCell::Kill).send(msender).await {
-                                    Err(_) => {
-                                        *alive.write().await = false; 
-                                        if *full_alive.read().await { eprintln!("Killing master! Custer channel is closed!"); } // mute during shutdown
-                                    },
-                                    Ok(receiver) => receivers.push_front(receiver),
-                                }
-                            };
-                            let mut handles = VecDeque::new();
-                            while let Some(receiver) = receivers.pop_back() {
-                                let alive = alive.clone();
-                                let full_alive = full_alive.clone(); 
-                                let task_id = task_id.clone();
-                                handles.push_front(spawn(async move {
-                                    match receiver.await {
-                                        Ok(reply) => {
-                                            let tid = match reply {
-                                                ReplyToMaster::Ok(tid) => { 
-                                                    if VERBOSE { println!("CLUSTER {} : TASK {} IS DONE", cluster_id, tid); }
-                                                    tid 
-                                                },
-                                                ReplyToMaster::Undefined(tid,) => {
-                                                   *alive.write().await = false; 
-                                                    eprintln!("Reply from servant: [31mUNDEFINED CHANNEL FOR TASK {}[0m",tid); tid
-                                                },
-                                                ReplyToMaster::PingFail(tid,) => {
-                                                    eprintln!("Reply from servant: ping fail for task {}",tid); tid
-                                                },
-                                                ReplyToMaster::Failure(tid,) => {
-                                                    eprintln!("Reply from servant: failure for task {}",tid); tid
-                                                },
-                                                ReplyToMaster::WrongType(tid,) => {
-                                                    *alive.write().await = false; 
-                                                    eprintln!("Reply from servant: [31mTYPE MISMATCH FOR TASK {}[0m",tid); tid
-                                                },
-                                                ReplyToMaster::OutOfTime(tid,) => {
-                                                    eprintln!("Reply from servant: task {} is out of time",tid); tid
-                                                },
-                                            };
-                                            match task_id.lock().await.delete(TaskId::new(tid)) { 
-                                                Ok(()) => (), Err(msg) => { eprintln!("Reply from servant -> {}",msg); }, 
-                                            }
-                                        },
-                                        Err(_)    => {
-                                            *alive.write().await = false; 
-                                            if *full_alive.read().await { eprintln!("Killing master (servant restart not implemented for now)! Custer channel is closed!"); } // mute during shutdown                                             
-                                        }
-                                    } 
-                                }));
-                            }
-                            for handle in handles { let _: Result<_,_> = handle.await; }
-                            *alive.write().await = false;
-                            if VERBOSE { println!("END: CLUSTER {} -> KILL", cluster_id); }
-
-                        }
-                    }
-                }    
-            }));
+        for (servant_id, mreceiver) in recv_from_servants {
+            handles.push_front(Self::listen_servant(servant_id, mreceiver, state.clone()));
         }
         //
         // WAIT FOR INITIALIZATION TO BE DONE
-        let _ = handle_recv.await.is_err();
-        if *alive.read().await { 
+        let initialization = match handle_recv.await {
+            Ok(initialization) => initialization,
+            Err(e) => Err(SilxError::protocol("initialization has failed").with_source(e)),
+        };
+        if let Err(e) = &initialization { 
+            error!("killing master: {}", e);
+            *alive.write().await = false; 
+            Self::shutdown(state.clone()).await; // servants are killed, so that the cluster ends
+        } else if *alive.read().await { 
             //
             // AND THEN START ALL
             //
@@ -319,27 +596,25 @@ impl Master {
                 let alive = alive.clone(); 
                 let full_alive = full_alive.clone(); 
                 let task_id = task_id.clone(); 
-                let channels = channels.clone(); 
-                let send_to_servants = send_to_servants.clone();
+                let channels = channels.read().await.clone(); 
+                let send_to_servants = send_to_servants.read().await.clone();
                 spawn(async move {
-                    if VERBOSE { println!("CLUSTER {} : TURN ON {} CHANNELS", cluster_id, nb_channels); }
+                    debug!(channels = nb_channels, "turn on channels");
                     let mut receivers = VecDeque::new();
                     for (servant_id, servant_channels) in channels {
                         for (ch_i,_) in servant_channels {
                             match send_to_servants.get(&servant_id) {
                                 None => {
                                     *alive.write().await = false; 
-                                    if *full_alive.read().await { eprintln!("Killing master! Custer channel is undefined!"); } // mute during shutdown 
+                                    if *full_alive.read().await { error!("killing master: cluster channel is undefined"); } // mute during shutdown 
                                 },
                                 Some(msender) => {
                                     let tid = task_id.lock().await.generate();
-                                    if VERBOSE { 
-                                        println!("CLUSTER {} : TASK {} -> TURN ON CHANNEL {} FOR SERVANT {}", cluster_id, tid.acknowledge_id(), ch_i, servant_id,); 
-                                    }
+                                    debug!(task = %tid.acknowledge_id(), channel = ch_i, servant_id, "turn on channel");
                                     match Ctrl(tid, CtrlCell::TurnOnChl(ch_i,)).send(msender).await {
                                         Err(_) => {
                                             *alive.write().await = false; 
-                                            if *full_alive.read().await { eprintln!("Killing master! Custer channel is closed!"); } // mute during shutdown 
+                                            if *full_alive.read().await { error!("killing master: cluster channel is closed"); } // mute during shutdown 
                                         },
                                         Ok(receiver) => receivers.push_front(receiver),
                                     }      
@@ -357,46 +632,53 @@ impl Master {
                                 Ok(reply) => {
                                     let tid = match reply {
                                         ReplyToMaster::Ok(tid) => { 
-                                            if VERBOSE { println!("CLUSTER {} : TASK {} IS DONE", cluster_id, tid); }
+                                            trace!(task = %tid, "task is done");
                                             tid 
                                         },
                                         ReplyToMaster::Undefined(tid,) => {
                                             *alive.write().await = false; 
-                                            eprintln!("Reply from servant: [31mUNDEFINED CHANNEL FOR TASK {}[0m",tid); tid
+                                            error!(task = %tid, "reply from servant: undefined channel"); tid
                                         },
                                         ReplyToMaster::PingFail(tid,) => {
-                                            eprintln!("Reply from servant: ping fail for task {}",tid); tid
+                                            warn!(task = %tid, "reply from servant: ping fail"); tid
                                         },
                                         ReplyToMaster::Failure(tid,) => {
-                                            eprintln!("Reply from servant: failure for task {}",tid); tid
+                                            error!(task = %tid, "reply from servant: failure"); tid
                                         },
                                         ReplyToMaster::WrongType(tid,) => {
                                             *alive.write().await = false; 
-                                            eprintln!("Reply from servant: [31mTYPE MISMATCH FOR TASK {}[0m",tid); tid
+                                            error!(task = %tid, "reply from servant: type mismatch"); tid
                                         },
                                         ReplyToMaster::OutOfTime(tid,) => {
-                                            eprintln!("Reply from servant: task {} is out of time",tid); tid
+                                            warn!(task = %tid, "reply from servant: task is out of time"); tid
                                         },
                                     };
                                     match task_id.lock().await.delete(TaskId::new(tid)) { 
-                                        Ok(()) => (), Err(msg) => { eprintln!("Reply from servant -> {}",msg); }, 
+                                        Ok(()) => (), Err(msg) => { error!("reply from servant -> {}", msg); }, 
                                     }
                                 },
                                 Err(_)    => {
                                     *alive.write().await = false; 
-                                    if *full_alive.read().await { eprintln!("Killing master (servant restart not implemented for now)! Custer channel is closed!"); } // mute during shutdown                                  
+                                    if *full_alive.read().await { error!("killing master: cluster channel is closed"); } // mute during shutdown                                  
                                 }
                             } 
                         }));
                     }
                     for handle in handles { let _: Result<_,_> = handle.await; }
-                    if VERBOSE { println!("END: CLUSTER {} -> TURN ON", cluster_id); }
+                    debug!("channels are turned on");
                 })
             };
             let _: Result<_,_> = handle_recv.await;
             for handle in handles { let _: Result<_,_> = handle.await;  }            
         }
-
-//        println!("\nMASTER {} IS DONE", cluster_id);
+        handle_updates.abort(); let _ = handle_updates.await;
+        if let Some((handle_listener, handle_admin, address)) = handles_admin {
+            handle_listener.abort(); handle_admin.abort(); let _ = handle_listener.await; let _ = handle_admin.await;
+            if let AdminAddress::Unix(path) = address { let _ = std::fs::remove_file(path); }
+        }
+        if let Some(handle_metrics) = handle_metrics { handle_metrics.abort(); let _ = handle_metrics.await; }
+        let dynamic_handles: Vec<_> = dynamic_handles.lock().await.drain(..).collect();
+        for handle in dynamic_handles { let _: Result<_,_> = handle.await; }
+        initialization
     }
 }
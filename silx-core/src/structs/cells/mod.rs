@@ -1,6 +1,9 @@
 /// Definition of servant
 pub mod servant;
 
+/// Definition of servant supervisor
+pub (crate) mod supervisor;
+
 /// Definition of master
 pub mod master;
 
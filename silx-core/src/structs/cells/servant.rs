@@ -1,24 +1,26 @@
 use crate::{
-    shared::id_tools::TaskId,
-    ServantIdType,
+    shared::{ id_tools::{ TaskId, AcknowledgeId, }, trace::spawn, },
+    ServantIdType, ChannelIdType,
     traits::procell::{ ProcessType as pty, HasProcess, },
+    builder::telemetry::{ Telemetry, ChannelMetrics, ServantMetrics, },
     structs::cells::{
         components::MembraneType,
         futures::{ 
-            FutureUndefined, FutureQuerySender, FutureQueryReceiver, FutureBroadCastSender, FutureBroadCastReceiver, FutureSignalSender, FutureSignalReceiver,
+            ChannelContext, FutureUndefined, FutureQuerySender, FutureQueryReceiver, FutureBroadCastSender, FutureBroadCastReceiver, FutureSignalSender, FutureSignalReceiver,
             FutureNetQuerySender, FutureNetQueryReceiver, FutureNetBroadCastSender, FutureNetBroadCastReceiver,
+            FutureNetSignalSender, FutureNetSignalReceiver,
             FutureTurnOnChannel, FutureTurnOffChannel, FuturePingChannel, FutureKillChannel,
         },
         ctrl_message::{ CtrlCell, MsgFromMaster, SetChannel as sch, ReplyToMaster, RecvFromMaster, SenderToMaster, },
     },
 };
 
-use std::{ sync::Arc, time::Duration, future::Future, fmt::Debug, fmt::{ Formatter, Error, }, };
+use std::{ sync::Arc, time::{ Duration, Instant, }, future::Future, fmt::Debug, fmt::{ Formatter, Error, }, };
 
-use tokio::{ spawn, time::timeout, sync::{ Mutex, RwLock, }, };
+use tokio::{ time::timeout, task::JoinHandle, sync::{ Mutex, RwLock, }, };
+use tracing::{ debug_span, error, trace, warn, Instrument, };
 use fnv::FnvHashMap;
 
-const VERBOSE: bool = false;
 
 /// doc to be defined
 pub struct Servant {
@@ -29,6 +31,9 @@ pub struct Servant {
     cluster_recv: RecvFromMaster,
     alive: Arc<RwLock<bool>>,
     max_cycle_time: Duration,
+    channel_names: FnvHashMap<ChannelIdType, String>,
+    telemetry: Arc<Telemetry>,
+    metrics: Arc<ServantMetrics>,
 }
 impl Debug for Servant {
     fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), Error> {
@@ -39,32 +44,65 @@ impl Servant {
     pub (crate) fn new(servant_id: ServantIdType, servant_name: String, processes: Box<dyn HasProcess + Send>, 
                                                   max_cycle_time: Duration, cluster_recv: RecvFromMaster,) -> Self {
         let processes = Mutex::new(processes);
+        let telemetry = Arc::new(Telemetry::default());
+        let metrics = telemetry.servant(&servant_name);
         Self { 
             servant_id, servant_name,
             processes, cluster_recv, max_cycle_time, 
             membrane: Arc::new(RwLock::new(FnvHashMap::default())), 
             alive: Arc::new(RwLock::new(true)), 
+            channel_names: FnvHashMap::default(), telemetry, metrics,
         }
     }
 
-    async fn launch_task<T>(&self, tid: TaskId, task: T, reply: SenderToMaster)
+    // Set the telemetry registry of the cluster and the channel names (used by the supervisor when building the servant)
+    pub (crate) fn set_telemetry(&mut self, telemetry: Arc<Telemetry>, ch_naming: &FnvHashMap<ChannelIdType, String,>) {
+        self.metrics = telemetry.servant(&self.servant_name);
+        self.telemetry = telemetry;
+        self.channel_names = ch_naming.clone();
+    }
+
+    // Get the counters of a channel of the servant
+    fn channel_metrics(&self, channel: ChannelIdType) -> Arc<ChannelMetrics> {
+        let name = self.channel_names.get(&channel).cloned().unwrap_or_else(|| channel.to_string());
+        self.telemetry.channel(&self.servant_name, &name)
+    }
+
+    // Get the context of the future setting a channel of the servant
+    fn channel_context(&self, acknowledge_id: AcknowledgeId, channel: ChannelIdType, max_ping: Duration) -> ChannelContext {
+        ChannelContext { acknowledge_id, membrane: self.membrane.clone(), channel, metrics: self.channel_metrics(channel), max_ping, }
+    }
+
+    // Get the membrane of the servant (used by the supervisor for checking channels failures)
+    pub (crate) fn membrane(&self) -> MembraneType { self.membrane.clone() }
+
+    // Take the handle of the future of the servant process (used by the supervisor for detecting panics)
+    pub (crate) fn take_process_handle(&mut self) -> Option<JoinHandle<()>> { self.processes.get_mut().take_handle() }
+
+    async fn launch_task<T>(&self, tid: TaskId, channel: ChannelIdType, task: T, reply: SenderToMaster)
                                                         where T: Future<Output = ReplyToMaster> + Send + 'static, {
         let max_cycle_time = self.max_cycle_time;
         let alive = self.alive.clone();
+        let metrics = self.metrics.clone();
+        let span = debug_span!("task", task = %tid.acknowledge_id(), channel);
         spawn(async move {
-            match timeout(max_cycle_time, task,).await {
+            let start = Instant::now();
+            let result = timeout(max_cycle_time, task,).await;
+            metrics.task(start.elapsed(), result.is_err());
+            match result {
                 Ok(rep) => {
                     if reply.send(rep).is_err() {
-                        *alive.write().await = false; eprintln!("Killing servant! Failed to reply to cluster!");
+                        *alive.write().await = false; error!("killing servant: failed to reply to cluster");
                     }
                 },
                 Err(_) => { 
+                    warn!("task is out of time");
                     if reply.send(ReplyToMaster::OutOfTime(tid.acknowledge_id())).is_err() {
-                        *alive.write().await = false; eprintln!("Killing servant! Failed to reply to cluster!");
+                        *alive.write().await = false; error!("killing servant: failed to reply to cluster");
                     }
                 },
             }    
-        });
+        }.instrument(span));
     }
 
     async fn kill(&mut self) {
@@ -74,110 +112,132 @@ impl Servant {
     }
 
     pub (crate) async fn run(mut self) {
-        if VERBOSE { println!("SERVANT {} : RUNNING START", self.servant_id); }
+        trace!("servant running start");
         use MsgFromMaster::Ctrl as Ctrl;
         let alive = self.alive.clone();
         while *alive.read().await {
-            if VERBOSE { println!("SERVANT {} : WAITING FOR TASK", self.servant_id,); }
+            trace!("waiting for task");
             match self.cluster_recv.recv().await {
-                None => { *self.alive.write().await = false; eprintln!("Killing servant! Cluster channel is closed!"); },
+                None => { *self.alive.write().await = false; error!("killing servant: cluster channel is closed"); },
                 Some((Ctrl(tid,ctrl),cluster_reply)) => {
                     let acknowledge_id =  tid.acknowledge_id();
-                    if VERBOSE { println!("SERVANT {} : START TASK {}", self.servant_id, acknowledge_id,); }
+                    trace!(task = %acknowledge_id, "start task");
                     let alive = alive.clone();
                     match ctrl {
+                        CtrlCell::NameChl(channel, name) => { // bind channel id to named process; processed directly
+                            self.channel_names.insert(channel, name.clone());
+                            let reply = if self.processes.lock().await.bind(channel, &name) { 
+                                ReplyToMaster::Ok(acknowledge_id) 
+                            } else { ReplyToMaster::Undefined(acknowledge_id) };
+                            if cluster_reply.send(reply).is_err() { error!("failed to reply to cluster"); }
+                        },
                         CtrlCell::SetChl(channel, set_channel) => {
                             let process = self.processes.lock().await.process(channel);
                             match (process, set_channel) {
-                                (None,_)  => self.launch_task(tid, FutureUndefined::new(acknowledge_id,),cluster_reply).await,
+                                (None,_)  => self.launch_task(tid, channel, FutureUndefined::new(acknowledge_id,),cluster_reply).await,
 
                                 (Some(pty::Query{in_chan,in_type,out_chan,out_type}),sch::NetQuerySender{max_ping,query_type,reply_type,sender,},) => {
-                                    let membrane = self.membrane.clone();
-                                    self.launch_task(tid, FutureNetQuerySender::new(acknowledge_id, membrane, channel,
-                                        in_chan, in_type, out_chan, out_type, max_ping, query_type, reply_type, sender,
+                                    let context = self.channel_context(acknowledge_id, channel, max_ping);
+                                    self.launch_task(tid, channel, FutureNetQuerySender::new(context,
+                                        in_chan, in_type, out_chan, out_type, (query_type, reply_type), sender,
                                     ),cluster_reply).await;
                                 },
                                 (Some(pty::Reply{in_type,out_type,mapper,}),sch::NetQueryReceiver{max_ping,query_type,reply_type,receiver,},) => {
-                                    let membrane = self.membrane.clone();
-                                    self.launch_task(tid, FutureNetQueryReceiver::new(acknowledge_id, membrane, channel,
-                                            in_type, out_type, mapper, max_ping, query_type, reply_type, receiver,
+                                    let context = self.channel_context(acknowledge_id, channel, max_ping);
+                                    self.launch_task(tid, channel, FutureNetQueryReceiver::new(context,
+                                            in_type, out_type, mapper, (query_type, reply_type), receiver,
                                     ),cluster_reply).await;
                                 },
                                 (Some(pty::Emit{in_chan,in_type,}),sch::NetBroadcastSender{max_ping,data_type,sender,},) => {
-                                    let membrane = self.membrane.clone();
-                                    self.launch_task(tid, FutureNetBroadCastSender::new(acknowledge_id, membrane, channel, 
-                                        in_chan, in_type, max_ping, data_type, sender,
+                                    let context = self.channel_context(acknowledge_id, channel, max_ping);
+                                    self.launch_task(tid, channel, FutureNetBroadCastSender::new(context,
+                                        in_chan, in_type, data_type, sender,
                                     ),cluster_reply).await;
                                 },
                                 (Some(pty::Read{out_chan,out_type,}),sch::NetBroadcastReceiver{max_ping,data_type,receiver,},) => {
-                                    let membrane = self.membrane.clone();
-                                    self.launch_task(tid, FutureNetBroadCastReceiver::new(acknowledge_id, membrane, channel,
-                                        out_chan, out_type, max_ping, data_type, receiver,
+                                    let context = self.channel_context(acknowledge_id, channel, max_ping);
+                                    self.launch_task(tid, channel, FutureNetBroadCastReceiver::new(context,
+                                        out_chan, out_type, data_type, receiver,
+                                    ),cluster_reply).await;
+                                },
+                                (Some(pty::Emit{in_chan,in_type,}),sch::NetSignalSender{max_ping,data_type,sender,},) => {
+                                    let context = self.channel_context(acknowledge_id, channel, max_ping);
+                                    self.launch_task(tid, channel, FutureNetSignalSender::new(context,
+                                        in_chan, in_type, data_type, sender,
+                                    ),cluster_reply).await;
+                                },
+                                (Some(pty::RefRead{assert,out_type,}),sch::NetSignalReceiver{max_ping,data_type,receiver,},) => {
+                                    let context = self.channel_context(acknowledge_id, channel, max_ping);
+                                    self.launch_task(tid, channel, FutureNetSignalReceiver::new(context,
+                                        assert, out_type, data_type, receiver,
                                     ),cluster_reply).await;
                                 },
 
                                 (Some(pty::Query{in_chan,in_type,out_chan,out_type}),sch::QuerySender{max_ping,query_type,reply_type,sender,},) => {
-                                    let membrane = self.membrane.clone();
-                                    self.launch_task(tid, FutureQuerySender::new(acknowledge_id, membrane, channel,
-                                        in_chan, in_type, out_chan, out_type, max_ping, query_type, reply_type, sender,
+                                    let context = self.channel_context(acknowledge_id, channel, max_ping);
+                                    self.launch_task(tid, channel, FutureQuerySender::new(context,
+                                        in_chan, in_type, out_chan, out_type, (query_type, reply_type), sender,
                                     ),cluster_reply).await;
                                 },
                                 (Some(pty::Reply{in_type,out_type,mapper,}),sch::QueryReceiver{max_ping,query_type,reply_type,receiver,},) => {
-                                    let membrane = self.membrane.clone();
-                                    self.launch_task(tid, FutureQueryReceiver::new(acknowledge_id, membrane, channel,
-                                            in_type, out_type, mapper, max_ping, query_type, reply_type, receiver,
+                                    let context = self.channel_context(acknowledge_id, channel, max_ping);
+                                    self.launch_task(tid, channel, FutureQueryReceiver::new(context,
+                                            in_type, out_type, mapper, (query_type, reply_type), receiver,
                                     ),cluster_reply).await;
                                 },
                                 (Some(pty::Emit{in_chan,in_type,}),sch::BroadcastSender{max_ping,data_type,sender,},) => {
-                                    let membrane = self.membrane.clone();
-                                    self.launch_task(tid, FutureBroadCastSender::new(acknowledge_id, membrane, channel, 
-                                        in_chan, in_type, max_ping, data_type, sender,
+                                    let context = self.channel_context(acknowledge_id, channel, max_ping);
+                                    self.launch_task(tid, channel, FutureBroadCastSender::new(context,
+                                        in_chan, in_type, data_type, sender,
                                     ),cluster_reply).await;
                                 },
                                 (Some(pty::Read{out_chan,out_type,}),sch::BroadcastReceiver{max_ping,data_type,receiver,},) => {
-                                    let membrane = self.membrane.clone();
-                                    self.launch_task(tid, FutureBroadCastReceiver::new(acknowledge_id, membrane, channel,
-                                        out_chan, out_type, max_ping, data_type, receiver,
+                                    let context = self.channel_context(acknowledge_id, channel, max_ping);
+                                    self.launch_task(tid, channel, FutureBroadCastReceiver::new(context,
+                                        out_chan, out_type, data_type, receiver,
                                     ),cluster_reply).await;
                                 },
                                 (Some(pty::Emit{in_chan,in_type,}),sch::SignalSender{max_ping,data_type,sender,},) => {
-                                    let membrane = self.membrane.clone();
-                                    self.launch_task(tid, FutureSignalSender::new(acknowledge_id, membrane, channel,
-                                        in_chan, in_type, max_ping, data_type, sender,
+                                    let context = self.channel_context(acknowledge_id, channel, max_ping);
+                                    self.launch_task(tid, channel, FutureSignalSender::new(context,
+                                        in_chan, in_type, data_type, sender,
                                     ),cluster_reply).await;
                                 },
                                 (Some(pty::RefRead{assert,out_type,}),sch::SignalReceiver{max_ping,data_type,receiver,},) => {
-                                    let membrane = self.membrane.clone();
-                                    self.launch_task(tid, FutureSignalReceiver::new(acknowledge_id, membrane, channel,
-                                        assert, out_type, max_ping, data_type, receiver,
+                                    let context = self.channel_context(acknowledge_id, channel, max_ping);
+                                    self.launch_task(tid, channel, FutureSignalReceiver::new(context,
+                                        assert, out_type, data_type, receiver,
                                     ),cluster_reply).await;
                                 },
                                 // MANQUE LE FLOW INITIALISE
-                                _ => panic!("impossible case"),
+                                _ => { // the process does not match the kind of the channel; only this request fails
+                                    warn!(task = %acknowledge_id, channel, "process does not match the channel");
+                                    if cluster_reply.send(ReplyToMaster::WrongType(acknowledge_id)).is_err() { error!("failed to reply to cluster"); }
+                                },
                             }
                         },
                         CtrlCell::TurnOnChl(channel) => {
                             let membrane = self.membrane.clone();
-                            self.launch_task(tid, FutureTurnOnChannel::new(acknowledge_id, membrane, channel,),cluster_reply).await;
+                            self.launch_task(tid, channel, FutureTurnOnChannel::new(acknowledge_id, membrane, channel,),cluster_reply).await;
                         },
                         CtrlCell::TurnOffChl(channel) => { // turn off channel
                             let membrane = self.membrane.clone();
-                            self.launch_task(tid, FutureTurnOffChannel::new(acknowledge_id, membrane, channel,),cluster_reply).await;
+                            self.launch_task(tid, channel, FutureTurnOffChannel::new(acknowledge_id, membrane, channel,),cluster_reply).await;
                         },
                         CtrlCell::PingChl(channel) => { // Ping the channel
                             let membrane = self.membrane.clone();
-                            self.launch_task(tid, FuturePingChannel::new(acknowledge_id, membrane, channel,),cluster_reply).await;
+                            self.launch_task(tid, channel, FuturePingChannel::new(acknowledge_id, membrane, channel,),cluster_reply).await;
                         },
                         CtrlCell::KillChl(channel) => { // Kill channel
                             let membrane = self.membrane.clone();
-                            self.launch_task(tid, FutureKillChannel::new(acknowledge_id, membrane, channel,),cluster_reply).await;
+                            self.launch_task(tid, channel, FutureKillChannel::new(acknowledge_id, membrane, channel,),cluster_reply).await;
                         },
                         CtrlCell::Kill => { // Kill Cell
                             self.kill().await; *alive.write().await = false; // kill process is not spawn, but processed directly
-                            if cluster_reply.send(ReplyToMaster::Ok(acknowledge_id,)).is_err() { eprintln!("Killing servant! Failed to reply to cluster!"); }
+                            if cluster_reply.send(ReplyToMaster::Ok(acknowledge_id,)).is_err() { error!("killing servant: failed to reply to cluster"); }
                         },
                     };
-                    if VERBOSE { println!("SERVANT {} : TASK {} DONE", self.servant_id, acknowledge_id,); }
+                    trace!(task = %acknowledge_id, "task done");
                 },
             }
         }
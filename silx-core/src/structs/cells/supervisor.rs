@@ -0,0 +1,272 @@
+use crate::{
+    shared::{ id_tools::{ IdBuilder, TaskId, }, trace::spawn, error::SilxError, },
+    ChannelIdType, ServantIdType,
+    builder::{ ServantBuilder, Supervision, telemetry::Telemetry, },
+    structs::cells::{
+        servant::Servant,
+        components::MembraneType,
+        ctrl_message::{
+            CtrlCell, MsgFromMaster, MsgFromServant, SetChannel as sch, ReplyToMaster, RecvFromMaster, SendToMaster,
+            SendToServant, SenderToMaster, SupervisionEvent,
+        },
+    },
+};
+
+use std::{ collections::{ BTreeMap, BTreeSet, VecDeque, }, sync::Arc, time::{ Duration, Instant, }, };
+
+use tokio::{ select, time::interval, task::JoinHandle, sync::{ Mutex, mpsc::{ self as msc, error::SendError, }, }, };
+use tracing::{ error, info_span, warn, Instrument, };
+use fnv::FnvHashMap;
+
+/// Running instance of a supervised servant
+struct ServantInstance {
+    send_to_servant: SendToServant,
+    membrane: MembraneType,
+    process: Option<JoinHandle<()>>,
+    handle: JoinHandle<()>,
+}
+
+// check if a restart is allowed at instant now, and record it if so
+// * restarts older than the supervision period `within` are forgotten
+// * at most `max_restarts` restarts are allowed within the supervision period
+fn allow_restart(restarts: &mut VecDeque<Instant>, max_restarts: usize, within: Duration, now: Instant) -> bool {
+    while restarts.front().map(|instant| now.duration_since(*instant) > within).unwrap_or(false) { restarts.pop_front(); }
+    let allowed = restarts.len() < max_restarts;
+    if allowed { restarts.push_back(now); }
+    allowed
+}
+
+/// Supervisor of a servant
+/// * The supervisor stands between the master of the cluster and the servant: it forwards the requests of the master to the servant,
+///   and records the settings of the channels, so as to replay them when the servant is restarted
+pub (crate) struct Supervisor {
+    servant_id: ServantIdType,
+    servant_name: String,
+    builder: Mutex<Box<dyn ServantBuilder>>,
+    ch_naming: FnvHashMap<ChannelIdType, String,>,
+    supervision: Supervision,
+    ctrl_ch_capacity: usize,
+    cluster_recv: RecvFromMaster,
+    send_2_master: SendToMaster,
+    task_id: IdBuilder,
+    telemetry: Arc<Telemetry>,
+    // settings of the channels and turned on channels, as requested by the master
+    channels: BTreeMap<ChannelIdType, sch>,
+    turned_on: BTreeSet<ChannelIdType>,
+    // instants of the restarts within the supervision period
+    restarts: VecDeque<Instant>,
+    // servant built but not yet launched
+    pending: Option<(Servant,SendToServant)>,
+    // running instance of the servant; None when the servant is stopped
+    instance: Option<ServantInstance>,
+}
+
+impl Supervisor {
+    #[allow(clippy::too_many_arguments)]
+    pub (crate) async fn new(servant_id: ServantIdType, servant_name: String, builder: Box<dyn ServantBuilder>, ch_naming: FnvHashMap<ChannelIdType, String,>,
+            supervision: Supervision, ctrl_ch_capacity: usize, cluster_recv: RecvFromMaster, send_2_master: SendToMaster, task_id: IdBuilder,
+            telemetry: Arc<Telemetry>,
+    ) -> Result<Self,SilxError> {
+        let mut supervisor = Self {
+            servant_id, servant_name, builder: Mutex::new(builder), ch_naming, supervision, ctrl_ch_capacity, cluster_recv, send_2_master, task_id, telemetry,
+            channels: BTreeMap::new(), turned_on: BTreeSet::new(), restarts: VecDeque::new(), pending: None, instance: None,
+        };
+        supervisor.pending = Some(supervisor.build_servant().await?);
+        Ok(supervisor)
+    }
+
+    // build a new instance of the servant, together with its control channel
+    async fn build_servant(&self) -> Result<(Servant,SendToServant),SilxError> {
+        let (send_to_servant, recv_from_master) = msc::channel(self.ctrl_ch_capacity);
+        let mut servant = self.builder.lock().await.build(
+            self.servant_id, self.servant_name.clone(), &self.ch_naming, recv_from_master, self.send_2_master.clone(), self.task_id.clone()
+        )?;
+        servant.set_telemetry(self.telemetry.clone(), &self.ch_naming);
+        Ok((servant,send_to_servant))
+    }
+
+    // launch an instance of the servant
+    fn launch(&mut self, (mut servant, send_to_servant): (Servant,SendToServant)) {
+        let membrane = servant.membrane();
+        let process = servant.take_process_handle();
+        let handle = spawn(servant.run());
+        self.instance = Some(ServantInstance { send_to_servant, membrane, process, handle, });
+    }
+
+    // abort the running instance of the servant
+    async fn abort(&mut self) {
+        if let Some(ServantInstance { membrane, process, handle, .. }) = self.instance.take() {
+            handle.abort(); let _ = handle.await;
+            if let Some(process) = process { process.abort(); let _ = process.await; }
+            // channels handles are not awaited, since they may have been awaited yet by a kill request
+            for (_,(_,_,_,rhandle)) in membrane.write().await.drain() { rhandle.read().await.abort(); }
+        }
+    }
+
+    // check the running instance of the servant for failure
+    async fn failure(&mut self) -> Option<String> {
+        let instance = self.instance.as_mut()?;
+        if instance.handle.is_finished() { return Some("servant is ended".to_string()); }
+        if instance.process.as_ref().map(|process| process.is_finished()).unwrap_or(false) {
+            let process = instance.process.take().expect("unexpected error");
+            if let Err(e) = process.await { if e.is_panic() { return Some("process has panicked".to_string()); } }
+        }
+        for (channel,(_,flag,_,_)) in instance.membrane.read().await.iter() {
+            if flag.is_failed().await { return Some(format!("channel {channel} has failed")); }
+        }
+        None
+    }
+
+    // report a supervision event to the master
+    async fn report(&self, event: SupervisionEvent) {
+        let tid = self.task_id.lock().await.generate();
+        match MsgFromServant::Supervision(tid, event).send(&self.send_2_master).await {
+            Ok(receiver) => { let _ = receiver.await; },
+            Err(e) => error!("failed to report event {:?} -> {}", event, e),
+        }
+    }
+
+    // escalate the failure to the master, which will shut the cluster down
+    async fn escalate(&self) {
+        self.report(SupervisionEvent::Escalated).await;
+        let tid = self.task_id.lock().await.generate();
+        // the reply is not awaited: the master is about to request this supervisor for the shutdown
+        if let Err(e) = MsgFromServant::Shutdown(tid).send(&self.send_2_master).await {
+            error!("failed to request shutdown -> {}", e);
+        }
+    }
+
+    // replay a request to the running instance of the servant
+    async fn replay(&self, ctrl: CtrlCell) -> Result<(),SilxError> {
+        let send_to_servant = match &self.instance {
+            Some(instance) => &instance.send_to_servant, None => return Err(SilxError::protocol("servant is not running")),
+        };
+        let tid = self.task_id.lock().await.generate();
+        let acknowledge_id = tid.acknowledge_id();
+        let receiver = MsgFromMaster::Ctrl(tid, ctrl).send(send_to_servant).await?;
+        let reply = receiver.await.map_err(|e| SilxError::protocol(format!("no reply to task {acknowledge_id}")).with_source(e))?;
+        self.task_id.lock().await.delete(TaskId::new(acknowledge_id))?;
+        match reply {
+            ReplyToMaster::Ok(_) => Ok(()),
+            _ => Err(SilxError::protocol(format!("task {acknowledge_id} has failed"))),
+        }
+    }
+
+    // rebuild the servant and set its channels again
+    async fn restart(&mut self) -> Result<(),SilxError> {
+        let servant = self.build_servant().await?;
+        self.launch(servant);
+        let channels: Vec<_> = self.channels.iter().map(|(channel,set_channel)| (*channel,set_channel.clone())).collect();
+        for (channel,set_channel) in channels { self.replay(CtrlCell::SetChl(channel, set_channel)).await?; }
+        let turned_on: Vec<_> = self.turned_on.iter().copied().collect();
+        for channel in turned_on { self.replay(CtrlCell::TurnOnChl(channel)).await?; }
+        Ok(())
+    }
+
+    // apply the supervision strategy to a failed servant
+    async fn supervise(&mut self, reason: String) {
+        warn!("servant has failed -> {}", reason);
+        self.abort().await;
+        match self.supervision {
+            Supervision::Stop => self.report(SupervisionEvent::Stopped).await,
+            Supervision::Restart { max_restarts, within, } => {
+                if allow_restart(&mut self.restarts, max_restarts, within, Instant::now()) {
+                    match self.restart().await {
+                        Ok(()) => self.report(SupervisionEvent::Restarted(self.restarts.len())).await,
+                        Err(e) => {
+                            error!("failed to restart servant -> {}", e);
+                            self.abort().await; self.escalate().await;
+                        },
+                    }
+                } else { self.escalate().await; }
+            },
+            Supervision::Escalate => self.escalate().await,
+        }
+    }
+
+    // forward a request of the master to the servant; output is true when the supervisor is ended
+    async fn forward(&mut self, tid: TaskId, ctrl: CtrlCell, cluster_reply: SenderToMaster) -> bool {
+        let kill = matches!(ctrl, CtrlCell::Kill);
+        match &ctrl {
+            CtrlCell::NameChl(channel, name) => { self.ch_naming.insert(*channel, name.clone()); },
+            CtrlCell::SetChl(channel, set_channel) => { self.channels.insert(*channel, set_channel.clone()); },
+            CtrlCell::TurnOnChl(channel) => { self.turned_on.insert(*channel); },
+            CtrlCell::TurnOffChl(channel) => { self.turned_on.remove(channel); },
+            CtrlCell::KillChl(channel) => { self.turned_on.remove(channel); self.channels.remove(channel); self.ch_naming.remove(channel); },
+            CtrlCell::PingChl(_) | CtrlCell::Kill => (),
+        }
+        let mut request = (MsgFromMaster::Ctrl(tid, ctrl), cluster_reply);
+        loop {
+            match &self.instance {
+                Some(instance) => match instance.send_to_servant.send(request).await {
+                    Ok(()) => break,
+                    Err(SendError(back)) => {
+                        request = back; self.supervise("servant is ended".to_string()).await;
+                        // the restarted servant has replayed the settings of the channels, including this one, which is thus not resent
+                        let (MsgFromMaster::Ctrl(tid, ctrl), _) = &request;
+                        if self.instance.is_some() && !matches!(ctrl, CtrlCell::PingChl(_) | CtrlCell::Kill) {
+                            let acknowledge_id = tid.acknowledge_id();
+                            if request.1.send(ReplyToMaster::Ok(acknowledge_id)).is_err() { error!("failed to reply to cluster"); }
+                            break;
+                        }
+                    },
+                },
+                None => { // stopped servant: the supervisor replies on its behalf
+                    let (MsgFromMaster::Ctrl(tid, ctrl), cluster_reply) = request;
+                    let acknowledge_id = tid.acknowledge_id();
+                    let reply = match ctrl {
+                        CtrlCell::TurnOffChl(_) | CtrlCell::KillChl(_) | CtrlCell::Kill => ReplyToMaster::Ok(acknowledge_id),
+                        _ => ReplyToMaster::Failure(acknowledge_id),
+                    };
+                    if cluster_reply.send(reply).is_err() { error!("failed to reply to cluster"); }
+                    break;
+                },
+            }
+        }
+        if kill { if let Some(instance) = self.instance.take() { let _ = instance.handle.await; } }
+        kill
+    }
+
+    pub (crate) async fn run(self) {
+        let span = info_span!("servant", servant_id = self.servant_id, servant = %self.servant_name);
+        self.run_loop().instrument(span).await
+    }
+
+    // supervision loop; the servant and its channels are run within the span of the supervisor
+    async fn run_loop(mut self) {
+        if let Some(servant) = self.pending.take() { self.launch(servant); }
+        let mut ticker = interval(self.builder.lock().await.max_cycle_time());
+        loop {
+            select! {
+                received = self.cluster_recv.recv() => match received {
+                    None => { self.abort().await; error!("killing supervisor: cluster channel is closed"); break; },
+                    Some((MsgFromMaster::Ctrl(tid,ctrl),cluster_reply)) => if self.forward(tid, ctrl, cluster_reply).await { break; },
+                },
+                _ = ticker.tick() => if let Some(reason) = self.failure().await { self.supervise(reason).await; },
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    /// restarts are allowed up to max_restarts within the supervision period, and then escalated
+    fn restart_then_escalate() {
+        let (max_restarts, within) = (2, Duration::from_secs(10));
+        let mut restarts = VecDeque::new();
+        let start = Instant::now();
+        assert!(allow_restart(&mut restarts, max_restarts, within, start));
+        assert!(allow_restart(&mut restarts, max_restarts, within, start + Duration::from_secs(1)));
+        assert!(!allow_restart(&mut restarts, max_restarts, within, start + Duration::from_secs(2)));
+        assert_eq!(restarts.len(), max_restarts);
+        // the first restart is out of the supervision period
+        assert!(allow_restart(&mut restarts, max_restarts, within, start + Duration::from_secs(11)));
+        assert!(!allow_restart(&mut restarts, max_restarts, within, start + Duration::from_secs(11)));
+        // all restarts are out of the supervision period
+        assert!(allow_restart(&mut restarts, max_restarts, within, start + Duration::from_secs(30)));
+        assert_eq!(restarts.len(), 1);
+    }
+}
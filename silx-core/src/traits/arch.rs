@@ -1,4 +1,4 @@
-use crate::{ shared::utils::ArchData, utils::SlxData, NALLOC }; 
+use crate::{ shared::{ utils::ArchData, error::SilxError, }, utils::SlxData, NALLOC }; 
 use rkyv::{ Serialize, ser::serializers::AllocSerializer, };
 
 /// A trait helper for building a sized archive from a slx data
@@ -8,12 +8,12 @@ pub trait ArchSized {
 
     /// Build the archive data from a slx data reference
     /// * Output: the archive data or an error
-    fn arch_sized(&self) -> Result<ArchData<Self::Archivable>,String>;
+    fn arch_sized(&self) -> Result<ArchData<Self::Archivable>,SilxError>;
 }
 
 impl<U> ArchSized for U where U: SlxData + Serialize<AllocSerializer<NALLOC>> {
     type Archivable = U;
-    #[inline] fn arch_sized(&self) -> Result<ArchData<Self::Archivable>,String> { 
+    #[inline] fn arch_sized(&self) -> Result<ArchData<Self::Archivable>,SilxError> { 
         ArchData::new_sized(self) 
     }
 }
\ No newline at end of file
@@ -1,6 +1,8 @@
 use std::{ path::{ Path, PathBuf, }, io::BufReader, fs::{ DirBuilder, File, }, mem, };
 use serde::{ Serialize, Deserialize, de::DeserializeOwned, };
 
+use crate::shared::error::SilxError;
+
 /// Properties of a type containing a variable data structure partially stored by means of several serialization files
 pub trait Filable { 
     /// Type of the unfiled data type
@@ -8,18 +10,18 @@ pub trait Filable {
 
     /// Unload all data (filed data needs to be fully loaded) into the associated unfiled data type
     /// * Output: unfiled data or error
-    fn unwrap(mut self) -> Result<Self::Unfiled,String,> where Self: Sized { self.unload(None) }
+    fn unwrap(mut self) -> Result<Self::Unfiled,SilxError,> where Self: Sized { self.unload(None) }
 
     /// Load all missing data from files; bool indicates if already loaded (false) or not
     /// * `dir: P` : directory path from which is loaded the serialized files of missing data
     /// * `P: AsRef<Path>` : type of the path
     /// * Output: a boolean or an error
-    fn load<P: AsRef<Path>>(&mut self, dir: P) -> Result<bool,String>;
+    fn load<P: AsRef<Path>>(&mut self, dir: P) -> Result<bool,SilxError>;
 
     /// Optionally serialize all data on disk (filed data needs to be fully loaded) while unloading into the associated unfiled data type
     /// * `odir: Option<&Path>` : optional directory path where are saved the serialized files of all data; if `None`, nothing is saved
     /// * Output: unfiled data or error
-    fn unload(&mut self, odir: Option<&Path>) -> Result<Self::Unfiled,String,>;
+    fn unload(&mut self, odir: Option<&Path>) -> Result<Self::Unfiled,SilxError,>;
 }
 
 #[derive(Clone, Serialize, Deserialize, Debug,)]
@@ -108,7 +110,7 @@ impl<T: Filable> RecFiled<T> {
 impl<T> Filable for Filed<T> where T: Serialize + DeserializeOwned, {
     type Unfiled = T;
 
-    fn load<P: AsRef<Path>,>(&mut self, prefix: P,) -> Result<bool,String> {
+    fn load<P: AsRef<Path>,>(&mut self, prefix: P,) -> Result<bool,SilxError> {
         let opath = match self { 
             Filed::loaded{..} => None, 
             Filed::unloaded { path } => Some(path.clone()), 
@@ -117,13 +119,12 @@ impl<T> Filable for Filed<T> where T: Serialize + DeserializeOwned, {
             None => { Ok(false) },
             Some(path) => { 
                 let full_path = prefix.as_ref().join(&path);
-                let reader = File::open(&full_path).map(|f| BufReader::new(f));
-                if let Ok(reader) = reader { 
-                    if let Ok(data) = serde_yaml::from_reader::<_,T>(reader) {
-                        *self = Filed::loaded { path, data }; 
-                        Ok(true)
-                    } else { Err("load: failed to unserialize".to_string()) }
-                } else { Err("load: failed to open file".to_string()) }
+                let reader = File::open(&full_path).map(|f| BufReader::new(f))
+                    .map_err(|e| SilxError::io(format!("load: failed to open file {}", full_path.display()), e))?;
+                let data = serde_yaml::from_reader::<_,T>(reader)
+                    .map_err(|e| SilxError::config(format!("load: failed to unserialize {}", full_path.display())).with_source(e))?;
+                *self = Filed::loaded { path, data }; 
+                Ok(true)
             }
         }
     }
@@ -131,32 +132,31 @@ impl<T> Filable for Filed<T> where T: Serialize + DeserializeOwned, {
     // optionally save all data on disk (all data should be loaded) while unloading data structure, and return unfiled data
     // opath indicate directory from which to load; if none, then data is actually not saved!
     // proc is the pre-process run before unloading
-    fn unload(&mut self, opath: Option<&Path>,) -> Result<T,String,> {
+    fn unload(&mut self, opath: Option<&Path>,) -> Result<T,SilxError,> {
         match if let Filed::loaded { data, path, } = self { 
             let opath_prefix = opath.map(|prefix| {                                                     // | create path if necessary
                 if let Some(path) = path.parent() { prefix.join(path) } else { prefix.to_path_buf() }   // |
             });                                                                                         // |
             let mut err = Ok(());                                                                       // |
             if let Some(path) = opath_prefix { err = DirBuilder::new().recursive(true).create(path); }  // |
-            if err.is_err() { Err("save: failed to build path".to_string()) } else {
+            if let Err(e) = err { Err(SilxError::io("save: failed to build path", e)) } else {
                 let filed = Filed::unloaded { path: path.to_path_buf(), };
                 let opath = opath.map(|prefix| prefix.join(path));
                 if let Some(path) = opath {
                     let buffer = File::create(path);
-                    if let Ok(buffer) = buffer {
-                        match serde_yaml::to_writer(buffer,data) {
+                    match buffer {
+                        Ok(buffer) => match serde_yaml::to_writer(buffer,data) {
                             Ok(_) => Ok(filed),
-                            Err(e) => Err(format!(
-                                "save_unload: failed to serialize -> {e}"
-                            )),
-                        }
-                    } else { Err("save_unload: failed to open file".to_string()) }
+                            Err(e) => Err(SilxError::config("save_unload: failed to serialize").with_source(e)),
+                        },
+                        Err(e) => Err(SilxError::io("save_unload: failed to open file", e)),
+                    }
                 } else { Ok(filed) }
             }
-        } else { Err("save_unload: unloaded data".to_string()) } { // matched data computed here
+        } else { Err(SilxError::config("save_unload: unloaded data")) } { // matched data computed here
             Ok(mut filed) => { 
                 mem::swap(&mut filed, self); 
-                if let Filed::loaded { data, .. } = filed { Ok(data) } else { Err("unload: unexpected error".to_string()) }
+                if let Filed::loaded { data, .. } = filed { Ok(data) } else { Err(SilxError::config("unload: unexpected error")) }
             },
             Err(e)     => Err(e),
         }
@@ -167,7 +167,7 @@ impl<T> Filable for Filed<T> where T: Serialize + DeserializeOwned, {
 impl<T> Filable for RecFiled<T> where T: Filable + Serialize + DeserializeOwned, {
     type Unfiled = T::Unfiled;
 
-    fn load<P: AsRef<Path>,>(&mut self, prefix: P,) -> Result<bool,String> {
+    fn load<P: AsRef<Path>,>(&mut self, prefix: P,) -> Result<bool,SilxError> {
         let opath = match self { 
             RecFiled::partially_loaded{..} => None, 
             RecFiled::unloaded { path } => Some(path.clone()), 
@@ -176,19 +176,18 @@ impl<T> Filable for RecFiled<T> where T: Filable + Serialize + DeserializeOwned,
             None => { Ok(false) },
             Some(path) => { 
                 let full_path = prefix.as_ref().join(&path);
-                let reader = File::open(&full_path).map(|f| BufReader::new(f));
-                if let Ok(reader) = reader { 
-                    if let Ok(mut data) = serde_yaml::from_reader::<_,T>(reader) {
-                        data.load(prefix)?;
-                        *self = RecFiled::partially_loaded { path, data }; 
-                        Ok(true)
-                    } else { Err("load: failed to unserialize".to_string()) }
-                } else { Err("load: failed to open file".to_string()) }
+                let reader = File::open(&full_path).map(|f| BufReader::new(f))
+                    .map_err(|e| SilxError::io(format!("load: failed to open file {}", full_path.display()), e))?;
+                let mut data = serde_yaml::from_reader::<_,T>(reader)
+                    .map_err(|e| SilxError::config(format!("load: failed to unserialize {}", full_path.display())).with_source(e))?;
+                data.load(prefix)?;
+                *self = RecFiled::partially_loaded { path, data }; 
+                Ok(true)
             }
         }
     }
 
-    fn unload(&mut self, opath: Option<&Path>,) -> Result<Self::Unfiled,String,> {
+    fn unload(&mut self, opath: Option<&Path>,) -> Result<Self::Unfiled,SilxError,> {
         match if let RecFiled::partially_loaded { data, path, } = self { 
             let inner_data = data.unload(opath.clone())?;
             let opath_prefix = opath.map(|prefix| {                                                     // | create path if necessary
@@ -196,18 +195,21 @@ impl<T> Filable for RecFiled<T> where T: Filable + Serialize + DeserializeOwned,
             });                                                                                         // |
             let mut err = Ok(());                                                                       // |
             if let Some(path) = opath_prefix { err = DirBuilder::new().recursive(true).create(path); }  // |
-            if err.is_err() { Err("save: failed to build path".to_string()) } else {
+            if let Err(e) = err { Err(SilxError::io("save: failed to build path", e)) } else {
                 let filed = RecFiled::unloaded { path: path.to_path_buf(), };
                 let opath = opath.map(|prefix| prefix.join(path));
                 if let Some(path) = opath {
                     let buffer = File::create(path);
-                    if let Ok(buffer) = buffer {
-                        if serde_yaml::to_writer(buffer,data).is_ok() { Ok((inner_data,filed)) } 
-                        else {  Err("save_unload: failed to serialize".to_string()) }
-                    } else { Err("save_unload: failed to open file".to_string()) }
+                    match buffer {
+                        Ok(buffer) => match serde_yaml::to_writer(buffer,data) {
+                            Ok(_) => Ok((inner_data,filed)),
+                            Err(e) => Err(SilxError::config("save_unload: failed to serialize").with_source(e)),
+                        },
+                        Err(e) => Err(SilxError::io("save_unload: failed to open file", e)),
+                    }
                 } else { Ok((inner_data,filed)) }
             }
-        } else { Err("save_unload: unloaded data".to_string()) } { // matched data computed here
+        } else { Err(SilxError::config("save_unload: unloaded data")) } { // matched data computed here
             Ok((inner_data,mut filed)) => { 
                 mem::swap(&mut filed, self); Ok(inner_data)
             },
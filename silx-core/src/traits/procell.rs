@@ -1,7 +1,7 @@
 use crate::{ 
     ChannelIdType, 
     traits::FullId,
-    shared::utils::{ SendToMaster, terminated, SlxData, },
+    shared::{ utils::{ SendToMaster, terminated, SlxData, }, trace::spawn, error::SilxError, },
     structs::{
         cells::components::{ Mapper, Assert, },
         archmod::{ 
@@ -11,12 +11,12 @@ use crate::{
         },
     },
 };
-use std::{ future::Future, sync::Arc, collections::HashMap, pin::Pin, };
+use std::{ future::Future, sync::{ Arc, Mutex as StdMutex, }, collections::HashMap, pin::Pin, };
 
 use hashed_type_def::HashedTypeDef;
 
 use fnv::FnvHashMap;
-use tokio::{ spawn, sync::Mutex, task::JoinHandle, }; 
+use tokio::{ sync::Mutex, task::JoinHandle, }; 
 
 
 #[derive(Clone,)]
@@ -74,6 +74,9 @@ pub (crate) trait HasNamedProcess where Self: __seal__::Sealer {
     fn processes(&self,) -> &HashMap<String, (FullId, Option<FullId>, ProcessType)>;
 
     fn activate(&mut self);
+
+    // Take the handle of the activated future
+    fn take_handle(&mut self) -> Option<JoinHandle<()>>;
     
     fn signature(&self) -> &HashMap<String, ProcessSignature>;
 }
@@ -82,6 +85,12 @@ pub (crate) trait HasNamedProcess where Self: __seal__::Sealer {
 pub trait HasProcess where Self: __seal__::Sealer {
     // Get processer for a given channel
     fn process(&self, channel: ChannelIdType,) -> Option<ProcessType>;
+
+    // Take the handle of the future of the process (used by the supervisor for detecting panics)
+    fn take_handle(&mut self) -> Option<JoinHandle<()>>;
+
+    // Bind a channel id to a named process (used when adding a channel at runtime); output is false if the name is unknown
+    fn bind(&mut self, channel: ChannelIdType, name: &str,) -> bool;
 }
 
 /// doc to be defined
@@ -89,7 +98,6 @@ struct NamedProcess<D, F> where F: Future<Output=()> + Send + 'static {
     #[allow(dead_code)]
     data: D,
     future: Option<F>,
-    #[allow(dead_code)]
     handle: Option<JoinHandle<()>>,
     _send_to_master: SendToMaster,  // necessary so as to not close channel illegaly
     processes: HashMap<String, (FullId, Option<FullId>, ProcessType)>,
@@ -102,6 +110,8 @@ pub struct ProcessCell {
     #[allow(dead_code)]
     named_process: Arc<Mutex<Box<dyn HasNamedProcess + Send>>>,
     map_process: FnvHashMap<ChannelIdType,ProcessType>,
+    name_process: HashMap<String,ProcessType>,
+    handle: Arc<StdMutex<Option<JoinHandle<()>>>>,
 }
 
 /// Process instance for a servant
@@ -183,7 +193,7 @@ impl ProcessProducer {
     /// * `V` : type of the reply; needs to implement `SlxData`
     /// * `F` : type of the processing closure; needs to implement `Fn(&'static mut ArchData<U>) -> Pin<Box<dyn Future<Output = ArchData<V> > + Send>>` 
     /// * Output: nothing or an error
-    pub fn add_reply2<U,V,F>(&mut self, name_channel: &String, f: F) -> Result<(),String>
+    pub fn add_reply2<U,V,F>(&mut self, name_channel: &String, f: F) -> Result<(),SilxError>
             where F: Fn(&'static mut ArchData<U>) -> Pin<Box<dyn Future<Output = ArchData<V> > + Send>> + Clone + Send + Sync + 'static, 
                   U: 'static + SlxData + Send, V: 'static + SlxData, {
         let name = name_channel.clone();
@@ -202,7 +212,7 @@ impl ProcessProducer {
             } ), 
         };
         if self.processes.insert(name, (name_u,Some(name_v),process_type)).is_none() { Ok(()) } 
-        else { Err(format!("Duplicate channel name: {}", name_channel)) } 
+        else { Err(SilxError::config(format!("Duplicate channel name: {}", name_channel))) } 
     }
 
     /// Add a reply-to-query component of type 1 to the process producer
@@ -212,7 +222,7 @@ impl ProcessProducer {
     /// * `U` : type of the query and of reply; needs to implement `SlxData`
     /// * `F` : type of the processing closure; needs to implement `Fn(&'static mut ArchData<U>) -> Pin<Box<dyn Future<Output = ArchData<V> > + Send>>` 
     /// * Output: nothing or an error
-    pub fn add_reply1<U,F>(&mut self, name_channel: &String, f: F) -> Result<(),String>
+    pub fn add_reply1<U,F>(&mut self, name_channel: &String, f: F) -> Result<(),SilxError>
             where F: Fn(Pin<&'static mut ArchData<U>>) -> Pin<Box<dyn Future<Output = ()> + Send>> + Send + Sync + 'static,
                   U: 'static + SlxData {
         let name = name_channel.clone();
@@ -220,7 +230,7 @@ impl ProcessProducer {
         let mapper = Arc::new(move |bytes: &'static mut SerializedData,| f(unsafe { std::mem::transmute(Pin::new(bytes)) }));
         let process_type = ProcessType::Reply{ in_type: name_u.clone(), out_type: name_u.clone(), mapper, };
         if self.processes.insert(name, (name_u.clone(),Some(name_u),process_type)).is_none() { Ok(()) } 
-        else { Err(format!("Duplicate channel name: {}", name_channel)) } 
+        else { Err(SilxError::config(format!("Duplicate channel name: {}", name_channel))) } 
     }
 
     /// Add a query-and-get-reply component to the process producer
@@ -230,7 +240,7 @@ impl ProcessProducer {
     /// * `U` : type of the query; needs to implement `SlxData`
     /// * `V` : type of the reply; needs to implement `SlxData`
     /// * Output: a dispatch sender for the query and a dispatch receiver for the reply or an error
-    pub fn add_query<U,V,>(&mut self, name_channel: &String, capacity: Option<usize>,) -> Result<(ArchDispatchSender<U>, ArchDispatchReceiver<V>),String>
+    pub fn add_query<U,V,>(&mut self, name_channel: &String, capacity: Option<usize>,) -> Result<(ArchDispatchSender<U>, ArchDispatchReceiver<V>),SilxError>
             where U: SlxData, V: SlxData,  { 
         let name = name_channel.clone();
         let name_u = U::UUID; let name_v = V::UUID;
@@ -240,7 +250,7 @@ impl ProcessProducer {
         let out_chan = vsender.inner();
         let process_type = ProcessType::Query{ in_chan, in_type: name_u.clone(), out_chan, out_type: name_v.clone(), };
         if self.processes.insert(name, (name_u,Some(name_v),process_type)).is_none() { Ok((usender,vreceiver)) } 
-        else { Err(format!("Duplicate channel name: {}", name_channel)) } 
+        else { Err(SilxError::config(format!("Duplicate channel name: {}", name_channel))) } 
     }
 
     /// Add an emit component to the process producer
@@ -249,14 +259,14 @@ impl ProcessProducer {
     /// * `capacity: Option<usize>` : capacity of the channel (`None` for unlimited)
     /// * `U` : type of the emitted data; needs to implement `SlxData`
     /// * Output: a dispatch sender for emitting or an error
-    pub fn add_emit<U,>(&mut self, name_channel: &String, capacity: Option<usize>,) -> Result<ArchDispatchSender<U>,String> where U: SlxData, { 
+    pub fn add_emit<U,>(&mut self, name_channel: &String, capacity: Option<usize>,) -> Result<ArchDispatchSender<U>,SilxError> where U: SlxData, { 
         let name = name_channel.clone();
         let name_u = U::UUID;
         let (usender, ureceiver) = if let Some(capacity) = capacity { ArchDispatch::bounded::<U>(capacity) } else { ArchDispatch::unbounded::<U>() };
         let in_chan = ureceiver.inner();
         let process_type = ProcessType::Emit{ in_chan, in_type: name_u.clone(), };
         if self.processes.insert(name, (name_u,None,process_type)).is_none() { Ok(usender) } 
-        else { Err(format!("Duplicate channel name: {}", name_channel)) } 
+        else { Err(SilxError::config(format!("Duplicate channel name: {}", name_channel))) } 
     }
 
     /// Add a read component to the process producer
@@ -265,14 +275,14 @@ impl ProcessProducer {
     /// * `capacity: Option<usize>` : capacity of the channel (`None` for unlimited)
     /// * `V` : type of the read data; needs to implement `SlxData`
     /// * Output: a dispatch receiver for reading or an error
-    pub fn add_read<V,>(&mut self, name_channel: &String, capacity: Option<usize>,) -> Result<ArchDispatchReceiver<V>,String> where V: SlxData, {
+    pub fn add_read<V,>(&mut self, name_channel: &String, capacity: Option<usize>,) -> Result<ArchDispatchReceiver<V>,SilxError> where V: SlxData, {
         let name = name_channel.clone();
         let name_v = V::UUID;
         let (vsender, vreceiver) = if let Some(capacity) = capacity { ArchDispatch::bounded::<V>(capacity) } else { ArchDispatch::unbounded::<V>() };
         let out_chan = vsender.inner();
         let process_type = ProcessType::Read{ out_chan, out_type: name_v.clone(), };
         if self.processes.insert(name, (name_v,None,process_type)).is_none() { Ok(vreceiver) } 
-        else { Err(format!("Duplicate channel name: {}", name_channel)) } 
+        else { Err(SilxError::config(format!("Duplicate channel name: {}", name_channel))) } 
     }
 
     /// Add a read-by-reference component to the process producer; the data referenceis then processed by a closure
@@ -282,14 +292,14 @@ impl ProcessProducer {
     /// * `V` : type of the read data; needs to implement `SlxData`
     /// * `F` : type of the reading closure; needs to implement `Fn(&'static ArchData<V>) -> Pin<Box<dyn Future<Output = ()> + Send>>`
     /// * Output: nothing or an error
-    pub fn add_ref_read<V,F,>(&mut self, name_channel: &String, reader: F,) -> Result<(),String>
+    pub fn add_ref_read<V,F,>(&mut self, name_channel: &String, reader: F,) -> Result<(),SilxError>
             where F: Fn(&'static ArchData<V>) -> Pin<Box<dyn Future<Output = ()> + Send>> + Send + Sync + 'static, V: 'static + SlxData, { 
         let name = name_channel.clone();                
         let name_v = <V as HashedTypeDef>::UUID;
         let assert = Arc::new(move |bytes: &SerializedData,| reader(unsafe { std::mem::transmute(bytes) }));
         let process_type = ProcessType::RefRead{ out_type: name_v.clone(), assert, };
         if self.processes.insert(name, (name_v,None,process_type)).is_none() { Ok(()) }
-        else { Err(format!("Duplicate channel name: {}", name_channel)) }
+        else { Err(SilxError::config(format!("Duplicate channel name: {}", name_channel))) }
     }
 }
 
@@ -300,31 +310,42 @@ impl<D,F> HasNamedProcess for NamedProcess<D,F> where F: Future<Output=()> + Sen
         std::mem::swap(&mut tmp, &mut self.future);
         if let Some(future) = tmp { self.handle = Some(spawn(future)); }
     }
+    fn take_handle(&mut self) -> Option<JoinHandle<()>> { self.handle.take() }
     fn signature(&self) -> &HashMap<String, ProcessSignature> { &self.signature }
     fn processes(&self,) -> &HashMap<String, (FullId, Option<FullId>, ProcessType)> { &self.processes } 
 }
 
 impl ProcessCell {
     pub (crate) fn new(mut named_process: Box<dyn HasNamedProcess + Send>, map_name: &FnvHashMap<ChannelIdType,String>,) -> Option<Self> {
+        let handle = Arc::new(StdMutex::new(None));
         let omap_process = {
             named_process.activate();
+            *handle.lock().expect("unexpected error: poisoned lock") = named_process.take_handle();
             let processes = named_process.processes();
             let compliant = map_name.values().all(|v| processes.contains_key(v));
             if compliant {
                 let map_process: FnvHashMap<ChannelIdType,ProcessType> = map_name.iter()
                     .map(|(&channel,name)| (channel, processes.get(name).expect("unexpected error").2.clone())).collect();
-                Some(map_process)
+                let name_process: HashMap<String,ProcessType> = processes.iter().map(|(name,(_,_,pt))| (name.clone(), pt.clone())).collect();
+                Some((map_process,name_process))
             } else { None }
         };
-        if let Some(map_process) = omap_process {
+        if let Some((map_process,name_process)) = omap_process {
             let named_process = Arc::new(Mutex::new(named_process));
-            Some(Self { named_process, map_process, })    
+            Some(Self { named_process, map_process, name_process, handle, })    
         } else { None }
     }
 }
 impl __seal__::Sealer for ProcessCell {}
 impl HasProcess for ProcessCell {
     fn process(&self, channel: ChannelIdType,) -> Option<ProcessType>  { self.map_process.get(&channel).cloned() }
+    fn take_handle(&mut self) -> Option<JoinHandle<()>> { self.handle.lock().expect("unexpected error: poisoned lock").take() }
+    fn bind(&mut self, channel: ChannelIdType, name: &str,) -> bool {
+        match self.name_process.get(name) {
+            Some(process) => { self.map_process.insert(channel, process.clone()); true },
+            None => false,
+        }
+    }
 }
 
 
@@ -3,6 +3,7 @@ pub mod crate_main;
 use crate::crate_main::exp_load_start;
 
 use self::crate_main::{ exp_silx_scalar, exp_silx_scalar_mono, exp_silx_vec, exp_silx_vec_mono, };
+use tracing_subscriber::EnvFilter;
 
 
 
@@ -15,7 +16,11 @@ use self::crate_main::{ exp_silx_scalar, exp_silx_scalar_mono, exp_silx_vec, exp
 /// * exp_silx_vec_mono : execution of one-cluster asynchroneous network for computing vectorial sequence
 /// * exp_silx_scalar : execution of asynchroneous network for computing scalar (`f64slx`) sequence
 /// * exp_silx_scalar_mono : execution of one-cluster asynchroneous network for computing scalar (`f64slx`) sequence
+///
+/// Traces are filtered at runtime by the `RUST_LOG` environment variable (default is `info`), e.g. `RUST_LOG=silx_core=debug`
 pub async fn main() {
+    let filter = EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new("info"));
+    tracing_subscriber::fmt().with_env_filter(filter).init();
     println!("Available paralelism -> {:?}",std::thread::available_parallelism());
     let args: Vec<String> = std::env::args().collect();
     match args.len() {
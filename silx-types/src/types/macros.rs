@@ -79,7 +79,7 @@ macro_rules! impl_num_char_type {
         // implement `DerefArch` on silx array storages
         // as a consequence is implemented `ArchToRef` for reference to archived silx data
         impl<'a> DerefArch<'a, $I> for &'a $I {
-            #[inline] fn deref_arch(arch:  &'a ArchData<$I>) -> Result<Self,String> {
+            #[inline] fn deref_arch(arch:  &'a ArchData<$I>) -> Result<Self,SilxError> {
                 let arc = arch.archive_ref()?;
                 Ok(unsafe { std::mem::transmute(&arc.0) })
             }
@@ -88,7 +88,7 @@ macro_rules! impl_num_char_type {
         // implement `DerefMutArch` on silx array storages
         // as a consequence is implemented `ArchToMut` for pinned mutable reference to archived silx data
         impl<'a> DerefMutArch<'a, $I> for &'a mut $I {
-            #[inline] fn deref_mut_arch(arch:  Pin<&'a mut ArchData<$I>>) -> Result<Pin<Self>,String> {
+            #[inline] fn deref_mut_arch(arch:  Pin<&'a mut ArchData<$I>>) -> Result<Pin<Self>,SilxError> {
                 let arc = arch.archive_mut()?;
                 Ok(unsafe { arc.map_unchecked_mut(|s| std::mem::transmute(&mut s.0)) } ) 
             }
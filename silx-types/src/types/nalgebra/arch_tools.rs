@@ -1,6 +1,6 @@
 use std::pin::Pin;
 use hashed_type_def::HashedTypeDef;
-use silx_core::types::{DerefArch, DerefMutArch};
+use silx_core::{ types::{DerefArch, DerefMutArch}, utils::SilxError, };
 use super::{ 
     ArchData, ConstSlx, DerefMatrixSlx, ArrayStorageSlx,
     ArchRefArrayStorageSlx, ArchMutArrayStorageSlx, 
@@ -9,7 +9,7 @@ use super::{
 // as a consequence is implemented `ArchToRef` for reference to archived silx array storages
 impl<'a, T, const R: usize, const C: usize> DerefArch<'a, ArrayStorageSlx<T,R,C>> 
             for DerefMatrixSlx<T,ConstSlx<R>,ConstSlx<C>,ArchRefArrayStorageSlx<'a,T,R,C>>  where T: 'a + rkyv::Archive<Archived = T> + HashedTypeDef {
-    #[inline] fn deref_arch(arch:  &'a ArchData<ArrayStorageSlx<T,R,C>>) -> Result<Self,String> {
+    #[inline] fn deref_arch(arch:  &'a ArchData<ArrayStorageSlx<T,R,C>>) -> Result<Self,SilxError> {
         Ok(From::from(arch.archive_ref()?))
     }
 }
@@ -18,7 +18,7 @@ impl<'a, T, const R: usize, const C: usize> DerefArch<'a, ArrayStorageSlx<T,R,C>
 // as a consequence is implemented `ArchToMut` for pinned mutable reference to archived silx array storages
 impl<'a, T, const R: usize, const C: usize> DerefMutArch<'a, ArrayStorageSlx<T,R,C>> 
             for DerefMatrixSlx<T,ConstSlx<R>,ConstSlx<C>,ArchMutArrayStorageSlx<'a,T,R,C>> where T: 'a + Unpin + rkyv::Archive<Archived = T> + HashedTypeDef {
-    #[inline] fn deref_mut_arch(arch:  Pin<&'a mut ArchData<ArrayStorageSlx<T,R,C>>>) -> Result<Pin<Self>,String> {
+    #[inline] fn deref_mut_arch(arch:  Pin<&'a mut ArchData<ArrayStorageSlx<T,R,C>>>) -> Result<Pin<Self>,SilxError> {
         Ok(From::from(arch.archive_mut()?)) 
     }
 }
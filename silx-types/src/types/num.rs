@@ -32,7 +32,7 @@ pub use num_traits::{
     one, zero, checked_pow, pow, abs, abs_sub, signum, bounds, cast, float, identities, int, ops, real, sign,
 };
 
-use silx_core::{ types::{DerefArch, DerefMutArch}, utils::ArchData, };
+use silx_core::{ types::{DerefArch, DerefMutArch}, utils::{ ArchData, SilxError, }, };
 use super::{ 
     SlxFrom, SlxInto, macros::{ 
         impl_num_char_type, impl_inc_int_type, impl_inc_num_type, impl_inc_signed_num_type, impl_inc_int_char_type, impl_inc_float_type,